@@ -8,16 +8,22 @@ use {
             logging, Config, ConfigEntry, ConfigEntryGen, VERSION,
         },
         exec::Command,
-        input::{acceleration::AccelProfile, capability::Capability, InputDevice, Seat},
-        keyboard::Keymap,
+        input::{
+            acceleration::AccelProfile, capability::Capability, InputDevice, PointerInfo, Seat,
+            SurfaceInfo,
+        },
+        keyboard::{mods::Modifiers, Keymap},
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable, Color},
-        timer::Timer,
+        timer::{MissedTicks, Timer},
         video::{
             connector_type::{ConnectorType, CON_UNKNOWN},
-            Connector, DrmDevice, GfxApi, Mode, Transform,
+            Connector, ContentType, DrmDevice, FormatCapabilities, FramebufferInfo, GfxApi,
+            HotplugPolicy, Mode, OutputLayout, OutputLayoutValidation, Transform,
         },
-        Axis, Direction, ModifiedKeySym, PciId, Workspace,
+        Axis, ClientInfo, CompositorInfo, Direction, EmptyWorkspaceAction, FloatPlacement,
+        IdleInhibitor, ModifiedKeySym, PciId, UnresponsiveWindow, Window, WindowInfo, WindowState,
+        Workspace,
     },
     bincode::Options,
     std::{
@@ -42,12 +48,17 @@ pub(crate) struct Client {
     on_new_seat: RefCell<Option<Rc<dyn Fn(Seat)>>>,
     on_new_input_device: RefCell<Option<Rc<dyn Fn(InputDevice)>>>,
     on_connector_connected: RefCell<Option<Rc<dyn Fn(Connector)>>>,
+    on_connector_scale_changed: RefCell<Option<Rc<dyn Fn(Connector, f64)>>>,
+    on_connector_transform_changed: RefCell<Option<Rc<dyn Fn(Connector, Transform)>>>,
+    on_connector_hotplug: RefCell<Option<Rc<dyn Fn(Connector)>>>,
     on_graphics_initialized: Cell<Option<Box<dyn FnOnce()>>>,
     on_devices_enumerated: Cell<Option<Box<dyn FnOnce()>>>,
+    on_outputs_ready: Cell<Option<Box<dyn FnOnce()>>>,
     on_new_connector: RefCell<Option<Rc<dyn Fn(Connector)>>>,
     on_new_drm_device: RefCell<Option<Rc<dyn Fn(DrmDevice)>>>,
     on_del_drm_device: RefCell<Option<Rc<dyn Fn(DrmDevice)>>>,
     on_idle: RefCell<Option<Rc<dyn Fn()>>>,
+    on_window_unresponsive: RefCell<Option<Rc<dyn Fn(UnresponsiveWindow)>>>,
     bufs: RefCell<Vec<Vec<u8>>>,
     reload: Cell<bool>,
 }
@@ -130,12 +141,17 @@ pub unsafe extern "C" fn init(
         on_new_seat: Default::default(),
         on_new_input_device: Default::default(),
         on_connector_connected: Default::default(),
+        on_connector_scale_changed: Default::default(),
+        on_connector_transform_changed: Default::default(),
+        on_connector_hotplug: Default::default(),
         on_graphics_initialized: Default::default(),
         on_devices_enumerated: Default::default(),
+        on_outputs_ready: Default::default(),
         on_new_connector: Default::default(),
         on_new_drm_device: Default::default(),
         on_del_drm_device: Default::default(),
         on_idle: Default::default(),
+        on_window_unresponsive: Default::default(),
         bufs: Default::default(),
         reload: Cell::new(false),
     });
@@ -197,6 +213,15 @@ impl Client {
             .iter()
             .map(|(a, b)| (a.to_string(), b.to_string()))
             .collect();
+        if !command.inherit_env {
+            self.send(&ClientMessage::RunWithoutInheritedEnv {
+                prog: &command.prog,
+                args: command.args.clone(),
+                env,
+                workspace: None,
+            });
+            return;
+        }
         self.send(&ClientMessage::Run {
             prog: &command.prog,
             args: command.args.clone(),
@@ -204,6 +229,44 @@ impl Client {
         });
     }
 
+    pub fn spawn_on_workspace(&self, command: &Command, workspace: Workspace) {
+        let env = command
+            .env
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        if !command.inherit_env {
+            self.send(&ClientMessage::RunWithoutInheritedEnv {
+                prog: &command.prog,
+                args: command.args.clone(),
+                env,
+                workspace: Some(workspace),
+            });
+            return;
+        }
+        self.send(&ClientMessage::RunOnWorkspace {
+            prog: &command.prog,
+            args: command.args.clone(),
+            env,
+            workspace,
+        });
+    }
+
+    pub fn spawn_on_render_device(&self, command: &Command, device: DrmDevice) {
+        let env = command
+            .env
+            .iter()
+            .map(|(a, b)| (a.to_string(), b.to_string()))
+            .collect();
+        self.send(&ClientMessage::RunOnRenderDevice {
+            prog: &command.prog,
+            args: command.args.clone(),
+            env,
+            inherit_env: command.inherit_env,
+            device,
+        });
+    }
+
     pub fn grab(&self, kb: InputDevice, grab: bool) {
         self.send(&ClientMessage::GrabKb { kb, grab });
     }
@@ -232,6 +295,21 @@ impl Client {
         }
     }
 
+    pub fn focused_surface_info(&self, seat: Seat) -> Option<SurfaceInfo> {
+        let res = self.send_with_response(&ClientMessage::GetSurfaceInfo { seat });
+        get_response!(res, None, GetSurfaceInfo { info });
+        info
+    }
+
+    pub fn set_shortcut_inhibit_break<T: Into<ModifiedKeySym>>(&self, seat: Seat, mod_sym: T) {
+        let mod_sym = mod_sym.into();
+        self.send(&ClientMessage::SetShortcutInhibitBreak {
+            seat,
+            mods: mod_sym.mods,
+            sym: mod_sym.sym,
+        });
+    }
+
     fn with_response<F: FnOnce()>(&self, f: F) -> Response {
         f();
         self.response.borrow_mut().pop().unwrap_or(Response::None)
@@ -264,14 +342,26 @@ impl Client {
         timer: Timer,
         initial: Option<Duration>,
         periodic: Option<Duration>,
+        missed: MissedTicks,
     ) {
-        self.send(&ClientMessage::ProgramTimer {
+        self.send(&ClientMessage::ProgramTimer2 {
             timer,
             initial,
             periodic,
+            missed,
         });
     }
 
+    pub fn timer_remaining(&self, timer: Timer) -> Option<Duration> {
+        let res = self.send_with_response(&ClientMessage::GetTimerRemaining { timer });
+        get_response!(res, None, TimerRemaining { remaining });
+        remaining
+    }
+
+    pub fn trigger_timer(&self, timer: Timer) {
+        self.send(&ClientMessage::TriggerTimer { timer });
+    }
+
     pub fn on_timer_tick<F: Fn() + 'static>(&self, timer: Timer, f: F) {
         self.timer_handlers.borrow_mut().insert(timer, Rc::new(f));
     }
@@ -302,6 +392,37 @@ impl Client {
         self.send(&ClientMessage::SetWorkspaceCapture { workspace, capture });
     }
 
+    pub fn set_floating_defaults(&self, width: i32, height: i32, placement: FloatPlacement) {
+        self.send(&ClientMessage::SetFloatingDefaults {
+            width,
+            height,
+            placement,
+        });
+    }
+
+    pub fn set_auto_destroy_empty_workspaces(&self, enabled: bool) {
+        self.send(&ClientMessage::SetAutoDestroyEmptyWorkspaces { enabled });
+    }
+
+    pub fn set_empty_workspace_action(&self, action: EmptyWorkspaceAction) {
+        self.send(&ClientMessage::SetEmptyWorkspaceAction { action });
+    }
+
+    pub fn set_input_device_connector(&self, device: InputDevice, connector: Connector) {
+        self.send(&ClientMessage::SetInputDeviceConnector { device, connector });
+    }
+
+    pub fn set_workspace_persistent(&self, workspace: Workspace, persistent: bool) {
+        self.send(&ClientMessage::SetWorkspacePersistent {
+            workspace,
+            persistent,
+        });
+    }
+
+    pub fn set_workspace_switch_animation(&self, axis: Axis, duration: Duration) {
+        self.send(&ClientMessage::SetWorkspaceSwitchAnimation { axis, duration });
+    }
+
     pub fn get_default_workspace_capture(&self) -> bool {
         let res = self.send_with_response(&ClientMessage::GetDefaultWorkspaceCapture);
         get_response!(res, true, GetDefaultWorkspaceCapture { capture });
@@ -332,6 +453,20 @@ impl Client {
         self.send(&ClientMessage::DisablePointerConstraint { seat });
     }
 
+    pub fn center_pointer_on_focus(&self, seat: Seat) {
+        self.send(&ClientMessage::CenterPointerOnFocus { seat });
+    }
+
+    pub fn pointer_info(&self, seat: Seat) -> PointerInfo {
+        let res = self.send_with_response(&ClientMessage::GetPointer { seat });
+        get_response!(res, Default::default(), GetPointer { info });
+        info
+    }
+
+    pub fn set_pointer_position(&self, seat: Seat, x: i32, y: i32) {
+        self.send(&ClientMessage::SetPointerPosition { seat, x, y });
+    }
+
     pub fn set_fullscreen(&self, seat: Seat, fullscreen: bool) {
         self.send(&ClientMessage::SetFullscreen { seat, fullscreen });
     }
@@ -350,6 +485,10 @@ impl Client {
         self.send(&ClientMessage::SetFont { font });
     }
 
+    pub fn set_active_font(&self, font: Option<&str>) {
+        self.send(&ClientMessage::SetActiveFont { font });
+    }
+
     pub fn get_font(&self) -> String {
         let res = self.send_with_response(&ClientMessage::GetFont);
         get_response!(res, String::new(), GetFont { font });
@@ -366,6 +505,40 @@ impl Client {
         self.send(&ClientMessage::SetFloating { seat, floating });
     }
 
+    pub fn focus_output(&self, seat: Seat, connector: Connector) {
+        self.send(&ClientMessage::FocusOutput { seat, connector });
+    }
+
+    pub fn set_move_pointer_on_output_focus(&self, seat: Seat, enabled: bool) {
+        self.send(&ClientMessage::SetMovePointerOnOutputFocus { seat, enabled });
+    }
+
+    pub fn set_window_border_color(&self, seat: Seat, color: Option<Color>) {
+        self.send(&ClientMessage::SetWindowBorderColor { seat, color });
+    }
+
+    pub fn cycle_window_state(&self, seat: Seat) {
+        self.send(&ClientMessage::CycleWindowState { seat });
+    }
+
+    pub fn set_window_state_cycle(&self, states: &[WindowState]) {
+        self.send(&ClientMessage::SetWindowStateCycle {
+            states: states.to_vec(),
+        });
+    }
+
+    pub fn set_xwayland_scale(&self, scale: f64) {
+        self.send(&ClientMessage::SetXwaylandScale { scale });
+    }
+
+    pub fn set_xwayland_lazy(&self, enabled: bool) {
+        self.send(&ClientMessage::SetXwaylandLazy { enabled });
+    }
+
+    pub fn set_sticky(&self, seat: Seat, sticky: bool) {
+        self.send(&ClientMessage::SetSticky { seat, sticky });
+    }
+
     pub fn toggle_floating(&self, seat: Seat) {
         self.set_floating(seat, !self.get_floating(seat));
     }
@@ -388,6 +561,26 @@ impl Client {
         self.send(&ClientMessage::SetColor { colorable, color });
     }
 
+    pub fn set_title_gradient(&self, color: Option<Color>) {
+        self.send(&ClientMessage::SetTitleGradient { color });
+    }
+
+    pub fn flush_layout(&self) {
+        self.send(&ClientMessage::FlushLayout);
+    }
+
+    pub fn set_dummy_output_color(&self, color: Option<Color>) {
+        self.send(&ClientMessage::SetDummyOutputColor { color });
+    }
+
+    pub fn set_hotplug_debounce(&self, ms: u64) {
+        self.send(&ClientMessage::SetHotplugDebounceMs { ms });
+    }
+
+    pub fn set_border_antialiasing(&self, enabled: bool) {
+        self.send(&ClientMessage::SetBorderAntialiasing { enabled });
+    }
+
     pub fn get_size(&self, sized: Resizable) -> i32 {
         let res = self.send_with_response(&ClientMessage::GetSize { sized });
         get_response!(res, 0, GetSize { size });
@@ -417,6 +610,16 @@ impl Client {
         self.send(&ClientMessage::SetEnv { key, val });
     }
 
+    pub fn unset_env(&self, key: &str) {
+        self.send(&ClientMessage::UnsetEnv { key });
+    }
+
+    pub fn get_env(&self, key: &str) -> Option<String> {
+        let res = self.send_with_response(&ClientMessage::GetEnv { key });
+        get_response!(res, None, GetEnv { val });
+        val
+    }
+
     pub fn set_status(&self, status: &str) {
         self.send(&ClientMessage::SetStatus { status });
     }
@@ -477,10 +680,146 @@ impl Client {
         self.send(&ClientMessage::ConnectorSetPosition { connector, x, y });
     }
 
+    pub fn validate_output_layout(&self) -> OutputLayoutValidation {
+        let res = self.send_with_response(&ClientMessage::ValidateOutputLayout);
+        get_response!(
+            res,
+            Default::default(),
+            ValidateOutputLayout { overlapping, gaps }
+        );
+        OutputLayoutValidation { overlapping, gaps }
+    }
+
+    pub fn auto_arrange_outputs(&self) {
+        self.send(&ClientMessage::AutoArrangeOutputs);
+    }
+
+    pub fn set_primary_output(&self, connector: Connector) {
+        self.send(&ClientMessage::SetPrimaryOutput { connector });
+    }
+
+    pub fn gpu_time(&self) -> Option<Duration> {
+        let res = self.send_with_response(&ClientMessage::GetGpuTime);
+        get_response!(res, None, GetGpuTime { time });
+        time
+    }
+
+    pub fn max_texture_size(&self) -> Option<(u32, u32)> {
+        let res = self.send_with_response(&ClientMessage::GetMaxTextureSize);
+        get_response!(res, None, MaxTextureSize { size });
+        size
+    }
+
+    pub fn set_max_in_flight_frames(&self, frames: usize) {
+        self.send(&ClientMessage::SetMaxInFlightFrames { frames });
+    }
+
+    pub fn set_linear_blending(&self, enabled: bool) {
+        self.send(&ClientMessage::SetLinearBlending { enabled });
+    }
+
+    pub fn disable_modifier(&self, format_drm: u32, modifier: u64) {
+        self.send(&ClientMessage::DisableModifier {
+            format_drm,
+            modifier,
+        });
+    }
+
+    pub fn format_capabilities(&self, format_drm: u32) -> Option<FormatCapabilities> {
+        let res = self.send_with_response(&ClientMessage::GetFormatCapabilities { format_drm });
+        get_response!(res, None, GetFormatCapabilities { capabilities });
+        capabilities
+    }
+
+    pub fn in_flight_frames(&self) -> usize {
+        let res = self.send_with_response(&ClientMessage::GetInFlightFrames);
+        get_response!(res, 0, GetInFlightFrames { frames });
+        frames
+    }
+
+    pub fn get_content_type(&self, seat: Seat) -> Option<ContentType> {
+        let res = self.send_with_response(&ClientMessage::GetContentType { seat });
+        get_response!(res, None, GetContentType { content_type });
+        content_type
+    }
+
+    pub fn set_float_modifier(&self, seat: Seat, mods: Modifiers) {
+        self.send(&ClientMessage::SetFloatModifier { seat, mods });
+    }
+
+    pub fn get_focused_window_info(&self, seat: Seat) -> Option<WindowInfo> {
+        let res = self.send_with_response(&ClientMessage::GetFocusedWindowInfo { seat });
+        get_response!(res, None, GetFocusedWindowInfo { info });
+        info
+    }
+
+    pub fn get_window_info(&self, window: Window) -> Option<WindowInfo> {
+        let res = self.send_with_response(&ClientMessage::GetWindowInfo { window });
+        get_response!(res, None, GetWindowInfo { info });
+        info
+    }
+
+    pub fn focus_window(&self, window: Window, seat: Seat) {
+        self.send(&ClientMessage::FocusWindow { window, seat });
+    }
+
+    pub fn close_window(&self, window: Window) {
+        self.send(&ClientMessage::CloseWindow { window });
+    }
+
+    pub fn move_window_to_workspace(&self, window: Window, workspace: Workspace) {
+        self.send(&ClientMessage::MoveWindowToWorkspace { window, workspace });
+    }
+
     pub fn connector_set_enabled(&self, connector: Connector, enabled: bool) {
         self.send(&ClientMessage::ConnectorSetEnabled { connector, enabled });
     }
 
+    pub fn connector_set_throttle_frame_callbacks(&self, connector: Connector, throttle: bool) {
+        self.send(&ClientMessage::ConnectorSetThrottleFrameCallbacks {
+            connector,
+            throttle,
+        });
+    }
+
+    pub fn connector_freeze(&self, connector: Connector, frozen: bool) {
+        self.send(&ClientMessage::ConnectorFreeze { connector, frozen });
+    }
+
+    pub fn connector_set_hotplug_policy(&self, connector: Connector, policy: HotplugPolicy) {
+        self.send(&ClientMessage::ConnectorSetHotplugPolicy { connector, policy });
+    }
+
+    pub fn connector_set_non_desktop(&self, connector: Connector, value: bool) {
+        self.send(&ClientMessage::ConnectorSetNonDesktop { connector, value });
+    }
+
+    pub fn connector_accept_hotplug(&self, connector: Connector) {
+        self.send(&ClientMessage::ConnectorAcceptHotplug { connector });
+    }
+
+    pub fn connector_set_brightness(&self, connector: Connector, percent: f64) {
+        self.send(&ClientMessage::ConnectorSetBrightness { connector, percent });
+    }
+
+    pub fn connector_get_brightness(&self, connector: Connector) -> Option<f64> {
+        let res = self.send_with_response(&ClientMessage::ConnectorGetBrightness { connector });
+        get_response!(res, None, ConnectorGetBrightness { percent });
+        percent
+    }
+
+    pub fn get_output_layout(&self) -> OutputLayout {
+        let res = self.send_with_response(&ClientMessage::GetOutputLayout);
+        get_response!(res, Default::default(), GetOutputLayout { layout });
+        layout
+    }
+
+    pub fn apply_output_layout(&self, layout: &OutputLayout) {
+        self.send(&ClientMessage::ApplyOutputLayout {
+            layout: layout.clone(),
+        });
+    }
+
     pub fn connector_set_transform(&self, connector: Connector, transform: Transform) {
         self.send(&ClientMessage::ConnectorSetTransform {
             connector,
@@ -500,6 +839,12 @@ impl Client {
         syspath
     }
 
+    pub fn drm_device_render_node(&self, device: DrmDevice) -> String {
+        let res = self.send_with_response(&ClientMessage::GetDrmDeviceRenderNode { device });
+        get_response!(res, String::new(), GetDrmDeviceRenderNode { render_node });
+        render_node
+    }
+
     pub fn drm_device_vendor(&self, device: DrmDevice) -> String {
         let res = self.send_with_response(&ClientMessage::GetDrmDeviceVendor { device });
         get_response!(res, String::new(), GetDrmDeviceVendor { vendor });
@@ -518,6 +863,18 @@ impl Client {
         pci_id
     }
 
+    pub fn drm_device_gfx_api(&self, device: DrmDevice) -> GfxApi {
+        let res = self.send_with_response(&ClientMessage::GetDrmDeviceGfxApi { device });
+        get_response!(res, GfxApi::OpenGl, GetDrmDeviceGfxApi { api });
+        api
+    }
+
+    pub fn drm_device_supported_gfx_apis(&self, device: DrmDevice) -> Vec<GfxApi> {
+        let res = self.send_with_response(&ClientMessage::GetDrmDeviceSupportedGfxApis { device });
+        get_response!(res, vec![], GetDrmDeviceSupportedGfxApis { apis });
+        apis
+    }
+
     pub fn make_render_device(&self, device: DrmDevice) {
         self.send(&ClientMessage::MakeRenderDevice { device });
     }
@@ -526,6 +883,25 @@ impl Client {
         self.send(&ClientMessage::SetGfxApi { device, api });
     }
 
+    pub fn try_set_gfx_api(&self, device: DrmDevice, api: GfxApi) -> bool {
+        let res = self.send_with_response(&ClientMessage::TrySetGfxApi { device, api });
+        get_response!(res, false, TrySetGfxApi { success });
+        success
+    }
+
+    pub fn run_gfx_self_test(&self, device: DrmDevice) -> Result<(), String> {
+        let res = self.send_with_response(&ClientMessage::RunGfxSelfTest { device });
+        get_response!(
+            res,
+            Err("The compositor did not respond".to_string()),
+            GfxSelfTest { passed, details }
+        );
+        match passed {
+            true => Ok(()),
+            false => Err(details),
+        }
+    }
+
     pub fn set_direct_scanout_enabled(&self, device: Option<DrmDevice>, enabled: bool) {
         self.send(&ClientMessage::SetDirectScanoutEnabled { device, enabled });
     }
@@ -536,6 +912,13 @@ impl Client {
         connected
     }
 
+    pub fn connector_get_framebuffer_info(&self, connector: Connector) -> Option<FramebufferInfo> {
+        let res =
+            self.send_with_response(&ClientMessage::ConnectorGetFramebufferInfo { connector });
+        get_response!(res, None, FramebufferInfo { info });
+        info
+    }
+
     pub fn connector_set_scale(&self, connector: Connector, scale: f64) {
         self.send(&ClientMessage::ConnectorSetScale { connector, scale });
     }
@@ -552,6 +935,19 @@ impl Client {
         ty
     }
 
+    pub fn connector_modes(&self, connector: Connector) -> Vec<Mode> {
+        let res = self.send_with_response(&ClientMessage::ConnectorModes { connector });
+        get_response!(res, vec![], ConnectorModes { modes });
+        modes
+            .into_iter()
+            .map(|(width, height, refresh_millihz)| Mode {
+                width,
+                height,
+                refresh_millihz,
+            })
+            .collect()
+    }
+
     pub fn connector_mode(&self, connector: Connector) -> Mode {
         let res = self.send_with_response(&ClientMessage::ConnectorMode { connector });
         get_response!(
@@ -594,14 +990,78 @@ impl Client {
         *self.on_new_connector.borrow_mut() = Some(Rc::new(f));
     }
 
+    pub fn idle_inhibitors(&self) -> Vec<IdleInhibitor> {
+        let res = self.send_with_response(&ClientMessage::GetIdleInhibitors);
+        get_response!(res, vec![], GetIdleInhibitors { inhibitors });
+        inhibitors
+    }
+
+    pub fn set_idle_inhibited(&self, inhibited: Option<bool>) {
+        self.send(&ClientMessage::SetIdleInhibited { inhibited });
+    }
+
+    pub fn set_persistent_clipboard(&self, enabled: bool) {
+        self.send(&ClientMessage::SetPersistentClipboard { enabled });
+    }
+
+    pub fn set_commit_throttling(&self, enabled: bool) {
+        self.send(&ClientMessage::SetCommitThrottling { enabled });
+    }
+
+    pub fn set_max_clients(&self, max: Option<u32>) {
+        self.send(&ClientMessage::SetMaxClients { max });
+    }
+
+    pub fn set_max_connection_rate(&self, rate: Option<u32>) {
+        self.send(&ClientMessage::SetMaxConnectionRate { rate });
+    }
+
+    pub fn compositor_info(&self) -> CompositorInfo {
+        let res = self.send_with_response(&ClientMessage::GetCompositorInfo);
+        get_response!(res, CompositorInfo::default(), GetCompositorInfo { info });
+        info
+    }
+
+    pub fn client_count(&self) -> u32 {
+        let res = self.send_with_response(&ClientMessage::GetClientCount);
+        get_response!(res, 0, GetClientCount { count });
+        count
+    }
+
+    pub fn clients(&self) -> Vec<ClientInfo> {
+        let res = self.send_with_response(&ClientMessage::GetClients);
+        get_response!(res, vec![], GetClients { clients });
+        clients
+    }
+
+    pub fn kill_client(&self, client: crate::Client) {
+        self.send(&ClientMessage::KillClient { client });
+    }
+
     pub fn on_idle<F: Fn() + 'static>(&self, f: F) {
         *self.on_idle.borrow_mut() = Some(Rc::new(f));
     }
 
+    pub fn on_window_unresponsive<F: Fn(UnresponsiveWindow) + 'static>(&self, f: F) {
+        *self.on_window_unresponsive.borrow_mut() = Some(Rc::new(f));
+    }
+
     pub fn on_connector_connected<F: Fn(Connector) + 'static>(&self, f: F) {
         *self.on_connector_connected.borrow_mut() = Some(Rc::new(f));
     }
 
+    pub fn on_connector_scale_changed<F: Fn(Connector, f64) + 'static>(&self, f: F) {
+        *self.on_connector_scale_changed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_connector_transform_changed<F: Fn(Connector, Transform) + 'static>(&self, f: F) {
+        *self.on_connector_transform_changed.borrow_mut() = Some(Rc::new(f));
+    }
+
+    pub fn on_connector_hotplug<F: Fn(Connector) + 'static>(&self, f: F) {
+        *self.on_connector_hotplug.borrow_mut() = Some(Rc::new(f));
+    }
+
     pub fn on_graphics_initialized<F: FnOnce() + 'static>(&self, f: F) {
         self.on_graphics_initialized.set(Some(Box::new(f)));
     }
@@ -610,6 +1070,10 @@ impl Client {
         self.on_devices_enumerated.set(Some(Box::new(f)));
     }
 
+    pub fn on_outputs_ready<F: FnOnce() + 'static>(&self, f: F) {
+        self.on_outputs_ready.set(Some(Box::new(f)));
+    }
+
     pub fn set_seat(&self, device: InputDevice, seat: Seat) {
         self.send(&ClientMessage::SetSeat { device, seat })
     }
@@ -637,6 +1101,13 @@ impl Client {
         self.send(&ClientMessage::SetPxPerWheelScroll { device, px })
     }
 
+    pub fn set_button_map(&self, device: InputDevice, map: &[(u32, u32)]) {
+        self.send(&ClientMessage::SetButtonMap {
+            device,
+            map: map.to_vec(),
+        })
+    }
+
     pub fn set_input_tap_enabled(&self, device: InputDevice, enabled: bool) {
         self.send(&ClientMessage::SetTapEnabled { device, enabled })
     }
@@ -669,6 +1140,14 @@ impl Client {
         self.send(&ClientMessage::SeatSetKeymap { seat, keymap })
     }
 
+    pub fn seat_push_keymap(&self, seat: Seat, keymap: Keymap) {
+        self.send(&ClientMessage::SeatPushKeymap { seat, keymap })
+    }
+
+    pub fn seat_pop_keymap(&self, seat: Seat) {
+        self.send(&ClientMessage::SeatPopKeymap { seat })
+    }
+
     pub fn seat_set_repeat_rate(&self, seat: Seat, rate: i32, delay: i32) {
         self.send(&ClientMessage::SeatSetRepeatRate { seat, rate, delay })
     }
@@ -679,6 +1158,26 @@ impl Client {
         (rate, delay)
     }
 
+    pub fn seat_set_clipboard(&self, seat: Seat, mime_type: &str, data: &[u8]) {
+        self.send(&ClientMessage::SetClipboard {
+            seat,
+            mime_type,
+            data,
+        });
+    }
+
+    pub fn seat_get_clipboard(&self, seat: Seat, mime_type: &str) -> Option<Vec<u8>> {
+        let res = self.send_with_response(&ClientMessage::GetClipboard { seat, mime_type });
+        get_response!(res, None, GetClipboard { data });
+        data
+    }
+
+    pub fn seat_idle_time(&self, seat: Seat) -> Duration {
+        let res = self.send_with_response(&ClientMessage::GetSeatIdleTime { seat });
+        get_response!(res, Duration::ZERO, GetSeatIdleTime { ms });
+        Duration::from_millis(ms)
+    }
+
     pub fn parse_keymap(&self, keymap: &str) -> Keymap {
         let res = self.send_with_response(&ClientMessage::ParseKeymap { keymap });
         get_response!(res, Keymap(0), ParseKeymap { keymap });
@@ -710,6 +1209,16 @@ impl Client {
         }
     }
 
+    pub fn set_log_level(&self, subsystem: Option<&str>, level: LogLevel) {
+        self.send(&ClientMessage::SetLogLevel { subsystem, level });
+    }
+
+    pub fn recent_logs(&self, max_lines: usize) -> Vec<String> {
+        let res = self.send_with_response(&ClientMessage::GetRecentLogs { max_lines });
+        get_response!(res, vec![], RecentLogs { lines });
+        lines
+    }
+
     pub fn log(&self, level: LogLevel, msg: &str, file: Option<&str>, line: Option<u32>) {
         self.send(&ClientMessage::Log {
             level,
@@ -803,6 +1312,38 @@ impl Client {
                     handler();
                 }
             }
+            ServerMessage::ConnectorScaleChanged { connector, scale } => {
+                let handler = self.on_connector_scale_changed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    handler(connector, scale);
+                }
+            }
+            ServerMessage::ConnectorTransformChanged {
+                connector,
+                transform,
+            } => {
+                let handler = self.on_connector_transform_changed.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    handler(connector, transform);
+                }
+            }
+            ServerMessage::OutputsReady => {
+                if let Some(handler) = self.on_outputs_ready.take() {
+                    handler();
+                }
+            }
+            ServerMessage::ConnectorHotplug { connector } => {
+                let handler = self.on_connector_hotplug.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    handler(connector);
+                }
+            }
+            ServerMessage::WindowUnresponsive { window } => {
+                let handler = self.on_window_unresponsive.borrow_mut().clone();
+                if let Some(handler) = handler {
+                    handler(window);
+                }
+            }
         }
     }
 
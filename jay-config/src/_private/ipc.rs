@@ -1,12 +1,19 @@
 use {
     crate::{
-        input::{acceleration::AccelProfile, capability::Capability, InputDevice, Seat},
+        input::{
+            acceleration::AccelProfile, capability::Capability, InputDevice, PointerInfo, Seat,
+            SurfaceInfo,
+        },
         keyboard::{mods::Modifiers, syms::KeySym, Keymap},
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable, Color},
-        timer::Timer,
-        video::{connector_type::ConnectorType, Connector, DrmDevice, GfxApi, Transform},
-        Axis, Direction, PciId, Workspace,
+        timer::{MissedTicks, Timer},
+        video::{
+            connector_type::ConnectorType, Connector, ContentType, DrmDevice, FormatCapabilities,
+            FramebufferInfo, GfxApi, HotplugPolicy, OutputLayout, Transform,
+        },
+        Axis, Client, ClientInfo, CompositorInfo, Direction, EmptyWorkspaceAction, FloatPlacement,
+        IdleInhibitor, PciId, UnresponsiveWindow, Window, WindowInfo, WindowState, Workspace,
     },
     serde::{Deserialize, Serialize},
     std::time::Duration,
@@ -56,6 +63,21 @@ pub enum ServerMessage {
     },
     Idle,
     DevicesEnumerated,
+    ConnectorScaleChanged {
+        connector: Connector,
+        scale: f64,
+    },
+    ConnectorTransformChanged {
+        connector: Connector,
+        transform: Transform,
+    },
+    ConnectorHotplug {
+        connector: Connector,
+    },
+    OutputsReady,
+    WindowUnresponsive {
+        window: UnresponsiveWindow,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -352,6 +374,281 @@ pub enum ClientMessage<'a> {
     SetDoubleClickDistance {
         dist: i32,
     },
+    CenterPointerOnFocus {
+        seat: Seat,
+    },
+    ValidateOutputLayout,
+    AutoArrangeOutputs,
+    SetPrimaryOutput {
+        connector: Connector,
+    },
+    GetGpuTime,
+    GetMaxTextureSize,
+    SetMaxInFlightFrames {
+        frames: usize,
+    },
+    GetInFlightFrames,
+    GetContentType {
+        seat: Seat,
+    },
+    ConnectorGetFramebufferInfo {
+        connector: Connector,
+    },
+    SetLogLevel {
+        subsystem: Option<&'a str>,
+        level: LogLevel,
+    },
+    GetRecentLogs {
+        max_lines: usize,
+    },
+    GetFocusedWindow {
+        seat: Seat,
+    },
+    GetTimerRemaining {
+        timer: Timer,
+    },
+    TriggerTimer {
+        timer: Timer,
+    },
+    RunOnWorkspace {
+        prog: &'a str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        workspace: Workspace,
+    },
+    UnsetEnv {
+        key: &'a str,
+    },
+    GetEnv {
+        key: &'a str,
+    },
+    RunWithoutInheritedEnv {
+        prog: &'a str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        workspace: Option<Workspace>,
+    },
+    ConnectorSetThrottleFrameCallbacks {
+        connector: Connector,
+        throttle: bool,
+    },
+    SetWorkspaceSwitchAnimation {
+        axis: Axis,
+        duration: Duration,
+    },
+    ConnectorSetHotplugPolicy {
+        connector: Connector,
+        policy: HotplugPolicy,
+    },
+    ConnectorAcceptHotplug {
+        connector: Connector,
+    },
+    ConnectorSetBrightness {
+        connector: Connector,
+        percent: f64,
+    },
+    ConnectorGetBrightness {
+        connector: Connector,
+    },
+    GetOutputLayout,
+    ApplyOutputLayout {
+        layout: OutputLayout,
+    },
+    SetShortcutInhibitBreak {
+        seat: Seat,
+        mods: Modifiers,
+        sym: KeySym,
+    },
+    GetIdleInhibitors,
+    SetIdleInhibited {
+        inhibited: Option<bool>,
+    },
+    ConnectorSetNonDesktop {
+        connector: Connector,
+        value: bool,
+    },
+    GetSurfaceInfo {
+        seat: Seat,
+    },
+    SetPersistentClipboard {
+        enabled: bool,
+    },
+    SetMaxClients {
+        max: Option<u32>,
+    },
+    SetMaxConnectionRate {
+        rate: Option<u32>,
+    },
+    GetClientCount,
+    SetButtonMap {
+        device: InputDevice,
+        map: Vec<(u32, u32)>,
+    },
+    GetCompositorInfo,
+    GetSeatIdleTime {
+        seat: Seat,
+    },
+    SeatPushKeymap {
+        seat: Seat,
+        keymap: Keymap,
+    },
+    SeatPopKeymap {
+        seat: Seat,
+    },
+    ConnectorModes {
+        connector: Connector,
+    },
+    SetFloatingDefaults {
+        width: i32,
+        height: i32,
+        placement: FloatPlacement,
+    },
+    SetSticky {
+        seat: Seat,
+        sticky: bool,
+    },
+    SetAutoDestroyEmptyWorkspaces {
+        enabled: bool,
+    },
+    FocusOutput {
+        seat: Seat,
+        connector: Connector,
+    },
+    SetMovePointerOnOutputFocus {
+        seat: Seat,
+        enabled: bool,
+    },
+    SetWindowBorderColor {
+        seat: Seat,
+        color: Option<Color>,
+    },
+    SetActiveFont {
+        font: Option<&'a str>,
+    },
+    CycleWindowState {
+        seat: Seat,
+    },
+    SetWindowStateCycle {
+        states: Vec<WindowState>,
+    },
+    SetXwaylandScale {
+        scale: f64,
+    },
+    SetXwaylandLazy {
+        enabled: bool,
+    },
+    GetFocusedWindowInfo {
+        seat: Seat,
+    },
+    SetFloatModifier {
+        seat: Seat,
+        mods: Modifiers,
+    },
+    RunOnRenderDevice {
+        prog: &'a str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        inherit_env: bool,
+        device: DrmDevice,
+    },
+    DisableModifier {
+        format_drm: u32,
+        modifier: u64,
+    },
+    GetFormatCapabilities {
+        format_drm: u32,
+    },
+    SetLinearBlending {
+        enabled: bool,
+    },
+    SetTitleGradient {
+        color: Option<Color>,
+    },
+    SetBorderAntialiasing {
+        enabled: bool,
+    },
+    SetCommitThrottling {
+        enabled: bool,
+    },
+    ConnectorFreeze {
+        connector: Connector,
+        frozen: bool,
+    },
+    GetDrmDeviceRenderNode {
+        device: DrmDevice,
+    },
+    GetDrmDeviceGfxApi {
+        device: DrmDevice,
+    },
+    GetDrmDeviceSupportedGfxApis {
+        device: DrmDevice,
+    },
+    TrySetGfxApi {
+        device: DrmDevice,
+        api: GfxApi,
+    },
+    RunGfxSelfTest {
+        device: DrmDevice,
+    },
+    SetDummyOutputColor {
+        color: Option<Color>,
+    },
+    FlushLayout,
+    GetWindowInfo {
+        window: Window,
+    },
+    FocusWindow {
+        window: Window,
+        seat: Seat,
+    },
+    CloseWindow {
+        window: Window,
+    },
+    MoveWindowToWorkspace {
+        window: Window,
+        workspace: Workspace,
+    },
+    GetPointer {
+        seat: Seat,
+    },
+    SetPointerPosition {
+        seat: Seat,
+        x: i32,
+        y: i32,
+    },
+    SetHotplugDebounceMs {
+        ms: u64,
+    },
+    GetClients,
+    KillClient {
+        client: Client,
+    },
+    SetEmptyWorkspaceAction {
+        action: EmptyWorkspaceAction,
+    },
+    SetInputDeviceConnector {
+        device: InputDevice,
+        connector: Connector,
+    },
+    SetWorkspacePersistent {
+        workspace: Workspace,
+        persistent: bool,
+    },
+    ProgramTimer2 {
+        timer: Timer,
+        initial: Option<Duration>,
+        periodic: Option<Duration>,
+        missed: MissedTicks,
+    },
+    SetClipboard {
+        seat: Seat,
+        mime_type: &'a str,
+        data: &'a [u8],
+    },
+    GetClipboard {
+        seat: Seat,
+        mime_type: &'a str,
+    },
 }
 
 #[derive(Serialize, Deserialize, Debug)]
@@ -454,6 +751,101 @@ pub enum Response {
     GetWorkspaceCapture {
         capture: bool,
     },
+    ValidateOutputLayout {
+        overlapping: bool,
+        gaps: bool,
+    },
+    GetGpuTime {
+        time: Option<Duration>,
+    },
+    MaxTextureSize {
+        size: Option<(u32, u32)>,
+    },
+    GetInFlightFrames {
+        frames: usize,
+    },
+    GetContentType {
+        content_type: Option<ContentType>,
+    },
+    FramebufferInfo {
+        info: Option<FramebufferInfo>,
+    },
+    RecentLogs {
+        lines: Vec<String>,
+    },
+    GetFocusedWindow {
+        window: Option<FocusedWindow>,
+    },
+    TimerRemaining {
+        remaining: Option<Duration>,
+    },
+    GetEnv {
+        val: Option<String>,
+    },
+    ConnectorGetBrightness {
+        percent: Option<f64>,
+    },
+    GetOutputLayout {
+        layout: OutputLayout,
+    },
+    GetIdleInhibitors {
+        inhibitors: Vec<IdleInhibitor>,
+    },
+    GetSurfaceInfo {
+        info: Option<SurfaceInfo>,
+    },
+    GetClientCount {
+        count: u32,
+    },
+    GetCompositorInfo {
+        info: CompositorInfo,
+    },
+    GetSeatIdleTime {
+        ms: u64,
+    },
+    ConnectorModes {
+        modes: Vec<(i32, i32, u32)>,
+    },
+    GetFocusedWindowInfo {
+        info: Option<WindowInfo>,
+    },
+    GetFormatCapabilities {
+        capabilities: Option<FormatCapabilities>,
+    },
+    GetDrmDeviceRenderNode {
+        render_node: String,
+    },
+    GetDrmDeviceGfxApi {
+        api: GfxApi,
+    },
+    GetDrmDeviceSupportedGfxApis {
+        apis: Vec<GfxApi>,
+    },
+    TrySetGfxApi {
+        success: bool,
+    },
+    GfxSelfTest {
+        passed: bool,
+        details: String,
+    },
+    GetWindowInfo {
+        info: Option<WindowInfo>,
+    },
+    GetPointer {
+        info: PointerInfo,
+    },
+    GetClients {
+        clients: Vec<ClientInfo>,
+    },
+    GetClipboard {
+        data: Option<Vec<u8>>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct FocusedWindow {
+    pub window: Window,
+    pub title: String,
 }
 
 #[derive(Serialize, Deserialize, Debug)]
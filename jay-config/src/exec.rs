@@ -1,6 +1,9 @@
 //! Tools for spawning programs.
 
-use std::collections::HashMap;
+use {
+    crate::{video::DrmDevice, Workspace},
+    std::collections::HashMap,
+};
 
 /// Sets an environment variable.
 ///
@@ -9,11 +12,28 @@ pub fn set_env(key: &str, val: &str) {
     get!().set_env(key, val);
 }
 
+/// Unsets an environment variable.
+///
+/// This does not affect the compositor itself but only programs spawned by the compositor.
+pub fn unset_env(key: &str) {
+    get!().unset_env(key);
+}
+
+/// Returns the value of an environment variable as seen by programs spawned by the
+/// compositor.
+///
+/// This includes variables set by the compositor itself, such as `WAYLAND_DISPLAY`.
+/// Returns `None` if the variable is not set or if its value is not valid UTF-8.
+pub fn get_env(key: &str) -> Option<String> {
+    get!(None).get_env(key)
+}
+
 /// A command to be spawned.
 pub struct Command {
     pub(crate) prog: String,
     pub(crate) args: Vec<String>,
     pub(crate) env: HashMap<String, String>,
+    pub(crate) inherit_env: bool,
 }
 
 impl Command {
@@ -28,6 +48,7 @@ impl Command {
             prog: prog.to_string(),
             args: vec![],
             env: Default::default(),
+            inherit_env: true,
         }
     }
 
@@ -43,8 +64,39 @@ impl Command {
         self
     }
 
+    /// Sets whether the command inherits the environment of the compositor.
+    ///
+    /// This is `true` by default. If it is `false`, the command only sees the variables
+    /// set via [`Command::env`] and the `WAYLAND_DISPLAY` and `DISPLAY` variables that
+    /// are required to connect to the compositor.
+    pub fn inherit_env(&mut self, inherit: bool) -> &mut Self {
+        self.inherit_env = inherit;
+        self
+    }
+
     /// Executes the command.
     pub fn spawn(&self) {
         get!().spawn(self);
     }
+
+    /// Executes the command and maps its first window on the given workspace.
+    ///
+    /// Only windows created by the spawned process itself are affected. If the
+    /// process hands off to another process, the window is mapped as usual.
+    pub fn spawn_on_workspace(&self, workspace: Workspace) {
+        get!().spawn_on_workspace(self, workspace);
+    }
+
+    /// Executes the command and asks its clients to render on the given device.
+    ///
+    /// The default dmabuf feedback of the clients of the spawned process gets a preferred
+    /// tranche targeting the device, and `DRI_PRIME` is set to its PCI id unless the
+    /// command sets it itself. The tranche only contains linear buffers and the
+    /// modifiers that both the device and the render device of the compositor
+    /// support. The render device of the compositor remains the main device.
+    ///
+    /// Only clients created by the spawned process itself are affected.
+    pub fn spawn_on_render_device(&self, device: DrmDevice) {
+        get!().spawn_on_render_device(self, device);
+    }
 }
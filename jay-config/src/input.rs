@@ -6,8 +6,10 @@ pub mod capability;
 use {
     crate::{
         input::{acceleration::AccelProfile, capability::Capability},
-        keyboard::Keymap,
-        Axis, Direction, ModifiedKeySym, Workspace,
+        keyboard::{mods::Modifiers, Keymap},
+        theme::Color,
+        video::{Connector, ContentType},
+        Axis, Direction, ModifiedKeySym, WindowInfo, Workspace,
     },
     serde::{Deserialize, Serialize},
     std::time::Duration,
@@ -85,6 +87,32 @@ impl InputDevice {
         get!().set_px_per_wheel_scroll(self, px);
     }
 
+    /// Remaps the buttons of the device.
+    ///
+    /// Each entry `(physical, logical)` causes presses of the button `physical` to be
+    /// reported as presses of the button `logical`. Buttons are identified by their evdev
+    /// codes, e.g. `0x110` for the left button or `0x113` for the first side button.
+    /// Buttons that do not appear in the map are not affected. Each physical button can
+    /// appear at most once and no two buttons may be reported as the same logical button.
+    /// For example, to swap two buttons, both of them have to appear in the map.
+    ///
+    /// Buttons that are pressed while the map changes are released with the logical
+    /// button that was used for the press.
+    ///
+    /// Calling this function replaces the previous map. An empty map restores the default.
+    pub fn set_button_map(self, map: &[(u32, u32)]) {
+        get!().set_button_map(self, map);
+    }
+
+    /// Maps the device to the output of a connector.
+    ///
+    /// Touch events of the device are mapped to the output of this connector. If no
+    /// connector has been set, or the connector currently has no output, they are mapped
+    /// to the output that contains the pointer.
+    pub fn set_connector(self, connector: Connector) {
+        get!().set_input_device_connector(self, connector);
+    }
+
     /// Sets whether tap-to-click is enabled for this device.
     ///
     /// See <https://wayland.freedesktop.org/libinput/doc/latest/tapping.html>
@@ -114,6 +142,47 @@ impl InputDevice {
     }
 }
 
+/// A snapshot of the state of a surface, intended for debugging.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct SurfaceInfo {
+    /// The role of the surface, e.g. `xdg_toplevel` or `subsurface`.
+    pub role: String,
+    /// The format of the attached buffer, if any.
+    pub buffer_format: Option<String>,
+    /// The width of the attached buffer in pixels.
+    pub buffer_width: i32,
+    /// The height of the attached buffer in pixels.
+    pub buffer_height: i32,
+    /// The number of times the client has committed the surface.
+    pub commits: u64,
+}
+
+/// Identifies a surface of a client.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub struct SurfaceId {
+    /// The id of the client.
+    pub client: u64,
+    /// The wire id of the `wl_surface` object within the client.
+    pub surface: u32,
+}
+
+/// A snapshot of the state of the pointer of a seat, intended for debugging.
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct PointerInfo {
+    /// The x coordinate of the pointer in the global compositor space.
+    pub x: f64,
+    /// The y coordinate of the pointer in the global compositor space.
+    pub y: f64,
+    /// The surface under the pointer, if any.
+    pub surface: Option<SurfaceId>,
+    /// The surface that currently receives pointer events, if any.
+    ///
+    /// This differs from `surface` while a button is held down or while the pointer is
+    /// grabbed. It is `None` while the compositor itself handles the pointer, that is,
+    /// during drag-and-drop and while a floating window is being moved or resized.
+    pub focus: Option<SurfaceId>,
+}
+
 /// A seat.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Seat(pub u64);
@@ -170,6 +239,23 @@ impl Seat {
         get!().unbind(self, mod_sym)
     }
 
+    /// Returns information about the surface that has the keyboard focus of this seat.
+    pub fn focused_surface_info(self) -> Option<SurfaceInfo> {
+        get!(None).focused_surface_info(self)
+    }
+
+    /// Sets the key combination that breaks a keyboard shortcuts inhibitor.
+    ///
+    /// Applications can ask the compositor to forward all key presses to them instead of
+    /// invoking hotkeys, for example virtual machine viewers. While such an application is
+    /// focused, pressing this combination restores the hotkeys until the application is
+    /// focused again.
+    ///
+    /// The default is `LOGO | SYM_Escape`.
+    pub fn set_shortcut_inhibit_break<T: Into<ModifiedKeySym>>(self, mod_sym: T) {
+        get!().set_shortcut_inhibit_break(self, mod_sym)
+    }
+
     /// Moves the keyboard focus of the seat in the specified direction.
     pub fn focus(self, direction: Direction) {
         get!().focus(self, direction)
@@ -185,6 +271,22 @@ impl Seat {
         get!().seat_set_keymap(self, keymap)
     }
 
+    /// Activates a keymap and remembers the previously active keymap.
+    ///
+    /// The previous keymap can be restored with [`Seat::pop_keymap`]. Keys that are held
+    /// down are released before the keymap is changed.
+    pub fn push_keymap(self, keymap: Keymap) {
+        get!().seat_push_keymap(self, keymap)
+    }
+
+    /// Restores the keymap that was active before the last call to [`Seat::push_keymap`].
+    ///
+    /// Does nothing if no keymap has been pushed. Keys that are held down are released
+    /// before the keymap is changed.
+    pub fn pop_keymap(self) {
+        get!().seat_pop_keymap(self)
+    }
+
     /// Returns the repeat rate of the seat.
     ///
     /// The returned tuple is `(rate, delay)` where `rate` is the number of times keys repeat per second
@@ -198,6 +300,27 @@ impl Seat {
         get!().seat_set_repeat_rate(self, rate, delay)
     }
 
+    /// Sets the selection of the seat to the given contents.
+    ///
+    /// The selection is owned by the compositor and offered with the given mime type.
+    pub fn set_clipboard(self, mime_type: &str, data: &[u8]) {
+        get!().seat_set_clipboard(self, mime_type, data)
+    }
+
+    /// Returns the contents of the selection of the seat for the given mime type.
+    ///
+    /// Only contents that the compositor has a copy of are returned. These are the
+    /// contents set with [`Seat::set_clipboard`] and, if the persistent clipboard is
+    /// enabled, the textual contents of client selections once they have been read.
+    pub fn get_clipboard(self, mime_type: &str) -> Option<Vec<u8>> {
+        get!(None).seat_get_clipboard(self, mime_type)
+    }
+
+    /// Returns the time that has passed since the last input event on this seat.
+    pub fn idle_time(self) -> Duration {
+        get!(Duration::ZERO).seat_idle_time(self)
+    }
+
     /// Returns whether the parent-container of the currently focused window is in mono-mode.
     pub fn mono(self) -> bool {
         get!(false).mono(self)
@@ -257,6 +380,60 @@ impl Seat {
         get!().set_floating(self, floating);
     }
 
+    /// Moves the keyboard focus to the active workspace of the output.
+    ///
+    /// The pointer only follows the focus if [`Seat::set_move_pointer_on_output_focus`] is
+    /// enabled. Otherwise it stays where it is and functions that use the output containing
+    /// the cursor, such as [`Seat::get_workspace`], keep using the previous output.
+    pub fn focus_output(self, connector: Connector) {
+        get!().focus_output(self, connector);
+    }
+
+    /// Sets whether the pointer follows the keyboard focus to other outputs.
+    ///
+    /// If this is enabled and the focus moves to another output, either with
+    /// [`Seat::focus`] or with [`Seat::focus_output`], the pointer is moved to the center of
+    /// the newly focused window or, if there is none, to the center of the output.
+    ///
+    /// The default is `false`.
+    pub fn set_move_pointer_on_output_focus(self, enabled: bool) {
+        get!().set_move_pointer_on_output_focus(self, enabled);
+    }
+
+    /// Sets the modifier that allows floating windows to be moved and resized with the
+    /// pointer.
+    ///
+    /// While the modifier is held, dragging a floating window with the left button moves
+    /// it and dragging it with the right button resizes it from the nearest corner. The
+    /// window does not receive these pointer events.
+    ///
+    /// If `mods` is empty, this is disabled. The default is empty.
+    pub fn set_float_modifier(self, mods: Modifiers) {
+        get!().set_float_modifier(self, mods);
+    }
+
+    /// Overrides the border color of the currently focused window.
+    ///
+    /// If `color` is `None`, the window uses the border color of the theme again.
+    pub fn set_window_border_color(self, color: Option<Color>) {
+        get!().set_window_border_color(self, color);
+    }
+
+    /// Moves the currently focused window to its next state.
+    ///
+    /// By default, windows move from tiled to floating to fullscreen and back to tiled. The
+    /// order can be changed with [`set_window_state_cycle`](crate::set_window_state_cycle).
+    pub fn cycle_window_state(self) {
+        get!().cycle_window_state(self);
+    }
+
+    /// Sets whether the currently focused window is shown on all workspaces of its output.
+    ///
+    /// Making a tiled window sticky also makes it floating.
+    pub fn set_sticky(self, sticky: bool) {
+        get!().set_sticky(self, sticky);
+    }
+
     /// Toggles whether the currently focused window is floating.
     ///
     /// You can do the same by double-clicking on the header.
@@ -300,10 +477,42 @@ impl Seat {
         get!().set_fullscreen(self, fullscreen)
     }
 
+    /// Returns the content type of the currently focused window.
+    ///
+    /// Returns `None` if the window has not declared a content type.
+    pub fn content_type(self) -> Option<ContentType> {
+        get!(None).get_content_type(self)
+    }
+
+    /// Returns information about the currently focused window.
+    pub fn focused_window(self) -> Option<WindowInfo> {
+        get!(None).get_focused_window_info(self)
+    }
+
     /// Disables the currently active pointer constraint on this seat.
     pub fn disable_pointer_constraint(self) {
         get!().disable_pointer_constraint(self)
     }
+
+    /// Moves the cursor of this seat to the center of the currently focused window.
+    ///
+    /// This has no effect if no window is focused.
+    pub fn center_pointer_on_focus(self) {
+        get!().center_pointer_on_focus(self)
+    }
+
+    /// Returns information about the pointer of this seat.
+    pub fn pointer_info(self) -> PointerInfo {
+        get!().pointer_info(self)
+    }
+
+    /// Moves the cursor of this seat to the position in the global compositor space.
+    ///
+    /// If the position is not contained in any output, the cursor is moved to the closest
+    /// point of the nearest output instead.
+    pub fn set_pointer_position(self, x: i32, y: i32) {
+        get!().set_pointer_position(self, x, y)
+    }
 }
 
 /// Returns all seats.
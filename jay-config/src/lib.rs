@@ -41,9 +41,12 @@
 )]
 
 use {
-    crate::keyboard::ModifiedKeySym,
+    crate::{input::Seat, keyboard::ModifiedKeySym, video::GfxApi},
     serde::{Deserialize, Serialize},
-    std::fmt::{Debug, Display, Formatter},
+    std::{
+        fmt::{Debug, Display, Formatter},
+        time::Duration,
+    },
 };
 
 #[macro_use]
@@ -99,6 +102,12 @@ pub fn switch_to_vt(n: u32) {
 /// Reloads the configuration.
 ///
 /// If the configuration cannot be reloaded, this function has no effect.
+///
+/// Reloading only discards state owned by the configuration: shortcuts, timers,
+/// callbacks, and all handles such as [`Keymap`](keyboard::Keymap) that were handed out to
+/// the old configuration. Windows, workspaces, seats, outputs, and settings applied to them
+/// are preserved. The new configuration is then executed with [`is_reload`] returning
+/// `true` and should re-register everything it needs.
 pub fn reload() {
     get!().reload()
 }
@@ -128,6 +137,146 @@ pub fn toggle_default_workspace_capture() {
     get.set_default_workspace_capture(!get.get_default_workspace_capture());
 }
 
+/// Configures the animation played when switching the workspace shown on an output.
+///
+/// The outgoing and incoming workspaces slide along `axis` over `duration`. If the
+/// incoming workspace comes after the outgoing one on the output, it enters from the
+/// right or bottom, otherwise from the left or top.
+///
+/// A `duration` of zero disables the animation. This is the default.
+pub fn set_workspace_switch_animation(axis: Axis, duration: Duration) {
+    get!().set_workspace_switch_animation(axis, duration)
+}
+
+/// The strategy used to place new floating windows.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+pub enum FloatPlacement {
+    /// Centers the window on the output.
+    #[default]
+    Center,
+    /// Centers the window under the pointer.
+    Cursor,
+    /// Places each window slightly below and to the right of the previous one.
+    Cascade,
+}
+
+/// Sets the default size and placement of new floating windows.
+///
+/// The size is used for windows that do not request a size of their own. A `width` or
+/// `height` of zero uses half the size of the output. This is the default.
+///
+/// The default placement is [`FloatPlacement::Center`].
+pub fn set_floating_defaults(width: i32, height: i32, placement: FloatPlacement) {
+    get!().set_floating_defaults(width, height, placement)
+}
+
+/// A window state that can be reached with [`Seat::cycle_window_state`](input::Seat::cycle_window_state).
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
+pub enum WindowState {
+    /// The window is part of the tiling layout.
+    Tiled,
+    /// The window is floating.
+    Floating,
+    /// The window is fullscreen.
+    Fullscreen,
+}
+
+/// Sets the order in which [`Seat::cycle_window_state`](input::Seat::cycle_window_state)
+/// moves windows through their states.
+///
+/// States that are not part of the list are skipped. A window whose current state is not
+/// part of the list moves to the first state.
+///
+/// The default is `[Tiled, Floating, Fullscreen]`.
+pub fn set_window_state_cycle(states: &[WindowState]) {
+    get!().set_window_state_cycle(states)
+}
+
+/// Sets the scale at which X11 applications are rendered.
+///
+/// Xwayland can only render at integer scales. Fractional scales are rounded up and the
+/// windows are downscaled by the compositor. X11 applications see the scaled size of
+/// the outputs and `Xft.dpi` is set accordingly.
+///
+/// This takes effect the next time Xwayland is started.
+///
+/// The default is `1`.
+pub fn set_xwayland_scale(scale: f64) {
+    get!().set_xwayland_scale(scale)
+}
+
+/// Sets whether Xwayland is only started once an X11 client connects.
+///
+/// If this is disabled, Xwayland is started as soon as the display is allocated. This
+/// reduces the startup time of the first X11 application at the cost of memory.
+///
+/// This takes effect the next time Xwayland is started.
+///
+/// The default is `true`.
+pub fn set_xwayland_lazy(enabled: bool) {
+    get!().set_xwayland_lazy(enabled)
+}
+
+/// Sets whether workspaces are destroyed as soon as they become empty.
+///
+/// Empty workspaces are always destroyed when the output switches to another workspace.
+/// If this is enabled, a workspace that is not visible is also destroyed when its last
+/// window is closed or moved away. Workspaces marked with [`Workspace::set_persistent`]
+/// are never destroyed.
+///
+/// The default is `false`.
+pub fn set_auto_destroy_empty_workspaces(enabled: bool) {
+    get!().set_auto_destroy_empty_workspaces(enabled)
+}
+
+/// An action performed when the last window on a visible workspace is closed or moved
+/// away.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub enum EmptyWorkspaceAction {
+    /// Do nothing.
+    #[default]
+    None,
+    /// Show the workspace that was visible on the output before.
+    ///
+    /// If that workspace no longer exists, a neighboring workspace on the same output is
+    /// shown instead. The empty workspace is then destroyed.
+    SwitchToPrevious,
+    /// Run a program.
+    ///
+    /// The first window of the program is mapped on the empty workspace. Use
+    /// [`EmptyWorkspaceAction::run`] to create this action from a [`Command`](exec::Command).
+    Run {
+        prog: String,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        inherit_env: bool,
+    },
+}
+
+impl EmptyWorkspaceAction {
+    /// Creates an action that runs the command.
+    pub fn run(command: &exec::Command) -> Self {
+        Self::Run {
+            prog: command.prog.clone(),
+            args: command.args.clone(),
+            env: command
+                .env
+                .iter()
+                .map(|(a, b)| (a.to_string(), b.to_string()))
+                .collect(),
+            inherit_env: command.inherit_env,
+        }
+    }
+}
+
+/// Sets the action performed when the last window on a visible workspace is closed or
+/// moved away.
+///
+/// The default is [`EmptyWorkspaceAction::None`].
+pub fn set_empty_workspace_action(action: EmptyWorkspaceAction) {
+    get!().set_empty_workspace_action(action)
+}
+
 /// A workspace.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Workspace(pub u64);
@@ -150,6 +299,16 @@ impl Workspace {
         get!(true).get_workspace_capture(self)
     }
 
+    /// Sets whether the workspace is kept when it is empty.
+    ///
+    /// Persistent workspaces are neither destroyed when the output switches to another
+    /// workspace nor by [`set_auto_destroy_empty_workspaces`].
+    ///
+    /// The default is `false`.
+    pub fn set_persistent(self, persistent: bool) {
+        get!().set_workspace_persistent(self, persistent)
+    }
+
     /// Toggles whether the workspaces is captured.
     pub fn toggle_capture(self) {
         let get = get!();
@@ -187,6 +346,229 @@ pub fn on_idle<F: Fn() + 'static>(f: F) {
     get!().on_idle(f)
 }
 
+/// An application that currently prevents the display from going idle.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct IdleInhibitor {
+    /// The app id of the inhibiting window, or the empty string if it has none.
+    pub app_id: String,
+    /// The process id of the client.
+    pub pid: i32,
+    /// The command name of the client process.
+    pub comm: String,
+}
+
+/// Information about the running compositor.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct CompositorInfo {
+    /// The version of the compositor.
+    pub version: String,
+    /// The number of seconds since the compositor was started.
+    pub uptime_secs: u64,
+    /// The graphics API used for rendering, if rendering has been initialized.
+    pub gfx_api: Option<GfxApi>,
+    /// The path of the render node used for rendering, if rendering has been initialized.
+    pub render_node: Option<String>,
+}
+
+/// Returns information about the running compositor.
+pub fn compositor_info() -> CompositorInfo {
+    get!(CompositorInfo::default()).compositor_info()
+}
+
+/// A window that did not respond to the compositor in time.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct UnresponsiveWindow {
+    /// The app id of the window, or the empty string if it has none.
+    pub app_id: String,
+    /// The title of the window.
+    pub title: String,
+    /// The process id of the client.
+    pub pid: i32,
+    /// The command name of the client process.
+    pub comm: String,
+}
+
+/// A window.
+///
+/// The id of a window is assigned by the compositor when the window is created. It does
+/// not change while the window exists and is never reused. It can therefore be stored
+/// and used to refer to the same window later.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct Window(pub u64);
+
+impl Window {
+    /// Returns whether this window still exists.
+    pub fn exists(self) -> bool {
+        self.info().is_some()
+    }
+
+    /// Returns information about this window.
+    ///
+    /// Returns `None` if the window no longer exists.
+    pub fn info(self) -> Option<WindowInfo> {
+        get!(None).get_window_info(self)
+    }
+
+    /// Gives the keyboard focus of the seat to this window.
+    ///
+    /// If the window is on a workspace that is not visible, the workspace is shown first.
+    pub fn focus(self, seat: Seat) {
+        get!().focus_window(self, seat)
+    }
+
+    /// Requests this window to close.
+    pub fn close(self) {
+        get!().close_window(self)
+    }
+
+    /// Moves this window to a workspace.
+    pub fn move_to_workspace(self, workspace: Workspace) {
+        get!().move_window_to_workspace(self, workspace)
+    }
+}
+
+/// Information about a window.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq, Eq)]
+pub struct WindowInfo {
+    /// The window.
+    pub window: Window,
+    /// The title of the window.
+    pub title: String,
+    /// The app id of the window, or the empty string if it has none.
+    pub app_id: String,
+    /// The class part of the `WM_CLASS` property of an X11 window.
+    ///
+    /// This is `None` for Wayland windows and for X11 windows without this property.
+    pub x11_class: Option<String>,
+    /// The instance part of the `WM_CLASS` property of an X11 window.
+    ///
+    /// This is `None` for Wayland windows and for X11 windows without this property.
+    pub x11_instance: Option<String>,
+}
+
+/// Sets the callback to be called when a window stops responding.
+///
+/// A window is considered unresponsive if it does not acknowledge a configuration
+/// change within 5 seconds. The callback is called once until the window responds
+/// again. It can, for example, ask the user whether the application should be killed.
+pub fn on_window_unresponsive<F: Fn(UnresponsiveWindow) + 'static>(f: F) {
+    get!().on_window_unresponsive(f)
+}
+
+/// Returns the applications that currently prevent the display from going idle.
+pub fn idle_inhibitors() -> Vec<IdleInhibitor> {
+    get!(vec![]).idle_inhibitors()
+}
+
+/// Overrides the idle inhibition requested by applications.
+///
+/// - `Some(true)` keeps the display from going idle regardless of the applications.
+/// - `Some(false)` lets the display go idle even if applications try to prevent it.
+/// - `None` restores the default behavior of following the applications.
+pub fn set_idle_inhibited(inhibited: Option<bool>) {
+    get!().set_idle_inhibited(inhibited)
+}
+
+/// Sets whether the clipboard contents survive the exit of the application that owns them.
+///
+/// If this is enabled, the compositor keeps a copy of small textual clipboard contents
+/// and continues to offer them after the application has exited. The contents are only
+/// copied once the clipboard has not changed for half a second. Other contents are
+/// unaffected.
+///
+/// The default is `false`.
+pub fn set_persistent_clipboard(enabled: bool) {
+    get!().set_persistent_clipboard(enabled)
+}
+
+/// Sets the maximum number of clients that can be connected at the same time.
+///
+/// New connections on the wayland socket are rejected while this many clients are
+/// connected. `None` removes the limit.
+///
+/// The default is `None`.
+pub fn set_max_clients(max: Option<u32>) {
+    get!().set_max_clients(max)
+}
+
+/// Sets the maximum number of new connections per second.
+///
+/// Connections on the wayland socket that exceed this rate are rejected. `None` removes
+/// the limit.
+///
+/// The default is `None`.
+pub fn set_max_connection_rate(rate: Option<u32>) {
+    get!().set_max_connection_rate(rate)
+}
+
+/// Performs all pending layout work immediately.
+///
+/// Changes to the tree, such as creating splits or mapping windows, are usually laid out
+/// asynchronously. After this function returns, the geometry of all windows reflects
+/// all previous requests.
+pub fn flush_layout() {
+    get!().flush_layout()
+}
+
+/// Sets whether commits of hidden surfaces are throttled.
+///
+/// If this is enabled, commits of surfaces that are not currently visible are applied at
+/// most once per refresh interval of their output. Commits that arrive faster are
+/// coalesced. This protects the compositor against applications that commit in a busy
+/// loop.
+///
+/// The default is `false`.
+pub fn set_commit_throttling(enabled: bool) {
+    get!().set_commit_throttling(enabled)
+}
+
+/// Returns the number of currently connected clients.
+pub fn client_count() -> u32 {
+    get!(0).client_count()
+}
+
+/// A client connected to the compositor.
+///
+/// Client ids are never reused.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Default, Hash, Eq, PartialEq)]
+pub struct Client(pub u64);
+
+impl Client {
+    /// Disconnects this client.
+    ///
+    /// The client is removed immediately without waiting for outstanding messages to be
+    /// sent.
+    pub fn kill(self) {
+        get!().kill_client(self)
+    }
+}
+
+/// Information about a connected client.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct ClientInfo {
+    /// The client.
+    pub client: Client,
+    /// The process id of the client.
+    pub pid: i32,
+    /// The user id of the client.
+    pub uid: u32,
+    /// The command name of the client process.
+    pub comm: String,
+    /// Whether the client connected through the privileged jay socket.
+    pub secure: bool,
+    /// Whether the client is Xwayland.
+    pub xwayland: bool,
+    /// The app ids of the xdg toplevels of the client.
+    pub app_ids: Vec<String>,
+    /// The number of wayland objects that the client has created.
+    pub objects: u64,
+}
+
+/// Returns a snapshot of the currently connected clients.
+pub fn clients() -> Vec<ClientInfo> {
+    get!(vec![]).clients()
+}
+
 /// Sets the callback to be called when all devices have been enumerated.
 ///
 /// This callback is only invoked once during the lifetime of the compositor. This is a
@@ -194,3 +576,14 @@ pub fn on_idle<F: Fn() + 'static>(f: F) {
 pub fn on_devices_enumerated<F: FnOnce() + 'static>(f: F) {
     get!().on_devices_enumerated(f)
 }
+
+/// Sets the callback to be called when the outputs have been initialized.
+///
+/// This callback is invoked once after the graphics have been initialized and every
+/// connector that was connected at that time has an output with a mode. If a hotplug
+/// debounce interval has been configured, connectors whose connection is still being
+/// debounced delay this callback until they have either created their output or been
+/// ignored. This is a good place to restore a saved output layout.
+pub fn on_outputs_ready<F: FnOnce() + 'static>(f: F) {
+    get!().on_outputs_ready(f)
+}
@@ -14,3 +14,42 @@ pub enum LogLevel {
     Debug,
     Trace,
 }
+
+/// Sets the log level of the compositor.
+///
+/// This does not affect subsystems whose log level was set with
+/// [set_subsystem_log_level].
+pub fn set_log_level(level: LogLevel) {
+    get!().set_log_level(None, level)
+}
+
+/// Sets the log level of a subsystem of the compositor.
+///
+/// The subsystem can be one of the following names:
+///
+/// - `vulkan`
+/// - `opengl`
+/// - `metal`
+/// - `x11`
+/// - `input`
+/// - `xwayland`
+/// - `wayland`
+/// - `config`
+/// - `pipewire`
+/// - `portal`
+/// - `dbus`
+///
+/// Alternatively, it can be a module path such as `jay::ifs::wl_surface`.
+///
+/// Messages logged by the configuration belong to the `config` subsystem.
+pub fn set_subsystem_log_level(subsystem: &str, level: LogLevel) {
+    get!().set_log_level(Some(subsystem), level)
+}
+
+/// Returns up to `max_lines` of the most recent lines of the compositor log.
+///
+/// The lines are returned oldest first. The compositor only retains a limited number of
+/// lines and truncates very long lines.
+pub fn recent_logs(max_lines: usize) -> Vec<String> {
+    get!(vec![]).recent_logs(max_lines)
+}
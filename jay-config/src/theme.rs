@@ -133,6 +133,29 @@ pub fn reset_colors() {
     get!().reset_colors();
 }
 
+/// Sets the color that title backgrounds fade into.
+///
+/// If this is set, title backgrounds are drawn as a horizontal gradient from their
+/// background color on the left to this color on the right. `None` disables the
+/// gradient.
+///
+/// Default: `None`. This is reset by `reset_colors`.
+pub fn set_title_gradient(color: Option<Color>) {
+    get!().set_title_gradient(color);
+}
+
+/// Sets whether window borders are anti-aliased.
+///
+/// At fractional scales, the edges of borders usually do not fall on pixel boundaries.
+/// By default they are rounded to the nearest pixel which can cause borders to change
+/// their width as windows move. If this is enabled, the edges are placed exactly and
+/// partially covered pixels are blended.
+///
+/// Default: `false`. This currently only has an effect with the Vulkan API.
+pub fn set_border_antialiasing(enabled: bool) {
+    get!().set_border_antialiasing(enabled);
+}
+
 /// Returns the current font.
 pub fn get_font() -> String {
     get!().get_font()
@@ -151,11 +174,24 @@ pub fn set_font(font: &str) {
 
 /// Resets the font to the default.
 ///
-/// Currently the default is `monospace 8`.
+/// Currently the default is `monospace 8`. This also removes the font set with
+/// [`set_active_font`].
 pub fn reset_font() {
     get!().reset_font()
 }
 
+/// Sets the font used for the titles of focused windows.
+///
+/// If `font` is `None`, focused windows use the font set with [`set_font`]. This is the
+/// default.
+///
+/// The font name should be specified in [pango][pango] syntax.
+///
+/// [pango]: https://docs.gtk.org/Pango/type_func.FontDescription.from_string.html
+pub fn set_active_font(font: Option<&str>) {
+    get!().set_active_font(font)
+}
+
 /// Elements of the compositor whose color can be changed.
 pub mod colors {
     use {
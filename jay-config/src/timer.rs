@@ -9,6 +9,17 @@ use {
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq)]
 pub struct Timer(pub u64);
 
+/// How a repeated timer handles ticks that were missed while the compositor was busy or
+/// the system was suspended.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Hash, Eq, PartialEq, Default)]
+pub enum MissedTicks {
+    /// The timer fires once regardless of how many ticks were missed.
+    #[default]
+    Coalesce,
+    /// The timer fires once for each missed tick.
+    CatchUp,
+}
+
 /// Creates a new timer or returns an existing one.
 ///
 /// Timers are identified by their name and their lifetime is bound by the lifetime of
@@ -26,14 +37,40 @@ pub fn get_timer(name: &str) -> Timer {
 impl Timer {
     /// Programs the timer to fire once.
     pub fn once(self, initial: Duration) {
-        get!().program_timer(self, Some(initial), None);
+        get!().program_timer(self, Some(initial), None, MissedTicks::Coalesce);
     }
 
     /// Programs the timer to fire repeatedly.
     ///
     /// `initial` is the period after which the timer expires for the first time.
+    ///
+    /// Missed ticks are coalesced. See [`Timer::repeated_with_missed_ticks`].
     pub fn repeated(self, initial: Duration, period: Duration) {
-        get!().program_timer(self, Some(initial), Some(period));
+        self.repeated_with_missed_ticks(initial, period, MissedTicks::Coalesce);
+    }
+
+    /// Programs the timer to fire repeatedly and sets how it handles missed ticks.
+    pub fn repeated_with_missed_ticks(
+        self,
+        initial: Duration,
+        period: Duration,
+        missed: MissedTicks,
+    ) {
+        get!().program_timer(self, Some(initial), Some(period), missed);
+    }
+
+    /// Returns the time until the timer next expires.
+    ///
+    /// Returns `None` if the timer is not programmed.
+    pub fn remaining(self) -> Option<Duration> {
+        get!(None).timer_remaining(self)
+    }
+
+    /// Fires the timer immediately.
+    ///
+    /// This does not affect when the timer next expires.
+    pub fn trigger(self) {
+        get!().trigger_timer(self);
     }
 
     /// Cancels the timer.
@@ -41,7 +78,7 @@ impl Timer {
     /// The timer remains valid but will never expire. It can be reprogrammed by calling
     /// `once` or `repeated`.
     pub fn cancel(self) {
-        get!().program_timer(self, None, None);
+        get!().program_timer(self, None, None, MissedTicks::Coalesce);
     }
 
     /// Removes the time.
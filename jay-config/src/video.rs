@@ -2,6 +2,7 @@
 
 use {
     crate::{
+        theme::Color,
         video::connector_type::{
             ConnectorType, CON_9PIN_DIN, CON_COMPONENT, CON_COMPOSITE, CON_DISPLAY_PORT, CON_DPI,
             CON_DSI, CON_DVIA, CON_DVID, CON_DVII, CON_EDP, CON_EMBEDDED_WINDOW, CON_HDMIA,
@@ -11,7 +12,7 @@ use {
         PciId,
     },
     serde::{Deserialize, Serialize},
-    std::str::FromStr,
+    std::{str::FromStr, time::Duration},
 };
 
 /// The mode of a connector.
@@ -41,7 +42,8 @@ impl Mode {
 
     /// Returns the refresh rate of the mode in mhz.
     ///
-    /// For a 60hz monitor, this function would return 60_000.
+    /// For a 60hz monitor, this function would return 60_000. Fractional rates are reported
+    /// exactly, e.g. 59_940 for a 59.94hz mode.
     pub fn refresh_rate(&self) -> u32 {
         self.refresh_millihz
     }
@@ -112,6 +114,14 @@ impl Connector {
         get!(Mode::zeroed()).connector_mode(self)
     }
 
+    /// Returns the modes supported by the connected monitor.
+    pub fn modes(self) -> Vec<Mode> {
+        if !self.exists() {
+            return vec![];
+        }
+        get!(vec![]).connector_modes(self)
+    }
+
     /// Returns the logical width of the connector.
     ///
     /// The returned value will be different from `mode().width()` if the scale is not 1.
@@ -139,7 +149,8 @@ impl Connector {
     /// Any reasonable values for `x` and `y` should work.
     ///
     /// This function allows the connector to overlap with other connectors, however, such
-    /// configurations are not supported and might result in unexpected behavior.
+    /// configurations are not supported and might result in unexpected behavior. Use
+    /// [validate_output_layout] to check the resulting arrangement.
     pub fn set_position(self, x: i32, y: i32) {
         if !self.exists() {
             log::warn!("set_position called on a connector that does not exist");
@@ -159,6 +170,96 @@ impl Connector {
         get!().connector_set_enabled(self, enabled);
     }
 
+    /// Freezes or thaws the contents of the connector.
+    ///
+    /// While a connector is frozen, it keeps displaying the last presented frame and
+    /// no new frames are presented. The display stays on and applications and input
+    /// continue to be processed. Applications keep receiving frame callbacks at the
+    /// refresh rate, but their presentation feedback reports the frames as discarded.
+    /// Once the connector is thawed, the current contents are presented.
+    ///
+    /// This currently only has an effect on connectors of DRM devices.
+    pub fn set_frozen(self, frozen: bool) {
+        if !self.exists() {
+            log::warn!("set_frozen called on a connector that does not exist");
+            return;
+        }
+        get!().connector_freeze(self, frozen);
+    }
+
+    /// Sets whether frame callbacks of surfaces on this connector are throttled.
+    ///
+    /// If this is enabled, clients receive frame callbacks at most once per refresh
+    /// cycle of the connector, and surfaces that are completely covered by an opaque
+    /// floating window stacked above them receive no frame callbacks until they become
+    /// visible again.
+    ///
+    /// The default is `false`.
+    pub fn set_throttle_frame_callbacks(self, throttle: bool) {
+        if !self.exists() {
+            log::warn!("set_throttle_frame_callbacks called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_throttle_frame_callbacks(self, throttle);
+    }
+
+    /// Overrides whether this connector is a non-desktop output.
+    ///
+    /// Non-desktop outputs, such as VR headsets, are not part of the desktop layout and do
+    /// not host workspaces. They are also not reported as connected outputs. By default,
+    /// this is determined by the `non-desktop` property of the connector.
+    pub fn set_non_desktop(self, non_desktop: bool) {
+        if !self.exists() {
+            log::warn!("set_non_desktop called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_non_desktop(self, non_desktop);
+    }
+
+    /// Sets how hotplug events of this connector are handled.
+    ///
+    /// See [`HotplugPolicy`]. The default is [`HotplugPolicy::Auto`].
+    pub fn set_hotplug_policy(self, policy: HotplugPolicy) {
+        if !self.exists() {
+            log::warn!("set_hotplug_policy called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_hotplug_policy(self, policy);
+    }
+
+    /// Applies a hotplug event that was held back by the hotplug policy of this connector.
+    ///
+    /// This should be called from the callback registered with [`on_connector_hotplug`].
+    /// It does nothing if no hotplug event is pending.
+    pub fn accept_hotplug(self) {
+        if !self.exists() {
+            return;
+        }
+        get!().connector_accept_hotplug(self);
+    }
+
+    /// Sets the brightness of the backlight of this connector in percent.
+    ///
+    /// The value is clamped to `[0, 100]`. This only works for connectors that have a
+    /// backlight, usually the internal panel of a laptop.
+    pub fn set_brightness(self, percent: f64) {
+        if !self.exists() {
+            log::warn!("set_brightness called on a connector that does not exist");
+            return;
+        }
+        get!().connector_set_brightness(self, percent);
+    }
+
+    /// Returns the brightness of the backlight of this connector in percent.
+    ///
+    /// Returns `None` if the connector does not have a backlight.
+    pub fn brightness(self) -> Option<f64> {
+        if !self.exists() {
+            return None;
+        }
+        get!(None).connector_get_brightness(self)
+    }
+
     /// Sets the transformation to apply to the content of this connector.
     pub fn set_transform(self, transform: Transform) {
         if !self.exists() {
@@ -167,9 +268,146 @@ impl Connector {
         }
         get!().connector_set_transform(self, transform);
     }
+
+    /// Makes this connector the primary output.
+    ///
+    /// The primary output is used to place new windows, workspaces, and layer surfaces
+    /// if no seat is positioned on an output. If this connector is not connected, the
+    /// setting takes effect once it becomes connected.
+    pub fn set_primary(self) {
+        if !self.exists() {
+            log::warn!("set_primary called on a connector that does not exist");
+            return;
+        }
+        get!().set_primary_output(self);
+    }
+
+    /// Returns information about the framebuffer currently being displayed by this
+    /// connector.
+    ///
+    /// Returns `None` if the connector is not displaying anything or if the backend does
+    /// not support this query.
+    pub fn framebuffer_info(self) -> Option<FramebufferInfo> {
+        if !self.exists() {
+            return None;
+        }
+        get!(None).connector_get_framebuffer_info(self)
+    }
+}
+
+/// Information about the framebuffer currently being displayed by a connector.
+///
+/// See [Connector::framebuffer_info].
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq)]
+pub struct FramebufferInfo {
+    /// The width of the framebuffer.
+    pub width: i32,
+    /// The height of the framebuffer.
+    pub height: i32,
+    /// The DRM fourcc code of the format of the framebuffer.
+    pub format_drm: u32,
+    /// The DRM format modifier of the framebuffer.
+    pub modifier: u64,
+    /// Whether the framebuffer is a client buffer that is being scanned out directly.
+    pub direct_scanout_active: bool,
+}
+
+/// The result of validating the arrangement of all connected outputs.
+///
+/// See [validate_output_layout].
+#[derive(Copy, Clone, Debug, Default, Eq, PartialEq)]
+pub struct OutputLayoutValidation {
+    /// Whether any two outputs overlap.
+    pub overlapping: bool,
+    /// Whether the outputs do not form a single connected area.
+    ///
+    /// If this is `true`, there are outputs that cannot be reached by moving the cursor
+    /// from another output.
+    pub gaps: bool,
+}
+
+impl OutputLayoutValidation {
+    /// Returns whether the layout has neither overlaps nor gaps.
+    pub fn is_valid(&self) -> bool {
+        !self.overlapping && !self.gaps
+    }
+}
+
+/// Checks whether the current positions of the connected outputs overlap or leave gaps.
+pub fn validate_output_layout() -> OutputLayoutValidation {
+    get!(Default::default()).validate_output_layout()
+}
+
+/// A description of the arrangement of all outputs.
+///
+/// This type can be serialized to persist the layout and later be restored with
+/// [apply_output_layout].
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct OutputLayout {
+    /// The outputs in the layout.
+    pub outputs: Vec<OutputLayoutEntry>,
+}
+
+/// The description of a single output in an [OutputLayout].
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct OutputLayoutEntry {
+    /// The kernel name of the connector, e.g. `eDP-1`.
+    pub connector: String,
+    /// The manufacturer of the monitor.
+    pub manufacturer: String,
+    /// The model of the monitor.
+    pub model: String,
+    /// The serial number of the monitor.
+    pub serial_number: String,
+    /// The width of the mode in pixels.
+    pub width: i32,
+    /// The height of the mode in pixels.
+    pub height: i32,
+    /// The refresh rate of the mode in mhz.
+    pub refresh_millihz: u32,
+    /// The x coordinate of the top-left corner of the output.
+    pub x: i32,
+    /// The y coordinate of the top-left corner of the output.
+    pub y: i32,
+    /// The scale of the output.
+    pub scale: f64,
+    /// The transform of the output.
+    pub transform: Transform,
+    /// Whether the connector is enabled.
+    pub enabled: bool,
+}
+
+/// Returns the current arrangement of all outputs.
+///
+/// Connectors that are neither connected nor disabled are not part of the layout.
+pub fn get_output_layout() -> OutputLayout {
+    get!(Default::default()).get_output_layout()
+}
+
+/// Applies a layout previously returned by [get_output_layout].
+///
+/// Outputs are matched by their manufacturer, model, and serial number. Entries whose
+/// monitor is not connected are matched by the name of the connector. Entries that match
+/// no connector are ignored.
+///
+/// The layout is validated before any output is changed. If any entry is invalid, no
+/// change is made. An entry is invalid if it refers to the same connector or monitor as
+/// another entry or if its monitor does not support the mode of the entry.
+pub fn apply_output_layout(layout: &OutputLayout) {
+    get!().apply_output_layout(layout)
+}
+
+/// Arranges all connected outputs left-to-right in connector order.
+///
+/// The outputs are aligned at the top edge and placed without gaps.
+pub fn auto_arrange_outputs() {
+    get!().auto_arrange_outputs()
 }
 
 /// Returns all available DRM devices.
+///
+/// The devices are sorted by their syspath. The order is therefore stable across restarts
+/// of the compositor as long as the hardware configuration does not change.
 pub fn drm_devices() -> Vec<DrmDevice> {
     get!().drm_devices()
 }
@@ -194,6 +432,30 @@ pub fn on_connector_connected<F: Fn(Connector) + 'static>(f: F) {
     get!().on_connector_connected(f)
 }
 
+/// Sets the callback to be called when the scale of a connector changes.
+///
+/// This is called both for changes made by the configuration and for changes made by
+/// the compositor.
+pub fn on_connector_scale_changed<F: Fn(Connector, f64) + 'static>(f: F) {
+    get!().on_connector_scale_changed(f)
+}
+
+/// Sets the callback to be called when the transform of a connector changes.
+///
+/// This is called both for changes made by the configuration and for changes made by
+/// the compositor.
+pub fn on_connector_transform_changed<F: Fn(Connector, Transform) + 'static>(f: F) {
+    get!().on_connector_transform_changed(f)
+}
+
+/// Sets the callback to be called when a hotplug event of a connector has been held back.
+///
+/// This is only called for connectors whose hotplug policy is [`HotplugPolicy::AskConfig`].
+/// The callback can call [`Connector::accept_hotplug`] to apply the event.
+pub fn on_connector_hotplug<F: Fn(Connector) + 'static>(f: F) {
+    get!().on_connector_hotplug(f)
+}
+
 /// Sets the callback to be called when the graphics of the compositor have been initialized.
 ///
 /// This callback is only invoked once during the lifetime of the compositor. This is a good place
@@ -346,6 +608,15 @@ impl DrmDevice {
         get!().drm_device_syspath(self)
     }
 
+    /// Returns the path of the render node of this device.
+    ///
+    /// E.g. `/dev/dri/renderD128`.
+    ///
+    /// If the device has no render node, the path of the primary node is returned.
+    pub fn render_node(self) -> String {
+        get!().drm_device_render_node(self)
+    }
+
     /// Returns the vendor of this device.
     ///
     /// E.g. `Advanced Micro Devices, Inc. [AMD/ATI]`.
@@ -374,11 +645,37 @@ impl DrmDevice {
 
     /// Sets the preferred graphics API for this device.
     ///
-    /// If the API cannot be used, the compositor will try other APIs.
+    /// If the API cannot be used, the device keeps using its current API.
     pub fn set_gfx_api(self, gfx_api: GfxApi) {
         get!().set_gfx_api(Some(self), gfx_api);
     }
 
+    /// Like [DrmDevice::set_gfx_api] but returns whether the device now uses the API.
+    pub fn try_set_gfx_api(self, gfx_api: GfxApi) -> bool {
+        get!(false).try_set_gfx_api(self, gfx_api)
+    }
+
+    /// Returns the graphics API currently used by this device.
+    pub fn gfx_api(self) -> GfxApi {
+        get!(GfxApi::OpenGl).drm_device_gfx_api(self)
+    }
+
+    /// Returns the graphics APIs that can be used with this device.
+    ///
+    /// The first call for a device probes all APIs, which can take a moment.
+    pub fn supported_gfx_apis(self) -> Vec<GfxApi> {
+        get!().drm_device_supported_gfx_apis(self)
+    }
+
+    /// Renders a known pattern with the graphics API of this device and checks that it
+    /// can be read back correctly.
+    ///
+    /// This can be used to detect broken drivers. On failure, the returned string describes
+    /// the first problem that was found.
+    pub fn run_gfx_self_test(self) -> Result<(), String> {
+        get!(Err("Not connected to the compositor".to_string())).run_gfx_self_test(self)
+    }
+
     /// Enables or disables direct scanout of client surfaces for this device.
     pub fn set_direct_scanout_enabled(self, enabled: bool) {
         get!().set_direct_scanout_enabled(Some(self), enabled);
@@ -404,6 +701,33 @@ pub fn set_gfx_api(gfx_api: GfxApi) {
     get!().set_gfx_api(None, gfx_api);
 }
 
+/// Sets the color that the dummy output is filled with.
+///
+/// The dummy output holds the workspaces and windows while no output is connected or
+/// enabled. The compositor also logs a warning whenever a window is mapped on it.
+///
+/// Default: `None`.
+pub fn set_dummy_output_color(color: Option<Color>) {
+    get!().set_dummy_output_color(color);
+}
+
+/// Sets the interval over which connector hotplug events are debounced.
+///
+/// A newly connected monitor is only used once it has stayed connected for this long.
+/// If a monitor is disconnected and the same monitor is reconnected within this
+/// interval, the output and its workspaces are left in place. Disconnects caused by
+/// [`Connector::set_enabled`] are not debounced.
+///
+/// This helps with cables and docks that rapidly toggle the connection state.
+///
+/// Setting the interval to zero disables debouncing.
+///
+/// Default: 0
+pub fn set_hotplug_debounce(interval: Duration) {
+    let ms = interval.as_millis().min(u64::MAX as u128);
+    get!().set_hotplug_debounce(ms as u64);
+}
+
 /// Enables or disables direct scanout of client surfaces.
 ///
 /// The default is `true`.
@@ -413,6 +737,118 @@ pub fn set_direct_scanout_enabled(enabled: bool) {
     get!().set_direct_scanout_enabled(None, enabled);
 }
 
+/// Returns the GPU time spent on the most recently completed frame.
+///
+/// This is a debugging aid. It is only available with the Vulkan API and only if the
+/// compositor was started with the environment variable `JAY_GPU_TIMING=1`.
+pub fn gpu_time() -> Option<Duration> {
+    get!(None).gpu_time()
+}
+
+/// Returns the maximum width and height of textures and framebuffers.
+///
+/// Buffers larger than this cannot be imported or rendered to. Returns `None` if the
+/// compositor currently has no renderer.
+pub fn max_texture_size() -> Option<(u32, u32)> {
+    get!(None).max_texture_size()
+}
+
+/// Sets the maximum number of frames that can be in flight on the GPU.
+///
+/// If this limit is reached, the compositor waits for the GPU to finish the oldest frame
+/// before submitting another frame. Values smaller than 1 are treated as 1.
+///
+/// The default is `3`. This currently only has an effect with the Vulkan API.
+pub fn set_max_in_flight_frames(frames: usize) {
+    get!().set_max_in_flight_frames(frames)
+}
+
+/// Sets whether colors are blended in linear space.
+///
+/// If this is enabled, the compositor composites each frame into an intermediate image
+/// in linear space and converts the result to sRGB before it is displayed. This produces
+/// more accurate results for translucent surfaces and scaled textures at the cost of
+/// additional memory and GPU time. Render passes that draw on top of the existing
+/// contents of an image are still blended in sRGB space.
+///
+/// The default is `false`. This currently only has an effect with the Vulkan API.
+pub fn set_linear_blending(enabled: bool) {
+    get!().set_linear_blending(enabled)
+}
+
+/// Returns the number of frames that are currently in flight on the GPU.
+///
+/// This is a debugging aid.
+pub fn in_flight_frames() -> usize {
+    get!(0).in_flight_frames()
+}
+
+/// Stops advertising a format modifier to clients.
+///
+/// This is a workaround for driver bugs that cause corruption with specific modifiers.
+/// The modifier is removed from the dmabuf feedback and the feedback is re-sent to all
+/// clients so that they can reallocate their buffers. Buffers that have already been
+/// allocated with the modifier can still be used.
+pub fn disable_modifier(format_drm: u32, modifier: u64) {
+    get!().disable_modifier(format_drm, modifier)
+}
+
+/// Returns the modifiers the renderer supports for a format.
+///
+/// `format_drm` is a DRM fourcc code. Returns `None` if the compositor currently has no
+/// renderer or if the renderer does not support the format.
+pub fn format_capabilities(format_drm: u32) -> Option<FormatCapabilities> {
+    get!(None).format_capabilities(format_drm)
+}
+
+/// The modifiers supported for a format.
+///
+/// See [format_capabilities].
+#[derive(Serialize, Deserialize, Clone, Debug, Eq, PartialEq)]
+pub struct FormatCapabilities {
+    /// The modifiers of buffers that can be used as textures.
+    pub sampleable: Vec<u64>,
+    /// The modifiers of buffers that can be rendered to.
+    pub renderable: Vec<u64>,
+    /// The modifiers of buffers that can be scanned out directly by at least one
+    /// connector.
+    pub scanout: Vec<u64>,
+}
+
+/// The type of content displayed by a window.
+///
+/// Windows declare their content type via the `wp_content_type_v1` protocol.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum ContentType {
+    /// Still images.
+    Photo,
+    /// Video or animation.
+    Video,
+    /// An interactive game.
+    ///
+    /// Fullscreen windows with this content type are presented with tearing page flips
+    /// if the device supports them.
+    Game,
+}
+
+/// The policy for handling hotplug events of a connector.
+///
+/// Some displays emit spurious hotplug events that cause the compositor to renegotiate the
+/// mode of the connector. The policy can be used to suppress this.
+#[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
+pub enum HotplugPolicy {
+    /// Hotplug events are applied immediately.
+    #[default]
+    Auto,
+    /// Hotplug events of a connected connector are ignored.
+    ///
+    /// The connector keeps its current mode even if the display is unplugged.
+    Ignore,
+    /// Hotplug events of a connected connector are held back and the callback registered
+    /// with [`on_connector_hotplug`] is invoked.
+    AskConfig,
+}
+
 /// A transformation.
 #[derive(Serialize, Deserialize, Copy, Clone, Debug, Eq, PartialEq, Hash, Default)]
 pub enum Transform {
@@ -175,6 +175,9 @@ async fn accept(fd: Rc<OwnedFd>, state: Rc<State>, secure: bool) {
                 break;
             }
         };
+        if !state.clients.may_accept(&state) {
+            continue;
+        }
         let id = state.clients.id();
         if let Err(e) = state.clients.spawn(id, &state, fd, secure) {
             log::error!("Could not spawn a client: {}", ErrorFmt(e));
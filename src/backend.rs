@@ -3,14 +3,19 @@ use {
         async_engine::SpawnedFuture,
         drm_feedback::DrmFeedback,
         fixed::Fixed,
-        gfx_api::GfxFramebuffer,
+        format::Format,
+        gfx_api::{GfxContext, GfxFramebuffer},
         ifs::wl_seat::wl_pointer::{CONTINUOUS, FINGER, HORIZONTAL_SCROLL, VERTICAL_SCROLL, WHEEL},
-        video::drm::{ConnectorType, DrmError, DrmVersion},
+        video::{
+            drm::{ConnectorType, DrmError, DrmVersion},
+            Modifier,
+        },
     },
-    jay_config::video::GfxApi,
+    jay_config::video::{GfxApi, HotplugPolicy},
     std::{
         any::Any,
         error::Error,
+        ffi::CString,
         fmt::{Debug, Display, Formatter},
         rc::Rc,
     },
@@ -58,6 +63,7 @@ pub struct MonitorInfo {
     pub initial_mode: Mode,
     pub width_mm: i32,
     pub height_mm: i32,
+    pub non_desktop: bool,
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -80,9 +86,39 @@ pub trait Connector {
     fn damage(&self);
     fn drm_dev(&self) -> Option<DrmDeviceId>;
     fn set_enabled(&self, enabled: bool);
+    fn set_mode(&self, mode: Mode) {
+        let _ = mode;
+    }
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         None
     }
+    fn update_drm_feedback(&self) {}
+    fn framebuffer_info(&self) -> Option<FramebufferInfo> {
+        None
+    }
+    fn set_hotplug_policy(&self, policy: HotplugPolicy) {
+        let _ = policy;
+    }
+    fn accept_hotplug(&self) {}
+    fn set_frozen(&self, frozen: bool) {
+        let _ = frozen;
+    }
+    fn brightness(&self) -> Option<f64> {
+        None
+    }
+    fn set_brightness(&self, percent: f64) -> bool {
+        let _ = percent;
+        false
+    }
+}
+
+#[derive(Copy, Clone, Debug)]
+pub struct FramebufferInfo {
+    pub width: i32,
+    pub height: i32,
+    pub format: &'static Format,
+    pub modifier: Modifier,
+    pub direct_scanout_active: bool,
 }
 
 #[derive(Debug)]
@@ -92,6 +128,7 @@ pub enum ConnectorEvent {
     Disconnected,
     Removed,
     ModeChanged(Mode),
+    HotplugPending,
 }
 
 pub trait HardwareCursor: Debug {
@@ -213,6 +250,66 @@ pub enum InputEvent {
     AxisFrame {
         time_usec: u64,
     },
+
+    TouchDown {
+        time_usec: u64,
+        id: i32,
+        x_normed: Fixed,
+        y_normed: Fixed,
+    },
+    TouchUp {
+        time_usec: u64,
+        id: i32,
+    },
+    TouchMotion {
+        time_usec: u64,
+        id: i32,
+        x_normed: Fixed,
+        y_normed: Fixed,
+    },
+    TouchCancel {
+        time_usec: u64,
+    },
+    TouchFrame {
+        time_usec: u64,
+    },
+
+    SwipeBegin {
+        time_usec: u64,
+        finger_count: u32,
+    },
+    SwipeUpdate {
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+    },
+    SwipeEnd {
+        time_usec: u64,
+        cancelled: bool,
+    },
+    PinchBegin {
+        time_usec: u64,
+        finger_count: u32,
+    },
+    PinchUpdate {
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+        scale: Fixed,
+        rotation: Fixed,
+    },
+    PinchEnd {
+        time_usec: u64,
+        cancelled: bool,
+    },
+    HoldBegin {
+        time_usec: u64,
+        finger_count: u32,
+    },
+    HoldEnd {
+        time_usec: u64,
+        cancelled: bool,
+    },
 }
 
 pub enum DrmEvent {
@@ -227,8 +324,11 @@ pub trait BackendDrmDevice {
     fn on_change(&self, cb: Rc<dyn Fn()>);
     fn dev_t(&self) -> c::dev_t;
     fn make_render_device(&self);
-    fn set_gfx_api(&self, api: GfxApi);
+    fn set_gfx_api(&self, api: GfxApi) -> Result<(), Box<dyn Error>>;
     fn gtx_api(&self) -> GfxApi;
+    fn supported_gfx_apis(&self) -> Vec<GfxApi>;
+    fn gfx_ctx(&self) -> Rc<dyn GfxContext>;
     fn version(&self) -> Result<DrmVersion, DrmError>;
+    fn render_node(&self) -> Result<Option<CString>, DrmError>;
     fn set_direct_scanout_enabled(&self, enabled: bool);
 }
@@ -1,3 +1,4 @@
+mod backlight;
 mod input;
 mod monitor;
 mod video;
@@ -11,7 +12,6 @@ use {
         },
         backends::metal::video::{MetalDrmDeviceData, MetalRenderContext, PendingDrmDevice},
         dbus::{DbusError, SignalHandler},
-        drm_feedback::DrmFeedback,
         gfx_api::GfxError,
         libinput::{
             consts::{
@@ -42,6 +42,7 @@ use {
             gbm::GbmError,
         },
     },
+    jay_config::video::GfxApi,
     std::{
         any::Any,
         cell::{Cell, RefCell},
@@ -117,6 +118,8 @@ pub enum MetalError {
     MissingDevModifier(&'static str),
     #[error("Device GFX API cannot read any buffers writable by the render GFX API (format {0})")]
     MissingRenderModifier(&'static str),
+    #[error("Device does not support the {0:?} API")]
+    UnsupportedGfxApi(GfxApi),
 }
 
 pub struct MetalBackend {
@@ -131,7 +134,6 @@ pub struct MetalBackend {
     pause_handler: Cell<Option<SignalHandler>>,
     resume_handler: Cell<Option<SignalHandler>>,
     ctx: CloneCell<Option<Rc<MetalRenderContext>>>,
-    default_feedback: CloneCell<Option<Rc<DrmFeedback>>>,
 }
 
 impl Debug for MetalBackend {
@@ -255,7 +257,6 @@ pub async fn create(state: &Rc<State>) -> Result<Rc<MetalBackend>, MetalError> {
         pause_handler: Default::default(),
         resume_handler: Default::default(),
         ctx: Default::default(),
-        default_feedback: Default::default(),
     });
     metal.pause_handler.set(Some({
         let mtl = metal.clone();
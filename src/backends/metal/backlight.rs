@@ -0,0 +1,78 @@
+use {
+    crate::{backend::ConnectorKernelId, video::drm::ConnectorType},
+    std::{fs, path::Path},
+};
+
+const BACKLIGHT_CLASS: &str = "/sys/class/backlight";
+
+pub struct Backlight {
+    pub name: String,
+    pub max_brightness: u32,
+}
+
+impl Backlight {
+    pub fn brightness(&self) -> Option<u32> {
+        read_u32(
+            &Path::new(BACKLIGHT_CLASS)
+                .join(&self.name)
+                .join("brightness"),
+        )
+    }
+}
+
+/// Locates the backlight device of a connector.
+///
+/// Backlights that are registered as children of the connector in sysfs are preferred.
+/// Otherwise, internal panels use the best backlight of the system.
+pub fn find_backlight(card: &str, kernel_id: &ConnectorKernelId) -> Option<Backlight> {
+    let connector_dir = format!("/sys/class/drm/{}-{}", card, kernel_id);
+    if let Ok(entries) = fs::read_dir(&connector_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if let Some(bl) = open_backlight(&name) {
+                return Some(bl);
+            }
+        }
+    }
+    let internal = matches!(
+        kernel_id.ty,
+        ConnectorType::eDP | ConnectorType::LVDS | ConnectorType::DSI
+    );
+    if !internal {
+        return None;
+    }
+    let mut best: Option<(usize, Backlight)> = None;
+    for entry in fs::read_dir(BACKLIGHT_CLASS).ok()?.flatten() {
+        let name = entry.file_name().to_string_lossy().into_owned();
+        let priority = match fs::read_to_string(entry.path().join("type")) {
+            Ok(ty) => match ty.trim() {
+                "firmware" => 0,
+                "platform" => 1,
+                _ => 2,
+            },
+            _ => continue,
+        };
+        if best.as_ref().map(|(p, _)| priority < *p).unwrap_or(true) {
+            if let Some(bl) = open_backlight(&name) {
+                best = Some((priority, bl));
+            }
+        }
+    }
+    best.map(|(_, bl)| bl)
+}
+
+fn open_backlight(name: &str) -> Option<Backlight> {
+    let dir = Path::new(BACKLIGHT_CLASS).join(name);
+    let max_brightness = read_u32(&dir.join("max_brightness"))?;
+    if max_brightness == 0 {
+        return None;
+    }
+    Some(Backlight {
+        name: name.to_string(),
+        max_brightness,
+    })
+}
+
+fn read_u32(path: &Path) -> Option<u32> {
+    fs::read_to_string(path).ok()?.trim().parse().ok()
+}
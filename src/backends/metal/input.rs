@@ -91,6 +91,19 @@ impl MetalBackend {
             c::LIBINPUT_EVENT_POINTER_SCROLL_CONTINUOUS => {
                 self.handle_pointer_axis(event, AxisSource::Continuous)
             }
+            c::LIBINPUT_EVENT_TOUCH_DOWN => self.handle_touch_down(event),
+            c::LIBINPUT_EVENT_TOUCH_UP => self.handle_touch_up(event),
+            c::LIBINPUT_EVENT_TOUCH_MOTION => self.handle_touch_motion(event),
+            c::LIBINPUT_EVENT_TOUCH_CANCEL => self.handle_touch_cancel(event),
+            c::LIBINPUT_EVENT_TOUCH_FRAME => self.handle_touch_frame(event),
+            c::LIBINPUT_EVENT_GESTURE_SWIPE_BEGIN => self.handle_gesture_swipe_begin(event),
+            c::LIBINPUT_EVENT_GESTURE_SWIPE_UPDATE => self.handle_gesture_swipe_update(event),
+            c::LIBINPUT_EVENT_GESTURE_SWIPE_END => self.handle_gesture_swipe_end(event),
+            c::LIBINPUT_EVENT_GESTURE_PINCH_BEGIN => self.handle_gesture_pinch_begin(event),
+            c::LIBINPUT_EVENT_GESTURE_PINCH_UPDATE => self.handle_gesture_pinch_update(event),
+            c::LIBINPUT_EVENT_GESTURE_PINCH_END => self.handle_gesture_pinch_end(event),
+            c::LIBINPUT_EVENT_GESTURE_HOLD_BEGIN => self.handle_gesture_hold_begin(event),
+            c::LIBINPUT_EVENT_GESTURE_HOLD_END => self.handle_gesture_hold_end(event),
             _ => {}
         }
     }
@@ -205,4 +218,114 @@ impl MetalBackend {
             dy_unaccelerated: Fixed::from_f64(dy_unaccelerated),
         });
     }
+
+    fn handle_touch_down(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        dev.event(InputEvent::TouchDown {
+            time_usec: event.time_usec(),
+            id: event.seat_slot(),
+            x_normed: Fixed::from_f64(event.x_normed()),
+            y_normed: Fixed::from_f64(event.y_normed()),
+        });
+    }
+
+    fn handle_touch_up(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        dev.event(InputEvent::TouchUp {
+            time_usec: event.time_usec(),
+            id: event.seat_slot(),
+        });
+    }
+
+    fn handle_touch_motion(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        dev.event(InputEvent::TouchMotion {
+            time_usec: event.time_usec(),
+            id: event.seat_slot(),
+            x_normed: Fixed::from_f64(event.x_normed()),
+            y_normed: Fixed::from_f64(event.y_normed()),
+        });
+    }
+
+    fn handle_touch_cancel(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        dev.event(InputEvent::TouchCancel {
+            time_usec: event.time_usec(),
+        });
+    }
+
+    fn handle_touch_frame(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, touch_event);
+        dev.event(InputEvent::TouchFrame {
+            time_usec: event.time_usec(),
+        });
+    }
+
+    fn handle_gesture_swipe_begin(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::SwipeBegin {
+            time_usec: event.time_usec(),
+            finger_count: event.finger_count(),
+        });
+    }
+
+    fn handle_gesture_swipe_update(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::SwipeUpdate {
+            time_usec: event.time_usec(),
+            dx: Fixed::from_f64(event.dx()),
+            dy: Fixed::from_f64(event.dy()),
+        });
+    }
+
+    fn handle_gesture_swipe_end(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::SwipeEnd {
+            time_usec: event.time_usec(),
+            cancelled: event.cancelled(),
+        });
+    }
+
+    fn handle_gesture_pinch_begin(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::PinchBegin {
+            time_usec: event.time_usec(),
+            finger_count: event.finger_count(),
+        });
+    }
+
+    fn handle_gesture_pinch_update(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::PinchUpdate {
+            time_usec: event.time_usec(),
+            dx: Fixed::from_f64(event.dx()),
+            dy: Fixed::from_f64(event.dy()),
+            scale: Fixed::from_f64(event.scale()),
+            rotation: Fixed::from_f64(event.angle_delta()),
+        });
+    }
+
+    fn handle_gesture_pinch_end(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::PinchEnd {
+            time_usec: event.time_usec(),
+            cancelled: event.cancelled(),
+        });
+    }
+
+    fn handle_gesture_hold_begin(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::HoldBegin {
+            time_usec: event.time_usec(),
+            finger_count: event.finger_count(),
+        });
+    }
+
+    fn handle_gesture_hold_end(self: &Rc<Self>, event: LibInputEvent) {
+        let (event, dev) = unpack!(self, event, gesture_event);
+        dev.event(InputEvent::HoldEnd {
+            time_usec: event.time_usec(),
+            cancelled: event.cancelled(),
+        });
+    }
 }
@@ -3,9 +3,12 @@ use {
         async_engine::{Phase, SpawnedFuture},
         backend::{
             BackendDrmDevice, BackendEvent, Connector, ConnectorEvent, ConnectorId,
-            ConnectorKernelId, DrmDeviceId, HardwareCursor, MonitorInfo,
+            ConnectorKernelId, DrmDeviceId, FramebufferInfo, HardwareCursor, Mode, MonitorInfo,
+        },
+        backends::metal::{
+            backlight::{find_backlight, Backlight},
+            MetalBackend, MetalError,
         },
-        backends::metal::{MetalBackend, MetalError},
         drm_feedback::DrmFeedback,
         edid::Descriptor,
         format::{Format, ARGB8888, XRGB8888},
@@ -28,8 +31,9 @@ use {
                 drm_mode_modeinfo, Change, ConnectorStatus, ConnectorType, DrmBlob, DrmConnector,
                 DrmCrtc, DrmEncoder, DrmError, DrmEvent, DrmFramebuffer, DrmMaster, DrmModeInfo,
                 DrmObject, DrmPlane, DrmProperty, DrmPropertyDefinition, DrmPropertyType,
-                DrmVersion, PropBlob, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
-                DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_EVENT,
+                DrmVersion, PropBlob, DRM_CAP_ATOMIC_ASYNC_PAGE_FLIP, DRM_CLIENT_CAP_ATOMIC,
+                DRM_MODE_ATOMIC_ALLOW_MODESET, DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC,
+                DRM_MODE_PAGE_FLIP_EVENT,
             },
             gbm::{GbmDevice, GBM_BO_USE_LINEAR, GBM_BO_USE_RENDERING, GBM_BO_USE_SCANOUT},
             Modifier, INVALID_MODIFIER,
@@ -38,9 +42,10 @@ use {
     ahash::{AHashMap, AHashSet},
     bstr::{BString, ByteSlice},
     indexmap::{indexset, IndexSet},
-    jay_config::video::GfxApi,
+    jay_config::video::{GfxApi, HotplugPolicy},
     std::{
         cell::{Cell, RefCell},
+        error::Error,
         ffi::CString,
         fmt::{Debug, Formatter},
         mem,
@@ -83,6 +88,8 @@ pub struct MetalDrmDevice {
     pub ctx: CloneCell<Rc<MetalRenderContext>>,
     pub on_change: OnChange<crate::backend::DrmEvent>,
     pub direct_scanout_enabled: Cell<Option<bool>>,
+    pub supports_async_commit: bool,
+    pub supported_gfx_apis: RefCell<Option<Vec<GfxApi>>>,
 }
 
 impl MetalDrmDevice {
@@ -115,18 +122,36 @@ impl BackendDrmDevice for MetalDrmDevice {
         self.backend.make_render_device(&self, false);
     }
 
-    fn set_gfx_api(&self, api: GfxApi) {
-        self.backend.set_gfx_api(self, api)
+    fn set_gfx_api(&self, api: GfxApi) -> Result<(), Box<dyn Error>> {
+        self.backend.set_gfx_api(self, api)?;
+        Ok(())
     }
 
     fn gtx_api(&self) -> GfxApi {
         self.ctx.get().gfx.gfx_api()
     }
 
+    fn gfx_ctx(&self) -> Rc<dyn GfxContext> {
+        self.ctx.get().gfx.clone()
+    }
+
+    fn supported_gfx_apis(&self) -> Vec<GfxApi> {
+        if let Some(apis) = &*self.supported_gfx_apis.borrow() {
+            return apis.clone();
+        }
+        let apis = self.backend.state.probe_gfx_apis(&self.master);
+        *self.supported_gfx_apis.borrow_mut() = Some(apis.clone());
+        apis
+    }
+
     fn version(&self) -> Result<DrmVersion, DrmError> {
         self.gbm.drm.version()
     }
 
+    fn render_node(&self) -> Result<Option<CString>, DrmError> {
+        self.gbm.drm.get_render_node()
+    }
+
     fn set_direct_scanout_enabled(&self, enabled: bool) {
         self.direct_scanout_enabled.set(Some(enabled));
     }
@@ -166,6 +191,7 @@ pub struct ConnectorDisplayData {
     pub mm_width: u32,
     pub mm_height: u32,
     pub subpixel: u32,
+    pub non_desktop: bool,
 
     pub connector_type: ConnectorType,
     pub connector_type_id: u32,
@@ -198,6 +224,7 @@ pub struct MetalConnector {
     pub can_present: Cell<bool>,
     pub has_damage: Cell<bool>,
     pub cursor_changed: Cell<bool>,
+    pub frozen: Cell<bool>,
 
     pub display: RefCell<ConnectorDisplayData>,
 
@@ -224,9 +251,14 @@ pub struct MetalConnector {
 
     pub drm_feedback: CloneCell<Option<Rc<DrmFeedback>>>,
     pub scanout_buffers: RefCell<AHashMap<DmaBufId, DirectScanoutCache>>,
+    pub scanout_unsupported_formats: RefCell<AHashSet<(u32, Modifier)>>,
     pub active_framebuffer: OpaqueCell<Option<PresentFb>>,
     pub next_framebuffer: OpaqueCell<Option<PresentFb>>,
     pub direct_scanout_active: Cell<bool>,
+
+    pub hotplug_policy: Cell<HotplugPolicy>,
+    pub hotplug_pending: Cell<bool>,
+    pub hotplug_accepted: Cell<bool>,
 }
 
 #[derive(Debug)]
@@ -375,10 +407,54 @@ pub struct PresentFb {
 }
 
 impl MetalConnector {
+    fn backlight(&self) -> Option<Backlight> {
+        let devnode = self.dev.devnode.to_str().ok()?;
+        let card = devnode.rsplit('/').next()?;
+        find_backlight(card, &self.kernel_id())
+    }
+
     async fn present_loop(self: Rc<Self>) {
         loop {
             self.present_trigger.triggered().await;
             let _ = self.present(true);
+            if self.frozen.get() {
+                // Frozen connectors do not receive page-flip events. Pace the frame
+                // callbacks at the refresh rate instead.
+                let refresh = self.display.borrow().refresh as u64;
+                let ms = refresh.div_ceil(1_000_000).max(1);
+                if let Err(e) = self.state.wheel.timeout(ms).await {
+                    log::error!("Could not wait for the next refresh cycle: {}", ErrorFmt(e));
+                }
+            }
+        }
+    }
+
+    fn dispatch_frozen_frame(&self) {
+        if !self.has_damage.get() {
+            return;
+        }
+        let Some(node) = self.state.root.outputs.get(&self.connector_id) else {
+            return;
+        };
+        let Some(buffers) = self.buffers.get() else {
+            return;
+        };
+        let buffer = &buffers[self.next_buffer.get() % buffers.len()];
+        let mut rr = RenderResult::default();
+        buffer.render_fb().create_render_pass(
+            &*node,
+            &self.state,
+            Some(node.global.pos.get()),
+            Some(&mut rr),
+            node.global.preferred_scale.get(),
+            !self.cursor_enabled.get(),
+            node.has_fullscreen(),
+            node.global.transform.get(),
+        );
+        node.dispatch_frame_requests(&mut rr);
+        for fb in rr.presentation_feedbacks.drain(..) {
+            fb.send_discarded();
+            let _ = fb.client.remove_obj(&*fb);
         }
     }
 
@@ -432,7 +508,7 @@ impl MetalConnector {
                 for opt in &mut ops {
                     match opt {
                         GfxApiOpt::Sync => {}
-                        GfxApiOpt::FillRect(_) => {
+                        GfxApiOpt::FillRect(_) | GfxApiOpt::FillGradient(_) => {
                             // Top-most layer must be a texture.
                             return None;
                         }
@@ -460,6 +536,10 @@ impl MetalConnector {
                             return None;
                         }
                     }
+                    GfxApiOpt::FillGradient(_) => {
+                        // Gradient could be visible.
+                        return None;
+                    }
                     GfxApiOpt::CopyTexture(_) => {
                         // Texture could be visible.
                         return None;
@@ -514,6 +594,10 @@ impl MetalConnector {
             // Shm buffers cannot be scanned out.
             return None;
         };
+        if ct.acquire_sync.is_some() {
+            // Explicitly synchronized buffers cannot be scanned out.
+            return None;
+        }
         let position = DirectScanoutPosition {
             src_width: tex_w,
             src_height: tex_h,
@@ -534,19 +618,43 @@ impl MetalConnector {
         }
         let format = 'format: {
             if let Some(f) = plane.formats.get(&dmabuf.format.drm) {
-                break 'format f;
+                break 'format Some(f);
             }
             // Try opaque format if possible.
             if let Some(opaque) = dmabuf.format.opaque {
                 if let Some(f) = plane.formats.get(&opaque.drm) {
-                    break 'format f;
+                    break 'format Some(f);
                 }
             }
-            return None;
+            None
+        };
+        let format = match format {
+            Some(f) if f.modifiers.contains(&dmabuf.modifier) => f,
+            _ => {
+                // The buffer will be composited instead. Remember this so that we don't
+                // have to check again for every frame.
+                if self
+                    .scanout_unsupported_formats
+                    .borrow_mut()
+                    .insert((dmabuf.format.drm, dmabuf.modifier))
+                {
+                    log::info!(
+                        "Format {} with modifier {:#x} cannot be scanned out on {}, falling back to composition",
+                        dmabuf.format.name,
+                        dmabuf.modifier,
+                        self.kernel_id(),
+                    );
+                }
+                cache.insert(
+                    dmabuf.id,
+                    DirectScanoutCache {
+                        tex: Rc::downgrade(&ct.tex),
+                        fb: None,
+                    },
+                );
+                return None;
+            }
         };
-        if !format.modifiers.contains(&dmabuf.modifier) {
-            return None;
-        }
         let data = match self.dev.master.add_fb(dmabuf, Some(format.format)) {
             Ok(fb) => Some(DirectScanoutData {
                 tex: ct.tex.clone(),
@@ -656,6 +764,12 @@ impl MetalConnector {
         if (!self.has_damage.get() && !self.cursor_changed.get()) || !self.can_present.get() {
             return Ok(());
         }
+        if self.frozen.get() {
+            // The damage is retained and presented once the connector is thawed. Until
+            // then, clients receive their frame callbacks without the scanout changing.
+            self.dispatch_frozen_frame();
+            return Ok(());
+        }
         if !crtc.active.value.get() {
             return Ok(());
         }
@@ -669,17 +783,19 @@ impl MetalConnector {
         };
         let cursor = self.cursor_plane.get();
         let mut new_fb = None;
+        let mut tearing = false;
         let mut changes = self.master.change();
         if self.has_damage.get() {
             if !self.backend.check_render_context(&self.dev) {
                 return Ok(());
             }
             if let Some(node) = self.state.root.outputs.get(&self.connector_id) {
+                tearing = self.dev.supports_async_commit && node.fullscreen_wants_tearing();
                 let buffer = &buffers[self.next_buffer.get() % buffers.len()];
                 let mut rr = self.render_result.borrow_mut();
                 let fb =
                     self.prepare_present_fb(&mut rr, buffer, &plane, &node, try_direct_scanout);
-                rr.dispatch_frame_requests();
+                node.dispatch_frame_requests(&mut rr);
                 let (crtc_x, crtc_y, crtc_w, crtc_h, src_width, src_height) =
                     match &fb.direct_scanout_data {
                         None => {
@@ -712,6 +828,8 @@ impl MetalConnector {
             }
         }
         if self.cursor_changed.get() && cursor.is_some() {
+            // Async commits can only change the framebuffer of the primary plane.
+            tearing = false;
             let plane = cursor.unwrap();
             if self.cursor_enabled.get() {
                 let swap_buffer = self.cursor_swap_buffer.take();
@@ -745,7 +863,17 @@ impl MetalConnector {
                 });
             }
         }
-        if let Err(e) = changes.commit(DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT, 0) {
+        let mut flags = DRM_MODE_ATOMIC_NONBLOCK | DRM_MODE_PAGE_FLIP_EVENT;
+        if tearing {
+            flags |= DRM_MODE_PAGE_FLIP_ASYNC;
+        }
+        let mut res = changes.commit(flags, 0);
+        if tearing && res.is_err() {
+            // The driver can reject async commits for many reasons, e.g. if the
+            // framebuffer layout changes. Fall back to a synchronous commit.
+            res = changes.commit(flags & !DRM_MODE_PAGE_FLIP_ASYNC, 0);
+        }
+        if let Err(e) = res {
             match e {
                 DrmError::Atomic(OsError(c::EACCES)) => {
                     log::debug!("Could not perform atomic commit, likely because we're no longer the DRM master");
@@ -787,16 +915,21 @@ impl MetalConnector {
         }
     }
 
-    pub fn update_drm_feedback(&self) {
+    fn update_drm_feedback_(&self) {
         let fb = self.compute_drm_feedback();
         self.drm_feedback.set(fb);
+        if let Some(node) = self.state.root.outputs.get(&self.connector_id) {
+            for ws in node.workspaces.iter() {
+                ws.send_scanout_feedback();
+            }
+        }
     }
 
     fn compute_drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         if !self.dev.is_render_device() {
             return None;
         }
-        let default = self.backend.default_feedback.get()?;
+        let default = self.state.drm_feedback.get()?;
         let plane = self.primary_plane.get()?;
         let mut formats = vec![];
         for (format, info) in &plane.formats {
@@ -859,9 +992,107 @@ impl Connector for MetalConnector {
         }
     }
 
+    fn set_mode(&self, be_mode: Mode) {
+        {
+            let mut dd = self.display.borrow_mut();
+            if dd.mode.as_ref().map(|m| m.to_backend()) == Some(be_mode) {
+                return;
+            }
+            let Some(mode) = dd.modes.iter().find(|m| m.to_backend() == be_mode).cloned() else {
+                log::warn!(
+                    "Connector {}-{} does not support mode {:?}",
+                    dd.connector_type,
+                    dd.connector_type_id,
+                    be_mode,
+                );
+                return;
+            };
+            dd.refresh = 1_000_000_000_000u64
+                .checked_div(mode.refresh_rate_millihz() as u64)
+                .unwrap_or(0) as u32;
+            dd.mode = Some(Rc::new(mode));
+        }
+        if let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) {
+            if let Err(e) = self.backend.handle_drm_change_(&dev, false) {
+                dev.unprocessed_change.set(true);
+                log::error!("Could not change the mode: {}", ErrorFmt(e));
+                return;
+            }
+        }
+        if self.connect_sent.get() {
+            self.on_change
+                .send_event(ConnectorEvent::ModeChanged(be_mode));
+        }
+    }
+
+    fn set_hotplug_policy(&self, policy: HotplugPolicy) {
+        self.hotplug_policy.set(policy);
+        if policy == HotplugPolicy::Auto {
+            self.accept_hotplug();
+        }
+    }
+
+    fn brightness(&self) -> Option<f64> {
+        let bl = self.backlight()?;
+        let brightness = bl.brightness()?;
+        Some(brightness as f64 * 100.0 / bl.max_brightness as f64)
+    }
+
+    fn set_brightness(&self, percent: f64) -> bool {
+        let Some(bl) = self.backlight() else {
+            return false;
+        };
+        let percent = percent.clamp(0.0, 100.0);
+        let brightness = (percent / 100.0 * bl.max_brightness as f64).round() as u32;
+        self.backend
+            .session
+            .set_brightness("backlight", &bl.name, brightness);
+        true
+    }
+
+    fn accept_hotplug(&self) {
+        if !self.hotplug_pending.take() {
+            return;
+        }
+        self.hotplug_accepted.set(true);
+        if let Some(dev) = self.backend.device_holder.drm_devices.get(&self.dev.devnum) {
+            if let Err(e) = self.backend.handle_drm_change_(&dev, true) {
+                dev.unprocessed_change.set(true);
+                log::error!("Could not apply hotplug event: {}", ErrorFmt(e));
+            }
+        }
+        self.hotplug_accepted.set(false);
+    }
+
+    fn set_frozen(&self, frozen: bool) {
+        if self.frozen.replace(frozen) == frozen {
+            return;
+        }
+        if !frozen && (self.has_damage.get() || self.cursor_changed.get()) {
+            self.schedule_present();
+        }
+    }
+
+    fn framebuffer_info(&self) -> Option<FramebufferInfo> {
+        let fb = self.active_framebuffer.take();
+        let info = fb.as_ref().map(|fb| FramebufferInfo {
+            width: fb.fb.width,
+            height: fb.fb.height,
+            format: fb.fb.format,
+            modifier: fb.fb.modifier,
+            direct_scanout_active: fb.direct_scanout_data.is_some(),
+        });
+        self.active_framebuffer.set(fb);
+        info
+    }
+
     fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
         self.drm_feedback.get()
     }
+
+    fn update_drm_feedback(&self) {
+        self.update_drm_feedback_();
+    }
 }
 
 #[derive(Debug)]
@@ -972,6 +1203,7 @@ fn create_connector(
         enabled: Cell::new(true),
         can_present: Cell::new(true),
         has_damage: Cell::new(true),
+        frozen: Cell::new(false),
         primary_plane: Default::default(),
         cursor_plane: Default::default(),
         crtc: Default::default(),
@@ -990,9 +1222,13 @@ fn create_connector(
         cursor_swap_buffer: Cell::new(false),
         drm_feedback: Default::default(),
         scanout_buffers: Default::default(),
+        scanout_unsupported_formats: Default::default(),
         active_framebuffer: Default::default(),
         next_framebuffer: Default::default(),
         direct_scanout_active: Cell::new(false),
+        hotplug_policy: Cell::new(HotplugPolicy::Auto),
+        hotplug_pending: Cell::new(false),
+        hotplug_accepted: Cell::new(false),
     });
     let futures = ConnectorFutures {
         present: backend
@@ -1024,7 +1260,7 @@ fn create_connector_display_data(
     let mode = info.modes.first().cloned().map(Rc::new);
     let refresh = mode
         .as_ref()
-        .map(|m| 1_000_000_000_000u64 / (m.refresh_rate_millihz() as u64))
+        .and_then(|m| 1_000_000_000_000u64.checked_div(m.refresh_rate_millihz() as u64))
         .unwrap_or(0) as u32;
     let connector_type = ConnectorType::from_drm(info.connector_type);
     let connector_name = debug_fn(|f| write!(f, "{}-{}", connector_type, info.connector_type_id));
@@ -1105,6 +1341,10 @@ fn create_connector_display_data(
         mm_width: info.mm_width,
         mm_height: info.mm_height,
         subpixel: info.subpixel,
+        non_desktop: props
+            .get("non-desktop")
+            .map(|v| v.value.get() != 0)
+            .unwrap_or(false),
         connector_type,
         connector_type_id: info.connector_type_id,
     })
@@ -1403,6 +1643,38 @@ impl MetalBackend {
             };
             let mut old = c.display.borrow_mut();
             mem::swap(old.deref_mut(), &mut dd);
+            if old.is_same_monitor(&dd) {
+                let mode = dd
+                    .mode
+                    .as_ref()
+                    .filter(|m| old.modes.iter().any(|n| modes_equal(m, n)));
+                if let Some(mode) = mode {
+                    old.mode = Some(mode.clone());
+                    old.refresh = dd.refresh;
+                }
+            }
+            if c.connect_sent.get()
+                && c.enabled.get()
+                && c.hotplug_policy.get() != HotplugPolicy::Auto
+                && !c.hotplug_accepted.get()
+                && (old.connection != ConnectorStatus::Connected || !old.is_same_monitor(&dd))
+            {
+                let kernel_id = ConnectorKernelId {
+                    ty: dd.connector_type,
+                    idx: dd.connector_type_id,
+                };
+                log::info!("Holding back hotplug event of connector {}", kernel_id);
+                mem::swap(old.deref_mut(), &mut dd);
+                if preserve_any {
+                    preserve.connectors.insert(c.id);
+                }
+                if c.hotplug_policy.get() == HotplugPolicy::AskConfig
+                    && !c.hotplug_pending.replace(true)
+                {
+                    c.on_change.send_event(ConnectorEvent::HotplugPending);
+                }
+                continue;
+            }
             if c.connect_sent.get() {
                 if !c.enabled.get()
                     || old.connection != ConnectorStatus::Connected
@@ -1460,6 +1732,7 @@ impl MetalBackend {
                 initial_mode: dd.mode.clone().unwrap().to_backend(),
                 width_mm: dd.mm_width as _,
                 height_mm: dd.mm_height as _,
+                non_desktop: dd.non_desktop,
             }));
         connector.connect_sent.set(true);
         connector.send_hardware_cursor();
@@ -1483,6 +1756,11 @@ impl MetalBackend {
             }
         };
 
+        let supports_async_commit = master
+            .get_cap(DRM_CAP_ATOMIC_ASYNC_PAGE_FLIP)
+            .map(|v| v == 1)
+            .unwrap_or(false);
+
         let mut planes = AHashMap::new();
         for plane in master.get_planes()? {
             match create_plane(plane, master) {
@@ -1549,6 +1827,8 @@ impl MetalBackend {
             ctx: CloneCell::new(ctx),
             on_change: Default::default(),
             direct_scanout_enabled: Default::default(),
+            supports_async_commit,
+            supported_gfx_apis: Default::default(),
         });
 
         let (connectors, futures) = get_connectors(self, &dev, &resources.connectors)?;
@@ -1853,41 +2133,33 @@ impl MetalBackend {
         }
         let ctx = dev.ctx.get();
         self.state.set_render_ctx(Some(ctx.gfx.clone()));
-        let fb = match DrmFeedback::new(&self.state.drm_feedback_ids, &*ctx.gfx) {
-            Ok(fb) => Some(Rc::new(fb)),
-            Err(e) => {
-                log::error!("Could not create feedback for new context: {}", ErrorFmt(e));
-                None
-            }
-        };
-        self.default_feedback.set(fb);
         self.ctx.set(Some(ctx));
         for dev in self.device_holder.drm_devices.lock().values() {
             self.re_init_drm_device(&dev);
         }
     }
 
-    fn set_gfx_api(&self, dev: &MetalDrmDevice, api: GfxApi) {
+    fn set_gfx_api(&self, dev: &MetalDrmDevice, api: GfxApi) -> Result<(), MetalError> {
         if dev.ctx.get().gfx.gfx_api() == api {
-            return;
+            return Ok(());
         }
-        let gfx = match self.state.create_gfx_context(&dev.master, Some(api)) {
-            Ok(r) => r,
-            Err(e) => {
-                log::error!(
-                    "Could not create a new graphics context for device {:?}: {}",
-                    dev.devnode,
-                    ErrorFmt(e)
-                );
-                return;
+        if let Some(apis) = &*dev.supported_gfx_apis.borrow() {
+            if !apis.contains(&api) {
+                return Err(MetalError::UnsupportedGfxApi(api));
             }
+        }
+        // Create the new context before touching the old one so that the device keeps
+        // its current context if the requested API cannot be used.
+        let gfx = match self.state.create_exact_gfx_context(&dev.master, api) {
+            Ok(r) => r,
+            Err(e) => return Err(MetalError::CreateRenderContex(e)),
         };
-        dev.on_change
-            .send_event(crate::backend::DrmEvent::GfxApiChanged);
         dev.ctx.set(Rc::new(MetalRenderContext {
             dev_id: dev.id,
             gfx,
         }));
+        dev.on_change
+            .send_event(crate::backend::DrmEvent::GfxApiChanged);
         if dev.is_render_device() {
             self.make_render_device(dev, true);
         } else {
@@ -1895,6 +2167,7 @@ impl MetalBackend {
                 self.re_init_drm_device(&dev);
             }
         }
+        Ok(())
     }
 
     fn re_init_drm_device(&self, dev: &Rc<MetalDrmDeviceData>) {
@@ -1952,7 +2225,7 @@ impl MetalBackend {
                 continue;
             }
             connector.send_hardware_cursor();
-            connector.update_drm_feedback();
+            connector.update_drm_feedback_();
         }
         Ok(())
     }
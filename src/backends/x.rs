@@ -55,6 +55,7 @@ use {
         cell::{Cell, RefCell},
         collections::VecDeque,
         error::Error,
+        ffi::CString,
         future::pending,
         rc::Rc,
     },
@@ -118,6 +119,8 @@ pub enum XBackendError {
     DrmDeviceFstat(#[source] Errno),
     #[error("Render device does not support XRGB8888 format")]
     XRGB8888,
+    #[error("The X backend does not support changing the graphics API")]
+    SetGfxApi,
 }
 
 pub async fn create(state: &Rc<State>) -> Result<Rc<XBackend>, XBackendError> {
@@ -574,6 +577,7 @@ impl XBackend {
             },
             width_mm: output.width.get(),
             height_mm: output.height.get(),
+            non_desktop: false,
         }));
         output.changed();
         self.present(output).await;
@@ -978,19 +982,33 @@ impl BackendDrmDevice for XDrmDevice {
         // nothing
     }
 
-    fn set_gfx_api(&self, _api: GfxApi) {
-        log::warn!("set_gfx_api is not supported by the X backend");
-        // nothing
+    fn set_gfx_api(&self, api: GfxApi) -> Result<(), Box<dyn Error>> {
+        if api == self.backend.ctx.gfx_api() {
+            return Ok(());
+        }
+        Err(Box::new(XBackendError::SetGfxApi))
     }
 
     fn gtx_api(&self) -> GfxApi {
         self.backend.ctx.gfx_api()
     }
 
+    fn gfx_ctx(&self) -> Rc<dyn GfxContext> {
+        self.backend.ctx.clone()
+    }
+
+    fn supported_gfx_apis(&self) -> Vec<GfxApi> {
+        vec![self.backend.ctx.gfx_api()]
+    }
+
     fn version(&self) -> Result<DrmVersion, DrmError> {
         self.backend.gbm.drm.version()
     }
 
+    fn render_node(&self) -> Result<Option<CString>, DrmError> {
+        self.backend.gbm.drm.get_render_node()
+    }
+
     fn set_direct_scanout_enabled(&self, enabled: bool) {
         let _ = enabled;
     }
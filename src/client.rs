@@ -2,6 +2,7 @@ use {
     crate::{
         async_engine::SpawnedFuture,
         client::{error::LookupError, objects::Objects},
+        drm_feedback::{DrmFeedback, DrmFeedbackId},
         ifs::{wl_display::WlDisplay, wl_registry::WlRegistry, wl_surface::WlSurface},
         leaks::Tracker,
         object::{Interface, Object, ObjectId, WL_DISPLAY_ID},
@@ -10,11 +11,13 @@ use {
             activation_token::ActivationToken,
             asyncevent::AsyncEvent,
             buffd::{MsgFormatter, MsgParser, MsgParserError, OutBufferSwapchain},
+            clonecell::CloneCell,
             copyhashmap::{CopyHashMap, Locked},
             errorfmt::ErrorFmt,
             numcell::NumCell,
             trim::AsciiTrim,
         },
+        video::{Modifier, LINEAR_MODIFIER},
         wire::WlRegistryId,
     },
     ahash::AHashMap,
@@ -47,7 +50,6 @@ impl ClientId {
         self.0
     }
 
-    #[cfg_attr(not(feature = "it"), allow(dead_code))]
     pub fn from_raw(val: u64) -> Self {
         Self(val)
     }
@@ -63,6 +65,9 @@ pub struct Clients {
     next_client_id: NumCell<u64>,
     pub clients: RefCell<AHashMap<ClientId, ClientHolder>>,
     shutdown_clients: RefCell<AHashMap<ClientId, ClientHolder>>,
+    pub max_clients: Cell<Option<usize>>,
+    pub max_connection_rate: Cell<Option<u32>>,
+    connection_window: Cell<(u64, u32)>,
 }
 
 impl Clients {
@@ -71,9 +76,37 @@ impl Clients {
             next_client_id: NumCell::new(1),
             clients: Default::default(),
             shutdown_clients: Default::default(),
+            max_clients: Cell::new(None),
+            max_connection_rate: Cell::new(None),
+            connection_window: Cell::new((0, 0)),
         }
     }
 
+    /// Returns whether a new connection on the wayland socket may be accepted.
+    pub fn may_accept(&self, state: &State) -> bool {
+        if let Some(max) = self.max_clients.get() {
+            let num = self.clients.borrow().len();
+            if num >= max {
+                log::warn!("Rejecting connection: {num} clients are already connected");
+                return false;
+            }
+        }
+        if let Some(rate) = self.max_connection_rate.get() {
+            let now = state.clock.now().map(|t| t.usec()).unwrap_or(0);
+            let (mut start, mut count) = self.connection_window.get();
+            if now.saturating_sub(start) >= 1_000_000 {
+                (start, count) = (now, 0);
+            }
+            if count >= rate {
+                log::warn!("Rejecting connection: more than {rate} connections per second");
+                self.connection_window.set((start, count));
+                return false;
+            }
+            self.connection_window.set((start, count + 1));
+        }
+        true
+    }
+
     pub fn clear(&self) {
         mem::take(self.clients.borrow_mut().deref_mut());
         mem::take(self.shutdown_clients.borrow_mut().deref_mut());
@@ -149,6 +182,13 @@ impl Clients {
             last_xwayland_serial: Cell::new(0),
             surfaces_by_xwayland_serial: Default::default(),
             activation_tokens: Default::default(),
+            spawn_workspace: CloneCell::new(global.spawn_workspaces.remove(&pid)),
+            wire_scale: match is_xwayland {
+                true => global.xwayland.scale.get().round_up() as i32,
+                false => 1,
+            },
+            render_device: global.spawn_render_devices.get(&pid),
+            render_device_feedback: Default::default(),
         });
         track!(data, data);
         let display = Rc::new(WlDisplay::new(&data));
@@ -260,6 +300,10 @@ pub struct Client {
     pub last_xwayland_serial: Cell<u64>,
     pub surfaces_by_xwayland_serial: CopyHashMap<u64, Rc<WlSurface>>,
     pub activation_tokens: RefCell<VecDeque<ActivationToken>>,
+    pub spawn_workspace: CloneCell<Option<Rc<String>>>,
+    pub wire_scale: i32,
+    pub render_device: Option<c::dev_t>,
+    render_device_feedback: RefCell<Option<(DrmFeedbackId, Rc<DrmFeedback>)>>,
 }
 
 pub const NUM_CACHED_SERIAL_RANGES: usize = 64;
@@ -270,6 +314,63 @@ pub struct SerialRange {
 }
 
 impl Client {
+    /// Returns the default dmabuf feedback of this client.
+    ///
+    /// If the client was spawned on a specific render device, the feedback prefers that
+    /// device for the formats that both it and the compositor's render device support.
+    pub fn drm_feedback(&self) -> Option<Rc<DrmFeedback>> {
+        let fb = self.state.drm_feedback.get()?;
+        let Some(dev) = self.render_device else {
+            return Some(fb);
+        };
+        let mut cached = self.render_device_feedback.borrow_mut();
+        if let Some((id, dev_fb)) = &*cached {
+            if *id == fb.id {
+                return Some(dev_fb.clone());
+            }
+        }
+        let id = fb.id;
+        let formats = self.render_device_formats(&fb, dev);
+        let dev_fb = match fb.for_render_device(&self.state.drm_feedback_ids, dev, &formats) {
+            Some(dev_fb) => Rc::new(dev_fb),
+            None => fb,
+        };
+        *cached = Some((id, dev_fb.clone()));
+        Some(dev_fb)
+    }
+
+    /// Returns the formats of the compositor's feedback that the device `dev` can render
+    /// to.
+    ///
+    /// Linear buffers can be shared between all devices. Other modifiers are only used
+    /// if the device is managed by the compositor and supports them.
+    fn render_device_formats(&self, fb: &DrmFeedback, dev: c::dev_t) -> Vec<(u32, Modifier)> {
+        let dev_formats = self
+            .state
+            .drm_devs
+            .lock()
+            .values()
+            .find(|d| d.dev.dev_t() == dev)
+            .map(|d| d.dev.gfx_ctx().formats());
+        fb.shared
+            .indices
+            .keys()
+            .filter(|(format, modifier)| {
+                if *modifier == LINEAR_MODIFIER {
+                    return true;
+                }
+                let Some(dev_formats) = &dev_formats else {
+                    return false;
+                };
+                match dev_formats.get(format) {
+                    Some(f) => f.write_modifiers.contains(modifier),
+                    None => false,
+                }
+            })
+            .copied()
+            .collect()
+    }
+
     pub fn invalid_request(&self, obj: &dyn Object, request: u32) {
         log::error!(
             "Client {} sent an invalid request {} on object {} of type {}",
@@ -24,9 +24,11 @@ use {
         sighand::{self, SighandError},
         state::{ConnectorData, IdleState, ScreenlockState, State, XWaylandState},
         tasks::{self, idle},
+        time::{Clock, MonotonicClock},
         tree::{
             container_layout, container_render_data, float_layout, float_titles,
-            output_render_data, DisplayNode, NodeIds, OutputNode, WorkspaceNode,
+            handle_empty_workspaces, output_render_data, DisplayNode, NodeIds, OutputNode,
+            WorkspaceNode,
         },
         user_session::import_environment,
         utils::{
@@ -39,8 +41,16 @@ use {
     },
     ahash::AHashSet,
     forker::ForkerProxy,
-    jay_config::video::GfxApi,
-    std::{cell::Cell, env, future::Future, ops::Deref, rc::Rc, sync::Arc, time::Duration},
+    jay_config::{video::GfxApi, Axis, FloatPlacement, WindowState},
+    std::{
+        cell::{Cell, RefCell},
+        env,
+        future::Future,
+        ops::Deref,
+        rc::Rc,
+        sync::Arc,
+        time::{Duration, Instant},
+    },
     thiserror::Error,
     uapi::c,
 };
@@ -116,7 +126,8 @@ fn start_compositor2(
     let engine = AsyncEngine::new();
     let ring = IoUring::new(&engine, 32)?;
     let _signal_future = sighand::install(&engine, &ring)?;
-    let wheel = Wheel::new(&engine, &ring)?;
+    let clock = create_clock(test_future.is_some());
+    let wheel = Wheel::with_clock(&engine, &ring, &clock)?;
     let (_run_toplevel_future, run_toplevel) = RunToplevel::install(&engine);
     let node_ids = NodeIds::default();
     let scales = RefCounted::default();
@@ -132,13 +143,19 @@ fn start_compositor2(
         drm_feedback_consumers: Default::default(),
         render_ctx_version: NumCell::new(1),
         render_ctx_ever_initialized: Cell::new(false),
+        syncobj_manager_global: Default::default(),
+        outputs_ready: Default::default(),
         cursors: Default::default(),
         wheel,
+        clock,
         clients: Clients::new(),
         globals: Globals::new(),
         connector_ids: Default::default(),
         root: Rc::new(DisplayNode::new(node_ids.next())),
         workspaces: Default::default(),
+        spawn_workspaces: Default::default(),
+        spawn_render_devices: Default::default(),
+        disabled_modifiers: Default::default(),
         dummy_output: Default::default(),
         node_ids,
         backend_events: AsyncQueue::new(),
@@ -156,6 +173,7 @@ fn start_compositor2(
         pending_output_render_data: Default::default(),
         pending_float_layout: Default::default(),
         pending_float_titles: Default::default(),
+        pending_empty_workspaces: Default::default(),
         dbus: Dbus::new(&engine, &ring, &run_toplevel),
         fdcloser: FdCloser::new(),
         logger,
@@ -170,12 +188,16 @@ fn start_compositor2(
             timeout_changed: Default::default(),
             inhibitors: Default::default(),
             inhibitors_changed: Default::default(),
+            inhibited_override: Default::default(),
+            clock_advanced: Default::default(),
         },
         run_args,
         xwayland: XWaylandState {
             enabled: Cell::new(true),
             handler: Default::default(),
             queue: Default::default(),
+            scale: Cell::new(Scale::from_int(1)),
+            lazy: Cell::new(true),
         },
         acceptor: Default::default(),
         serial: Default::default(),
@@ -198,15 +220,36 @@ fn start_compositor2(
         render_ctx_watchers: Default::default(),
         workspace_watchers: Default::default(),
         default_workspace_capture: Cell::new(true),
+        workspace_switch_animation: Cell::new((Axis::Horizontal, Duration::ZERO)),
+        float_default_size: Cell::new((0, 0)),
+        float_placement: Cell::new(FloatPlacement::Center),
+        float_cascade_idx: Cell::new(0),
+        auto_destroy_empty_workspaces: Cell::new(false),
+        persistent_workspaces: Default::default(),
+        empty_workspace_action: Default::default(),
+        window_state_cycle: RefCell::new(vec![
+            WindowState::Tiled,
+            WindowState::Floating,
+            WindowState::Fullscreen,
+        ]),
         default_gfx_api: Cell::new(GfxApi::OpenGl),
         activation_tokens: Default::default(),
         toplevel_lists: Default::default(),
         dma_buf_ids: Default::default(),
         drm_feedback_ids: Default::default(),
         direct_scanout_enabled: Cell::new(true),
+        persistent_clipboard: Cell::new(false),
+        start_time: Instant::now(),
         output_transforms: Default::default(),
         double_click_interval_usec: Cell::new(400 * 1000),
         double_click_distance: Cell::new(5),
+        primary_output: Default::default(),
+        max_in_flight_frames: Cell::new(3),
+        linear_blending: Cell::new(false),
+        commit_throttling: Cell::new(false),
+        dummy_output_color: Default::default(),
+        hotplug_debounce_ms: Cell::new(0),
+        pending_connectors: Default::default(),
     });
     state.tracker.register(ClientId::from_raw(0));
     create_dummy_output(&state);
@@ -264,6 +307,14 @@ async fn start_compositor3(state: Rc<State>, test_future: Option<TestFuture>) {
     state.ring.stop();
 }
 
+fn create_clock(#[allow(unused_variables)] for_test: bool) -> Rc<dyn Clock> {
+    #[cfg(feature = "it")]
+    if for_test {
+        return Rc::new(crate::time::VirtualClock::new());
+    }
+    Rc::new(MonotonicClock)
+}
+
 fn load_config(state: &Rc<State>, #[allow(unused_variables)] for_test: bool) -> ConfigProxy {
     #[cfg(feature = "it")]
     if for_test {
@@ -294,6 +345,7 @@ fn start_global_event_handlers(
         eng.spawn2(Phase::PostLayout, output_render_data(state.clone())),
         eng.spawn2(Phase::Layout, float_layout(state.clone())),
         eng.spawn2(Phase::PostLayout, float_titles(state.clone())),
+        eng.spawn2(Phase::PostLayout, handle_empty_workspaces(state.clone())),
         eng.spawn2(Phase::PostLayout, idle(state.clone(), backend.clone())),
     ]
 }
@@ -370,6 +422,8 @@ fn create_dummy_output(state: &Rc<State>) {
                 }),
                 handler: Cell::new(None),
                 connected: Cell::new(true),
+                enabled: Cell::new(true),
+                non_desktop_override: Default::default(),
                 name: "Dummy".to_string(),
                 drm_dev: None,
                 async_event: Default::default(),
@@ -402,6 +456,13 @@ fn create_dummy_output(state: &Rc<State>) {
         update_render_data_scheduled: Cell::new(false),
         screencasts: Default::default(),
         hardware_cursor_needs_render: Cell::new(false),
+        throttle_frame_callbacks: Cell::new(false),
+        frame_callbacks_dispatched: Cell::new(0),
+        throttled_frame_requests: Default::default(),
+        throttled_frame_flush: Default::default(),
+        workspace_transition: Default::default(),
+        sticky: Default::default(),
+        previous_workspace: Default::default(),
     });
     let dummy_workspace = Rc::new(WorkspaceNode {
         id: state.node_ids.next(),
@@ -7,6 +7,7 @@ use {
         backend::{ConnectorId, DrmDeviceId, InputDeviceId},
         config::handler::ConfigProxyHandler,
         ifs::wl_seat::SeatId,
+        scale::Scale,
         state::State,
         utils::{
             clonecell::CloneCell, numcell::NumCell, ptr_ext::PtrExt, unlink_on_drop::UnlinkOnDrop,
@@ -22,7 +23,8 @@ use {
         },
         input::{InputDevice, Seat},
         keyboard::ModifiedKeySym,
-        video::{Connector, DrmDevice},
+        video::{Connector, DrmDevice, Transform},
+        UnresponsiveWindow,
     },
     libloading::Library,
     std::{cell::Cell, io, mem, ptr, rc::Rc},
@@ -107,6 +109,26 @@ impl ConfigProxy {
         });
     }
 
+    pub fn connector_scale_changed(&self, connector: ConnectorId, scale: Scale) {
+        self.send(&ServerMessage::ConnectorScaleChanged {
+            connector: Connector(connector.raw() as _),
+            scale: scale.to_f64(),
+        });
+    }
+
+    pub fn connector_transform_changed(&self, connector: ConnectorId, transform: Transform) {
+        self.send(&ServerMessage::ConnectorTransformChanged {
+            connector: Connector(connector.raw() as _),
+            transform,
+        });
+    }
+
+    pub fn connector_hotplug(&self, connector: ConnectorId) {
+        self.send(&ServerMessage::ConnectorHotplug {
+            connector: Connector(connector.raw() as _),
+        });
+    }
+
     pub fn new_input_device(&self, dev: InputDeviceId) {
         self.send(&ServerMessage::NewInputDevice {
             device: InputDevice(dev.raw() as _),
@@ -127,6 +149,10 @@ impl ConfigProxy {
         self.send(&ServerMessage::DevicesEnumerated);
     }
 
+    pub fn outputs_ready(&self) {
+        self.send(&ServerMessage::OutputsReady);
+    }
+
     pub fn clear(&self) {
         self.send(&ServerMessage::Clear);
     }
@@ -134,6 +160,10 @@ impl ConfigProxy {
     pub fn idle(&self) {
         self.send(&ServerMessage::Idle);
     }
+
+    pub fn window_unresponsive(&self, window: UnresponsiveWindow) {
+        self.send(&ServerMessage::WindowUnresponsive { window });
+    }
 }
 
 impl Drop for ConfigProxy {
@@ -156,13 +186,20 @@ unsafe extern "C" fn default_client_init(
 }
 
 impl ConfigProxy {
-    fn new(lib: Option<Library>, entry: &ConfigEntry, state: &Rc<State>) -> Self {
+    fn new(
+        lib: Option<Library>,
+        entry: &ConfigEntry,
+        state: &Rc<State>,
+        #[allow(unused_variables)] for_test: bool,
+    ) -> Self {
         let version = entry.version.min(VERSION);
         let data = Rc::new(ConfigProxyHandler {
             client_data: Cell::new(ptr::null()),
             dropped: Cell::new(false),
             _lib: lib,
             _version: version,
+            #[cfg(feature = "it")]
+            for_test,
             unref: entry.unref,
             handle_msg: entry.handle_msg,
             state: state.clone(),
@@ -205,12 +242,12 @@ impl ConfigProxy {
             unref: jay_config::_private::client::unref,
             handle_msg: jay_config::_private::client::handle_msg,
         };
-        Self::new(None, &entry, state)
+        Self::new(None, &entry, state, false)
     }
 
     #[cfg(feature = "it")]
     pub fn for_test(state: &Rc<State>) -> Self {
-        Self::new(None, &TEST_CONFIG_ENTRY, state)
+        Self::new(None, &TEST_CONFIG_ENTRY, state, true)
     }
 
     pub fn from_config_dir(state: &Rc<State>) -> Result<Self, ConfigError> {
@@ -259,7 +296,7 @@ impl ConfigProxy {
             Ok(e) => *e,
             Err(e) => return Err(ConfigError::LibraryDoesNotContainEntry(e)),
         };
-        Ok(Self::new(Some(lib), entry, state))
+        Ok(Self::new(Some(lib), entry, state, false))
     }
 }
 
@@ -5,13 +5,23 @@ use {
             self, ConnectorId, DrmDeviceId, InputDeviceAccelProfile, InputDeviceCapability,
             InputDeviceId,
         },
+        client::{Client, ClientId},
         compositor::MAX_EXTENTS,
-        config::ConfigProxy,
-        ifs::wl_seat::{SeatId, WlSeatGlobal},
+        config::{ConfigError, ConfigProxy},
+        gfx_apis::self_test,
+        ifs::{
+            wl_seat::{SeatId, WlSeatError, WlSeatGlobal},
+            wl_surface::{x_surface::xwindow::Xwindow, xdg_surface::xdg_toplevel::XdgToplevel},
+            wp_content_type_v1::ContentType,
+        },
         scale::Scale,
         state::{ConnectorData, DeviceHandlerData, DrmDevData, OutputData, State},
         theme::{Color, ThemeSized, DEFAULT_FONT},
-        tree::{ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase, OutputNode},
+        time::{Time, TimeError},
+        tree::{
+            ContainerNode, ContainerSplit, FloatNode, Node, NodeVisitorBase, OutputNode,
+            ToplevelNode, ToplevelNodeBase,
+        },
         utils::{
             copyhashmap::CopyHashMap,
             debug_fn::debug_fn,
@@ -22,11 +32,12 @@ use {
         },
         xkbcommon::{XkbCommonError, XkbKeymap},
     },
+    ahash::AHashSet,
     bincode::Options,
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage},
+            ipc::{ClientMessage, FocusedWindow, Response, ServerMessage},
         },
         input::{
             acceleration::{AccelProfile, ACCEL_PROFILE_ADAPTIVE, ACCEL_PROFILE_FLAT},
@@ -34,14 +45,19 @@ use {
                 Capability, CAP_GESTURE, CAP_KEYBOARD, CAP_POINTER, CAP_SWITCH, CAP_TABLET_PAD,
                 CAP_TABLET_TOOL, CAP_TOUCH,
             },
-            InputDevice, Seat,
+            InputDevice, PointerInfo, Seat, SurfaceId, SurfaceInfo,
         },
         keyboard::{mods::Modifiers, syms::KeySym, Keymap},
         logging::LogLevel,
         theme::{colors::Colorable, sized::Resizable},
-        timer::Timer as JayTimer,
-        video::{Connector, DrmDevice, GfxApi, Transform},
-        Axis, Direction, Workspace,
+        timer::{MissedTicks, Timer as JayTimer},
+        video::{
+            Connector, ContentType as ConfigContentType, DrmDevice, FormatCapabilities,
+            FramebufferInfo as ConfigFramebufferInfo, GfxApi, HotplugPolicy, OutputLayout,
+            OutputLayoutEntry, Transform,
+        },
+        Axis, ClientInfo, CompositorInfo, Direction, EmptyWorkspaceAction, FloatPlacement,
+        IdleInhibitor, Window, WindowInfo, WindowState, Workspace,
     },
     libloading::Library,
     log::Level,
@@ -55,6 +71,8 @@ pub(super) struct ConfigProxyHandler {
     pub dropped: Cell<bool>,
     pub _lib: Option<Library>,
     pub _version: u32,
+    #[cfg(feature = "it")]
+    pub for_test: bool,
     pub unref: unsafe extern "C" fn(data: *const u8),
     pub handle_msg: unsafe extern "C" fn(data: *const u8, msg: *const u8, size: usize),
     pub state: Rc<State>,
@@ -75,7 +93,11 @@ pub(super) struct TimerData {
     timer: TimerFd,
     id: u64,
     name: Rc<String>,
+    missed_ticks: Rc<Cell<MissedTicks>>,
     _handler: SpawnedFuture<()>,
+    /// The next expiration of the timer if it runs on a virtual clock.
+    virtual_expiration: Cell<Option<Time>>,
+    virtual_handler: Cell<Option<SpawnedFuture<()>>>,
 }
 
 impl ConfigProxyHandler {
@@ -111,13 +133,7 @@ impl ConfigProxyHandler {
         file: Option<&str>,
         line: Option<u32>,
     ) {
-        let level = match level {
-            LogLevel::Error => Level::Error,
-            LogLevel::Warn => Level::Warn,
-            LogLevel::Info => Level::Info,
-            LogLevel::Debug => Level::Debug,
-            LogLevel::Trace => Level::Trace,
-        };
+        let level = log_level(level);
         let debug = debug_fn(|fmt| {
             if let Some(file) = file {
                 write!(fmt, "{}", file)?;
@@ -132,6 +148,34 @@ impl ConfigProxyHandler {
         log::log!(level, "{:?}", debug);
     }
 
+    fn handle_set_log_level(
+        &self,
+        subsystem: Option<&str>,
+        level: LogLevel,
+    ) -> Result<(), CphError> {
+        let Some(logger) = &self.state.logger else {
+            return Ok(());
+        };
+        let level = log_level(level);
+        match subsystem {
+            None => logger.set_level(level),
+            Some(subsystem) => {
+                if logger.set_subsystem_level(subsystem, level).is_err() {
+                    return Err(CphError::UnknownSubsystem(subsystem.to_string()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_get_recent_logs(&self, max_lines: usize) {
+        let lines = match &self.state.logger {
+            Some(logger) => logger.recent_lines(max_lines),
+            None => vec![],
+        };
+        self.respond(Response::RecentLogs { lines });
+    }
+
     fn handle_get_seat(&self, name: &str) {
         for seat in self.state.globals.seats.lock().values() {
             if seat.seat_name() == name {
@@ -179,6 +223,39 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_drm_device_render_node(&self, dev: DrmDevice) -> Result<(), CphError> {
+        let dev = self.get_drm_device(dev)?;
+        let render_node = dev.render_node.clone().unwrap_or_default();
+        self.respond(Response::GetDrmDeviceRenderNode { render_node });
+        Ok(())
+    }
+
+    fn handle_get_drm_device_gfx_api(&self, dev: DrmDevice) -> Result<(), CphError> {
+        let dev = self.get_drm_device(dev)?;
+        let api = dev.dev.gtx_api();
+        self.respond(Response::GetDrmDeviceGfxApi { api });
+        Ok(())
+    }
+
+    fn handle_get_drm_device_supported_gfx_apis(&self, dev: DrmDevice) -> Result<(), CphError> {
+        let dev = self.get_drm_device(dev)?;
+        let apis = dev.dev.supported_gfx_apis();
+        self.respond(Response::GetDrmDeviceSupportedGfxApis { apis });
+        Ok(())
+    }
+
+    fn handle_run_gfx_self_test(&self, dev: DrmDevice) -> Result<(), CphError> {
+        let dev = self.get_drm_device(dev)?;
+        let ctx = dev.dev.gfx_ctx();
+        let api = ctx.gfx_api();
+        let (passed, details) = match self_test::run(&ctx) {
+            Ok(()) => (true, format!("{:?}: All checks passed", api)),
+            Err(e) => (false, format!("{:?}: {}", api, ErrorFmt(e))),
+        };
+        self.respond(Response::GfxSelfTest { passed, details });
+        Ok(())
+    }
+
     fn handle_get_drm_device_vendor(&self, dev: DrmDevice) -> Result<(), CphError> {
         let dev = self.get_drm_device(dev)?;
         let vendor = dev.vendor.clone().unwrap_or_default();
@@ -188,8 +265,15 @@ impl ConfigProxyHandler {
 
     fn handle_get_drm_devices(&self) {
         let devs = self.state.drm_devs.lock();
+        let mut devs: Vec<_> = devs.values().collect();
+        devs.sort_by(|a, b| {
+            a.syspath
+                .cmp(&b.syspath)
+                .then_with(|| a.devnode.cmp(&b.devnode))
+                .then_with(|| a.dev.id().raw().cmp(&b.dev.id().raw()))
+        });
         let mut res = vec![];
-        for dev in devs.values() {
+        for dev in devs {
             res.push(DrmDevice(dev.dev.id().raw() as _));
         }
         self.respond(Response::GetDrmDevices { devices: res });
@@ -215,9 +299,23 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn load_new_config(&self) -> Result<ConfigProxy, ConfigError> {
+        #[cfg(feature = "it")]
+        if self.for_test {
+            return Ok(ConfigProxy::for_test(&self.state));
+        }
+        ConfigProxy::from_config_dir(&self.state)
+    }
+
+    /// Replaces the config by a freshly loaded one.
+    ///
+    /// Only config-owned state is discarded: shortcuts, timers, and the ids handed out to
+    /// the old config. The tree, seats, outputs, and workspaces are left untouched. The new
+    /// config receives `Configure { reload: true }` and is expected to re-register
+    /// everything it needs.
     fn handle_reload(&self) {
         log::info!("Reloading config");
-        let config = match ConfigProxy::from_config_dir(&self.state) {
+        let config = match self.load_new_config() {
             Ok(c) => c,
             Err(e) => {
                 log::error!("Cannot reload config: {}", ErrorFmt(e));
@@ -242,6 +340,131 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_get_content_type(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let content_type = seat.get_content_type().map(|ct| match ct {
+            ContentType::Photo => ConfigContentType::Photo,
+            ContentType::Video => ConfigContentType::Video,
+            ContentType::Game => ConfigContentType::Game,
+        });
+        self.respond(Response::GetContentType { content_type });
+        Ok(())
+    }
+
+    fn handle_get_focused_window_info(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let info = seat.get_focused_toplevel().map(|tl| window_info(&*tl));
+        self.respond(Response::GetFocusedWindowInfo { info });
+        Ok(())
+    }
+
+    fn find_window(&self, window: Window) -> Option<Rc<dyn ToplevelNode>> {
+        struct V {
+            id: u64,
+            res: Option<Rc<dyn ToplevelNode>>,
+        }
+        impl V {
+            fn check(&mut self, tl: Rc<dyn ToplevelNode>) -> bool {
+                if tl.node_id().raw() as u64 == self.id {
+                    self.res = Some(tl);
+                }
+                self.res.is_some()
+            }
+        }
+        impl NodeVisitorBase for V {
+            fn visit_toplevel(&mut self, node: &Rc<XdgToplevel>) {
+                if !self.check(node.clone()) {
+                    node.node_visit_children(self);
+                }
+            }
+            fn visit_xwindow(&mut self, node: &Rc<Xwindow>) {
+                if !self.check(node.clone()) {
+                    node.node_visit_children(self);
+                }
+            }
+        }
+        let mut v = V {
+            id: window.0,
+            res: None,
+        };
+        self.state.root.clone().node_visit(&mut v);
+        v.res
+    }
+
+    fn get_window(&self, window: Window) -> Result<Rc<dyn ToplevelNode>, CphError> {
+        match self.find_window(window) {
+            Some(tl) => Ok(tl),
+            _ => Err(CphError::WindowDoesNotExist(window)),
+        }
+    }
+
+    fn handle_get_window_info(&self, window: Window) {
+        let info = self.find_window(window).map(|tl| window_info(&*tl));
+        self.respond(Response::GetWindowInfo { info });
+    }
+
+    fn handle_focus_window(&self, window: Window, seat: Seat) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let seat = self.get_seat(seat)?;
+        if let Some(ws) = tl.tl_data().workspace.get() {
+            if !ws.visible.get() {
+                self.state.show_workspace(&seat, &ws.name);
+            }
+        }
+        tl.tl_into_node()
+            .node_do_focus(&seat, crate::tree::Direction::Unspecified);
+        Ok(())
+    }
+
+    fn handle_close_window(&self, window: Window) -> Result<(), CphError> {
+        self.get_window(window)?.tl_close();
+        Ok(())
+    }
+
+    fn handle_move_window_to_workspace(
+        &self,
+        window: Window,
+        ws: Workspace,
+    ) -> Result<(), CphError> {
+        let tl = self.get_window(window)?;
+        let name = self.get_workspace(ws)?;
+        let workspace = match self.state.workspaces.get(name.deref()) {
+            Some(ws) => ws,
+            _ => match tl.tl_data().workspace.get() {
+                Some(ws) => ws.output.get().create_workspace(name.deref()),
+                _ => return Ok(()),
+            },
+        };
+        self.state.move_toplevel_to_workspace(tl, &workspace);
+        Ok(())
+    }
+
+    fn handle_get_focused_window(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let window = seat.get_focused_toplevel().map(|tl| FocusedWindow {
+            window: Window(tl.node_id().raw() as _),
+            title: tl.tl_data().title.borrow().clone(),
+        });
+        self.respond(Response::GetFocusedWindow { window });
+        Ok(())
+    }
+
+    fn handle_get_surface_info(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let info = seat.get_focused_surface().map(|surface| {
+            let buffer = surface.buffer.get();
+            SurfaceInfo {
+                role: surface.role_name().to_string(),
+                buffer_format: buffer.as_ref().map(|b| b.format.name.to_string()),
+                buffer_width: buffer.as_ref().map(|b| b.rect.width()).unwrap_or(0),
+                buffer_height: buffer.as_ref().map(|b| b.rect.height()).unwrap_or(0),
+                commits: surface.commits(),
+            }
+        });
+        self.respond(Response::GetSurfaceInfo { info });
+        Ok(())
+    }
+
     fn handle_set_fullscreen(&self, seat: Seat, fullscreen: bool) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.set_fullscreen(fullscreen);
@@ -259,6 +482,23 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_push_keymap(&self, seat: Seat, keymap: Keymap) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let keymap = if keymap.is_invalid() {
+            self.state.default_keymap.clone()
+        } else {
+            self.get_keymap(keymap)?
+        };
+        seat.push_keymap(&keymap);
+        Ok(())
+    }
+
+    fn handle_pop_keymap(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.pop_keymap();
+        Ok(())
+    }
+
     fn handle_set_status(&self, status: &str) {
         self.state.set_status(status);
     }
@@ -283,14 +523,136 @@ impl ConfigProxyHandler {
         }
     }
 
+    fn handle_unset_env(&self, key: &str) {
+        if let Some(f) = self.state.forker.get() {
+            f.unsetenv(key.as_bytes());
+        }
+    }
+
+    fn handle_get_env(&self, key: &str) {
+        let val = self
+            .state
+            .forker
+            .get()
+            .and_then(|f| f.getenv(key.as_bytes()))
+            .and_then(|v| String::from_utf8(v).ok());
+        self.respond(Response::GetEnv { val });
+    }
+
     fn handle_program_timer(
-        &self,
+        self: &Rc<Self>,
         timer: JayTimer,
         initial: Option<Duration>,
         periodic: Option<Duration>,
+        missed: MissedTicks,
+    ) -> Result<(), CphError> {
+        let timer = self.get_timer(timer)?;
+        timer.missed_ticks.set(missed);
+        if self.state.clock.is_virtual() {
+            self.program_virtual_timer(&timer, initial, periodic)?;
+        } else {
+            timer.timer.program(initial, periodic)?;
+        }
+        Ok(())
+    }
+
+    /// Programs a timer when the compositor runs on a virtual clock.
+    ///
+    /// Such a clock only advances when told to, so the timer is driven by the wheel
+    /// instead of a timerfd.
+    fn program_virtual_timer(
+        self: &Rc<Self>,
+        timer: &Rc<TimerData>,
+        initial: Option<Duration>,
+        periodic: Option<Duration>,
     ) -> Result<(), CphError> {
+        timer.virtual_handler.take();
+        timer.virtual_expiration.set(None);
+        let Some(initial) = initial else {
+            return Ok(());
+        };
+        let periodic = periodic.filter(|p| !p.is_zero());
+        timer
+            .virtual_expiration
+            .set(Some(self.state.clock.now()? + initial));
+        let slf = self.clone();
+        let td = Rc::downgrade(timer);
+        let handler = self.state.eng.spawn(async move {
+            loop {
+                let Some(timer) = td.upgrade() else {
+                    return;
+                };
+                let Some(next) = timer.virtual_expiration.get() else {
+                    return;
+                };
+                let now = match slf.state.clock.now() {
+                    Ok(now) => now,
+                    Err(e) => {
+                        log::error!("Could not read the clock: {}", ErrorFmt(e));
+                        return;
+                    }
+                };
+                if now < next {
+                    let ms = (next - now).as_nanos().div_ceil(1_000_000) as u64;
+                    drop(timer);
+                    if let Err(e) = slf.state.wheel.timeout(ms).await {
+                        log::error!("Could not wait for timer expiration: {}", ErrorFmt(e));
+                        return;
+                    }
+                    continue;
+                }
+                let n = match periodic {
+                    Some(periodic) => {
+                        let n = ((now - next).as_nanos() / periodic.as_nanos()) as u64 + 1;
+                        timer
+                            .virtual_expiration
+                            .set(Some(next + periodic * n as u32));
+                        n
+                    }
+                    None => {
+                        timer.virtual_expiration.set(None);
+                        1
+                    }
+                };
+                slf.send_timer_ticks(timer.id, timer.missed_ticks.get(), n);
+            }
+        });
+        timer.virtual_handler.set(Some(handler));
+        Ok(())
+    }
+
+    fn send_timer_ticks(&self, id: u64, missed_ticks: MissedTicks, n: u64) {
+        let ticks = match missed_ticks {
+            MissedTicks::Coalesce => 1,
+            MissedTicks::CatchUp => n.max(1),
+        };
+        for _ in 0..ticks {
+            self.send(&ServerMessage::TimerExpired {
+                timer: JayTimer(id),
+            });
+        }
+    }
+
+    fn handle_get_timer_remaining(&self, timer: JayTimer) -> Result<(), CphError> {
+        let timer = self.get_timer(timer)?;
+        let remaining = if self.state.clock.is_virtual() {
+            let now = self.state.clock.now()?;
+            timer.virtual_expiration.get().map(|next| match next > now {
+                true => next - now,
+                false => Duration::ZERO,
+            })
+        } else {
+            timer.timer.remaining()?
+        };
+        self.respond(Response::TimerRemaining { remaining });
+        Ok(())
+    }
+
+    fn handle_trigger_timer(&self, timer: JayTimer) -> Result<(), CphError> {
         let timer = self.get_timer(timer)?;
-        timer.timer.program(initial, periodic)?;
+        self.send(&ServerMessage::TimerExpired {
+            timer: JayTimer(timer.id),
+        });
         Ok(())
     }
 
@@ -304,15 +666,15 @@ impl ConfigProxyHandler {
         }
         let id = self.timer_ids.fetch_add(1);
         let timer = TimerFd::new(c::CLOCK_BOOTTIME)?;
+        let missed_ticks = Rc::new(Cell::new(MissedTicks::Coalesce));
         let handler = {
             let timer = timer.clone();
+            let missed_ticks = missed_ticks.clone();
             let slf = self.clone();
             self.state.eng.spawn(async move {
                 loop {
                     match timer.expired(&slf.state.ring).await {
-                        Ok(_) => slf.send(&ServerMessage::TimerExpired {
-                            timer: JayTimer(id),
-                        }),
+                        Ok(n) => slf.send_timer_ticks(id, missed_ticks.get(), n),
                         Err(e) => {
                             log::error!("Could not wait for timer expiration: {}", ErrorFmt(e));
                             if let Some(timer) = slf.timers_by_id.remove(&id) {
@@ -328,7 +690,10 @@ impl ConfigProxyHandler {
             timer,
             id,
             name: name.clone(),
+            missed_ticks,
             _handler: handler,
+            virtual_expiration: Default::default(),
+            virtual_handler: Default::default(),
         });
         self.timers_by_name.set(name.clone(), td.clone());
         self.timers_by_id.set(id, td.clone());
@@ -363,6 +728,32 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_clipboard(
+        &self,
+        seat: Seat,
+        mime_type: &str,
+        data: &[u8],
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_selection_contents(mime_type, data.to_vec())
+            .map_err(CphError::SetSelection)
+    }
+
+    fn handle_get_clipboard(&self, seat: Seat, mime_type: &str) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let data = seat.selection_contents(mime_type);
+        self.respond(Response::GetClipboard { data });
+        Ok(())
+    }
+
+    fn handle_get_seat_idle_time(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let now = self.state.clock.now().map(|t| t.usec()).unwrap_or(0);
+        let elapsed = now.saturating_sub(seat.last_input());
+        self.respond(Response::GetSeatIdleTime { ms: elapsed / 1000 });
+        Ok(())
+    }
+
     fn handle_set_repeat_rate(&self, seat: Seat, rate: i32, delay: i32) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         if rate < 0 {
@@ -459,7 +850,13 @@ impl ConfigProxyHandler {
             Some(self.get_seat(seat)?)
         };
         let dev = self.get_device_handler_data(device)?;
-        dev.seat.set(seat);
+        let old = dev.seat.set(seat.clone());
+        if let Some(old) = old {
+            old.update_capabilities();
+        }
+        if let Some(seat) = seat {
+            seat.update_capabilities();
+        }
         Ok(())
     }
 
@@ -500,6 +897,43 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_input_device_connector(
+        &self,
+        device: InputDevice,
+        connector: Connector,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let connector = self.get_connector(connector)?;
+        dev.output.set(Some(connector.connector.id()));
+        Ok(())
+    }
+
+    fn handle_set_button_map(
+        &self,
+        device: InputDevice,
+        map: Vec<(u32, u32)>,
+    ) -> Result<(), CphError> {
+        let dev = self.get_device_handler_data(device)?;
+        let mut seen = AHashSet::new();
+        for &(physical, _) in &map {
+            if !seen.insert(physical) {
+                return Err(CphError::DuplicateButton(physical));
+            }
+        }
+        // Buttons that are not in the map keep reporting their own code.
+        let mut logical_seen = AHashSet::new();
+        for &(physical, logical) in &map {
+            if !logical_seen.insert(logical) || (logical != physical && !seen.contains(&logical)) {
+                return Err(CphError::ButtonMapCollision(logical));
+            }
+        }
+        dev.button_map.clear();
+        for (physical, logical) in map {
+            dev.button_map.set(physical, logical);
+        }
+        Ok(())
+    }
+
     fn handle_set_tap_enabled(&self, device: InputDevice, enabled: bool) -> Result<(), CphError> {
         let dev = self.get_device_handler_data(device)?;
         dev.device.set_tap_enabled(enabled);
@@ -584,12 +1018,24 @@ impl ConfigProxyHandler {
 
     fn handle_set_gfx_api(&self, device: Option<DrmDevice>, api: GfxApi) -> Result<(), CphError> {
         match device {
-            Some(dev) => self.get_drm_device(dev)?.dev.set_gfx_api(api),
+            Some(dev) => self
+                .get_drm_device(dev)?
+                .dev
+                .set_gfx_api(api)
+                .map_err(CphError::SetGfxApi)?,
             _ => self.state.default_gfx_api.set(api),
         }
         Ok(())
     }
 
+    fn handle_try_set_gfx_api(&self, device: DrmDevice, api: GfxApi) -> Result<(), CphError> {
+        let res = self.handle_set_gfx_api(Some(device), api);
+        self.respond(Response::TrySetGfxApi {
+            success: res.is_ok(),
+        });
+        res
+    }
+
     fn handle_set_direct_scanout_enabled(
         &self,
         device: Option<DrmDevice>,
@@ -611,6 +1057,57 @@ impl ConfigProxyHandler {
         });
     }
 
+    fn handle_set_floating_defaults(
+        &self,
+        width: i32,
+        height: i32,
+        placement: FloatPlacement,
+    ) -> Result<(), CphError> {
+        if width < 0 || height < 0 {
+            return Err(CphError::NegativeFloatSize(width, height));
+        }
+        self.state.float_default_size.set((width, height));
+        self.state.float_placement.set(placement);
+        self.state.float_cascade_idx.set(0);
+        Ok(())
+    }
+
+    fn handle_set_workspace_persistent(
+        &self,
+        workspace: Workspace,
+        persistent: bool,
+    ) -> Result<(), CphError> {
+        let name = self.get_workspace(workspace)?;
+        let mut persistent_workspaces = self.state.persistent_workspaces.borrow_mut();
+        if persistent {
+            persistent_workspaces.insert(name.to_string());
+        } else {
+            persistent_workspaces.remove(name.as_str());
+            drop(persistent_workspaces);
+            if let Some(ws) = self.state.workspaces.get(name.as_str()) {
+                ws.schedule_empty_check();
+            }
+        }
+        Ok(())
+    }
+
+    fn handle_set_auto_destroy_empty_workspaces(&self, enabled: bool) {
+        self.state.auto_destroy_empty_workspaces.set(enabled);
+        if enabled {
+            for ws in self.state.workspaces.lock().values() {
+                ws.schedule_empty_check();
+            }
+        }
+    }
+
+    fn handle_set_empty_workspace_action(&self, action: EmptyWorkspaceAction) {
+        *self.state.empty_workspace_action.borrow_mut() = action;
+    }
+
+    fn handle_set_workspace_switch_animation(&self, axis: Axis, duration: Duration) {
+        self.state.workspace_switch_animation.set((axis, duration));
+    }
+
     fn handle_set_default_workspace_capture(&self, capture: bool) {
         self.state.default_workspace_capture.set(capture);
     }
@@ -675,6 +1172,22 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_get_framebuffer_info(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        let info = connector
+            .connector
+            .framebuffer_info()
+            .map(|info| ConfigFramebufferInfo {
+                width: info.width,
+                height: info.height,
+                format_drm: info.format.drm,
+                modifier: info.modifier,
+                direct_scanout_active: info.direct_scanout_active,
+            });
+        self.respond(Response::FramebufferInfo { info });
+        Ok(())
+    }
+
     fn handle_connector_type(&self, connector: Connector) -> Result<(), CphError> {
         let connector = self.get_connector(connector)?;
         self.respond(Response::ConnectorType {
@@ -694,6 +1207,18 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_modes(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        let modes = connector
+            .monitor_info
+            .modes
+            .iter()
+            .map(|mode| (mode.width, mode.height, mode.refresh_rate_millihz))
+            .collect();
+        self.respond(Response::ConnectorModes { modes });
+        Ok(())
+    }
+
     fn handle_set_cursor_size(&self, seat: Seat, size: i32) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         if size < 0 {
@@ -709,6 +1234,61 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_center_pointer_on_focus(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.center_pointer_on_focus();
+        Ok(())
+    }
+
+    fn handle_get_pointer(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let surface_id = |node: Option<Rc<dyn Node>>| {
+            node.and_then(|n| n.node_into_surface())
+                .map(|surface| SurfaceId {
+                    client: surface.client.id.raw(),
+                    surface: surface.id.raw(),
+                })
+        };
+        let (x, y) = seat.position();
+        let info = PointerInfo {
+            x: x.to_f64(),
+            y: y.to_f64(),
+            surface: surface_id(seat.pointer_node()),
+            focus: surface_id(seat.pointer_focus()),
+        };
+        self.respond(Response::GetPointer { info });
+        Ok(())
+    }
+
+    fn handle_set_pointer_position(
+        &self,
+        seat: Seat,
+        mut x: i32,
+        mut y: i32,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let nearest = self
+            .state
+            .root
+            .outputs
+            .lock()
+            .values()
+            .map(|output| {
+                let pos = output.global.pos.get();
+                let nx = x.clamp(pos.x1(), (pos.x2() - 1).max(pos.x1()));
+                let ny = y.clamp(pos.y1(), (pos.y2() - 1).max(pos.y1()));
+                let dx = nx as i64 - x as i64;
+                let dy = ny as i64 - y as i64;
+                (dx * dx + dy * dy, nx, ny)
+            })
+            .min();
+        if let Some((_, nx, ny)) = nearest {
+            (x, y) = (nx, ny);
+        }
+        seat.set_position(x, y);
+        Ok(())
+    }
+
     fn handle_set_use_hardware_cursor(
         &self,
         seat: Seat,
@@ -770,6 +1350,16 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_connector_set_throttle_frame_callbacks(
+        &self,
+        connector: Connector,
+        throttle: bool,
+    ) -> Result<(), CphError> {
+        let connector = self.get_output(connector)?;
+        connector.node.set_throttle_frame_callbacks(throttle);
+        Ok(())
+    }
+
     fn handle_connector_set_position(
         &self,
         connector: Connector,
@@ -780,11 +1370,16 @@ impl ConfigProxyHandler {
         if x < 0 || y < 0 || x > MAX_EXTENTS || y > MAX_EXTENTS {
             return Err(CphError::InvalidConnectorPosition(x, y));
         }
-        let old_pos = connector.node.global.pos.get();
-        connector.node.set_position(x, y);
+        self.set_output_position(&connector, x, y);
+        Ok(())
+    }
+
+    fn set_output_position(&self, output: &OutputData, x: i32, y: i32) {
+        let old_pos = output.node.global.pos.get();
+        output.node.set_position(x, y);
         let seats = self.state.globals.seats.lock();
         for seat in seats.values() {
-            if seat.get_output().id == connector.node.id {
+            if seat.get_output().id == output.node.id {
                 let seat_pos = seat.position();
                 seat.set_position(
                     seat_pos.0.round_down() + x - old_pos.x1(),
@@ -792,6 +1387,277 @@ impl ConfigProxyHandler {
                 );
             }
         }
+    }
+
+    fn handle_validate_output_layout(&self) {
+        let rects: Vec<_> = self
+            .state
+            .outputs
+            .lock()
+            .values()
+            .map(|o| o.node.global.pos.get())
+            .collect();
+        let mut overlapping = false;
+        for (idx, r1) in rects.iter().enumerate() {
+            for r2 in &rects[idx + 1..] {
+                overlapping |= r1.intersects(r2);
+            }
+        }
+        let mut reached = vec![false; rects.len()];
+        let mut stack = vec![];
+        if !rects.is_empty() {
+            reached[0] = true;
+            stack.push(0);
+        }
+        while let Some(idx) = stack.pop() {
+            for (other, rect) in rects.iter().enumerate() {
+                if !reached[other] && (rects[idx].intersects(rect) || rects[idx].touches(rect)) {
+                    reached[other] = true;
+                    stack.push(other);
+                }
+            }
+        }
+        let gaps = reached.contains(&false);
+        self.respond(Response::ValidateOutputLayout { overlapping, gaps });
+    }
+
+    fn handle_get_output_layout(&self) {
+        let mut outputs = vec![];
+        for data in self.state.connectors.lock().values() {
+            let entry = match self.state.outputs.get(&data.connector.id()) {
+                Some(output) => {
+                    let global = &output.node.global;
+                    let mode = global.mode.get();
+                    let pos = global.pos.get();
+                    OutputLayoutEntry {
+                        connector: data.name.clone(),
+                        manufacturer: output.monitor_info.manufacturer.clone(),
+                        model: output.monitor_info.product.clone(),
+                        serial_number: output.monitor_info.serial_number.clone(),
+                        width: mode.width,
+                        height: mode.height,
+                        refresh_millihz: mode.refresh_rate_millihz,
+                        x: pos.x1(),
+                        y: pos.y1(),
+                        scale: global.preferred_scale.get().to_f64(),
+                        transform: global.transform.get(),
+                        enabled: true,
+                    }
+                }
+                _ if !data.enabled.get() => OutputLayoutEntry {
+                    connector: data.name.clone(),
+                    scale: 1.0,
+                    enabled: false,
+                    ..Default::default()
+                },
+                _ => continue,
+            };
+            outputs.push(entry);
+        }
+        outputs.sort_by(|a, b| a.connector.cmp(&b.connector));
+        self.respond(Response::GetOutputLayout {
+            layout: OutputLayout { outputs },
+        });
+    }
+
+    fn handle_apply_output_layout(&self, layout: OutputLayout) -> Result<(), CphError> {
+        let mut changes = vec![];
+        let mut seen_names = AHashSet::new();
+        let mut seen_identities = AHashSet::new();
+        let mut seen_connectors = AHashSet::new();
+        for entry in &layout.outputs {
+            if !seen_names.insert(&entry.connector) {
+                return Err(CphError::DuplicateLayoutEntry(entry.connector.clone()));
+            }
+            if entry.enabled {
+                if entry.x < 0 || entry.y < 0 || entry.x > MAX_EXTENTS || entry.y > MAX_EXTENTS {
+                    return Err(CphError::InvalidConnectorPosition(entry.x, entry.y));
+                }
+                if entry.scale < 0.1 {
+                    return Err(CphError::ScaleTooSmall(entry.scale));
+                }
+                if entry.scale > 1000.0 {
+                    return Err(CphError::ScaleTooLarge(entry.scale));
+                }
+            }
+            let has_identity = !entry.manufacturer.is_empty()
+                || !entry.model.is_empty()
+                || !entry.serial_number.is_empty();
+            let mut output = None;
+            if has_identity {
+                let identity = (&entry.manufacturer, &entry.model, &entry.serial_number);
+                if !seen_identities.insert(identity) {
+                    return Err(CphError::DuplicateLayoutEntry(entry.connector.clone()));
+                }
+                output = self
+                    .state
+                    .outputs
+                    .lock()
+                    .values()
+                    .find(|o| {
+                        let mi = &o.monitor_info;
+                        mi.manufacturer == entry.manufacturer
+                            && mi.product == entry.model
+                            && mi.serial_number == entry.serial_number
+                    })
+                    .cloned();
+            }
+            let data = match &output {
+                Some(o) => o.connector.clone(),
+                _ => {
+                    let data = self
+                        .state
+                        .connectors
+                        .lock()
+                        .values()
+                        .find(|c| c.name == entry.connector)
+                        .cloned();
+                    match data {
+                        Some(d) => d,
+                        _ => continue,
+                    }
+                }
+            };
+            if !seen_connectors.insert(data.connector.id()) {
+                return Err(CphError::DuplicateLayoutEntry(entry.connector.clone()));
+            }
+            if output.is_none() {
+                output = self.state.outputs.get(&data.connector.id());
+            }
+            let mode = backend::Mode {
+                width: entry.width,
+                height: entry.height,
+                refresh_rate_millihz: entry.refresh_millihz,
+            };
+            if let Some(output) = &output {
+                if entry.enabled && !output.monitor_info.modes.contains(&mode) {
+                    return Err(CphError::UnknownMode(entry.connector.clone(), mode));
+                }
+            }
+            changes.push((entry, data, output, mode));
+        }
+        for (entry, data, output, mode) in changes {
+            if data.enabled.replace(entry.enabled) != entry.enabled {
+                data.connector.set_enabled(entry.enabled);
+            }
+            if !entry.enabled {
+                continue;
+            }
+            if let Some(output) = output {
+                if output.node.global.mode.get() != mode {
+                    data.connector.set_mode(mode);
+                }
+                output
+                    .node
+                    .set_preferred_scale(Scale::from_f64(entry.scale));
+                output.node.update_transform(entry.transform);
+                self.set_output_position(&output, entry.x, entry.y);
+            }
+        }
+        self.state.damage();
+        Ok(())
+    }
+
+    fn handle_get_gpu_time(&self) {
+        let time = self.state.render_ctx.get().and_then(|ctx| ctx.gpu_time());
+        self.respond(Response::GetGpuTime { time });
+    }
+
+    fn handle_get_max_texture_size(&self) {
+        let size = self
+            .state
+            .render_ctx
+            .get()
+            .map(|ctx| ctx.max_texture_size());
+        self.respond(Response::MaxTextureSize { size });
+    }
+
+    fn handle_set_max_in_flight_frames(&self, frames: usize) {
+        let frames = frames.max(1);
+        self.state.max_in_flight_frames.set(frames);
+        if let Some(ctx) = self.state.render_ctx.get() {
+            ctx.set_max_in_flight_frames(frames);
+        }
+    }
+
+    fn handle_set_linear_blending(&self, enabled: bool) {
+        self.state.linear_blending.set(enabled);
+        if let Some(ctx) = self.state.render_ctx.get() {
+            ctx.set_linear_blending(enabled);
+        }
+        self.state.damage();
+    }
+
+    fn handle_disable_modifier(&self, format_drm: u32, modifier: u64) {
+        if self
+            .state
+            .disabled_modifiers
+            .set((format_drm, modifier), ())
+            .is_some()
+        {
+            return;
+        }
+        self.state.update_drm_feedback();
+        for connector in self.state.connectors.lock().values() {
+            connector.connector.update_drm_feedback();
+        }
+    }
+
+    fn handle_get_format_capabilities(&self, format_drm: u32) {
+        let capabilities = self.state.render_ctx.get().and_then(|ctx| {
+            let formats = ctx.formats();
+            let format = formats.get(&format_drm)?;
+            let mut scanout = vec![];
+            for connector in self.state.connectors.lock().values() {
+                let Some(fb) = connector.connector.drm_feedback() else {
+                    continue;
+                };
+                for tranche in fb.tranches.iter().filter(|t| t.scanout) {
+                    for (&(format, modifier), idx) in &fb.shared.indices {
+                        if format == format_drm
+                            && tranche.indices.contains(idx)
+                            && !scanout.contains(&modifier)
+                        {
+                            scanout.push(modifier);
+                        }
+                    }
+                }
+            }
+            scanout.sort_unstable();
+            Some(FormatCapabilities {
+                sampleable: format.read_modifiers.iter().copied().collect(),
+                renderable: format.write_modifiers.iter().copied().collect(),
+                scanout,
+            })
+        });
+        self.respond(Response::GetFormatCapabilities { capabilities });
+    }
+
+    fn handle_get_in_flight_frames(&self) {
+        let frames = self
+            .state
+            .render_ctx
+            .get()
+            .map(|ctx| ctx.in_flight_frames())
+            .unwrap_or(0);
+        self.respond(Response::GetInFlightFrames { frames });
+    }
+
+    fn handle_auto_arrange_outputs(&self) {
+        let mut outputs: Vec<_> = self.state.outputs.lock().values().cloned().collect();
+        outputs.sort_by_key(|o| o.connector.connector.id().raw());
+        let mut x = 0;
+        for output in outputs {
+            self.set_output_position(&output, x, 0);
+            x += output.node.global.pos.get().width();
+        }
+    }
+
+    fn handle_set_primary_output(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        self.state
+            .primary_output
+            .set(Some(connector.connector.id()));
         Ok(())
     }
 
@@ -801,10 +1667,63 @@ impl ConfigProxyHandler {
         enabled: bool,
     ) -> Result<(), CphError> {
         let connector = self.get_connector(connector)?;
+        connector.enabled.set(enabled);
         connector.connector.set_enabled(enabled);
         Ok(())
     }
 
+    fn handle_connector_freeze(&self, connector: Connector, frozen: bool) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector.connector.set_frozen(frozen);
+        Ok(())
+    }
+
+    fn handle_connector_set_hotplug_policy(
+        &self,
+        connector: Connector,
+        policy: HotplugPolicy,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector.connector.set_hotplug_policy(policy);
+        Ok(())
+    }
+
+    fn handle_connector_set_non_desktop(
+        &self,
+        connector: Connector,
+        value: bool,
+    ) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector.non_desktop_override.set(Some(value));
+        connector.async_event.trigger();
+        Ok(())
+    }
+
+    fn handle_connector_accept_hotplug(&self, connector: Connector) -> Result<(), CphError> {
+        let connector = self.get_connector(connector)?;
+        connector.connector.accept_hotplug();
+        Ok(())
+    }
+
+    fn handle_connector_set_brightness(
+        &self,
+        connector: Connector,
+        percent: f64,
+    ) -> Result<(), CphError> {
+        let data = self.get_connector(connector)?;
+        if !data.connector.set_brightness(percent.clamp(0.0, 100.0)) {
+            return Err(CphError::NoBacklight(connector));
+        }
+        Ok(())
+    }
+
+    fn handle_connector_get_brightness(&self, connector: Connector) -> Result<(), CphError> {
+        let data = self.get_connector(connector)?;
+        let percent = data.connector.brightness();
+        self.respond(Response::ConnectorGetBrightness { percent });
+        Ok(())
+    }
+
     fn handle_get_connector(
         &self,
         ty: jay_config::video::connector_type::ConnectorType,
@@ -904,6 +1823,85 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_set_shortcut_inhibit_break(
+        &self,
+        seat: Seat,
+        mods: Modifiers,
+        sym: KeySym,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_shortcut_inhibit_break(mods, sym);
+        Ok(())
+    }
+
+    fn handle_get_compositor_info(&self) {
+        let ctx = self.state.render_ctx.get();
+        let info = CompositorInfo {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_secs: self.state.start_time.elapsed().as_secs(),
+            gfx_api: ctx.as_ref().map(|ctx| ctx.gfx_api()),
+            render_node: ctx.map(|ctx| ctx.render_node().to_string_lossy().into_owned()),
+        };
+        self.respond(Response::GetCompositorInfo { info });
+    }
+
+    fn handle_get_client_count(&self) {
+        let count = self.state.clients.clients.borrow().len() as u32;
+        self.respond(Response::GetClientCount { count });
+    }
+
+    fn handle_get_clients(&self) {
+        let clients: Vec<Rc<Client>> = self
+            .state
+            .clients
+            .clients
+            .borrow()
+            .values()
+            .map(|c| c.data.clone())
+            .collect();
+        let clients = clients
+            .iter()
+            .map(|client| ClientInfo {
+                client: jay_config::Client(client.id.raw()),
+                pid: client.pid_info.pid as _,
+                uid: client.pid_info.uid as _,
+                comm: client.pid_info.comm.clone(),
+                secure: client.secure,
+                xwayland: client.is_xwayland,
+                app_ids: client
+                    .objects
+                    .xdg_toplevel
+                    .lock()
+                    .values()
+                    .map(|tl| tl.tl_data().app_id.borrow().clone())
+                    .collect(),
+                objects: client.objects.count() as _,
+            })
+            .collect();
+        self.respond(Response::GetClients { clients });
+    }
+
+    fn handle_kill_client(&self, client: jay_config::Client) {
+        self.state.clients.kill(ClientId::from_raw(client.0));
+    }
+
+    fn handle_get_idle_inhibitors(&self) {
+        let mut inhibitors = vec![];
+        for inhibitor in self.state.idle.inhibitors.lock().values() {
+            let surface = &inhibitor.surface;
+            let app_id = match surface.get_toplevel() {
+                Some(tl) => tl.tl_data().app_id.borrow().clone(),
+                _ => String::new(),
+            };
+            inhibitors.push(IdleInhibitor {
+                app_id,
+                pid: surface.client.pid_info.pid as _,
+                comm: surface.client.pid_info.comm.clone(),
+            });
+        }
+        self.respond(Response::GetIdleInhibitors { inhibitors });
+    }
+
     fn handle_get_input_devices(&self, seat: Option<Seat>) {
         let id = seat.map(|s| SeatId::from_raw(s.0 as _));
         let matches = |dhd: &DeviceHandlerData| {
@@ -949,7 +1947,73 @@ impl ConfigProxyHandler {
             Some(f) => f,
             _ => return Err(CphError::NoForker),
         };
-        forker.spawn(prog.to_string(), args, env, None);
+        forker.spawn(prog.to_string(), args, env, true, None);
+        Ok(())
+    }
+
+    fn handle_run_on_workspace(
+        &self,
+        prog: &str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        workspace: Workspace,
+    ) -> Result<(), CphError> {
+        let forker = match self.state.forker.get() {
+            Some(f) => f,
+            _ => return Err(CphError::NoForker),
+        };
+        let workspace = self.get_workspace(workspace)?;
+        forker.spawn_on_workspace(&self.state, prog.to_string(), args, env, true, workspace);
+        Ok(())
+    }
+
+    fn handle_run_on_render_device(
+        &self,
+        prog: &str,
+        args: Vec<String>,
+        mut env: Vec<(String, String)>,
+        inherit_env: bool,
+        device: DrmDevice,
+    ) -> Result<(), CphError> {
+        let forker = match self.state.forker.get() {
+            Some(f) => f,
+            _ => return Err(CphError::NoForker),
+        };
+        let dev = self.get_drm_device(device)?;
+        if let Some(pci_id) = dev.pci_id {
+            if env.iter().all(|(k, _)| k != "DRI_PRIME") {
+                env.push(("DRI_PRIME".to_string(), pci_id.to_string()));
+            }
+        }
+        forker.spawn_on_render_device(
+            &self.state,
+            prog.to_string(),
+            args,
+            env,
+            inherit_env,
+            dev.dev.dev_t(),
+        );
+        Ok(())
+    }
+
+    fn handle_run_without_inherited_env(
+        &self,
+        prog: &str,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        workspace: Option<Workspace>,
+    ) -> Result<(), CphError> {
+        let forker = match self.state.forker.get() {
+            Some(f) => f,
+            _ => return Err(CphError::NoForker),
+        };
+        match workspace {
+            Some(ws) => {
+                let ws = self.get_workspace(ws)?;
+                forker.spawn_on_workspace(&self.state, prog.to_string(), args, env, false, ws);
+            }
+            _ => forker.spawn(prog.to_string(), args, env, false, None),
+        }
         Ok(())
     }
 
@@ -988,6 +2052,67 @@ impl ConfigProxyHandler {
         Ok(())
     }
 
+    fn handle_focus_output(&self, seat: Seat, connector: Connector) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        let output = self.get_output(connector)?;
+        seat.focus_output(&output.node);
+        Ok(())
+    }
+
+    fn handle_set_move_pointer_on_output_focus(
+        &self,
+        seat: Seat,
+        enabled: bool,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.move_pointer_on_output_focus.set(enabled);
+        Ok(())
+    }
+
+    fn handle_set_float_modifier(&self, seat: Seat, mods: Modifiers) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.float_modifier.set(mods);
+        Ok(())
+    }
+
+    fn handle_set_window_border_color(
+        &self,
+        seat: Seat,
+        color: Option<jay_config::theme::Color>,
+    ) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_window_border_color(color.map(|c| c.into()));
+        Ok(())
+    }
+
+    fn handle_cycle_window_state(&self, seat: Seat) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.cycle_window_state();
+        Ok(())
+    }
+
+    fn handle_set_window_state_cycle(&self, states: Vec<WindowState>) {
+        *self.state.window_state_cycle.borrow_mut() = states;
+    }
+
+    fn handle_set_xwayland_scale(&self, scale: f64) -> Result<(), CphError> {
+        if !(1.0..=1000.0).contains(&scale) {
+            return Err(CphError::InvalidXwaylandScale(scale));
+        }
+        self.state.xwayland.scale.set(Scale::from_f64(scale));
+        Ok(())
+    }
+
+    fn handle_set_xwayland_lazy(&self, enabled: bool) {
+        self.state.xwayland.lazy.set(enabled);
+    }
+
+    fn handle_set_sticky(&self, seat: Seat, sticky: bool) -> Result<(), CphError> {
+        let seat = self.get_seat(seat)?;
+        seat.set_sticky(sticky);
+        Ok(())
+    }
+
     fn handle_set_floating(&self, seat: Seat, floating: bool) -> Result<(), CphError> {
         let seat = self.get_seat(seat)?;
         seat.set_floating(floating);
@@ -1060,9 +2185,29 @@ impl ConfigProxyHandler {
 
     fn handle_reset_colors(&self) {
         self.state.theme.colors.reset();
+        self.state.theme.title_gradient.take();
         self.colors_changed();
     }
 
+    fn handle_set_border_antialiasing(&self, enabled: bool) {
+        self.state.theme.border_antialiasing.set(enabled);
+        self.state.damage();
+    }
+
+    fn handle_set_title_gradient(&self, color: Option<jay_config::theme::Color>) {
+        self.state.theme.title_gradient.set(color.map(|c| c.into()));
+        self.colors_changed();
+    }
+
+    fn handle_set_dummy_output_color(&self, color: Option<jay_config::theme::Color>) {
+        self.state.dummy_output_color.set(color.map(|c| c.into()));
+        self.state.damage();
+    }
+
+    fn handle_set_hotplug_debounce_ms(&self, ms: u64) {
+        self.state.hotplug_debounce_ms.set(ms);
+    }
+
     fn handle_reset_sizes(&self) {
         self.state.theme.sizes.reset();
         self.spaces_change();
@@ -1070,6 +2215,11 @@ impl ConfigProxyHandler {
 
     fn handle_reset_font(&self) {
         *self.state.theme.font.borrow_mut() = DEFAULT_FONT.to_string();
+        self.state.theme.active_font.borrow_mut().take();
+    }
+
+    fn handle_set_active_font(&self, font: Option<&str>) {
+        *self.state.theme.active_font.borrow_mut() = font.map(|f| f.to_string());
     }
 
     fn handle_set_font(&self, font: &str) {
@@ -1142,6 +2292,9 @@ impl ConfigProxyHandler {
                 file,
                 line,
             } => self.handle_log_request(level, msg, file, line),
+            ClientMessage::SetLogLevel { subsystem, level } => self
+                .handle_set_log_level(subsystem, level)
+                .wrn("set_log_level")?,
             ClientMessage::GetSeat { name } => self.handle_get_seat(name),
             ClientMessage::ParseKeymap { keymap } => {
                 self.handle_parse_keymap(keymap).wrn("parse_keymap")?
@@ -1262,7 +2415,7 @@ impl ConfigProxyHandler {
                 initial,
                 periodic,
             } => self
-                .handle_program_timer(timer, initial, periodic)
+                .handle_program_timer(timer, initial, periodic, MissedTicks::Coalesce)
                 .wrn("program_timer")?,
             ClientMessage::SetEnv { key, val } => self.handle_set_env(key, val),
             ClientMessage::SetFullscreen { seat, fullscreen } => self
@@ -1369,6 +2522,254 @@ impl ConfigProxyHandler {
             ClientMessage::SetDoubleClickDistance { dist } => {
                 self.handle_set_double_click_distance(dist)
             }
+            ClientMessage::CenterPointerOnFocus { seat } => self
+                .handle_center_pointer_on_focus(seat)
+                .wrn("center_pointer_on_focus")?,
+            ClientMessage::ValidateOutputLayout => self.handle_validate_output_layout(),
+            ClientMessage::AutoArrangeOutputs => self.handle_auto_arrange_outputs(),
+            ClientMessage::SetPrimaryOutput { connector } => self
+                .handle_set_primary_output(connector)
+                .wrn("set_primary_output")?,
+            ClientMessage::GetGpuTime => self.handle_get_gpu_time(),
+            ClientMessage::GetMaxTextureSize => self.handle_get_max_texture_size(),
+            ClientMessage::SetMaxInFlightFrames { frames } => {
+                self.handle_set_max_in_flight_frames(frames)
+            }
+            ClientMessage::GetInFlightFrames => self.handle_get_in_flight_frames(),
+            ClientMessage::GetContentType { seat } => {
+                self.handle_get_content_type(seat).wrn("get_content_type")?
+            }
+            ClientMessage::ConnectorGetFramebufferInfo { connector } => self
+                .handle_connector_get_framebuffer_info(connector)
+                .wrn("connector_get_framebuffer_info")?,
+            ClientMessage::GetRecentLogs { max_lines } => self.handle_get_recent_logs(max_lines),
+            ClientMessage::GetFocusedWindow { seat } => self
+                .handle_get_focused_window(seat)
+                .wrn("get_focused_window")?,
+            ClientMessage::GetTimerRemaining { timer } => self
+                .handle_get_timer_remaining(timer)
+                .wrn("get_timer_remaining")?,
+            ClientMessage::TriggerTimer { timer } => {
+                self.handle_trigger_timer(timer).wrn("trigger_timer")?
+            }
+            ClientMessage::RunOnWorkspace {
+                prog,
+                args,
+                env,
+                workspace,
+            } => self
+                .handle_run_on_workspace(prog, args, env, workspace)
+                .wrn("run_on_workspace")?,
+            ClientMessage::UnsetEnv { key } => self.handle_unset_env(key),
+            ClientMessage::GetEnv { key } => self.handle_get_env(key),
+            ClientMessage::RunWithoutInheritedEnv {
+                prog,
+                args,
+                env,
+                workspace,
+            } => self
+                .handle_run_without_inherited_env(prog, args, env, workspace)
+                .wrn("run_without_inherited_env")?,
+            ClientMessage::ConnectorSetThrottleFrameCallbacks {
+                connector,
+                throttle,
+            } => self
+                .handle_connector_set_throttle_frame_callbacks(connector, throttle)
+                .wrn("connector_set_throttle_frame_callbacks")?,
+            ClientMessage::SetWorkspaceSwitchAnimation { axis, duration } => {
+                self.handle_set_workspace_switch_animation(axis, duration)
+            }
+            ClientMessage::ConnectorSetHotplugPolicy { connector, policy } => self
+                .handle_connector_set_hotplug_policy(connector, policy)
+                .wrn("connector_set_hotplug_policy")?,
+            ClientMessage::ConnectorAcceptHotplug { connector } => self
+                .handle_connector_accept_hotplug(connector)
+                .wrn("connector_accept_hotplug")?,
+            ClientMessage::ConnectorSetBrightness { connector, percent } => self
+                .handle_connector_set_brightness(connector, percent)
+                .wrn("connector_set_brightness")?,
+            ClientMessage::ConnectorGetBrightness { connector } => self
+                .handle_connector_get_brightness(connector)
+                .wrn("connector_get_brightness")?,
+            ClientMessage::GetOutputLayout => self.handle_get_output_layout(),
+            ClientMessage::ApplyOutputLayout { layout } => self
+                .handle_apply_output_layout(layout)
+                .wrn("apply_output_layout")?,
+            ClientMessage::SetShortcutInhibitBreak { seat, mods, sym } => self
+                .handle_set_shortcut_inhibit_break(seat, mods, sym)
+                .wrn("set_shortcut_inhibit_break")?,
+            ClientMessage::GetIdleInhibitors => self.handle_get_idle_inhibitors(),
+            ClientMessage::SetIdleInhibited { inhibited } => {
+                self.state.idle.set_inhibited_override(inhibited)
+            }
+            ClientMessage::ConnectorSetNonDesktop { connector, value } => self
+                .handle_connector_set_non_desktop(connector, value)
+                .wrn("connector_set_non_desktop")?,
+            ClientMessage::GetSurfaceInfo { seat } => {
+                self.handle_get_surface_info(seat).wrn("get_surface_info")?
+            }
+            ClientMessage::SetPersistentClipboard { enabled } => {
+                self.state.persistent_clipboard.set(enabled)
+            }
+            ClientMessage::SetMaxClients { max } => {
+                self.state.clients.max_clients.set(max.map(|m| m as usize))
+            }
+            ClientMessage::SetMaxConnectionRate { rate } => {
+                self.state.clients.max_connection_rate.set(rate)
+            }
+            ClientMessage::GetClientCount => self.handle_get_client_count(),
+            ClientMessage::SetButtonMap { device, map } => self
+                .handle_set_button_map(device, map)
+                .wrn("set_button_map")?,
+            ClientMessage::GetCompositorInfo => self.handle_get_compositor_info(),
+            ClientMessage::GetSeatIdleTime { seat } => self
+                .handle_get_seat_idle_time(seat)
+                .wrn("get_seat_idle_time")?,
+            ClientMessage::SeatPushKeymap { seat, keymap } => {
+                self.handle_push_keymap(seat, keymap).wrn("push_keymap")?
+            }
+            ClientMessage::SeatPopKeymap { seat } => {
+                self.handle_pop_keymap(seat).wrn("pop_keymap")?
+            }
+            ClientMessage::ConnectorModes { connector } => self
+                .handle_connector_modes(connector)
+                .wrn("connector_modes")?,
+            ClientMessage::SetFloatingDefaults {
+                width,
+                height,
+                placement,
+            } => self
+                .handle_set_floating_defaults(width, height, placement)
+                .wrn("set_floating_defaults")?,
+            ClientMessage::SetSticky { seat, sticky } => {
+                self.handle_set_sticky(seat, sticky).wrn("set_sticky")?
+            }
+            ClientMessage::SetAutoDestroyEmptyWorkspaces { enabled } => {
+                self.handle_set_auto_destroy_empty_workspaces(enabled)
+            }
+            ClientMessage::FocusOutput { seat, connector } => self
+                .handle_focus_output(seat, connector)
+                .wrn("focus_output")?,
+            ClientMessage::SetMovePointerOnOutputFocus { seat, enabled } => self
+                .handle_set_move_pointer_on_output_focus(seat, enabled)
+                .wrn("set_move_pointer_on_output_focus")?,
+            ClientMessage::SetWindowBorderColor { seat, color } => self
+                .handle_set_window_border_color(seat, color)
+                .wrn("set_window_border_color")?,
+            ClientMessage::SetActiveFont { font } => self.handle_set_active_font(font),
+            ClientMessage::CycleWindowState { seat } => self
+                .handle_cycle_window_state(seat)
+                .wrn("cycle_window_state")?,
+            ClientMessage::SetWindowStateCycle { states } => {
+                self.handle_set_window_state_cycle(states)
+            }
+            ClientMessage::SetXwaylandScale { scale } => self
+                .handle_set_xwayland_scale(scale)
+                .wrn("set_xwayland_scale")?,
+            ClientMessage::SetXwaylandLazy { enabled } => self.handle_set_xwayland_lazy(enabled),
+            ClientMessage::GetFocusedWindowInfo { seat } => self
+                .handle_get_focused_window_info(seat)
+                .wrn("get_focused_window_info")?,
+            ClientMessage::SetFloatModifier { seat, mods } => self
+                .handle_set_float_modifier(seat, mods)
+                .wrn("set_float_modifier")?,
+            ClientMessage::RunOnRenderDevice {
+                prog,
+                args,
+                env,
+                inherit_env,
+                device,
+            } => self
+                .handle_run_on_render_device(prog, args, env, inherit_env, device)
+                .wrn("run_on_render_device")?,
+            ClientMessage::DisableModifier {
+                format_drm,
+                modifier,
+            } => self.handle_disable_modifier(format_drm, modifier),
+            ClientMessage::GetFormatCapabilities { format_drm } => {
+                self.handle_get_format_capabilities(format_drm)
+            }
+            ClientMessage::SetLinearBlending { enabled } => {
+                self.handle_set_linear_blending(enabled)
+            }
+            ClientMessage::SetTitleGradient { color } => self.handle_set_title_gradient(color),
+            ClientMessage::SetBorderAntialiasing { enabled } => {
+                self.handle_set_border_antialiasing(enabled)
+            }
+            ClientMessage::SetCommitThrottling { enabled } => {
+                self.state.commit_throttling.set(enabled)
+            }
+            ClientMessage::ConnectorFreeze { connector, frozen } => self
+                .handle_connector_freeze(connector, frozen)
+                .wrn("connector_freeze")?,
+            ClientMessage::GetDrmDeviceRenderNode { device } => self
+                .handle_get_drm_device_render_node(device)
+                .wrn("get_drm_device_render_node")?,
+            ClientMessage::GetDrmDeviceGfxApi { device } => self
+                .handle_get_drm_device_gfx_api(device)
+                .wrn("get_drm_device_gfx_api")?,
+            ClientMessage::GetDrmDeviceSupportedGfxApis { device } => self
+                .handle_get_drm_device_supported_gfx_apis(device)
+                .wrn("get_drm_device_supported_gfx_apis")?,
+            ClientMessage::TrySetGfxApi { device, api } => self
+                .handle_try_set_gfx_api(device, api)
+                .wrn("try_set_gfx_api")?,
+            ClientMessage::FlushLayout => self.state.flush_layout(),
+            ClientMessage::GetWindowInfo { window } => self.handle_get_window_info(window),
+            ClientMessage::FocusWindow { window, seat } => {
+                self.handle_focus_window(window, seat).wrn("focus_window")?
+            }
+            ClientMessage::CloseWindow { window } => {
+                self.handle_close_window(window).wrn("close_window")?
+            }
+            ClientMessage::MoveWindowToWorkspace { window, workspace } => self
+                .handle_move_window_to_workspace(window, workspace)
+                .wrn("move_window_to_workspace")?,
+            ClientMessage::GetPointer { seat } => {
+                self.handle_get_pointer(seat).wrn("get_pointer")?
+            }
+            ClientMessage::SetPointerPosition { seat, x, y } => self
+                .handle_set_pointer_position(seat, x, y)
+                .wrn("set_pointer_position")?,
+            ClientMessage::SetDummyOutputColor { color } => {
+                self.handle_set_dummy_output_color(color)
+            }
+            ClientMessage::RunGfxSelfTest { device } => self
+                .handle_run_gfx_self_test(device)
+                .wrn("run_gfx_self_test")?,
+            ClientMessage::SetHotplugDebounceMs { ms } => self.handle_set_hotplug_debounce_ms(ms),
+            ClientMessage::GetClients => self.handle_get_clients(),
+            ClientMessage::KillClient { client } => self.handle_kill_client(client),
+            ClientMessage::SetEmptyWorkspaceAction { action } => {
+                self.handle_set_empty_workspace_action(action)
+            }
+            ClientMessage::SetInputDeviceConnector { device, connector } => self
+                .handle_set_input_device_connector(device, connector)
+                .wrn("set_input_device_connector")?,
+            ClientMessage::SetWorkspacePersistent {
+                workspace,
+                persistent,
+            } => self
+                .handle_set_workspace_persistent(workspace, persistent)
+                .wrn("set_workspace_persistent")?,
+            ClientMessage::ProgramTimer2 {
+                timer,
+                initial,
+                periodic,
+                missed,
+            } => self
+                .handle_program_timer(timer, initial, periodic, missed)
+                .wrn("program_timer")?,
+            ClientMessage::SetClipboard {
+                seat,
+                mime_type,
+                data,
+            } => self
+                .handle_set_clipboard(seat, mime_type, data)
+                .wrn("set_clipboard")?,
+            ClientMessage::GetClipboard { seat, mime_type } => self
+                .handle_get_clipboard(seat, mime_type)
+                .wrn("get_clipboard")?,
         }
         Ok(())
     }
@@ -1398,6 +2799,14 @@ enum CphError {
     TimerDoesNotExist(JayTimer),
     #[error("Connector {0:?} does not exist or is not connected")]
     OutputDoesNotExist(Connector),
+    #[error("Connector {0:?} does not have a backlight")]
+    NoBacklight(Connector),
+    #[error("Could not set the selection")]
+    SetSelection(#[source] WlSeatError),
+    #[error("The output layout contains multiple entries for connector {0}")]
+    DuplicateLayoutEntry(String),
+    #[error("Connector {0} does not support the mode {1:?}")]
+    UnknownMode(String, backend::Mode),
     #[error("{0}x{1} is not a valid connector position")]
     InvalidConnectorPosition(i32, i32),
     #[error("Keymap {0:?} does not exist")]
@@ -1408,6 +2817,8 @@ enum CphError {
     DrmDeviceDoesNotExist(DrmDevice),
     #[error("Workspace {0:?} does not exist")]
     WorkspaceDoesNotExist(Workspace),
+    #[error("Window {0:?} does not exist")]
+    WindowDoesNotExist(Window),
     #[error("Keyboard {0:?} does not exist")]
     KeyboardDoesNotExist(InputDevice),
     #[error("Colorable element {0} is not known")]
@@ -1420,12 +2831,51 @@ enum CphError {
     FailedRequest(&'static str, #[source] Box<Self>),
     #[error(transparent)]
     TimerError(#[from] TimerError),
+    #[error(transparent)]
+    TimeError(#[from] TimeError),
     #[error("The requested monitor scale {0} is too small")]
     ScaleTooSmall(f64),
     #[error("The requested monitor scale {0} is too large")]
     ScaleTooLarge(f64),
     #[error("Tried to set a negative cursor size")]
     NegativeCursorSize,
+    #[error("Could not change the graphics API")]
+    SetGfxApi(#[source] Box<dyn std::error::Error>),
+    #[error("Subsystem {0} is not known")]
+    UnknownSubsystem(String),
+    #[error("Button {0:#x} is mapped more than once")]
+    DuplicateButton(u32),
+    #[error("More than one button would be reported as button {0:#x}")]
+    ButtonMapCollision(u32),
+    #[error("The floating window size {0}x{1} is negative")]
+    NegativeFloatSize(i32, i32),
+    #[error("The Xwayland scale {0} is not in the range [1, 1000]")]
+    InvalidXwaylandScale(f64),
+}
+
+fn window_info(tl: &dyn ToplevelNode) -> WindowInfo {
+    let data = tl.tl_data();
+    let (x11_instance, x11_class) = match tl.tl_x11_class() {
+        Some((instance, class)) => (Some(instance), Some(class)),
+        _ => (None, None),
+    };
+    WindowInfo {
+        window: Window(tl.node_id().raw() as _),
+        title: data.title.borrow().clone(),
+        app_id: data.app_id.borrow().clone(),
+        x11_class,
+        x11_instance,
+    }
+}
+
+fn log_level(level: LogLevel) -> Level {
+    match level {
+        LogLevel::Error => Level::Error,
+        LogLevel::Warn => Level::Warn,
+        LogLevel::Info => Level::Info,
+        LogLevel::Debug => Level::Debug,
+        LogLevel::Trace => Level::Trace,
+    }
 }
 
 trait WithRequestName {
@@ -146,10 +146,22 @@ impl ServerCursors {
         let xcursor_theme = env::var_os(XCURSOR_THEME);
         let theme = xcursor_theme.as_ref().map(|theme| BStr::new(theme.bytes()));
 
-        let load =
-            |names: &[&str]| ServerCursorTemplate::load(names, theme, &scales, &sizes, &paths, ctx);
+        let default = match ServerCursorTemplate::load(
+            &["default", "left_ptr"],
+            theme,
+            &scales,
+            &sizes,
+            &paths,
+            ctx,
+        )? {
+            Some(c) => c,
+            _ => ServerCursorTemplate::empty(&scales, &sizes, ctx)?,
+        };
+        let load = |names: &[&str]| {
+            ServerCursorTemplate::load(names, theme, &scales, &sizes, &paths, ctx)
+                .map(|c| c.unwrap_or_else(|| default.clone()))
+        };
         Ok(Some(Self {
-            default: load(&["default", "left_ptr"])?,
             context_menu: load(&["context-menu"])?,
             help: load(&["help"])?,
             pointer: load(&["pointer", "hand2", "hand1"])?,
@@ -183,15 +195,18 @@ impl ServerCursors {
             all_scroll: load(&["all-scroll", "grabbing"])?,
             zoom_in: load(&["zoom-in"])?,
             zoom_out: load(&["zoom-out"])?,
+            default,
         }))
     }
 }
 
+#[derive(Clone)]
 pub struct ServerCursorTemplate {
     var: ServerCursorTemplateVariant,
     pub xcursor: Vec<AHashMap<(Scale, u32), Rc<XCursorImage>>>,
 }
 
+#[derive(Clone)]
 enum ServerCursorTemplateVariant {
     Static(Rc<CursorImage>),
     Animated(Rc<Vec<CursorImage>>),
@@ -205,7 +220,7 @@ impl ServerCursorTemplate {
         sizes: &[u32],
         paths: &[BString],
         ctx: &Rc<dyn GfxContext>,
-    ) -> Result<Self, CursorError> {
+    ) -> Result<Option<Self>, CursorError> {
         match open_cursor(names, theme, scales, sizes, paths) {
             Ok(cs) => {
                 if cs.images.len() == 1 {
@@ -219,10 +234,10 @@ impl ServerCursorTemplate {
                         );
                     }
                     let cursor = CursorImage::from_sizes(0, sizes)?;
-                    Ok(ServerCursorTemplate {
+                    Ok(Some(ServerCursorTemplate {
                         var: ServerCursorTemplateVariant::Static(Rc::new(cursor)),
                         xcursor: cs.images,
-                    })
+                    }))
                 } else {
                     let mut images = vec![];
                     for image in &cs.images {
@@ -240,33 +255,41 @@ impl ServerCursorTemplate {
                         let img = CursorImage::from_sizes(delay_ms as _, sizes)?;
                         images.push(img);
                     }
-                    Ok(ServerCursorTemplate {
+                    Ok(Some(ServerCursorTemplate {
                         var: ServerCursorTemplateVariant::Animated(Rc::new(images)),
                         xcursor: cs.images,
-                    })
+                    }))
                 }
             }
             Err(e) => {
                 log::warn!("Could not load cursor {:?}: {}", names, ErrorFmt(e));
-                let empty: [Cell<u8>; 4] = unsafe { MaybeUninit::zeroed().assume_init() };
-                let mut img_sizes = SmallMapMut::new();
-                for scale in scales {
-                    for size in sizes {
-                        img_sizes.insert(
-                            (*scale, *size),
-                            CursorImageScaled::from_bytes(ctx, &empty, 1, 1, 0, 0)?,
-                        );
-                    }
-                }
-                let cursor = CursorImage::from_sizes(0, img_sizes)?;
-                Ok(ServerCursorTemplate {
-                    var: ServerCursorTemplateVariant::Static(Rc::new(cursor)),
-                    xcursor: Default::default(),
-                })
+                Ok(None)
             }
         }
     }
 
+    fn empty(
+        scales: &[Scale],
+        sizes: &[u32],
+        ctx: &Rc<dyn GfxContext>,
+    ) -> Result<Self, CursorError> {
+        let empty: [Cell<u8>; 4] = unsafe { MaybeUninit::zeroed().assume_init() };
+        let mut img_sizes = SmallMapMut::new();
+        for scale in scales {
+            for size in sizes {
+                img_sizes.insert(
+                    (*scale, *size),
+                    CursorImageScaled::from_bytes(ctx, &empty, 1, 1, 0, 0)?,
+                );
+            }
+        }
+        let cursor = CursorImage::from_sizes(0, img_sizes)?;
+        Ok(ServerCursorTemplate {
+            var: ServerCursorTemplateVariant::Static(Rc::new(cursor)),
+            xcursor: Default::default(),
+        })
+    }
+
     pub fn instantiate(&self, size: u32) -> Rc<dyn Cursor> {
         match &self.var {
             ServerCursorTemplateVariant::Static(s) => Rc::new(StaticCursor {
@@ -367,6 +390,7 @@ fn render_img(image: &InstantiatedCursorImage, renderer: &mut Renderer, x: Fixed
             None,
             scale,
             None,
+            None,
         );
     }
 }
@@ -380,7 +404,7 @@ impl Cursor for StaticCursor {
         if let Some(img) = self.image.scales.get(&renderer.scale()) {
             renderer
                 .base
-                .render_texture(&img.tex, 0, 0, None, None, renderer.scale(), None);
+                .render_texture(&img.tex, 0, 0, None, None, renderer.scale(), None, None);
         }
     }
 
@@ -410,7 +434,7 @@ impl Cursor for AnimatedCursor {
         if let Some(img) = img.scales.get(&renderer.scale()) {
             renderer
                 .base
-                .render_texture(&img.tex, 0, 0, None, None, renderer.scale(), None);
+                .render_texture(&img.tex, 0, 0, None, None, renderer.scale(), None, None);
         }
     }
 
@@ -1,5 +1,9 @@
 use {
-    crate::{gfx_api::GfxContext, utils::oserror::OsError, video::Modifier},
+    crate::{
+        gfx_api::GfxContext,
+        utils::{copyhashmap::CopyHashMap, oserror::OsError},
+        video::Modifier,
+    },
     ahash::AHashMap,
     byteorder::{NativeEndian, WriteBytesExt},
     std::{io::Write, rc::Rc},
@@ -35,11 +39,12 @@ impl DrmFeedback {
     pub fn new(
         ids: &DrmFeedbackIds,
         render_ctx: &dyn GfxContext,
+        disabled_modifiers: &CopyHashMap<(u32, Modifier), ()>,
     ) -> Result<Self, DrmFeedbackError> {
         let main_device = uapi::fstat(render_ctx.gbm().drm.raw())
             .map_err(OsError::from)?
             .st_rdev;
-        let (data, index_map) = create_fd_data(render_ctx);
+        let (data, index_map) = create_fd_data(render_ctx, disabled_modifiers);
         let mut memfd =
             uapi::memfd_create("drm_feedback", c::MFD_CLOEXEC | c::MFD_ALLOW_SEALING).unwrap();
         memfd.write_all(&data).unwrap();
@@ -65,12 +70,35 @@ impl DrmFeedback {
         })
     }
 
+    /// Returns a feedback that prefers buffers allocated on the device `devnum`.
+    ///
+    /// The main device stays the render device of the compositor. `formats` should only
+    /// contain formats that can be used on both devices.
+    pub fn for_render_device(
+        &self,
+        ids: &DrmFeedbackIds,
+        devnum: c::dev_t,
+        formats: &[(u32, Modifier)],
+    ) -> Option<Self> {
+        self.with_preferred_tranche(ids, devnum, formats, false)
+    }
+
     pub fn for_scanout(
         &self,
         ids: &DrmFeedbackIds,
         devnum: c::dev_t,
         formats: &[(u32, Modifier)],
     ) -> Result<Option<Self>, DrmFeedbackError> {
+        Ok(self.with_preferred_tranche(ids, devnum, formats, true))
+    }
+
+    fn with_preferred_tranche(
+        &self,
+        ids: &DrmFeedbackIds,
+        devnum: c::dev_t,
+        formats: &[(u32, Modifier)],
+        scanout: bool,
+    ) -> Option<Self> {
         let mut tranches = vec![];
         {
             let mut indices = vec![];
@@ -83,27 +111,33 @@ impl DrmFeedback {
                 tranches.push(DrmFeedbackTranche {
                     device: devnum,
                     indices,
-                    scanout: true,
+                    scanout,
                 });
             } else {
-                return Ok(None);
+                return None;
             }
         }
         tranches.extend(self.tranches.iter().cloned());
-        Ok(Some(Self {
+        Some(Self {
             id: ids.next(),
             shared: self.shared.clone(),
             tranches,
-        }))
+        })
     }
 }
 
-fn create_fd_data(ctx: &dyn GfxContext) -> (Vec<u8>, AHashMap<(u32, Modifier), u16>) {
+fn create_fd_data(
+    ctx: &dyn GfxContext,
+    disabled_modifiers: &CopyHashMap<(u32, Modifier), ()>,
+) -> (Vec<u8>, AHashMap<(u32, Modifier), u16>) {
     let mut vec = vec![];
     let mut map = AHashMap::new();
     let mut pos = 0;
     for (format, info) in &*ctx.formats() {
         for modifier in &info.read_modifiers {
+            if disabled_modifiers.contains(&(*format, *modifier)) {
+                continue;
+            }
             vec.write_u32::<NativeEndian>(*format).unwrap();
             vec.write_u32::<NativeEndian>(0).unwrap();
             vec.write_u64::<NativeEndian>(*modifier).unwrap();
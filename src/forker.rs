@@ -17,6 +17,7 @@ use {
         },
         xwayland,
     },
+    ahash::AHashMap,
     bincode::Options,
     jay_config::_private::bincode_ops,
     log::Level,
@@ -26,7 +27,7 @@ use {
         env,
         ffi::OsStr,
         io::{Read, Write},
-        os::unix::ffi::OsStrExt,
+        os::unix::ffi::{OsStrExt, OsStringExt},
         rc::{Rc, Weak},
         task::{Poll, Waker},
     },
@@ -45,6 +46,13 @@ pub struct ForkerProxy {
     next_id: NumCell<u32>,
     pending_pidfds: CopyHashMap<u32, Weak<PidfdHandoff>>,
     fds: RefCell<Vec<Rc<OwnedFd>>>,
+    tracked_spawns: CopyHashMap<u32, SpawnedFuture<()>>,
+    env: RefCell<AHashMap<Vec<u8>, Vec<u8>>>,
+}
+
+enum SpawnTarget {
+    Workspace(Rc<String>),
+    RenderDevice(c::dev_t),
 }
 
 struct PidfdHandoff {
@@ -76,6 +84,7 @@ impl ForkerProxy {
         self.task_out.take();
         self.task_proc.take();
         self.outgoing.clear();
+        self.tracked_spawns.clear();
     }
 
     pub fn create() -> Result<Self, ForkerError> {
@@ -85,6 +94,12 @@ impl ForkerProxy {
                 Err(e) => return Err(ForkerError::Socketpair(e.into())),
             };
         let pid = uapi::getpid();
+        let mut env: AHashMap<_, _> = env::vars_os()
+            .map(|(k, v)| (k.into_vec(), v.into_vec()))
+            .collect();
+        env.insert(b"XDG_SESSION_TYPE".to_vec(), b"wayland".to_vec());
+        env.remove(DISPLAY.as_bytes());
+        env.remove(WAYLAND_DISPLAY.as_bytes());
         match fork_with_pidfd(false)? {
             Forked::Parent { pid, pidfd } => Ok(ForkerProxy {
                 pidfd: Rc::new(pidfd),
@@ -97,6 +112,8 @@ impl ForkerProxy {
                 next_id: Default::default(),
                 pending_pidfds: Default::default(),
                 fds: Default::default(),
+                tracked_spawns: Default::default(),
+                env: RefCell::new(env),
             }),
             Forked::Child { .. } => {
                 drop(parent);
@@ -117,6 +134,7 @@ impl ForkerProxy {
     }
 
     pub fn setenv(&self, key: &[u8], val: &[u8]) {
+        self.env.borrow_mut().insert(key.to_vec(), val.to_vec());
         self.outgoing.push(ServerMessage::SetEnv {
             var: key.to_vec(),
             val: Some(val.to_vec()),
@@ -124,12 +142,18 @@ impl ForkerProxy {
     }
 
     pub fn unsetenv(&self, key: &[u8]) {
+        self.env.borrow_mut().remove(key);
         self.outgoing.push(ServerMessage::SetEnv {
             var: key.to_vec(),
             val: None,
         })
     }
 
+    /// Returns the value of a variable in the environment of the forker.
+    pub fn getenv(&self, key: &[u8]) -> Option<Vec<u8>> {
+        self.env.borrow().get(key).cloned()
+    }
+
     async fn pidfd(&self, id: u32) -> Result<(Rc<OwnedFd>, c::pid_t), ForkerError> {
         let handoff = Rc::new(PidfdHandoff {
             pidfd: Cell::new(None),
@@ -168,6 +192,7 @@ impl ForkerProxy {
         prog: String,
         args: Vec<String>,
         env: Vec<(String, String)>,
+        inherit_env: bool,
         stderr: Option<Rc<OwnedFd>>,
     ) {
         let have_stderr = stderr.is_some();
@@ -178,10 +203,88 @@ impl ForkerProxy {
             prog,
             args,
             env,
+            inherit_env,
             stderr: have_stderr,
+            pidfd_id: None,
         })
     }
 
+    /// Spawns a program whose first toplevel will be mapped on `workspace`.
+    pub fn spawn_on_workspace(
+        self: &Rc<Self>,
+        state: &Rc<State>,
+        prog: String,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        inherit_env: bool,
+        workspace: Rc<String>,
+    ) {
+        let target = SpawnTarget::Workspace(workspace);
+        self.spawn_tracked(state, prog, args, env, inherit_env, target);
+    }
+
+    /// Spawns a program whose clients are told to render on the device `devnum`.
+    pub fn spawn_on_render_device(
+        self: &Rc<Self>,
+        state: &Rc<State>,
+        prog: String,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        inherit_env: bool,
+        devnum: c::dev_t,
+    ) {
+        let target = SpawnTarget::RenderDevice(devnum);
+        self.spawn_tracked(state, prog, args, env, inherit_env, target);
+    }
+
+    fn spawn_tracked(
+        self: &Rc<Self>,
+        state: &Rc<State>,
+        prog: String,
+        args: Vec<String>,
+        env: Vec<(String, String)>,
+        inherit_env: bool,
+        target: SpawnTarget,
+    ) {
+        let id = self.next_id.fetch_add(1);
+        self.outgoing.push(ServerMessage::Spawn {
+            prog,
+            args,
+            env,
+            inherit_env,
+            stderr: false,
+            pidfd_id: Some(id),
+        });
+        let slf = self.clone();
+        let st = state.clone();
+        let future = state.eng.spawn(async move {
+            let state = st;
+            match slf.pidfd(id).await {
+                Ok((pidfd, pid)) => {
+                    match target {
+                        SpawnTarget::Workspace(ws) => {
+                            state.spawn_workspaces.set(pid, ws);
+                        }
+                        SpawnTarget::RenderDevice(dev) => {
+                            state.spawn_render_devices.set(pid, dev);
+                        }
+                    }
+                    if let Err(e) = state.ring.readable(&pidfd).await {
+                        log::error!(
+                            "Cannot wait for the pidfd of a spawned program: {}",
+                            ErrorFmt(e)
+                        );
+                    }
+                    state.spawn_workspaces.remove(&pid);
+                    state.spawn_render_devices.remove(&pid);
+                }
+                Err(e) => log::error!("Could not spawn a program: {}", ErrorFmt(e)),
+            }
+            slf.tracked_spawns.remove(&id);
+        });
+        self.tracked_spawns.set(id, future);
+    }
+
     async fn incoming(self: Rc<Self>, state: Rc<State>) {
         let mut io = IoIn::new(&self.socket, &state.ring);
         loop {
@@ -272,7 +375,9 @@ enum ServerMessage {
         prog: String,
         args: Vec<String>,
         env: Vec<(String, String)>,
+        inherit_env: bool,
         stderr: bool,
+        pidfd_id: Option<u32>,
     },
     Xwayland {
         id: u32,
@@ -372,8 +477,10 @@ impl Forker {
                 prog,
                 args,
                 env,
+                inherit_env,
                 stderr,
-            } => self.handle_spawn(prog, args, env, stderr, io),
+                pidfd_id,
+            } => self.handle_spawn(prog, args, env, inherit_env, stderr, pidfd_id, io),
             ServerMessage::Xwayland { id } => self.handle_xwayland(io, id),
         }
     }
@@ -396,7 +503,7 @@ impl Forker {
         ];
         let (prog, args) = xwayland::build_args(&fds);
         let env = vec![("WAYLAND_SOCKET".to_string(), fds[3].raw().to_string())];
-        self.spawn(prog, args, env, stderr, fds, Some(id));
+        self.spawn(prog, args, env, true, stderr, fds, Some(id));
     }
 
     fn handle_spawn(
@@ -404,14 +511,16 @@ impl Forker {
         prog: String,
         args: Vec<String>,
         env: Vec<(String, String)>,
+        inherit_env: bool,
         stderr: bool,
+        pidfd_id: Option<u32>,
         io: &mut IoIn,
     ) {
         let stderr = match stderr {
             true => io.pop_fd(),
             _ => None,
         };
-        self.spawn(prog, args, env, stderr, vec![], None)
+        self.spawn(prog, args, env, inherit_env, stderr, vec![], pidfd_id)
     }
 
     fn spawn(
@@ -419,6 +528,7 @@ impl Forker {
         prog: String,
         args: Vec<String>,
         env: Vec<(String, String)>,
+        inherit_env: bool,
         stderr: Option<Rc<OwnedFd>>,
         fds: Vec<OwnedFd>,
         pidfd_id: Option<u32>,
@@ -492,6 +602,18 @@ impl Forker {
                     unsafe {
                         c::signal(c::SIGCHLD, c::SIG_DFL);
                     }
+                    if !inherit_env {
+                        let displays: Vec<_> = [WAYLAND_DISPLAY, DISPLAY]
+                            .into_iter()
+                            .filter_map(|key| env::var_os(key).map(|val| (key, val)))
+                            .collect();
+                        for (key, _) in env::vars_os() {
+                            env::remove_var(key);
+                        }
+                        for (key, val) in displays {
+                            env::set_var(key, val);
+                        }
+                    }
                     for (key, val) in env {
                         env::set_var(&key, &val);
                     }
@@ -1,9 +1,13 @@
 use {
     crate::{
-        gfx_apis::gl::sys::{GLenum, GLint, GL_BGRA_EXT, GL_RGBA, GL_RGBA8, GL_UNSIGNED_BYTE},
+        gfx_apis::gl::sys::{
+            GLenum, GLint, GL_BGRA_EXT, GL_RGB, GL_RGB10_A2, GL_RGB565, GL_RGB8, GL_RGBA, GL_RGBA8,
+            GL_UNSIGNED_BYTE, GL_UNSIGNED_INT_2_10_10_10_REV, GL_UNSIGNED_SHORT_5_6_5,
+        },
         pipewire::pw_pod::{
-            SPA_VIDEO_FORMAT_BGRx, SPA_VIDEO_FORMAT_RGBx, SpaVideoFormat, SPA_VIDEO_FORMAT_BGRA,
-            SPA_VIDEO_FORMAT_RGBA,
+            SPA_VIDEO_FORMAT_BGRx, SPA_VIDEO_FORMAT_RGBx, SPA_VIDEO_FORMAT_xRGB_210LE,
+            SpaVideoFormat, SPA_VIDEO_FORMAT_ARGB_210LE, SPA_VIDEO_FORMAT_BGRA,
+            SPA_VIDEO_FORMAT_RGB, SPA_VIDEO_FORMAT_RGB16, SPA_VIDEO_FORMAT_RGBA,
         },
         utils::debug_fn::debug_fn,
     },
@@ -153,9 +157,83 @@ static XBGR8888: &Format = &Format {
     opaque: None,
 };
 
+// GLES cannot upload or download BGRA-ordered 2_10_10_10 data, so these formats are not
+// offered for shm. They are available for dmabufs and framebuffers.
+static ARGB2101010: &Format = &Format {
+    name: "argb2101010",
+    bpp: 4,
+    gl_format: GL_BGRA_EXT,
+    gl_internal_format: GL_RGB10_A2,
+    gl_type: GL_UNSIGNED_INT_2_10_10_10_REV,
+    vk_format: vk::Format::A2R10G10B10_UNORM_PACK32,
+    drm: fourcc_code('A', 'R', '3', '0'),
+    wl_id: None,
+    external_only_guess: false,
+    has_alpha: true,
+    shm_supported: false,
+    pipewire: SPA_VIDEO_FORMAT_ARGB_210LE,
+    opaque: Some(XRGB2101010),
+};
+
+static XRGB2101010: &Format = &Format {
+    name: "xrgb2101010",
+    bpp: 4,
+    gl_format: GL_BGRA_EXT,
+    gl_internal_format: GL_RGB10_A2,
+    gl_type: GL_UNSIGNED_INT_2_10_10_10_REV,
+    vk_format: vk::Format::A2R10G10B10_UNORM_PACK32,
+    drm: fourcc_code('X', 'R', '3', '0'),
+    wl_id: None,
+    external_only_guess: false,
+    has_alpha: false,
+    shm_supported: false,
+    pipewire: SPA_VIDEO_FORMAT_xRGB_210LE,
+    opaque: None,
+};
+
+// Few Vulkan drivers can sample these formats. The Vulkan renderer converts them to
+// XRGB8888 while uploading shm buffers if necessary.
+pub static RGB565: &Format = &Format {
+    name: "rgb565",
+    bpp: 2,
+    gl_format: GL_RGB,
+    gl_internal_format: GL_RGB565,
+    gl_type: GL_UNSIGNED_SHORT_5_6_5,
+    vk_format: vk::Format::R5G6B5_UNORM_PACK16,
+    drm: fourcc_code('R', 'G', '1', '6'),
+    wl_id: None,
+    external_only_guess: false,
+    has_alpha: false,
+    shm_supported: true,
+    pipewire: SPA_VIDEO_FORMAT_RGB16,
+    opaque: None,
+};
+
+pub static BGR888: &Format = &Format {
+    name: "bgr888",
+    bpp: 3,
+    gl_format: GL_RGB,
+    gl_internal_format: GL_RGB8,
+    gl_type: GL_UNSIGNED_BYTE,
+    vk_format: vk::Format::R8G8B8_UNORM,
+    drm: fourcc_code('B', 'G', '2', '4'),
+    wl_id: None,
+    external_only_guess: false,
+    has_alpha: false,
+    shm_supported: true,
+    pipewire: SPA_VIDEO_FORMAT_RGB,
+    opaque: None,
+};
+
 pub static FORMATS: &[Format] = &[
-    *ARGB8888, *XRGB8888, *ABGR8888,
+    *ARGB8888,
+    *XRGB8888,
+    *ABGR8888,
     *XBGR8888,
+    *ARGB2101010,
+    *XRGB2101010,
+    *RGB565,
+    *BGR888,
     // *NV12,
     // Format {
     //     name: "nv12",
@@ -270,10 +348,6 @@ pub static FORMATS: &[Format] = &[
     //     name: "bgra5551",
     // },
     // Format {
-    //     id: fourcc_code('R', 'G', '1', '6'),
-    //     name: "rgb565",
-    // },
-    // Format {
     //     id: fourcc_code('B', 'G', '1', '6'),
     //     name: "bgr565",
     // },
@@ -282,10 +356,6 @@ pub static FORMATS: &[Format] = &[
     //     name: "rgb888",
     // },
     // Format {
-    //     id: fourcc_code('B', 'G', '2', '4'),
-    //     name: "bgr888",
-    // },
-    // Format {
     //     id: fourcc_code('X', 'R', '2', '4'),
     //     name: "xrgb8888",
     // },
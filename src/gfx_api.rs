@@ -22,13 +22,16 @@ use {
         ffi::CString,
         fmt::{Debug, Formatter},
         rc::Rc,
+        time::Duration,
     },
     thiserror::Error,
+    uapi::OwnedFd,
 };
 
 pub enum GfxApiOpt {
     Sync,
     FillRect(FillRect),
+    FillGradient(FillGradient),
     CopyTexture(CopyTexture),
 }
 
@@ -129,18 +132,120 @@ impl FramebufferRect {
     pub fn is_covering(&self) -> bool {
         self.x1 == -1.0 && self.y1 == -1.0 && self.x2 == 1.0 && self.y2 == 1.0
     }
+
+    pub fn contains(&self, other: &Self) -> bool {
+        self.output_transform == other.output_transform
+            && self.x1 <= other.x1
+            && self.y1 <= other.y1
+            && other.x2 <= self.x2
+            && other.y2 <= self.y2
+    }
 }
 
 #[derive(Debug)]
 pub struct FillRect {
     pub rect: FramebufferRect,
     pub color: Color,
+    /// If set, renderers that support it draw the rect with anti-aliased edges instead
+    /// of filling `rect`.
+    pub aa: Option<FillRectAa>,
+}
+
+#[derive(Debug)]
+pub struct FillRectAa {
+    /// The rect to rasterize. This contains all pixels that are partially covered.
+    pub rect: FramebufferRect,
+    /// The points of `rect`, in the order returned by [`FramebufferRect::to_points`], in
+    /// framebuffer pixels before the output transform is applied.
+    pub pos: [[f32; 2]; 4],
+    /// The exact bounds `[x1, y1, x2, y2]` of the fill in the same coordinate system.
+    pub bounds: [f32; 4],
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GradientDirection {
+    /// From the left edge to the right edge.
+    Horizontal,
+    /// From the top edge to the bottom edge.
+    #[allow(dead_code)]
+    Vertical,
+}
+
+impl GradientDirection {
+    /// Returns the weight of the end color at the points returned by
+    /// [`FramebufferRect::to_points`].
+    pub fn weights(self) -> [f32; 4] {
+        match self {
+            GradientDirection::Horizontal => [1.0, 0.0, 1.0, 0.0],
+            GradientDirection::Vertical => [0.0, 0.0, 1.0, 1.0],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct FillGradient {
+    pub rect: FramebufferRect,
+    pub start: Color,
+    pub end: Color,
+    pub direction: GradientDirection,
 }
 
 pub struct CopyTexture {
     pub tex: Rc<dyn GfxTexture>,
     pub source: SampleRect,
     pub target: FramebufferRect,
+    pub acquire_sync: Option<Rc<OwnedFd>>,
+    /// Whether the texture is fully opaque within `target`.
+    pub opaque: bool,
+}
+
+impl GfxApiOpt {
+    fn opaque_target(&self) -> Option<&FramebufferRect> {
+        match self {
+            GfxApiOpt::Sync => None,
+            GfxApiOpt::FillRect(f) if f.aa.is_none() && f.color.a >= 1.0 => Some(&f.rect),
+            GfxApiOpt::FillRect(_) => None,
+            GfxApiOpt::FillGradient(f) if f.start.a >= 1.0 && f.end.a >= 1.0 => Some(&f.rect),
+            GfxApiOpt::FillGradient(_) => None,
+            GfxApiOpt::CopyTexture(c) if c.opaque => Some(&c.target),
+            GfxApiOpt::CopyTexture(_) => None,
+        }
+    }
+
+    fn target(&self) -> Option<&FramebufferRect> {
+        match self {
+            GfxApiOpt::Sync => None,
+            GfxApiOpt::FillRect(f) => match &f.aa {
+                Some(aa) => Some(&aa.rect),
+                None => Some(&f.rect),
+            },
+            GfxApiOpt::FillGradient(f) => Some(&f.rect),
+            GfxApiOpt::CopyTexture(c) => Some(&c.target),
+        }
+    }
+}
+
+/// Determines which opts are completely covered by a later opaque opt.
+///
+/// After this function returns, `occluded[i]` is `true` if and only if `opts[i]` does
+/// not have to be executed. This is conservative: an opt is only considered occluded
+/// if a single later opaque opt covers all of it.
+pub fn find_occluded_opts(opts: &[GfxApiOpt], occluded: &mut Vec<bool>) {
+    occluded.clear();
+    occluded.resize(opts.len(), false);
+    let mut covering = Vec::<&FramebufferRect>::new();
+    for (idx, opt) in opts.iter().enumerate().rev() {
+        let Some(target) = opt.target() else {
+            continue;
+        };
+        if covering.iter().any(|c| c.contains(target)) {
+            occluded[idx] = true;
+            continue;
+        }
+        if let Some(target) = opt.opaque_target() {
+            covering.push(target);
+        }
+    }
 }
 
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
@@ -210,7 +315,7 @@ impl dyn GfxFramebuffer {
         let mut ops = self.take_render_ops();
         let scale = Scale::from_int(1);
         let mut renderer = self.renderer_base(&mut ops, scale, Transform::None);
-        renderer.render_texture(texture, x, y, None, None, scale, None);
+        renderer.render_texture(texture, x, y, None, None, scale, None, None);
         let clear = self.format().has_alpha.then_some(&Color::TRANSPARENT);
         self.render(ops, clear);
     }
@@ -248,6 +353,9 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            occluders: vec![],
+            first_occluder: 0,
+            throttle_occluded: false,
         };
         node.node_render(&mut renderer, 0, 0, None);
         if let Some(rect) = cursor_rect {
@@ -266,13 +374,14 @@ impl dyn GfxFramebuffer {
                     }
                 }
                 if let Some(dnd_icon) = seat.dnd_icon() {
-                    let extents = dnd_icon.extents.get().move_(
-                        x.round_down() + dnd_icon.buf_x.get(),
-                        y.round_down() + dnd_icon.buf_y.get(),
-                    );
-                    if extents.intersects(&rect) {
-                        let (x, y) = rect.translate(extents.x1(), extents.y1());
-                        renderer.render_surface(&dnd_icon, x, y, None);
+                    if dnd_icon.buffer.get().is_some() {
+                        let icon_x = x.round_down() + dnd_icon.buf_x.get();
+                        let icon_y = y.round_down() + dnd_icon.buf_y.get();
+                        let extents = dnd_icon.extents.get().move_(icon_x, icon_y);
+                        if extents.intersects(&rect) {
+                            let (x, y) = rect.translate(icon_x, icon_y);
+                            renderer.render_surface(&dnd_icon, x, y, None);
+                        }
                     }
                 }
                 if let Some(cursor) = seat.get_cursor() {
@@ -361,6 +470,9 @@ impl dyn GfxFramebuffer {
                 let (width, height) = self.logical_size(transform);
                 Rect::new(0, 0, width, height).unwrap()
             },
+            occluders: vec![],
+            first_occluder: 0,
+            throttle_occluded: false,
         };
         cursor.render_hardware_cursor(&mut renderer);
         self.render(ops, Some(&Color::TRANSPARENT));
@@ -461,6 +573,20 @@ pub trait GfxContext: Debug {
         stride: i32,
         format: &'static Format,
     ) -> Result<Rc<dyn GfxFramebuffer>, GfxError>;
+
+    fn gpu_time(&self) -> Option<Duration> {
+        None
+    }
+
+    fn max_texture_size(&self) -> (u32, u32);
+
+    fn set_max_in_flight_frames(&self, _frames: usize) {}
+
+    fn set_linear_blending(&self, _enabled: bool) {}
+
+    fn in_flight_frames(&self) -> usize {
+        0
+    }
 }
 
 #[derive(Debug)]
@@ -11,6 +11,7 @@ use {
 };
 
 pub mod gl;
+pub mod self_test;
 mod vulkan;
 
 pub fn create_gfx_context(
@@ -35,6 +36,26 @@ pub fn create_gfx_context(
     Err(last_err.unwrap())
 }
 
+pub fn create_exact_gfx_context(
+    eng: &Rc<AsyncEngine>,
+    ring: &Rc<IoUring>,
+    drm: &Drm,
+    api: GfxApi,
+) -> Result<Rc<dyn GfxContext>, GfxError> {
+    create_gfx_context_(eng, ring, drm, api)
+}
+
+pub fn probe_gfx_apis(eng: &Rc<AsyncEngine>, ring: &Rc<IoUring>, drm: &Drm) -> Vec<GfxApi> {
+    let mut res = vec![];
+    for api in [GfxApi::OpenGl, GfxApi::Vulkan] {
+        match create_gfx_context_(eng, ring, drm, api) {
+            Ok(_) => res.push(api),
+            Err(e) => log::debug!("{:?} API is not supported: {}", api, ErrorFmt(&e)),
+        }
+    }
+    res
+}
+
 fn create_gfx_context_(
     eng: &Rc<AsyncEngine>,
     ring: &Rc<IoUring>,
@@ -68,8 +68,8 @@ macro_rules! dynload {
 use {
     crate::{
         gfx_api::{
-            CopyTexture, FillRect, FramebufferRect, GfxApiOpt, GfxContext, GfxError, GfxTexture,
-            SampleRect,
+            CopyTexture, FillGradient, FillRect, FramebufferRect, GfxApiOpt, GfxContext, GfxError,
+            GfxTexture, SampleRect,
         },
         gfx_apis::gl::{
             gl::texture::image_target,
@@ -80,8 +80,9 @@ use {
             },
         },
         theme::Color,
-        utils::{rc_eq::rc_eq, vecstorage::VecStorage},
+        utils::{errorfmt::ErrorFmt, rc_eq::rc_eq, vecstorage::VecStorage},
         video::{
+            dmabuf::{dma_buf_import_sync_file, DMA_BUF_SYNC_WRITE},
             drm::{Drm, DrmError},
             gbm::GbmError,
         },
@@ -187,14 +188,33 @@ enum RenderError {
 struct GfxGlState {
     triangles: RefCell<Vec<[f32; 2]>>,
     fill_rect: VecStorage<&'static FillRect>,
+    fill_gradient: VecStorage<&'static FillGradient>,
     copy_tex: VecStorage<&'static CopyTexture>,
 }
 
+/// Attaches the explicit acquire fence of a texture to its dmabuf so that the implicit
+/// synchronization of the GL driver waits for it.
+fn import_acquire_sync(c: &CopyTexture) {
+    let (Some(sync_file), Some(dmabuf)) = (&c.acquire_sync, c.tex.dmabuf()) else {
+        return;
+    };
+    for plane in &dmabuf.planes {
+        if let Err(e) = dma_buf_import_sync_file(&plane.fd, DMA_BUF_SYNC_WRITE, sync_file) {
+            log::error!(
+                "Could not import acquire fence into dmabuf: {}",
+                ErrorFmt(e)
+            );
+        }
+    }
+}
+
 fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) {
     let mut state = fb.ctx.gl_state.borrow_mut();
     let state = &mut *state;
     let mut fill_rect = state.fill_rect.take();
     let fill_rect = &mut *fill_rect;
+    let mut fill_gradient = state.fill_gradient.take();
+    let fill_gradient = &mut *fill_gradient;
     let mut copy_tex = state.copy_tex.take();
     let copy_tex = &mut *copy_tex;
     let mut triangles = state.triangles.borrow_mut();
@@ -203,10 +223,11 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) {
     while i < ops.len() {
         macro_rules! has_ops {
             () => {
-                fill_rect.is_not_empty() || copy_tex.is_not_empty()
+                fill_rect.is_not_empty() || fill_gradient.is_not_empty() || copy_tex.is_not_empty()
             };
         }
         fill_rect.clear();
+        fill_gradient.clear();
         copy_tex.clear();
         while i < ops.len() {
             match &ops[i] {
@@ -220,7 +241,12 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) {
                     fill_rect.push(f);
                     i += 1;
                 }
+                GfxApiOpt::FillGradient(f) => {
+                    fill_gradient.push(f);
+                    i += 1;
+                }
                 GfxApiOpt::CopyTexture(c) => {
+                    import_acquire_sync(c);
                     copy_tex.push(c);
                     i += 1;
                 }
@@ -255,6 +281,9 @@ fn run_ops(fb: &Framebuffer, ops: &[GfxApiOpt]) {
                 }
             }
         }
+        for fg in &*fill_gradient {
+            render_gradient(&fb.ctx, fg);
+        }
         for tex in &*copy_tex {
             render_texture(&fb.ctx, &tex.tex.as_gl(), &tex.target, &tex.source)
         }
@@ -280,6 +309,40 @@ fn fill_boxes3(ctx: &GlRenderContext, boxes: &[[f32; 2]], color: &Color) {
     }
 }
 
+fn render_gradient(ctx: &GlRenderContext, fg: &FillGradient) {
+    let gles = ctx.ctx.dpy.gles;
+    let pos = fg.rect.to_points();
+    let weights = fg.direction.weights();
+    let (start, end) = (&fg.start, &fg.end);
+    unsafe {
+        (gles.glEnable)(GL_BLEND);
+        (gles.glUseProgram)(ctx.gradient_prog.prog);
+        (gles.glUniform4f)(ctx.gradient_prog_start, start.r, start.g, start.b, start.a);
+        (gles.glUniform4f)(ctx.gradient_prog_end, end.r, end.g, end.b, end.a);
+        (gles.glVertexAttribPointer)(
+            ctx.gradient_prog_pos as _,
+            2,
+            GL_FLOAT,
+            GL_FALSE,
+            0,
+            pos.as_ptr() as _,
+        );
+        (gles.glVertexAttribPointer)(
+            ctx.gradient_prog_weight as _,
+            1,
+            GL_FLOAT,
+            GL_FALSE,
+            0,
+            weights.as_ptr() as _,
+        );
+        (gles.glEnableVertexAttribArray)(ctx.gradient_prog_pos as _);
+        (gles.glEnableVertexAttribArray)(ctx.gradient_prog_weight as _);
+        (gles.glDrawArrays)(GL_TRIANGLE_STRIP, 0, 4);
+        (gles.glDisableVertexAttribArray)(ctx.gradient_prog_pos as _);
+        (gles.glDisableVertexAttribArray)(ctx.gradient_prog_weight as _);
+    }
+}
+
 fn render_texture(
     ctx: &GlRenderContext,
     texture: &Texture,
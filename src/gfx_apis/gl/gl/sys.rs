@@ -13,8 +13,12 @@ pub type GLuint = c::c_uint;
 
 egl_transparent!(GLeglImageOES);
 
+pub const GL_RGB: GLint = 0x1907;
 pub const GL_RGBA: GLint = 0x1908;
+pub const GL_RGB8: GLenum = 0x8051;
+pub const GL_RGB565: GLenum = 0x8D62;
 pub const GL_RGBA8: GLenum = 0x8058;
+pub const GL_RGB10_A2: GLenum = 0x8059;
 pub const GL_BGRA_EXT: GLint = 0x80E1;
 pub const GL_CLAMP_TO_EDGE: GLint = 0x812F;
 pub const GL_COLOR_ATTACHMENT0: GLenum = 0x8CE0;
@@ -39,16 +43,21 @@ pub const GL_TEXTURE_WRAP_S: GLenum = 0x2802;
 pub const GL_TEXTURE_WRAP_T: GLenum = 0x2803;
 pub const GL_TRIANGLE_STRIP: GLenum = 0x0005;
 pub const GL_TRIANGLES: GLenum = 0x0004;
+pub const GL_UNPACK_ALIGNMENT: GLenum = 0x0CF5;
 pub const GL_UNPACK_ROW_LENGTH_EXT: GLenum = 0x0CF2;
 pub const GL_UNSIGNED_BYTE: GLint = 0x1401;
+pub const GL_UNSIGNED_INT_2_10_10_10_REV: GLint = 0x8368;
+pub const GL_UNSIGNED_SHORT_5_6_5: GLint = 0x8363;
 pub const GL_VERTEX_SHADER: GLenum = 0x8B31;
 pub const GL_BLEND: GLenum = 0x0BE2;
 pub const GL_ONE: GLenum = 1;
 pub const GL_ONE_MINUS_SRC_ALPHA: GLenum = 0x0303;
+pub const GL_MAX_TEXTURE_SIZE: GLenum = 0x0D33;
 
 dynload! {
     GLESV2: GlesV2 from "libGLESv2.so" {
         glGetString: unsafe fn(name: GLenum) -> *const u8,
+        glGetIntegerv: unsafe fn(pname: GLenum, data: *mut GLint),
         glGenRenderbuffers: unsafe fn(n: GLsizei, renderbuffers: *mut GLuint),
         glRenderbufferStorage: unsafe fn(target: GLenum, format: GLenum, width: GLsizei, height: GLsizei),
         glDeleteRenderbuffers: unsafe fn(n: GLsizei, renderbuffers: *const GLuint),
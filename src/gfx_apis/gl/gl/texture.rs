@@ -6,7 +6,7 @@ use {
             ext::GL_OES_EGL_IMAGE_EXTERNAL,
             gl::sys::{
                 GLint, GLuint, GL_CLAMP_TO_EDGE, GL_TEXTURE_2D, GL_TEXTURE_WRAP_S,
-                GL_TEXTURE_WRAP_T, GL_UNPACK_ROW_LENGTH_EXT,
+                GL_TEXTURE_WRAP_T, GL_UNPACK_ALIGNMENT, GL_UNPACK_ROW_LENGTH_EXT,
             },
             sys::{GLeglImageOES, GLenum, GL_TEXTURE_EXTERNAL_OES},
             RenderError,
@@ -84,6 +84,7 @@ impl GlTexture {
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_S, GL_CLAMP_TO_EDGE);
             (gles.glTexParameteri)(GL_TEXTURE_2D, GL_TEXTURE_WRAP_T, GL_CLAMP_TO_EDGE);
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, stride / format.bpp as GLint);
+            (gles.glPixelStorei)(GL_UNPACK_ALIGNMENT, 1);
             (gles.glTexImage2D)(
                 GL_TEXTURE_2D,
                 0,
@@ -96,6 +97,7 @@ impl GlTexture {
                 data.as_ptr() as _,
             );
             (gles.glPixelStorei)(GL_UNPACK_ROW_LENGTH_EXT, 0);
+            (gles.glPixelStorei)(GL_UNPACK_ALIGNMENT, 4);
             (gles.glBindTexture)(GL_TEXTURE_2D, 0);
             Ok(tex)
         })?;
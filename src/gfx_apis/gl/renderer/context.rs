@@ -9,7 +9,10 @@ use {
             egl::{context::EglContext, display::EglDisplay, image::EglImage},
             ext::GL_OES_EGL_IMAGE_EXTERNAL,
             gl::{
-                program::GlProgram, render_buffer::GlRenderBuffer, sys::GLint, texture::GlTexture,
+                program::GlProgram,
+                render_buffer::GlRenderBuffer,
+                sys::{GLint, GL_MAX_TEXTURE_SIZE},
+                texture::GlTexture,
             },
             renderer::{framebuffer::Framebuffer, image::Image},
             GfxGlState, RenderError, Texture,
@@ -63,6 +66,14 @@ pub(in crate::gfx_apis::gl) struct GlRenderContext {
     pub(crate) fill_prog_pos: GLint,
     pub(crate) fill_prog_color: GLint,
 
+    pub(crate) gradient_prog: GlProgram,
+    pub(crate) gradient_prog_pos: GLint,
+    pub(crate) gradient_prog_weight: GLint,
+    pub(crate) gradient_prog_start: GLint,
+    pub(crate) gradient_prog_end: GLint,
+
+    pub(crate) max_texture_size: u32,
+
     pub(crate) gfx_ops: RefCell<Vec<GfxApiOpt>>,
     pub(in crate::gfx_apis::gl) gl_state: RefCell<GfxGlState>,
 }
@@ -123,6 +134,13 @@ impl GlRenderContext {
             include_str!("../shaders/fill.vert.glsl"),
             include_str!("../shaders/fill.frag.glsl"),
         )?;
+        let gradient_prog = GlProgram::from_shaders(
+            ctx,
+            include_str!("../shaders/gradient.vert.glsl"),
+            include_str!("../shaders/gradient.frag.glsl"),
+        )?;
+        let mut max_texture_size = 0;
+        (ctx.dpy.gles.glGetIntegerv)(GL_MAX_TEXTURE_SIZE, &mut max_texture_size);
         Ok(Self {
             ctx: ctx.clone(),
             gbm: ctx.dpy.gbm.clone(),
@@ -139,6 +157,14 @@ impl GlRenderContext {
             fill_prog_color: fill_prog.get_uniform_location(ustr!("color")),
             fill_prog,
 
+            gradient_prog_pos: gradient_prog.get_attrib_location(ustr!("pos")),
+            gradient_prog_weight: gradient_prog.get_attrib_location(ustr!("weight")),
+            gradient_prog_start: gradient_prog.get_uniform_location(ustr!("start")),
+            gradient_prog_end: gradient_prog.get_uniform_location(ustr!("end")),
+            gradient_prog,
+
+            max_texture_size: max_texture_size.max(0) as u32,
+
             gfx_ops: Default::default(),
             gl_state: Default::default(),
         })
@@ -268,4 +294,8 @@ impl GfxContext for GlRenderContext {
         })?;
         Ok(Rc::new(Framebuffer { ctx: self, gl: fb }))
     }
+
+    fn max_texture_size(&self) -> (u32, u32) {
+        (self.max_texture_size, self.max_texture_size)
+    }
 }
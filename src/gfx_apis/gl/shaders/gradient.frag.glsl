@@ -0,0 +1,8 @@
+precision mediump float;
+uniform vec4 start;
+uniform vec4 end;
+varying float v_weight;
+
+void main() {
+	gl_FragColor = mix(start, end, v_weight);
+}
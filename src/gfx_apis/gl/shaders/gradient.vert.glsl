@@ -0,0 +1,8 @@
+attribute vec2 pos;
+attribute float weight;
+varying float v_weight;
+
+void main() {
+	gl_Position = vec4(pos, 0.0, 1.0);
+	v_weight = weight;
+}
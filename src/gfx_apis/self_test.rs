@@ -0,0 +1,138 @@
+use {
+    crate::{
+        format::ARGB8888,
+        gfx_api::{GfxContext, GfxError, GfxFramebuffer},
+        rect::Rect,
+        scale::Scale,
+        theme::Color,
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+const SIZE: i32 = 16;
+const HALF: i32 = SIZE / 2;
+const STRIDE: i32 = SIZE * 4;
+const TOLERANCE: u8 = 2;
+
+/// The colors of the test pattern in the order top-left, top-right, bottom-left,
+/// bottom-right.
+const QUADRANTS: [[u8; 3]; 4] = [[255, 0, 0], [0, 255, 0], [0, 0, 255], [255, 255, 255]];
+
+#[derive(Debug, Error)]
+pub enum GfxSelfTestError {
+    #[error("Could not create a framebuffer")]
+    CreateFramebuffer(#[source] GfxError),
+    #[error("Could not create a texture")]
+    CreateTexture(#[source] GfxError),
+    #[error("Could not read back the framebuffer")]
+    CopyToShm(#[source] GfxError),
+    #[error("Could not read back the texture")]
+    ReadPixels(#[source] GfxError),
+    #[error("{stage}: Pixel {x}x{y} has the value {actual:?} but {expected:?} was expected")]
+    Mismatch {
+        stage: &'static str,
+        x: i32,
+        y: i32,
+        expected: [u8; 4],
+        actual: [u8; 4],
+    },
+}
+
+/// Renders a known pattern with the fill and texture pipelines, reads it back, and checks
+/// that the result is correct.
+pub fn run(ctx: &Rc<dyn GfxContext>) -> Result<(), GfxSelfTestError> {
+    test_fill(ctx)?;
+    test_texture(ctx)?;
+    Ok(())
+}
+
+fn test_fill(ctx: &Rc<dyn GfxContext>) -> Result<(), GfxSelfTestError> {
+    let fb = create_fb(ctx)?;
+    fb.render_custom(Scale::from_int(1), Some(&Color::SOLID_BLACK), &mut |r| {
+        for (idx, &[red, green, blue]) in QUADRANTS.iter().enumerate() {
+            let (x, y) = quadrant_origin(idx);
+            let rect = Rect::new_sized(x, y, HALF, HALF).unwrap();
+            r.fill_boxes(&[rect], &Color::from_rgb(red, green, blue));
+        }
+    });
+    let mem = buffer();
+    fb.copy_to_shm(0, 0, SIZE, SIZE, STRIDE, ARGB8888, &mem)
+        .map_err(GfxSelfTestError::CopyToShm)?;
+    check("fill", &mem)
+}
+
+fn test_texture(ctx: &Rc<dyn GfxContext>) -> Result<(), GfxSelfTestError> {
+    let pattern = buffer();
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let offset = (y * STRIDE + x * 4) as usize;
+            for (dst, src) in pattern[offset..offset + 4].iter().zip(expected(x, y)) {
+                dst.set(src);
+            }
+        }
+    }
+    let tex = ctx
+        .clone()
+        .shmem_texture(None, &pattern, ARGB8888, SIZE, SIZE, STRIDE)
+        .map_err(GfxSelfTestError::CreateTexture)?;
+    let mem = buffer();
+    tex.clone()
+        .read_pixels(0, 0, SIZE, SIZE, STRIDE, ARGB8888, &mem)
+        .map_err(GfxSelfTestError::ReadPixels)?;
+    check("texture readback", &mem)?;
+    let fb = create_fb(ctx)?;
+    fb.copy_texture(&tex, 0, 0);
+    let mem = buffer();
+    fb.copy_to_shm(0, 0, SIZE, SIZE, STRIDE, ARGB8888, &mem)
+        .map_err(GfxSelfTestError::CopyToShm)?;
+    check("texture sampling", &mem)
+}
+
+fn create_fb(ctx: &Rc<dyn GfxContext>) -> Result<Rc<dyn GfxFramebuffer>, GfxSelfTestError> {
+    ctx.clone()
+        .create_fb(SIZE, SIZE, STRIDE, ARGB8888)
+        .map_err(GfxSelfTestError::CreateFramebuffer)
+}
+
+fn buffer() -> Vec<Cell<u8>> {
+    vec![Cell::new(0); (STRIDE * SIZE) as usize]
+}
+
+fn quadrant_origin(idx: usize) -> (i32, i32) {
+    ((idx as i32 % 2) * HALF, (idx as i32 / 2) * HALF)
+}
+
+/// Returns the expected argb8888 pixel at the position in memory order.
+fn expected(x: i32, y: i32) -> [u8; 4] {
+    let idx = (y / HALF * 2 + x / HALF) as usize;
+    let [red, green, blue] = QUADRANTS[idx];
+    [blue, green, red, 255]
+}
+
+fn check(stage: &'static str, mem: &[Cell<u8>]) -> Result<(), GfxSelfTestError> {
+    for y in 0..SIZE {
+        for x in 0..SIZE {
+            let offset = (y * STRIDE + x * 4) as usize;
+            let mut actual = [0; 4];
+            for (dst, src) in actual.iter_mut().zip(&mem[offset..offset + 4]) {
+                *dst = src.get();
+            }
+            let expected = expected(x, y);
+            let matches = actual
+                .iter()
+                .zip(expected.iter())
+                .all(|(a, e)| a.abs_diff(*e) <= TOLERANCE);
+            if !matches {
+                return Err(GfxSelfTestError::Mismatch {
+                    stage,
+                    x,
+                    y,
+                    expected,
+                    actual,
+                });
+            }
+        }
+    }
+    Ok(())
+}
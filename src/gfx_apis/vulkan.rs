@@ -6,7 +6,9 @@ mod fence;
 mod format;
 mod image;
 mod instance;
+mod linear;
 mod pipeline;
+mod query;
 mod renderer;
 mod sampler;
 mod semaphore;
@@ -42,6 +44,7 @@ use {
         ffi::{CStr, CString},
         rc::Rc,
         sync::Arc,
+        time::Duration,
     },
     thiserror::Error,
     uapi::c::dev_t,
@@ -65,6 +68,10 @@ pub enum VulkanError {
     CreateSemaphore(#[source] vk::Result),
     #[error("Could not create a fence")]
     CreateFence(#[source] vk::Result),
+    #[error("Could not create a query pool")]
+    CreateQueryPool(#[source] vk::Result),
+    #[error("Could not retrieve query pool results")]
+    GetQueryPoolResults(#[source] vk::Result),
     #[error("Could not create the buffer")]
     CreateBuffer(#[source] vk::Result),
     #[error("Could not create a shader module")]
@@ -113,6 +120,8 @@ pub enum VulkanError {
     FormatNotSupported,
     #[error("The modifier is not supported")]
     ModifierNotSupported,
+    #[error("Could not determine the modifier of a buffer with an implicit modifier")]
+    UnknownImplicitModifier(#[source] Option<GbmError>),
     #[error("The modifier does not support this use-case")]
     ModifierUseNotSupported,
     #[error("The image has a non-positive size")]
@@ -143,6 +152,8 @@ pub enum VulkanError {
     BindImageMemory(#[source] vk::Result),
     #[error("The format does not support shared memory images")]
     ShmNotSupported,
+    #[error("The format cannot be used as a color attachment")]
+    RenderingNotSupported,
     #[error("Could not bind memory to the buffer")]
     BindBufferMemory(#[source] vk::Result),
     #[error("Could not map the memory")]
@@ -186,6 +197,16 @@ impl From<VulkanError> for GfxError {
 pub static VULKAN_VALIDATION: Lazy<bool> =
     Lazy::new(|| std::env::var("JAY_VULKAN_VALIDATION").ok().as_deref() == Some("1"));
 
+pub static GPU_TIMING: Lazy<bool> =
+    Lazy::new(|| std::env::var("JAY_GPU_TIMING").ok().as_deref() == Some("1"));
+
+pub static RELEASE_FENCE_TIMEOUT_MS: Lazy<u64> = Lazy::new(|| {
+    std::env::var("JAY_RELEASE_FENCE_TIMEOUT_MS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(500)
+});
+
 pub fn create_graphics_context(
     eng: &Rc<AsyncEngine>,
     ring: &Rc<IoUring>,
@@ -238,7 +259,7 @@ impl GfxContext for Context {
             if old.width as i32 == width
                 && old.height as i32 == height
                 && shm.stride as i32 == stride
-                && old.format.vk_format == format.vk_format
+                && shm.format.vk_format == format.vk_format
             {
                 shm.upload(data)?;
                 return Ok(old);
@@ -270,6 +291,30 @@ impl GfxContext for Context {
             .create_shm_texture(format, width, height, stride, &[], true)?;
         Ok(fb)
     }
+
+    fn gpu_time(&self) -> Option<Duration> {
+        self.0.last_gpu_time.get()
+    }
+
+    fn max_texture_size(&self) -> (u32, u32) {
+        let max = self.0.device.max_image_dimension_2d;
+        (max, max)
+    }
+
+    fn set_max_in_flight_frames(&self, frames: usize) {
+        self.0.max_in_flight_frames.set(frames.max(1));
+    }
+
+    fn set_linear_blending(&self, enabled: bool) {
+        self.0.linear_blending.set(enabled);
+        if !enabled {
+            self.0.linear_images.clear();
+        }
+    }
+
+    fn in_flight_frames(&self) -> usize {
+        self.0.pending_frames.len()
+    }
 }
 
 impl Drop for Context {
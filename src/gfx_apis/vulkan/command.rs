@@ -4,7 +4,7 @@ use {
         CommandBuffer, CommandBufferAllocateInfo, CommandBufferLevel, CommandPool,
         CommandPoolCreateFlags, CommandPoolCreateInfo,
     },
-    std::rc::Rc,
+    std::{cell::Cell, rc::Rc},
 };
 
 pub struct VulkanCommandPool {
@@ -15,6 +15,9 @@ pub struct VulkanCommandPool {
 pub struct VulkanCommandBuffer {
     pub(super) pool: Rc<VulkanCommandPool>,
     pub(super) buffer: CommandBuffer,
+    /// The point of the pending frame that is using this buffer. Only tracked if
+    /// validation is enabled.
+    pub(super) pending_point: Cell<Option<u64>>,
 }
 
 impl Drop for VulkanCommandPool {
@@ -48,6 +51,7 @@ impl VulkanCommandPool {
         Ok(Rc::new(VulkanCommandBuffer {
             pool: self.clone(),
             buffer: buffer.pop().unwrap(),
+            pending_point: Cell::new(None),
         }))
     }
 }
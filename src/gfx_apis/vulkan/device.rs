@@ -8,7 +8,7 @@ use {
                 API_VERSION,
             },
             util::OnDrop,
-            VulkanError,
+            VulkanError, GPU_TIMING,
         },
         video::{drm::Drm, gbm::GbmDevice},
     },
@@ -53,6 +53,8 @@ pub struct VulkanDevice {
     pub(super) memory_types: ArrayVec<MemoryType, MAX_MEMORY_TYPES>,
     pub(super) graphics_queue: Queue,
     pub(super) graphics_queue_idx: u32,
+    pub(super) timestamp_period: Option<f32>,
+    pub(super) max_image_dimension_2d: u32,
 }
 
 impl Drop for VulkanDevice {
@@ -186,6 +188,19 @@ impl VulkanInstance {
             .ok_or(VulkanError::NoGraphicsQueue)
     }
 
+    fn timestamp_period(&self, phy_dev: PhysicalDevice, queue_idx: u32) -> Option<f32> {
+        let queue_props = unsafe {
+            self.instance
+                .get_physical_device_queue_family_properties(phy_dev)
+        };
+        if queue_props[queue_idx as usize].timestamp_valid_bits == 0 {
+            log::warn!("Graphics queue does not support timestamps");
+            return None;
+        }
+        let props = unsafe { self.instance.get_physical_device_properties(phy_dev) };
+        Some(props.limits.timestamp_period)
+    }
+
     fn supports_semaphore_import(&self, phy_dev: PhysicalDevice) -> bool {
         let mut props = ExternalSemaphoreProperties::builder().build();
         let info = PhysicalDeviceExternalSemaphoreInfo::builder()
@@ -276,6 +291,16 @@ impl VulkanInstance {
             .copied()
             .collect();
         let graphics_queue = unsafe { device.get_device_queue(graphics_queue_idx, 0) };
+        let max_image_dimension_2d = unsafe {
+            self.instance
+                .get_physical_device_properties(phy_dev)
+                .limits
+                .max_image_dimension2_d
+        };
+        let timestamp_period = match *GPU_TIMING {
+            true => self.timestamp_period(phy_dev, graphics_queue_idx),
+            false => None,
+        };
         Ok(Rc::new(VulkanDevice {
             physical_device: phy_dev,
             render_node,
@@ -290,6 +315,8 @@ impl VulkanInstance {
             memory_types,
             graphics_queue,
             graphics_queue_idx,
+            timestamp_period,
+            max_image_dimension_2d,
         }))
     }
 }
@@ -1,6 +1,6 @@
 use {
     crate::{
-        format::{Format, FORMATS},
+        format::{Format, BGR888, FORMATS, RGB565, XRGB8888},
         gfx_apis::vulkan::{instance::VulkanInstance, VulkanError},
         video::Modifier,
     },
@@ -17,6 +17,7 @@ use {
         },
     },
     isnt::std_1::collections::IsntHashMapExt,
+    std::fmt::{Debug, Formatter},
 };
 
 #[derive(Debug)]
@@ -50,9 +51,61 @@ pub struct VulkanMaxExtents {
 #[derive(Debug)]
 pub struct VulkanShmFormat {
     pub max_extents: VulkanMaxExtents,
+    pub conversion: Option<&'static VulkanShmConversion>,
 }
 
-const FRAMEBUFFER_FEATURES: FormatFeatureFlags = FormatFeatureFlags::from_raw(
+/// A CPU conversion for shm formats that the device cannot sample.
+pub struct VulkanShmConversion {
+    pub target: &'static Format,
+    pub convert_row: fn(src: &[u8], dst: &mut [u8]),
+}
+
+impl Debug for VulkanShmConversion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VulkanShmConversion")
+            .field("target", &self.target.name)
+            .finish_non_exhaustive()
+    }
+}
+
+static RGB565_CONVERSION: VulkanShmConversion = VulkanShmConversion {
+    target: XRGB8888,
+    convert_row: |src, dst| {
+        for (src, dst) in src.chunks_exact(2).zip(dst.chunks_exact_mut(4)) {
+            let v = u16::from_le_bytes([src[0], src[1]]);
+            let r = (v >> 11) as u8 & 0x1f;
+            let g = (v >> 5) as u8 & 0x3f;
+            let b = v as u8 & 0x1f;
+            dst[0] = (b << 3) | (b >> 2);
+            dst[1] = (g << 2) | (g >> 4);
+            dst[2] = (r << 3) | (r >> 2);
+            dst[3] = 0xff;
+        }
+    },
+};
+
+static BGR888_CONVERSION: VulkanShmConversion = VulkanShmConversion {
+    target: XRGB8888,
+    convert_row: |src, dst| {
+        for (src, dst) in src.chunks_exact(3).zip(dst.chunks_exact_mut(4)) {
+            dst[0] = src[2];
+            dst[1] = src[1];
+            dst[2] = src[0];
+            dst[3] = 0xff;
+        }
+    },
+};
+
+fn shm_conversion(format: &Format) -> Option<&'static VulkanShmConversion> {
+    let conversion = match format.drm {
+        d if d == RGB565.drm => &RGB565_CONVERSION,
+        d if d == BGR888.drm => &BGR888_CONVERSION,
+        _ => return None,
+    };
+    Some(conversion)
+}
+
+pub(super) const FRAMEBUFFER_FEATURES: FormatFeatureFlags = FormatFeatureFlags::from_raw(
     0 | FormatFeatureFlags::COLOR_ATTACHMENT.as_raw()
         | FormatFeatureFlags::COLOR_ATTACHMENT_BLEND.as_raw(),
 );
@@ -108,7 +161,12 @@ impl VulkanInstance {
                 &mut format_properties,
             );
         }
-        let shm = self.load_shm_format(phy_dev, format, &format_properties)?;
+        let mut shm = self.load_shm_format(phy_dev, format, &format_properties)?;
+        if shm.is_none() {
+            if let Some(conversion) = shm_conversion(format) {
+                shm = self.load_converted_shm_format(phy_dev, conversion)?;
+            }
+        }
         let modifiers = self.load_drm_format(phy_dev, format, &modifier_props)?;
         if shm.is_some() || modifiers.is_not_empty() {
             dst.insert(
@@ -161,6 +219,27 @@ impl VulkanInstance {
                 width: format_properties.image_format_properties.max_extent.width,
                 height: format_properties.image_format_properties.max_extent.height,
             },
+            conversion: None,
+        }))
+    }
+
+    fn load_converted_shm_format(
+        &self,
+        phy_dev: PhysicalDevice,
+        conversion: &'static VulkanShmConversion,
+    ) -> Result<Option<VulkanShmFormat>, VulkanError> {
+        let mut format_properties = FormatProperties2::builder().build();
+        unsafe {
+            self.instance.get_physical_device_format_properties2(
+                phy_dev,
+                conversion.target.vk_format,
+                &mut format_properties,
+            );
+        }
+        let shm = self.load_shm_format(phy_dev, conversion.target, &format_properties)?;
+        Ok(shm.map(|shm| VulkanShmFormat {
+            conversion: Some(conversion),
+            ..shm
         }))
     }
 
@@ -3,12 +3,19 @@ use {
         format::Format,
         gfx_api::{GfxApiOpt, GfxError, GfxFramebuffer, GfxImage, GfxTexture, TextureReservations},
         gfx_apis::vulkan::{
-            allocator::VulkanAllocation, device::VulkanDevice, format::VulkanMaxExtents,
-            renderer::VulkanRenderer, util::OnDrop, VulkanError,
+            allocator::VulkanAllocation,
+            device::VulkanDevice,
+            format::{VulkanMaxExtents, VulkanShmConversion, FRAMEBUFFER_FEATURES},
+            renderer::VulkanRenderer,
+            util::OnDrop,
+            VulkanError,
         },
         theme::Color,
         utils::clonecell::CloneCell,
-        video::dmabuf::{DmaBuf, PlaneVec},
+        video::{
+            dmabuf::{DmaBuf, PlaneVec},
+            Modifier, INVALID_MODIFIER,
+        },
     },
     ash::vk::{
         BindImageMemoryInfo, BindImagePlaneMemoryInfo, ComponentMapping, ComponentSwizzle,
@@ -69,7 +76,11 @@ pub struct VulkanDmaBufImage {
 pub struct VulkanShmImage {
     pub(super) to_flush: RefCell<Option<Vec<u8>>>,
     pub(super) size: DeviceSize,
+    /// The format and stride of the client data. These differ from the image if the
+    /// data is converted during the upload.
+    pub(super) format: &'static Format,
     pub(super) stride: u32,
+    pub(super) conversion: Option<&'static VulkanShmConversion>,
     pub(super) _allocation: VulkanAllocation,
 }
 
@@ -136,10 +147,26 @@ impl VulkanRenderer {
             .get(&format.drm)
             .ok_or(VulkanError::FormatNotSupported)?;
         let shm = vk_format.shm.as_ref().ok_or(VulkanError::ShmNotSupported)?;
+        if for_download
+            && (shm.conversion.is_some() || !vk_format.features.contains(FRAMEBUFFER_FEATURES))
+        {
+            return Err(VulkanError::RenderingNotSupported);
+        }
         if width > shm.max_extents.width || height > shm.max_extents.height {
             return Err(VulkanError::ImageTooLarge);
         }
-        let size = stride.checked_mul(height).ok_or(VulkanError::ShmOverflow)?;
+        let (image_format, image_stride) = match shm.conversion {
+            Some(c) => {
+                let image_stride = (stride / format.bpp)
+                    .checked_mul(c.target.bpp)
+                    .ok_or(VulkanError::ShmOverflow)?;
+                (c.target, image_stride)
+            }
+            None => (format, stride),
+        };
+        let size = image_stride
+            .checked_mul(height)
+            .ok_or(VulkanError::ShmOverflow)?;
         let usage = ImageUsageFlags::TRANSFER_SRC
             | match for_download {
                 true => ImageUsageFlags::COLOR_ATTACHMENT,
@@ -147,7 +174,7 @@ impl VulkanRenderer {
             };
         let create_info = ImageCreateInfo::builder()
             .image_type(ImageType::TYPE_2D)
-            .format(format.vk_format)
+            .format(image_format.vk_format)
             .mip_levels(1)
             .array_layers(1)
             .tiling(ImageTiling::OPTIMAL)
@@ -177,7 +204,7 @@ impl VulkanRenderer {
         res.map_err(VulkanError::BindImageMemory)?;
         let image_view_create_info = ImageViewCreateInfo::builder()
             .image(image)
-            .format(format.vk_format)
+            .format(image_format.vk_format)
             .view_type(ImageViewType::TYPE_2D)
             .subresource_range(ImageSubresourceRange {
                 aspect_mask: ImageAspectFlags::COLOR,
@@ -195,17 +222,19 @@ impl VulkanRenderer {
         let shm = VulkanShmImage {
             to_flush: Default::default(),
             size: size as u64,
+            format,
             stride,
+            conversion: shm.conversion,
             _allocation: allocation,
         };
         shm.upload(data)?;
         destroy_image.forget();
         Ok(Rc::new(VulkanImage {
             renderer: self.clone(),
-            format,
+            format: image_format,
             width,
             height,
-            stride,
+            stride: image_stride,
             texture_view: view,
             render_view: None,
             image,
@@ -220,6 +249,10 @@ impl VulkanRenderer {
         self: &Rc<Self>,
         dmabuf: &DmaBuf,
     ) -> Result<Rc<VulkanDmaBufImageTemplate>, VulkanError> {
+        let mut dmabuf = dmabuf.clone();
+        if dmabuf.modifier == INVALID_MODIFIER {
+            dmabuf.modifier = self.probe_implicit_modifier(&dmabuf)?;
+        }
         let format = self
             .device
             .formats
@@ -260,11 +293,22 @@ impl VulkanRenderer {
             width,
             height,
             disjoint,
-            dmabuf: dmabuf.clone(),
+            dmabuf,
             render_max_extents: modifier.render_max_extents,
             texture_max_extents: modifier.texture_max_extents,
         }))
     }
+
+    /// Vulkan cannot import buffers with implicit modifiers. Ask GBM for the actual
+    /// modifier and reject the buffer if it cannot tell. Guessing a layout would show
+    /// garbage for tiled buffers.
+    fn probe_implicit_modifier(&self, dmabuf: &DmaBuf) -> Result<Modifier, VulkanError> {
+        match self.device.gbm.query_implicit_modifier(dmabuf) {
+            Ok(m) if m != INVALID_MODIFIER => Ok(m),
+            Ok(_) => Err(VulkanError::UnknownImplicitModifier(None)),
+            Err(e) => Err(VulkanError::UnknownImplicitModifier(Some(e))),
+        }
+    }
 }
 
 impl VulkanDevice {
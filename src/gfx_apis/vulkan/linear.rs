@@ -0,0 +1,157 @@
+use {
+    crate::gfx_apis::vulkan::{
+        allocator::VulkanAllocation,
+        device::VulkanDevice,
+        pipeline::{PipelineCreateInfo, VulkanPipeline},
+        renderer::{VulkanFormatPipelines, VulkanRenderer},
+        shaders::TexVertPushConstants,
+        util::OnDrop,
+        VulkanError,
+    },
+    ash::vk::{
+        self, Extent3D, Image, ImageAspectFlags, ImageCreateInfo, ImageLayout,
+        ImageSubresourceRange, ImageTiling, ImageType, ImageUsageFlags, ImageView,
+        ImageViewCreateInfo, ImageViewType, SampleCountFlags, SharingMode,
+    },
+    gpu_alloc::UsageFlags,
+    std::rc::Rc,
+};
+
+/// The format of the intermediate image used for linear blending.
+pub(super) const LINEAR_FORMAT: vk::Format = vk::Format::R16G16B16A16_SFLOAT;
+
+/// The maximum number of cached intermediate images. Usually there is one per output size.
+const MAX_LINEAR_IMAGES: usize = 4;
+
+/// An intermediate image that holds premultiplied, linear color values.
+///
+/// It is converted to sRGB when it is copied to the framebuffer.
+pub(super) struct VulkanLinearImage {
+    pub(super) device: Rc<VulkanDevice>,
+    pub(super) width: u32,
+    pub(super) height: u32,
+    pub(super) image: Image,
+    pub(super) view: ImageView,
+    pub(super) _allocation: VulkanAllocation,
+}
+
+impl Drop for VulkanLinearImage {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.device.destroy_image_view(self.view, None);
+            self.device.device.destroy_image(self.image, None);
+        }
+    }
+}
+
+impl VulkanRenderer {
+    pub(super) fn get_or_create_linear_image(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<Rc<VulkanLinearImage>, VulkanError> {
+        if let Some(img) = self.linear_images.get(&(width, height)) {
+            return Ok(img);
+        }
+        if self.linear_images.len() >= MAX_LINEAR_IMAGES {
+            self.linear_images.clear();
+        }
+        let img = Rc::new(self.create_linear_image(width, height)?);
+        self.linear_images.set((width, height), img.clone());
+        Ok(img)
+    }
+
+    fn create_linear_image(
+        &self,
+        width: u32,
+        height: u32,
+    ) -> Result<VulkanLinearImage, VulkanError> {
+        let create_info = ImageCreateInfo::builder()
+            .image_type(ImageType::TYPE_2D)
+            .format(LINEAR_FORMAT)
+            .mip_levels(1)
+            .array_layers(1)
+            .tiling(ImageTiling::OPTIMAL)
+            .samples(SampleCountFlags::TYPE_1)
+            .sharing_mode(SharingMode::EXCLUSIVE)
+            .initial_layout(ImageLayout::UNDEFINED)
+            .extent(Extent3D {
+                width,
+                height,
+                depth: 1,
+            })
+            .usage(ImageUsageFlags::COLOR_ATTACHMENT | ImageUsageFlags::SAMPLED)
+            .build();
+        let image = unsafe { self.device.device.create_image(&create_info, None) };
+        let image = image.map_err(VulkanError::CreateImage)?;
+        let destroy_image = OnDrop(|| unsafe { self.device.device.destroy_image(image, None) });
+        let memory_requirements =
+            unsafe { self.device.device.get_image_memory_requirements(image) };
+        let allocation =
+            self.allocator
+                .alloc(&memory_requirements, UsageFlags::FAST_DEVICE_ACCESS, false)?;
+        let res = unsafe {
+            self.device
+                .device
+                .bind_image_memory(image, allocation.memory, allocation.offset)
+        };
+        res.map_err(VulkanError::BindImageMemory)?;
+        let image_view_create_info = ImageViewCreateInfo::builder()
+            .image(image)
+            .format(LINEAR_FORMAT)
+            .view_type(ImageViewType::TYPE_2D)
+            .subresource_range(ImageSubresourceRange {
+                aspect_mask: ImageAspectFlags::COLOR,
+                base_mip_level: 0,
+                level_count: 1,
+                base_array_layer: 0,
+                layer_count: 1,
+            });
+        let view = unsafe {
+            self.device
+                .device
+                .create_image_view(&image_view_create_info, None)
+        };
+        let view = view.map_err(VulkanError::CreateImageView)?;
+        destroy_image.forget();
+        Ok(VulkanLinearImage {
+            device: self.device.clone(),
+            width,
+            height,
+            image,
+            view,
+            _allocation: allocation,
+        })
+    }
+
+    pub(super) fn get_or_create_linear_pipelines(
+        &self,
+    ) -> Result<Rc<VulkanFormatPipelines>, VulkanError> {
+        if let Some(pl) = self.linear_pipelines.get() {
+            return Ok(pl);
+        }
+        let pl = self.create_format_pipelines(LINEAR_FORMAT, &self.tex_to_linear_frag_shader)?;
+        self.linear_pipelines.set(Some(pl.clone()));
+        Ok(pl)
+    }
+
+    pub(super) fn get_or_create_srgb_pipeline(
+        &self,
+        format: vk::Format,
+    ) -> Result<Rc<VulkanPipeline>, VulkanError> {
+        if let Some(pl) = self.srgb_pipelines.get(&format) {
+            return Ok(pl);
+        }
+        let pl = self
+            .device
+            .create_pipeline::<TexVertPushConstants, ()>(PipelineCreateInfo {
+                format,
+                vert: self.tex_vert_shader.clone(),
+                frag: self.tex_to_srgb_frag_shader.clone(),
+                alpha: false,
+                frag_descriptor_set_layout: Some(self.tex_descriptor_set_layout.clone()),
+            })?;
+        self.srgb_pipelines.set(format, pl.clone());
+        Ok(pl)
+    }
+}
@@ -0,0 +1,61 @@
+use {
+    crate::gfx_apis::vulkan::{device::VulkanDevice, VulkanError},
+    ash::vk::{QueryPool, QueryPoolCreateInfo, QueryResultFlags, QueryType},
+    std::{rc::Rc, time::Duration},
+};
+
+pub struct VulkanTimestampQuery {
+    pub(super) device: Rc<VulkanDevice>,
+    pub(super) pool: QueryPool,
+}
+
+impl Drop for VulkanTimestampQuery {
+    fn drop(&mut self) {
+        unsafe {
+            self.device.device.destroy_query_pool(self.pool, None);
+        }
+    }
+}
+
+impl VulkanDevice {
+    pub fn create_timestamp_query(
+        self: &Rc<Self>,
+    ) -> Result<Rc<VulkanTimestampQuery>, VulkanError> {
+        let pool = {
+            let create_info = QueryPoolCreateInfo::builder()
+                .query_type(QueryType::TIMESTAMP)
+                .query_count(2);
+            let pool = unsafe { self.device.create_query_pool(&create_info, None) };
+            pool.map_err(VulkanError::CreateQueryPool)?
+        };
+        Ok(Rc::new(VulkanTimestampQuery {
+            device: self.clone(),
+            pool,
+        }))
+    }
+}
+
+impl VulkanTimestampQuery {
+    /// Returns the time between the two timestamps written into the pool.
+    ///
+    /// Must only be called after the command buffer has completed.
+    pub fn elapsed(&self, timestamp_period: f32) -> Result<Duration, VulkanError> {
+        let mut timestamps = [0u64; 2];
+        unsafe {
+            self.device
+                .device
+                .get_query_pool_results(
+                    self.pool,
+                    0,
+                    2,
+                    &mut timestamps,
+                    QueryResultFlags::TYPE_64 | QueryResultFlags::WAIT,
+                )
+                .map_err(VulkanError::GetQueryPoolResults)?;
+        }
+        let ticks = timestamps[1].saturating_sub(timestamps[0]);
+        Ok(Duration::from_nanos(
+            (ticks as f64 * timestamp_period as f64) as u64,
+        ))
+    }
+}
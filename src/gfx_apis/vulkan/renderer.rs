@@ -1,46 +1,59 @@
 use {
     crate::{
         async_engine::SpawnedFuture,
-        format::Format,
-        gfx_api::{GfxApiOpt, GfxFormat, GfxFramebuffer, GfxTexture},
+        format::{Format, XRGB8888},
+        gfx_api::{find_occluded_opts, GfxApiOpt, GfxFormat, GfxFramebuffer, GfxTexture},
         gfx_apis::vulkan::{
             allocator::VulkanAllocator,
             command::{VulkanCommandBuffer, VulkanCommandPool},
+            descriptor::VulkanDescriptorSetLayout,
             device::VulkanDevice,
             fence::VulkanFence,
             image::{VulkanImage, VulkanImageMemory},
+            linear::VulkanLinearImage,
             pipeline::{PipelineCreateInfo, VulkanPipeline},
+            query::VulkanTimestampQuery,
             semaphore::VulkanSemaphore,
             shaders::{
-                FillFragPushConstants, FillVertPushConstants, TexVertPushConstants, FILL_FRAG,
-                FILL_VERT, TEX_FRAG, TEX_VERT,
+                FillFragPushConstants, FillVertPushConstants, GradientFragPushConstants,
+                GradientVertPushConstants, TexVertPushConstants, VulkanShader, FILL_FRAG,
+                FILL_VERT, GRADIENT_FRAG, GRADIENT_VERT, TEX_FRAG, TEX_TO_LINEAR_FRAG,
+                TEX_TO_SRGB_FRAG, TEX_VERT,
             },
             staging::VulkanStagingBuffer,
-            VulkanError,
+            VulkanError, RELEASE_FENCE_TIMEOUT_MS, VULKAN_VALIDATION,
         },
-        io_uring::IoUring,
+        io_uring::{IoUring, IoUringError},
         theme::Color,
-        utils::{copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell, stack::Stack},
-        video::dmabuf::{
-            dma_buf_export_sync_file, dma_buf_import_sync_file, DMA_BUF_SYNC_READ,
-            DMA_BUF_SYNC_WRITE,
+        time::Time,
+        utils::{
+            clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell,
+            oserror::OsError, stack::Stack,
+        },
+        video::{
+            dmabuf::{
+                dma_buf_export_sync_file, dma_buf_import_sync_file, DMA_BUF_SYNC_READ,
+                DMA_BUF_SYNC_WRITE,
+            },
+            INVALID_MODIFIER,
         },
     },
     ahash::AHashMap,
     ash::{
         vk::{
-            AccessFlags2, AttachmentLoadOp, AttachmentStoreOp, BufferImageCopy, BufferImageCopy2,
-            BufferMemoryBarrier2, ClearColorValue, ClearValue, CommandBuffer,
+            self, AccessFlags2, AttachmentLoadOp, AttachmentStoreOp, BufferImageCopy,
+            BufferImageCopy2, BufferMemoryBarrier2, ClearColorValue, ClearValue, CommandBuffer,
             CommandBufferBeginInfo, CommandBufferSubmitInfo, CommandBufferUsageFlags,
             CopyBufferToImageInfo2, DependencyInfo, DependencyInfoKHR, DescriptorImageInfo,
             DescriptorType, Extent2D, Extent3D, Fence, ImageAspectFlags, ImageLayout,
             ImageMemoryBarrier2, ImageMemoryBarrier2Builder, ImageSubresourceLayers,
-            ImageSubresourceRange, PipelineBindPoint, PipelineStageFlags2, Rect2D,
+            ImageSubresourceRange, ImageView, PipelineBindPoint, PipelineStageFlags2, Rect2D,
             RenderingAttachmentInfo, RenderingInfo, SemaphoreSubmitInfo, SemaphoreSubmitInfoKHR,
             ShaderStageFlags, SubmitInfo2, Viewport, WriteDescriptorSet, QUEUE_FAMILY_FOREIGN_EXT,
         },
         Device,
     },
+    indexmap::IndexSet,
     isnt::std_1::collections::IsntHashMapExt,
     std::{
         cell::{Cell, RefCell},
@@ -48,23 +61,45 @@ use {
         mem, ptr,
         rc::Rc,
         slice,
+        time::Duration,
     },
-    uapi::OwnedFd,
+    uapi::{c, OwnedFd},
 };
 
 pub struct VulkanRenderer {
     pub(super) formats: Rc<AHashMap<u32, GfxFormat>>,
     pub(super) device: Rc<VulkanDevice>,
-    pub(super) fill_pipeline: Rc<VulkanPipeline>,
-    pub(super) tex_pipeline: Rc<VulkanPipeline>,
+    pub(super) fill_vert_shader: Rc<VulkanShader>,
+    pub(super) fill_frag_shader: Rc<VulkanShader>,
+    pub(super) gradient_vert_shader: Rc<VulkanShader>,
+    pub(super) gradient_frag_shader: Rc<VulkanShader>,
+    pub(super) tex_vert_shader: Rc<VulkanShader>,
+    pub(super) tex_frag_shader: Rc<VulkanShader>,
+    pub(super) tex_to_linear_frag_shader: Rc<VulkanShader>,
+    pub(super) tex_to_srgb_frag_shader: Rc<VulkanShader>,
+    pub(super) tex_descriptor_set_layout: Rc<VulkanDescriptorSetLayout>,
+    pub(super) pipelines: CopyHashMap<vk::Format, Rc<VulkanFormatPipelines>>,
+    pub(super) linear_pipelines: CloneCell<Option<Rc<VulkanFormatPipelines>>>,
+    pub(super) srgb_pipelines: CopyHashMap<vk::Format, Rc<VulkanPipeline>>,
+    pub(super) linear_blending: Cell<bool>,
+    pub(super) linear_images: CopyHashMap<(u32, u32), Rc<VulkanLinearImage>>,
     pub(super) command_pool: Rc<VulkanCommandPool>,
     pub(super) command_buffers: Stack<Rc<VulkanCommandBuffer>>,
     pub(super) wait_semaphores: Stack<Rc<VulkanSemaphore>>,
     pub(super) total_buffers: NumCell<usize>,
     pub(super) memory: RefCell<Memory>,
     pub(super) pending_frames: CopyHashMap<u64, Rc<PendingFrame>>,
+    pub(super) max_in_flight_frames: Cell<usize>,
     pub(super) allocator: Rc<VulkanAllocator>,
     pub(super) last_point: NumCell<u64>,
+    pub(super) timestamp_queries: Stack<Rc<VulkanTimestampQuery>>,
+    pub(super) last_gpu_time: Cell<Option<Duration>>,
+}
+
+pub(super) struct VulkanFormatPipelines {
+    pub(super) fill: Rc<VulkanPipeline>,
+    pub(super) gradient: Rc<VulkanPipeline>,
+    pub(super) tex: Rc<VulkanPipeline>,
 }
 
 #[derive(Default)]
@@ -73,12 +108,15 @@ pub(super) struct Memory {
     flush: Vec<Rc<VulkanImage>>,
     flush_staging: Vec<(Rc<VulkanImage>, VulkanStagingBuffer)>,
     textures: Vec<Rc<VulkanImage>>,
+    occluded: Vec<bool>,
+    acquire_syncs: Vec<Option<Rc<OwnedFd>>>,
     image_barriers: Vec<ImageMemoryBarrier2>,
     shm_barriers: Vec<BufferMemoryBarrier2>,
     wait_semaphores: Vec<Rc<VulkanSemaphore>>,
     wait_semaphore_infos: Vec<SemaphoreSubmitInfo>,
     release_fence: Option<Rc<VulkanFence>>,
     release_syncfile: Option<Rc<OwnedFd>>,
+    timestamp_query: Option<Rc<VulkanTimestampQuery>>,
 }
 
 pub(super) struct PendingFrame {
@@ -90,42 +128,49 @@ pub(super) struct PendingFrame {
     wait_semaphores: Cell<Vec<Rc<VulkanSemaphore>>>,
     waiter: Cell<Option<SpawnedFuture<()>>>,
     _release_fence: Option<Rc<VulkanFence>>,
+    release_syncfile: Option<Rc<OwnedFd>>,
+    _linear_image: Option<Rc<VulkanLinearImage>>,
+    timestamp_query: Cell<Option<Rc<VulkanTimestampQuery>>>,
+}
+
+struct LinearPass {
+    image: Rc<VulkanLinearImage>,
+    pipelines: Rc<VulkanFormatPipelines>,
+    srgb_pipeline: Rc<VulkanPipeline>,
 }
 
 impl VulkanDevice {
     pub fn create_renderer(self: &Rc<Self>) -> Result<Rc<VulkanRenderer>, VulkanError> {
-        let fill_pipeline = self.create_pipeline::<FillVertPushConstants, FillFragPushConstants>(
-            PipelineCreateInfo {
-                vert: self.create_shader(FILL_VERT)?,
-                frag: self.create_shader(FILL_FRAG)?,
-                alpha: true,
-                frag_descriptor_set_layout: None,
-            },
-        )?;
+        let fill_vert_shader = self.create_shader(FILL_VERT)?;
+        let fill_frag_shader = self.create_shader(FILL_FRAG)?;
+        let gradient_vert_shader = self.create_shader(GRADIENT_VERT)?;
+        let gradient_frag_shader = self.create_shader(GRADIENT_FRAG)?;
+        let tex_vert_shader = self.create_shader(TEX_VERT)?;
+        let tex_frag_shader = self.create_shader(TEX_FRAG)?;
+        let tex_to_linear_frag_shader = self.create_shader(TEX_TO_LINEAR_FRAG)?;
+        let tex_to_srgb_frag_shader = self.create_shader(TEX_TO_SRGB_FRAG)?;
         let sampler = self.create_sampler()?;
         let tex_descriptor_set_layout = self.create_descriptor_set_layout(&sampler)?;
-        let tex_pipeline =
-            self.create_pipeline::<TexVertPushConstants, ()>(PipelineCreateInfo {
-                vert: self.create_shader(TEX_VERT)?,
-                frag: self.create_shader(TEX_FRAG)?,
-                alpha: true,
-                frag_descriptor_set_layout: Some(tex_descriptor_set_layout.clone()),
-            })?;
         let command_pool = self.create_command_pool()?;
         let formats: AHashMap<u32, _> = self
             .formats
             .iter()
             .map(|(drm, vk)| {
+                let mut read_modifiers: IndexSet<_> = vk
+                    .modifiers
+                    .values()
+                    .filter(|m| m.texture_max_extents.is_some())
+                    .map(|m| m.modifier)
+                    .collect();
+                if !read_modifiers.is_empty() {
+                    // Buffers with implicit modifiers are probed during import.
+                    read_modifiers.insert(INVALID_MODIFIER);
+                }
                 (
                     *drm,
                     GfxFormat {
                         format: vk.format,
-                        read_modifiers: vk
-                            .modifiers
-                            .values()
-                            .filter(|m| m.texture_max_extents.is_some())
-                            .map(|m| m.modifier)
-                            .collect(),
+                        read_modifiers,
                         write_modifiers: vk
                             .modifiers
                             .values()
@@ -137,29 +182,106 @@ impl VulkanDevice {
             })
             .collect();
         let allocator = self.create_allocator()?;
-        Ok(Rc::new(VulkanRenderer {
+        let render = Rc::new(VulkanRenderer {
             formats: Rc::new(formats),
             device: self.clone(),
-            fill_pipeline,
-            tex_pipeline,
+            fill_vert_shader,
+            fill_frag_shader,
+            gradient_vert_shader,
+            gradient_frag_shader,
+            tex_vert_shader,
+            tex_frag_shader,
+            tex_to_linear_frag_shader,
+            tex_to_srgb_frag_shader,
+            tex_descriptor_set_layout,
+            pipelines: Default::default(),
+            linear_pipelines: Default::default(),
+            srgb_pipelines: Default::default(),
+            linear_blending: Cell::new(false),
+            linear_images: Default::default(),
             command_pool,
             command_buffers: Default::default(),
             wait_semaphores: Default::default(),
             total_buffers: Default::default(),
             memory: Default::default(),
             pending_frames: Default::default(),
+            max_in_flight_frames: Cell::new(3),
             allocator,
             last_point: Default::default(),
-        }))
+            timestamp_queries: Default::default(),
+            last_gpu_time: Default::default(),
+        });
+        render.get_or_create_pipelines(XRGB8888.vk_format)?;
+        Ok(render)
     }
 }
 
 impl VulkanRenderer {
+    fn get_or_create_pipelines(
+        &self,
+        format: vk::Format,
+    ) -> Result<Rc<VulkanFormatPipelines>, VulkanError> {
+        if let Some(pl) = self.pipelines.get(&format) {
+            return Ok(pl);
+        }
+        let pl = self.create_format_pipelines(format, &self.tex_frag_shader)?;
+        self.pipelines.set(format, pl.clone());
+        Ok(pl)
+    }
+
+    pub(super) fn create_format_pipelines(
+        &self,
+        format: vk::Format,
+        tex_frag_shader: &Rc<VulkanShader>,
+    ) -> Result<Rc<VulkanFormatPipelines>, VulkanError> {
+        let fill = self
+            .device
+            .create_pipeline::<FillVertPushConstants, FillFragPushConstants>(
+                PipelineCreateInfo {
+                    format,
+                    vert: self.fill_vert_shader.clone(),
+                    frag: self.fill_frag_shader.clone(),
+                    alpha: true,
+                    frag_descriptor_set_layout: None,
+                },
+            )?;
+        let gradient = self
+            .device
+            .create_pipeline::<GradientVertPushConstants, GradientFragPushConstants>(
+                PipelineCreateInfo {
+                    format,
+                    vert: self.gradient_vert_shader.clone(),
+                    frag: self.gradient_frag_shader.clone(),
+                    alpha: true,
+                    frag_descriptor_set_layout: None,
+                },
+            )?;
+        let tex = self
+            .device
+            .create_pipeline::<TexVertPushConstants, ()>(PipelineCreateInfo {
+                format,
+                vert: self.tex_vert_shader.clone(),
+                frag: tex_frag_shader.clone(),
+                alpha: true,
+                frag_descriptor_set_layout: Some(self.tex_descriptor_set_layout.clone()),
+            })?;
+        Ok(Rc::new(VulkanFormatPipelines {
+            fill,
+            gradient,
+            tex,
+        }))
+    }
+
     fn collect_memory(&self, opts: &[GfxApiOpt]) {
         let mut memory = self.memory.borrow_mut();
+        let memory = &mut *memory;
         memory.sample.clear();
         memory.flush.clear();
-        for cmd in opts {
+        find_occluded_opts(opts, &mut memory.occluded);
+        for (cmd, &occluded) in opts.iter().zip(memory.occluded.iter()) {
+            if occluded {
+                continue;
+            }
             if let GfxApiOpt::CopyTexture(c) = cmd {
                 let tex = c.tex.clone().into_vk(&self.device.device);
                 match &tex.ty {
@@ -171,6 +293,7 @@ impl VulkanRenderer {
                     }
                 }
                 memory.textures.push(tex);
+                memory.acquire_syncs.push(c.acquire_sync.clone());
             }
         }
     }
@@ -199,15 +322,29 @@ impl VulkanRenderer {
             let to_flush = shm.to_flush.borrow_mut();
             let to_flush = to_flush.as_ref().unwrap();
             staging.upload(|mem, size| unsafe {
-                let size = size.min(to_flush.len());
-                ptr::copy_nonoverlapping(to_flush.as_ptr(), mem, size);
+                match shm.conversion {
+                    Some(c) => {
+                        let dst = slice::from_raw_parts_mut(mem, size);
+                        let src_row_size = (img.width * shm.format.bpp) as usize;
+                        let src_rows = to_flush.chunks(shm.stride as usize);
+                        let dst_rows = dst.chunks_exact_mut(img.stride as usize);
+                        for (src, dst) in src_rows.zip(dst_rows) {
+                            let src = &src[..src_row_size.min(src.len())];
+                            (c.convert_row)(src, dst);
+                        }
+                    }
+                    None => {
+                        let size = size.min(to_flush.len());
+                        ptr::copy_nonoverlapping(to_flush.as_ptr(), mem, size);
+                    }
+                }
             })?;
             memory.flush_staging.push((img.clone(), staging));
         }
         Ok(())
     }
 
-    fn initial_barriers(&self, buf: CommandBuffer, fb: &VulkanImage) {
+    fn initial_barriers(&self, buf: CommandBuffer, fb: &VulkanImage, linear: Option<&LinearPass>) {
         let mut memory = self.memory.borrow_mut();
         let memory = &mut *memory;
         memory.image_barriers.clear();
@@ -226,6 +363,19 @@ impl VulkanRenderer {
             .dst_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
             .build();
         memory.image_barriers.push(fb_image_memory_barrier);
+        if let Some(linear) = linear {
+            // The contents of the previous frame are discarded but the image might still
+            // be sampled by a frame that is in flight.
+            let linear_image_memory_barrier = image_barrier()
+                .image(linear.image.image)
+                .old_layout(ImageLayout::UNDEFINED)
+                .new_layout(ImageLayout::GENERAL)
+                .src_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+                .dst_access_mask(AccessFlags2::COLOR_ATTACHMENT_WRITE)
+                .dst_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+                .build();
+            memory.image_barriers.push(linear_image_memory_barrier);
+        }
         for img in &memory.sample {
             let image_memory_barrier = image_barrier()
                 .src_queue_family_index(QUEUE_FAMILY_FOREIGN_EXT)
@@ -324,19 +474,24 @@ impl VulkanRenderer {
         }
     }
 
-    fn begin_rendering(&self, buf: CommandBuffer, fb: &VulkanImage, clear: Option<&Color>) {
+    fn begin_rendering(
+        &self,
+        buf: CommandBuffer,
+        view: ImageView,
+        width: u32,
+        height: u32,
+        clear: Option<[f32; 4]>,
+    ) {
         let rendering_attachment_info = {
             let mut rai = RenderingAttachmentInfo::builder()
-                .image_view(fb.render_view.unwrap_or(fb.texture_view))
+                .image_view(view)
                 .image_layout(ImageLayout::GENERAL)
                 .load_op(AttachmentLoadOp::LOAD)
                 .store_op(AttachmentStoreOp::STORE);
             if let Some(clear) = clear {
                 rai = rai
                     .clear_value(ClearValue {
-                        color: ClearColorValue {
-                            float32: clear.to_array_srgb(),
-                        },
+                        color: ClearColorValue { float32: clear },
                     })
                     .load_op(AttachmentLoadOp::CLEAR);
             }
@@ -345,10 +500,7 @@ impl VulkanRenderer {
         let rendering_info = RenderingInfo::builder()
             .render_area(Rect2D {
                 offset: Default::default(),
-                extent: Extent2D {
-                    width: fb.width,
-                    height: fb.height,
-                },
+                extent: Extent2D { width, height },
             })
             .layer_count(1)
             .color_attachments(slice::from_ref(&rendering_attachment_info));
@@ -357,21 +509,18 @@ impl VulkanRenderer {
         }
     }
 
-    fn set_viewport(&self, buf: CommandBuffer, fb: &VulkanImage) {
+    fn set_viewport(&self, buf: CommandBuffer, width: u32, height: u32) {
         let viewport = Viewport {
             x: 0.0,
             y: 0.0,
-            width: fb.width as _,
-            height: fb.height as _,
+            width: width as _,
+            height: height as _,
             min_depth: 0.0,
             max_depth: 1.0,
         };
         let scissor = Rect2D {
             offset: Default::default(),
-            extent: Extent2D {
-                width: fb.width,
-                height: fb.height,
-            },
+            extent: Extent2D { width, height },
         };
         unsafe {
             self.device
@@ -383,7 +532,13 @@ impl VulkanRenderer {
         }
     }
 
-    fn record_draws(&self, buf: CommandBuffer, opts: &[GfxApiOpt]) -> Result<(), VulkanError> {
+    fn record_draws(
+        &self,
+        buf: CommandBuffer,
+        pipelines: &VulkanFormatPipelines,
+        opts: &[GfxApiOpt],
+        linear: bool,
+    ) -> Result<(), VulkanError> {
         let dev = &self.device.device;
         let mut current_pipeline = None;
         let mut bind = |pipeline: &VulkanPipeline| {
@@ -394,30 +549,85 @@ impl VulkanRenderer {
                 }
             }
         };
-        for opt in opts {
+        let memory = self.memory.borrow();
+        for (opt, &occluded) in opts.iter().zip(memory.occluded.iter()) {
+            if occluded {
+                continue;
+            }
             match opt {
                 GfxApiOpt::Sync => {}
                 GfxApiOpt::FillRect(r) => {
-                    bind(&self.fill_pipeline);
-                    let vert = FillVertPushConstants {
-                        pos: r.rect.to_points(),
+                    bind(&pipelines.fill);
+                    let (vert, bounds) = match &r.aa {
+                        Some(aa) => {
+                            let vert = FillVertPushConstants {
+                                pos: aa.rect.to_points(),
+                                local_pos: aa.pos,
+                            };
+                            (vert, aa.bounds)
+                        }
+                        None => {
+                            let vert = FillVertPushConstants {
+                                pos: r.rect.to_points(),
+                                local_pos: [[0.0; 2]; 4],
+                            };
+                            (vert, [f32::MIN, f32::MIN, f32::MAX, f32::MAX])
+                        }
                     };
                     let frag = FillFragPushConstants {
-                        color: r.color.to_array_srgb(),
+                        color: match linear {
+                            true => r.color.to_array_linear(),
+                            false => r.color.to_array_srgb(),
+                        },
+                        bounds,
+                    };
+                    unsafe {
+                        dev.cmd_push_constants(
+                            buf,
+                            pipelines.fill.pipeline_layout,
+                            ShaderStageFlags::VERTEX,
+                            0,
+                            uapi::as_bytes(&vert),
+                        );
+                        dev.cmd_push_constants(
+                            buf,
+                            pipelines.fill.pipeline_layout,
+                            ShaderStageFlags::FRAGMENT,
+                            pipelines.fill.frag_push_offset,
+                            uapi::as_bytes(&frag),
+                        );
+                        dev.cmd_draw(buf, 4, 1, 0, 0);
+                    }
+                }
+                GfxApiOpt::FillGradient(r) => {
+                    bind(&pipelines.gradient);
+                    let vert = GradientVertPushConstants {
+                        pos: r.rect.to_points(),
+                        weights: r.direction.weights(),
+                    };
+                    let frag = match linear {
+                        true => GradientFragPushConstants {
+                            start: r.start.to_array_linear(),
+                            end: r.end.to_array_linear(),
+                        },
+                        false => GradientFragPushConstants {
+                            start: r.start.to_array_srgb(),
+                            end: r.end.to_array_srgb(),
+                        },
                     };
                     unsafe {
                         dev.cmd_push_constants(
                             buf,
-                            self.fill_pipeline.pipeline_layout,
+                            pipelines.gradient.pipeline_layout,
                             ShaderStageFlags::VERTEX,
                             0,
                             uapi::as_bytes(&vert),
                         );
                         dev.cmd_push_constants(
                             buf,
-                            self.fill_pipeline.pipeline_layout,
+                            pipelines.gradient.pipeline_layout,
                             ShaderStageFlags::FRAGMENT,
-                            self.fill_pipeline.frag_push_offset,
+                            pipelines.gradient.frag_push_offset,
                             uapi::as_bytes(&frag),
                         );
                         dev.cmd_draw(buf, 4, 1, 0, 0);
@@ -425,7 +635,7 @@ impl VulkanRenderer {
                 }
                 GfxApiOpt::CopyTexture(c) => {
                     let tex = c.tex.as_vk(&self.device.device);
-                    bind(&self.tex_pipeline);
+                    bind(&pipelines.tex);
                     let vert = TexVertPushConstants {
                         pos: c.target.to_points(),
                         tex_pos: c.source.to_points(),
@@ -441,13 +651,13 @@ impl VulkanRenderer {
                         self.device.push_descriptor.cmd_push_descriptor_set(
                             buf,
                             PipelineBindPoint::GRAPHICS,
-                            self.tex_pipeline.pipeline_layout,
+                            pipelines.tex.pipeline_layout,
                             0,
                             slice::from_ref(&write_descriptor_set),
                         );
                         dev.cmd_push_constants(
                             buf,
-                            self.tex_pipeline.pipeline_layout,
+                            pipelines.tex.pipeline_layout,
                             ShaderStageFlags::VERTEX,
                             0,
                             uapi::as_bytes(&vert),
@@ -466,6 +676,120 @@ impl VulkanRenderer {
         }
     }
 
+    fn record_linear_pass(
+        &self,
+        buf: CommandBuffer,
+        fb: &VulkanImage,
+        linear: &LinearPass,
+        opts: &[GfxApiOpt],
+        clear: &Color,
+    ) -> Result<(), VulkanError> {
+        let img = &linear.image;
+        self.begin_rendering(
+            buf,
+            img.view,
+            img.width,
+            img.height,
+            Some(clear.to_array_linear()),
+        );
+        self.set_viewport(buf, img.width, img.height);
+        self.record_draws(buf, &linear.pipelines, opts, true)?;
+        self.end_rendering(buf);
+        let barrier = image_barrier()
+            .image(img.image)
+            .old_layout(ImageLayout::GENERAL)
+            .new_layout(ImageLayout::GENERAL)
+            .src_access_mask(AccessFlags2::COLOR_ATTACHMENT_WRITE)
+            .src_stage_mask(PipelineStageFlags2::COLOR_ATTACHMENT_OUTPUT)
+            .dst_access_mask(AccessFlags2::SHADER_SAMPLED_READ)
+            .dst_stage_mask(PipelineStageFlags2::FRAGMENT_SHADER)
+            .build();
+        let dep_info =
+            DependencyInfoKHR::builder().image_memory_barriers(slice::from_ref(&barrier));
+        let dev = &self.device.device;
+        unsafe {
+            dev.cmd_pipeline_barrier2(buf, &dep_info);
+        }
+        self.begin_rendering(
+            buf,
+            fb.render_view.unwrap_or(fb.texture_view),
+            fb.width,
+            fb.height,
+            None,
+        );
+        self.set_viewport(buf, fb.width, fb.height);
+        let pipeline = &linear.srgb_pipeline;
+        let points = [[1.0, -1.0], [-1.0, -1.0], [1.0, 1.0], [-1.0, 1.0]];
+        let vert = TexVertPushConstants {
+            pos: points,
+            tex_pos: points.map(|[x, y]| [(x + 1.0) / 2.0, (y + 1.0) / 2.0]),
+        };
+        let image_info = DescriptorImageInfo::builder()
+            .image_view(img.view)
+            .image_layout(ImageLayout::GENERAL);
+        let write_descriptor_set = WriteDescriptorSet::builder()
+            .descriptor_type(DescriptorType::COMBINED_IMAGE_SAMPLER)
+            .image_info(slice::from_ref(&image_info))
+            .build();
+        unsafe {
+            dev.cmd_bind_pipeline(buf, PipelineBindPoint::GRAPHICS, pipeline.pipeline);
+            self.device.push_descriptor.cmd_push_descriptor_set(
+                buf,
+                PipelineBindPoint::GRAPHICS,
+                pipeline.pipeline_layout,
+                0,
+                slice::from_ref(&write_descriptor_set),
+            );
+            dev.cmd_push_constants(
+                buf,
+                pipeline.pipeline_layout,
+                ShaderStageFlags::VERTEX,
+                0,
+                uapi::as_bytes(&vert),
+            );
+            dev.cmd_draw(buf, 4, 1, 0, 0);
+        }
+        self.end_rendering(buf);
+        Ok(())
+    }
+
+    fn write_start_timestamp(&self, buf: CommandBuffer) -> Result<(), VulkanError> {
+        if self.device.timestamp_period.is_none() {
+            return Ok(());
+        }
+        let query = match self.timestamp_queries.pop() {
+            Some(q) => q,
+            _ => self.device.create_timestamp_query()?,
+        };
+        unsafe {
+            self.device
+                .device
+                .cmd_reset_query_pool(buf, query.pool, 0, 2);
+            self.device.device.cmd_write_timestamp2(
+                buf,
+                PipelineStageFlags2::TOP_OF_PIPE,
+                query.pool,
+                0,
+            );
+        }
+        self.memory.borrow_mut().timestamp_query = Some(query);
+        Ok(())
+    }
+
+    fn write_end_timestamp(&self, buf: CommandBuffer) {
+        let memory = self.memory.borrow();
+        if let Some(query) = &memory.timestamp_query {
+            unsafe {
+                self.device.device.cmd_write_timestamp2(
+                    buf,
+                    PipelineStageFlags2::BOTTOM_OF_PIPE,
+                    query.pool,
+                    1,
+                );
+            }
+        }
+    }
+
     fn final_barriers(&self, buf: CommandBuffer, fb: &VulkanImage) {
         let mut memory = self.memory.borrow_mut();
         let memory = &mut *memory;
@@ -516,6 +840,21 @@ impl VulkanRenderer {
         let mut memory = self.memory.borrow_mut();
         let memory = &mut *memory;
         memory.wait_semaphore_infos.clear();
+        let import_syncfile = |infos: &mut Vec<SemaphoreSubmitInfoKHR>,
+                               semaphores: &mut Vec<Rc<VulkanSemaphore>>,
+                               fd: OwnedFd|
+         -> Result<(), VulkanError> {
+            let semaphore = self.allocate_semaphore()?;
+            semaphore.import_syncfile(fd)?;
+            infos.push(
+                SemaphoreSubmitInfo::builder()
+                    .semaphore(semaphore.semaphore)
+                    .stage_mask(PipelineStageFlags2::TOP_OF_PIPE)
+                    .build(),
+            );
+            semaphores.push(semaphore);
+            Ok(())
+        };
         let import = |infos: &mut Vec<SemaphoreSubmitInfoKHR>,
                       semaphores: &mut Vec<Rc<VulkanSemaphore>>,
                       img: &VulkanImage,
@@ -525,20 +864,22 @@ impl VulkanRenderer {
                 for plane in &buf.template.dmabuf.planes {
                     let fd = dma_buf_export_sync_file(&plane.fd, flag)
                         .map_err(VulkanError::IoctlExportSyncFile)?;
-                    let semaphore = self.allocate_semaphore()?;
-                    semaphore.import_syncfile(fd)?;
-                    infos.push(
-                        SemaphoreSubmitInfo::builder()
-                            .semaphore(semaphore.semaphore)
-                            .stage_mask(PipelineStageFlags2::TOP_OF_PIPE)
-                            .build(),
-                    );
-                    semaphores.push(semaphore);
+                    import_syncfile(infos, semaphores, fd)?;
                 }
             }
             Ok(())
         };
-        for texture in &memory.textures {
+        for (texture, acquire_sync) in memory.textures.iter().zip(&memory.acquire_syncs) {
+            if let Some(acquire_sync) = acquire_sync {
+                let fd = uapi::fcntl_dupfd_cloexec(acquire_sync.raw(), 0)
+                    .map_err(|e| VulkanError::Dupfd(e.into()))?;
+                import_syncfile(
+                    &mut memory.wait_semaphore_infos,
+                    &mut memory.wait_semaphores,
+                    fd,
+                )?;
+                continue;
+            }
             import(
                 &mut memory.wait_semaphore_infos,
                 &mut memory.wait_semaphores,
@@ -624,8 +965,32 @@ impl VulkanRenderer {
         }
     }
 
-    fn create_pending_frame(self: &Rc<Self>, buf: Rc<VulkanCommandBuffer>) {
+    fn limit_in_flight_frames(&self) {
+        while self.pending_frames.len() >= self.max_in_flight_frames.get() {
+            let oldest = self
+                .pending_frames
+                .lock()
+                .values()
+                .min_by_key(|f| f.point)
+                .cloned();
+            let Some(frame) = oldest else {
+                return;
+            };
+            frame.wait_for_release();
+            frame.waiter.take();
+            frame.retire();
+        }
+    }
+
+    fn create_pending_frame(
+        self: &Rc<Self>,
+        buf: Rc<VulkanCommandBuffer>,
+        linear: Option<LinearPass>,
+    ) {
         let point = self.last_point.fetch_add(1) + 1;
+        if *VULKAN_VALIDATION {
+            buf.pending_point.set(Some(point));
+        }
         let mut memory = self.memory.borrow_mut();
         let frame = Rc::new(PendingFrame {
             point,
@@ -636,13 +1001,15 @@ impl VulkanRenderer {
             wait_semaphores: Cell::new(mem::take(&mut memory.wait_semaphores)),
             waiter: Cell::new(None),
             _release_fence: memory.release_fence.take(),
+            release_syncfile: memory.release_syncfile.clone(),
+            _linear_image: linear.map(|l| l.image),
+            timestamp_query: Cell::new(memory.timestamp_query.take()),
         });
         self.pending_frames.set(frame.point, frame.clone());
         let future = self.device.instance.eng.spawn(await_release(
             memory.release_syncfile.take(),
             self.device.instance.ring.clone(),
             frame.clone(),
-            self.clone(),
         ));
         frame.waiter.set(Some(future));
     }
@@ -821,16 +1188,22 @@ impl VulkanRenderer {
         opts: &[GfxApiOpt],
         clear: Option<&Color>,
     ) -> Result<(), VulkanError> {
+        if fb.width == 0 || fb.height == 0 {
+            return Ok(());
+        }
         let res = self.try_execute(fb, opts, clear);
         {
             let mut memory = self.memory.borrow_mut();
             memory.flush.clear();
             memory.textures.clear();
+            memory.occluded.clear();
+            memory.acquire_syncs.clear();
             memory.flush_staging.clear();
             memory.sample.clear();
             memory.wait_semaphores.clear();
             memory.release_fence.take();
             memory.release_syncfile.take();
+            memory.timestamp_query.take();
         }
         res
     }
@@ -843,6 +1216,11 @@ impl VulkanRenderer {
                 self.command_pool.allocate_buffer()?
             }
         };
+        if *VULKAN_VALIDATION {
+            if let Some(point) = buf.pending_point.get() {
+                panic!("Command buffer is still in use by pending frame {}", point);
+            }
+        }
         Ok(buf)
     }
 
@@ -860,24 +1238,45 @@ impl VulkanRenderer {
         opts: &[GfxApiOpt],
         clear: Option<&Color>,
     ) -> Result<(), VulkanError> {
+        let pipelines = self.get_or_create_pipelines(fb.format.vk_format)?;
+        let linear = match clear {
+            Some(_) if self.linear_blending.get() => Some(LinearPass {
+                image: self.get_or_create_linear_image(fb.width, fb.height)?,
+                pipelines: self.get_or_create_linear_pipelines()?,
+                srgb_pipeline: self.get_or_create_srgb_pipeline(fb.format.vk_format)?,
+            }),
+            _ => None,
+        };
+        self.limit_in_flight_frames();
         let buf = self.allocate_command_buffer()?;
         self.collect_memory(opts);
         self.begin_command_buffer(buf.buffer)?;
         self.write_shm_staging_buffers()?;
-        self.initial_barriers(buf.buffer, fb);
+        self.initial_barriers(buf.buffer, fb, linear.as_ref());
         self.copy_shm_to_image(buf.buffer);
         self.secondary_barriers(buf.buffer);
-        self.begin_rendering(buf.buffer, fb, clear);
-        self.set_viewport(buf.buffer, fb);
-        self.record_draws(buf.buffer, opts)?;
-        self.end_rendering(buf.buffer);
+        self.write_start_timestamp(buf.buffer)?;
+        match (&linear, clear) {
+            (Some(linear), Some(clear)) => {
+                self.record_linear_pass(buf.buffer, fb, linear, opts, clear)?;
+            }
+            _ => {
+                let view = fb.render_view.unwrap_or(fb.texture_view);
+                let clear = clear.map(|c| c.to_array_srgb());
+                self.begin_rendering(buf.buffer, view, fb.width, fb.height, clear);
+                self.set_viewport(buf.buffer, fb.width, fb.height);
+                self.record_draws(buf.buffer, &pipelines, opts, false)?;
+                self.end_rendering(buf.buffer);
+            }
+        }
+        self.write_end_timestamp(buf.buffer);
         self.final_barriers(buf.buffer, fb);
         self.end_command_buffer(buf.buffer)?;
         self.create_wait_semaphores(fb)?;
         self.submit(buf.buffer)?;
         self.import_release_semaphore(fb);
         self.store_layouts(fb);
-        self.create_pending_frame(buf);
+        self.create_pending_frame(buf, linear);
         Ok(())
     }
 
@@ -952,31 +1351,81 @@ fn image_barrier() -> ImageMemoryBarrier2Builder<'static> {
     )
 }
 
-async fn await_release(
-    syncfile: Option<Rc<OwnedFd>>,
-    ring: Rc<IoUring>,
-    frame: Rc<PendingFrame>,
-    renderer: Rc<VulkanRenderer>,
-) {
+async fn await_release(syncfile: Option<Rc<OwnedFd>>, ring: Rc<IoUring>, frame: Rc<PendingFrame>) {
     let mut is_released = false;
     if let Some(syncfile) = syncfile {
-        if let Err(e) = ring.readable(&syncfile).await {
-            log::error!(
-                "Could not wait for release semaphore to be signaled: {}",
-                ErrorFmt(e)
-            );
-        } else {
-            is_released = true;
+        let timeout = Time::now_unchecked() + Duration::from_millis(*RELEASE_FENCE_TIMEOUT_MS);
+        match ring.readable_with_timeout(&syncfile, timeout).await {
+            Ok(_) => is_released = true,
+            Err(IoUringError::OsError(OsError(c::ECANCELED))) => {
+                log::warn!(
+                    "Release semaphore was not signaled within {} ms",
+                    *RELEASE_FENCE_TIMEOUT_MS
+                );
+            }
+            Err(e) => {
+                log::error!(
+                    "Could not wait for release semaphore to be signaled: {}",
+                    ErrorFmt(e)
+                );
+            }
         }
     }
     if !is_released {
         frame.renderer.block();
     }
-    if let Some(buf) = frame.cmd.take() {
-        frame.renderer.command_buffers.push(buf);
+    frame.retire();
+}
+
+impl PendingFrame {
+    /// Synchronously waits until the GPU has finished this frame.
+    fn wait_for_release(&self) {
+        let mut is_released = false;
+        if let Some(syncfile) = &self.release_syncfile {
+            let mut fds = [c::pollfd {
+                fd: syncfile.raw(),
+                events: c::POLLIN,
+                revents: 0,
+            }];
+            match uapi::poll(&mut fds, *RELEASE_FENCE_TIMEOUT_MS as _) {
+                Ok(n) => is_released = n > 0,
+                Err(e) => {
+                    log::error!(
+                        "Could not wait for release fence to be signaled: {}",
+                        ErrorFmt(OsError::from(e))
+                    );
+                }
+            }
+        }
+        if !is_released {
+            self.renderer.block();
+        }
     }
-    for wait_semaphore in frame.wait_semaphores.take() {
-        frame.renderer.wait_semaphores.push(wait_semaphore);
+
+    fn retire(&self) {
+        let renderer = &self.renderer;
+        if let Some(buf) = self.cmd.take() {
+            if *VULKAN_VALIDATION {
+                assert_eq!(
+                    buf.pending_point.take(),
+                    Some(self.point),
+                    "Command buffer was not owned by the retired frame",
+                );
+            }
+            renderer.command_buffers.push(buf);
+        }
+        for wait_semaphore in self.wait_semaphores.take() {
+            renderer.wait_semaphores.push(wait_semaphore);
+        }
+        if let Some(query) = self.timestamp_query.take() {
+            if let Some(period) = renderer.device.timestamp_period {
+                match query.elapsed(period) {
+                    Ok(time) => renderer.last_gpu_time.set(Some(time)),
+                    Err(e) => log::error!("Could not read GPU timestamps: {}", ErrorFmt(e)),
+                }
+            }
+            renderer.timestamp_queries.push(query);
+        }
+        renderer.pending_frames.remove(&self.point);
     }
-    renderer.pending_frames.remove(&frame.point);
 }
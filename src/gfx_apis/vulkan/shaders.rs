@@ -7,8 +7,14 @@ use {
 
 pub const FILL_VERT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/fill.vert.spv"));
 pub const FILL_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/fill.frag.spv"));
+pub const GRADIENT_VERT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/gradient.vert.spv"));
+pub const GRADIENT_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/gradient.frag.spv"));
 pub const TEX_VERT: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/tex.vert.spv"));
 pub const TEX_FRAG: &[u8] = include_bytes!(concat!(env!("OUT_DIR"), "/tex.frag.spv"));
+pub const TEX_TO_LINEAR_FRAG: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex_to_linear.frag.spv"));
+pub const TEX_TO_SRGB_FRAG: &[u8] =
+    include_bytes!(concat!(env!("OUT_DIR"), "/tex_to_srgb.frag.spv"));
 
 pub struct VulkanShader {
     pub(super) device: Rc<VulkanDevice>,
@@ -19,6 +25,7 @@ pub struct VulkanShader {
 #[repr(C)]
 pub struct FillVertPushConstants {
     pub pos: [[f32; 2]; 4],
+    pub local_pos: [[f32; 2]; 4],
 }
 
 unsafe impl Packed for FillVertPushConstants {}
@@ -27,10 +34,29 @@ unsafe impl Packed for FillVertPushConstants {}
 #[repr(C)]
 pub struct FillFragPushConstants {
     pub color: [f32; 4],
+    pub bounds: [f32; 4],
 }
 
 unsafe impl Packed for FillFragPushConstants {}
 
+#[derive(Copy, Clone, Debug)]
+#[repr(C)]
+pub struct GradientVertPushConstants {
+    pub pos: [[f32; 2]; 4],
+    pub weights: [f32; 4],
+}
+
+unsafe impl Packed for GradientVertPushConstants {}
+
+#[derive(Copy, Clone)]
+#[repr(C)]
+pub struct GradientFragPushConstants {
+    pub start: [f32; 4],
+    pub end: [f32; 4],
+}
+
+unsafe impl Packed for GradientFragPushConstants {}
+
 #[derive(Copy, Clone, Debug)]
 #[repr(C)]
 pub struct TexVertPushConstants {
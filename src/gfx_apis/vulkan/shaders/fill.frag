@@ -1,11 +1,15 @@
 #version 450
 
 layout(push_constant, std430) uniform Data {
-	layout(offset = 32) vec4 color;
+	layout(offset = 64) vec4 color;
+	layout(offset = 80) vec4 bounds;
 } data;
 
+layout(location = 0) in vec2 local_pos;
 layout(location = 0) out vec4 out_color;
 
 void main() {
-	out_color = data.color;
+	// The fraction of the pixel that lies within the bounds.
+	vec2 coverage = clamp(min(local_pos + 0.5, data.bounds.zw) - max(local_pos - 0.5, data.bounds.xy), 0.0, 1.0);
+	out_color = data.color * coverage.x * coverage.y;
 }
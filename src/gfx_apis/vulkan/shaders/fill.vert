@@ -3,15 +3,18 @@
 
 layout(push_constant, std430) uniform Data {
 	layout(offset = 0) vec2 pos[4];
+	layout(offset = 32) vec2 local_pos[4];
 } data;
 
+layout(location = 0) out vec2 local_pos;
+
 void main() {
 	vec2 pos;
 	switch (gl_VertexIndex) {
-		case 0: pos = data.pos[0]; break;
-		case 1: pos = data.pos[1]; break;
-		case 2: pos = data.pos[2]; break;
-		case 3: pos = data.pos[3]; break;
+		case 0: pos = data.pos[0]; local_pos = data.local_pos[0]; break;
+		case 1: pos = data.pos[1]; local_pos = data.local_pos[1]; break;
+		case 2: pos = data.pos[2]; local_pos = data.local_pos[2]; break;
+		case 3: pos = data.pos[3]; local_pos = data.local_pos[3]; break;
 	}
 	gl_Position = vec4(pos, 0.0, 1.0);
 //	debugPrintfEXT("gl_Position = %v4f", gl_Position);
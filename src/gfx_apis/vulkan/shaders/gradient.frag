@@ -0,0 +1,13 @@
+#version 450
+
+layout(push_constant, std430) uniform Data {
+	layout(offset = 48) vec4 start;
+	layout(offset = 64) vec4 end;
+} data;
+
+layout(location = 0) in float weight;
+layout(location = 0) out vec4 out_color;
+
+void main() {
+	out_color = mix(data.start, data.end, weight);
+}
@@ -0,0 +1,19 @@
+#version 450
+
+layout(push_constant, std430) uniform Data {
+	layout(offset = 0) vec2 pos[4];
+	layout(offset = 32) vec4 weights;
+} data;
+
+layout(location = 0) out float weight;
+
+void main() {
+	vec2 pos;
+	switch (gl_VertexIndex) {
+		case 0: pos = data.pos[0]; weight = data.weights[0]; break;
+		case 1: pos = data.pos[1]; weight = data.weights[1]; break;
+		case 2: pos = data.pos[2]; weight = data.weights[2]; break;
+		case 3: pos = data.pos[3]; weight = data.weights[3]; break;
+	}
+	gl_Position = vec4(pos, 0.0, 1.0);
+}
@@ -0,0 +1,17 @@
+#version 450
+
+layout(set = 0, binding = 0) uniform sampler2D tex;
+layout(location = 0) in vec2 tex_pos;
+layout(location = 0) out vec4 out_color;
+
+vec3 to_linear(vec3 c) {
+	return mix(c / 12.92, pow((c + 0.055) / 1.055, vec3(2.4)), greaterThan(c, vec3(0.04045)));
+}
+
+void main() {
+	vec4 c = textureLod(tex, tex_pos, 0);
+	if (c.a > 0.0) {
+		c.rgb = to_linear(c.rgb / c.a) * c.a;
+	}
+	out_color = c;
+}
@@ -0,0 +1,17 @@
+#version 450
+
+layout(set = 0, binding = 0) uniform sampler2D tex;
+layout(location = 0) in vec2 tex_pos;
+layout(location = 0) out vec4 out_color;
+
+vec3 to_srgb(vec3 c) {
+	return mix(c * 12.92, 1.055 * pow(c, vec3(1.0 / 2.4)) - 0.055, greaterThan(c, vec3(0.0031308)));
+}
+
+void main() {
+	vec4 c = textureLod(tex, tex_pos, 0);
+	if (c.a > 0.0) {
+		c.rgb = to_srgb(clamp(c.rgb / c.a, 0.0, 1.0)) * c.a;
+	}
+	out_color = c;
+}
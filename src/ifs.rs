@@ -36,6 +36,8 @@ pub mod wp_content_type_v1;
 pub mod wp_cursor_shape_device_v1;
 pub mod wp_cursor_shape_manager_v1;
 pub mod wp_fractional_scale_manager_v1;
+pub mod wp_linux_drm_syncobj_manager_v1;
+pub mod wp_linux_drm_syncobj_timeline_v1;
 pub mod wp_presentation;
 pub mod wp_presentation_feedback;
 pub mod wp_single_pixel_buffer_manager_v1;
@@ -5,7 +5,6 @@ use {
         ifs::ext_idle_notification_v1::ExtIdleNotificationV1,
         leaks::Tracker,
         object::Object,
-        time::now_usec,
         utils::{
             buffd::{MsgParser, MsgParserError},
             errorfmt::ErrorFmt,
@@ -76,7 +75,7 @@ impl ExtIdleNotifierV1 {
 
 async fn run(n: Rc<ExtIdleNotificationV1>) {
     loop {
-        let now = now_usec();
+        let now = n.client.state.clock.now().map(|t| t.usec()).unwrap_or(0);
         let elapsed = now.saturating_sub(n.seat.last_input());
         if elapsed < n.duration_usec {
             let res = n
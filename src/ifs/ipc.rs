@@ -17,6 +17,7 @@ use {
     uapi::OwnedFd,
 };
 
+pub mod clipboard_cache;
 pub mod wl_data_device;
 pub mod wl_data_device_manager;
 pub mod wl_data_offer;
@@ -111,7 +112,9 @@ pub struct SourceData<T: IpcVtable> {
     offers: SmallMap<u64, Rc<T::Offer>, 1>,
     offer_client: Cell<ClientId>,
     mime_types: RefCell<AHashSet<String>>,
-    pub client: Rc<Client>,
+    /// The client that created the source. `None` if the source is owned by the
+    /// compositor.
+    client: Option<Rc<Client>>,
     state: NumCell<u32>,
     actions: Cell<Option<u32>>,
     role: Cell<Role>,
@@ -146,7 +149,7 @@ impl<T: IpcVtable> SourceData<T> {
             offers: Default::default(),
             offer_client: Cell::new(client.id),
             mime_types: Default::default(),
-            client: client.clone(),
+            client: Some(client.clone()),
             state: NumCell::new(0),
             actions: Cell::new(None),
             role: Cell::new(Role::Selection),
@@ -155,6 +158,38 @@ impl<T: IpcVtable> SourceData<T> {
         }
     }
 
+    /// Creates the data of a source that is owned by the compositor.
+    ///
+    /// Such sources are never registered as objects of a client.
+    fn new_compositor(mime_types: AHashSet<String>) -> Self {
+        Self {
+            seat: Default::default(),
+            offers: Default::default(),
+            offer_client: Cell::new(ClientId::from_raw(0)),
+            mime_types: RefCell::new(mime_types),
+            client: None,
+            state: NumCell::new(0),
+            actions: Cell::new(None),
+            role: Cell::new(Role::Selection),
+            shared: Default::default(),
+            is_xwm: false,
+        }
+    }
+
+    /// Returns the client that created the source.
+    ///
+    /// Must only be used for sources that were created by a client. Only those receive
+    /// requests and events.
+    pub fn owner(&self) -> &Rc<Client> {
+        self.client
+            .as_ref()
+            .expect("The source is owned by the compositor")
+    }
+
+    pub fn is_compositor_owned(&self) -> bool {
+        self.client.is_none()
+    }
+
     pub fn was_used(&self) -> bool {
         self.state.get().contains(SOURCE_STATE_USED)
     }
@@ -317,7 +352,7 @@ pub fn destroy_data_device<T: IpcVtable>(dd: &T::Device) {
 
 fn break_source_loops<T: IpcVtable>(src: &T::Source) {
     let data = T::get_source_data(src);
-    if data.offer_client.get() == data.client.id {
+    if data.client.as_ref().map(|c| c.id) == Some(data.offer_client.get()) {
         data.offers.take();
     }
     destroy_data_source::<T>(src);
@@ -0,0 +1,201 @@
+use {
+    crate::{
+        async_engine::SpawnedFuture,
+        ifs::ipc::wl_data_source::WlDataSource,
+        io_uring::IoUringError,
+        state::State,
+        time::Time,
+        utils::{
+            buf::Buf, copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell,
+            oserror::OsError,
+        },
+    },
+    ahash::AHashMap,
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    uapi::{c, OwnedFd},
+};
+
+/// The maximum number of bytes cached per selection, summed over all mime types.
+const MAX_CACHE_SIZE: usize = 1 << 20;
+/// The maximum number of mime types cached per selection.
+const MAX_MIME_TYPES: usize = 8;
+/// The time a selection has to stay unchanged before its contents are read.
+///
+/// Clients that replace the selection quickly, e.g. while the user selects text, do
+/// not have their selections read at all.
+const FILL_DELAY_MS: u64 = 500;
+
+fn is_cacheable(mime_type: &str) -> bool {
+    mime_type.starts_with("text/")
+        || matches!(
+            mime_type,
+            "UTF8_STRING" | "STRING" | "TEXT" | "COMPOUND_TEXT"
+        )
+}
+
+/// An in-memory copy of the textual contents of a selection.
+///
+/// Allows the selection to outlive the client that owns it.
+pub struct ClipboardCache {
+    state: Rc<State>,
+    entries: RefCell<AHashMap<String, Vec<u8>>>,
+    size: NumCell<usize>,
+    pending: NumCell<usize>,
+    failed: Cell<bool>,
+    transfer_ids: NumCell<u64>,
+    transfers: CopyHashMap<u64, SpawnedFuture<()>>,
+}
+
+impl ClipboardCache {
+    pub fn new(state: &Rc<State>) -> Rc<Self> {
+        Rc::new(Self {
+            state: state.clone(),
+            entries: Default::default(),
+            size: Default::default(),
+            pending: Default::default(),
+            failed: Cell::new(false),
+            transfer_ids: Default::default(),
+            transfers: Default::default(),
+        })
+    }
+
+    /// Reads the cacheable contents of the source once it has been the selection for
+    /// [`FILL_DELAY_MS`].
+    pub fn fill(self: &Rc<Self>, src: &Rc<WlDataSource>) {
+        let id = self.transfer_ids.fetch_add(1);
+        let slf = self.clone();
+        let src = Rc::downgrade(src);
+        let future = self.state.eng.spawn(async move {
+            if let Err(e) = slf.state.wheel.timeout(FILL_DELAY_MS).await {
+                log::error!(
+                    "Could not wait for the selection to settle: {}",
+                    ErrorFmt(e)
+                );
+                slf.failed.set(true);
+            } else if let Some(src) = src.upgrade() {
+                slf.start_reads(&src);
+            }
+            slf.transfers.remove(&id);
+        });
+        self.transfers.set(id, future);
+    }
+
+    fn start_reads(self: &Rc<Self>, src: &Rc<WlDataSource>) {
+        let mime_types: Vec<_> = src
+            .data
+            .mime_types
+            .borrow()
+            .iter()
+            .filter(|mt| is_cacheable(mt))
+            .take(MAX_MIME_TYPES)
+            .cloned()
+            .collect();
+        for mime_type in mime_types {
+            let (rx, tx) = match uapi::pipe2(c::O_CLOEXEC) {
+                Ok(p) => p,
+                Err(e) => {
+                    log::error!("Could not create pipe: {}", OsError::from(e));
+                    self.failed.set(true);
+                    return;
+                }
+            };
+            src.send_send(&mime_type, Rc::new(tx));
+            self.pending.fetch_add(1);
+            let id = self.transfer_ids.fetch_add(1);
+            let future = self
+                .state
+                .eng
+                .spawn(self.clone().read(id, mime_type, Rc::new(rx)));
+            self.transfers.set(id, future);
+        }
+    }
+
+    /// Returns whether all cacheable contents have been read successfully.
+    pub fn is_complete(&self) -> bool {
+        !self.failed.get() && self.pending.get() == 0 && !self.entries.borrow().is_empty()
+    }
+
+    pub fn mime_types(&self) -> Vec<String> {
+        self.entries.borrow().keys().cloned().collect()
+    }
+
+    /// Adds contents that have not been read from a source.
+    pub fn insert(&self, mime_type: &str, data: Vec<u8>) {
+        self.size.fetch_add(data.len());
+        self.entries
+            .borrow_mut()
+            .insert(mime_type.to_string(), data);
+    }
+
+    pub fn get(&self, mime_type: &str) -> Option<Vec<u8>> {
+        self.entries.borrow().get(mime_type).cloned()
+    }
+
+    pub fn send(self: &Rc<Self>, mime_type: &str, fd: Rc<OwnedFd>) {
+        let data = match self.entries.borrow().get(mime_type) {
+            Some(d) => Buf::from_slice(d),
+            _ => {
+                log::warn!("Mime type {} is not cached", mime_type);
+                return;
+            }
+        };
+        let id = self.transfer_ids.fetch_add(1);
+        let future = self.state.eng.spawn(self.clone().write(id, data, fd));
+        self.transfers.set(id, future);
+    }
+
+    pub fn clear(&self) {
+        self.transfers.clear();
+    }
+
+    async fn read(self: Rc<Self>, id: u64, mime_type: String, fd: Rc<OwnedFd>) {
+        let mut buf = Buf::new(4096);
+        let mut data = vec![];
+        loop {
+            match self.state.ring.read(&fd, buf.clone()).await {
+                Ok(0) => break,
+                Ok(n) => {
+                    if self.size.fetch_add(n) + n > MAX_CACHE_SIZE {
+                        log::warn!("Selection is too large to be cached");
+                        self.failed.set(true);
+                        break;
+                    }
+                    data.extend_from_slice(&buf[..n]);
+                }
+                Err(e) => {
+                    log::error!("Could not read from data source: {}", ErrorFmt(e));
+                    self.failed.set(true);
+                    break;
+                }
+            }
+        }
+        if !self.failed.get() {
+            self.entries.borrow_mut().insert(mime_type, data);
+        }
+        self.pending.fetch_sub(1);
+        self.transfers.remove(&id);
+    }
+
+    async fn write(self: Rc<Self>, id: u64, mut data: Buf, fd: Rc<OwnedFd>) {
+        let timeout = Time::in_ms(5000).unwrap();
+        let mut pos = 0;
+        while pos < data.len() {
+            let res = self.state.ring.write(&fd, data.slice(pos..), Some(timeout));
+            match res.await {
+                Ok(n) => pos += n,
+                Err(IoUringError::OsError(OsError(c::ECANCELED))) => {
+                    log::error!("Transfer timed out");
+                    break;
+                }
+                Err(e) => {
+                    log::error!("Could not write to data offer: {}", ErrorFmt(e));
+                    break;
+                }
+            }
+        }
+        self.transfers.remove(&id);
+    }
+}
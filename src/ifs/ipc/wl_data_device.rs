@@ -108,8 +108,8 @@ impl WlDataDevice {
                 self_id: self.id,
                 serial,
                 surface,
-                x,
-                y,
+                x: x * self.client.wire_scale,
+                y: y * self.client.wire_scale,
                 id: offer,
             })
         }
@@ -120,8 +120,8 @@ impl WlDataDevice {
             self.client.event(Motion {
                 self_id: self.id,
                 time: (time_usec / 1000) as _,
-                x,
-                y,
+                x: x * self.client.wire_scale,
+                y: y * self.client.wire_scale,
             })
         }
     }
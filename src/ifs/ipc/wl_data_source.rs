@@ -3,11 +3,13 @@ use {
         client::{Client, ClientError},
         ifs::{
             ipc::{
-                add_data_source_mime_type, break_source_loops, cancel_offers, destroy_data_source,
+                add_data_source_mime_type, break_source_loops, cancel_offers,
+                clipboard_cache::ClipboardCache,
+                destroy_data_source,
                 wl_data_device::ClipboardIpc,
-                wl_data_device_manager::{DND_ALL, DND_NONE},
+                wl_data_device_manager::{DND_ALL, DND_COPY, DND_MOVE, DND_NONE},
                 wl_data_offer::WlDataOffer,
-                SharedState, SourceData, OFFER_STATE_ACCEPTED, OFFER_STATE_DROPPED,
+                Role, SharedState, SourceData, OFFER_STATE_ACCEPTED, OFFER_STATE_DROPPED,
                 SOURCE_STATE_CANCELLED, SOURCE_STATE_DROPPED,
             },
             wl_seat::WlSeatGlobal,
@@ -15,6 +17,7 @@ use {
         },
         leaks::Tracker,
         object::Object,
+        state::State,
         utils::{
             bitflags::BitflagsExt,
             buffd::{MsgParser, MsgParserError},
@@ -24,6 +27,7 @@ use {
         wire::{wl_data_source::*, WlDataSourceId},
         xwayland::XWaylandEvent,
     },
+    jay_config::keyboard::mods::{CTRL, SHIFT},
     std::rc::Rc,
     thiserror::Error,
     uapi::OwnedFd,
@@ -40,6 +44,7 @@ pub struct WlDataSource {
     pub version: u32,
     pub tracker: Tracker<Self>,
     pub toplevel_drag: CloneCell<Option<Rc<XdgToplevelDragV1>>>,
+    pub cache: CloneCell<Option<Rc<ClipboardCache>>>,
 }
 
 impl WlDataSource {
@@ -50,9 +55,41 @@ impl WlDataSource {
             data: SourceData::new(client, is_xwm),
             version,
             toplevel_drag: Default::default(),
+            cache: Default::default(),
         }
     }
 
+    /// Creates a compositor-owned copy of the selection that serves the cached contents.
+    ///
+    /// Returns `None` if the contents have not been cached completely.
+    fn copy_to_compositor(&self) -> Option<(Rc<WlSeatGlobal>, Rc<Self>)> {
+        let cache = self.cache.get()?;
+        let seat = self.data.seat.get()?;
+        if !cache.is_complete() || self.data.role.get() != Role::Selection {
+            return None;
+        }
+        Some((seat, Self::new_compositor(cache, self.version)))
+    }
+
+    /// Creates a compositor-owned source that serves the given contents.
+    pub fn with_contents(state: &Rc<State>, mime_type: &str, data: Vec<u8>) -> Rc<Self> {
+        let cache = ClipboardCache::new(state);
+        cache.insert(mime_type, data);
+        Self::new_compositor(cache, 1)
+    }
+
+    fn new_compositor(cache: Rc<ClipboardCache>, version: u32) -> Rc<Self> {
+        let mime_types = cache.mime_types().into_iter().collect();
+        Rc::new(Self {
+            id: WlDataSourceId::NONE,
+            data: SourceData::new_compositor(mime_types),
+            version,
+            tracker: Default::default(),
+            toplevel_drag: Default::default(),
+            cache: CloneCell::new(Some(cache)),
+        })
+    }
+
     pub fn on_leave(&self) {
         if self
             .data
@@ -80,7 +117,10 @@ impl WlDataSource {
             }
         };
         let actions = server_actions & shared.receiver_actions.get();
-        let action = if actions.contains(shared.receiver_preferred_action.get()) {
+        let forced_action = self.forced_action();
+        let action = if forced_action != DND_NONE && actions.contains(forced_action) {
+            forced_action
+        } else if actions.contains(shared.receiver_preferred_action.get()) {
             shared.receiver_preferred_action.get()
         } else if actions != 0 {
             1 << actions.trailing_zeros()
@@ -93,7 +133,24 @@ impl WlDataSource {
                 // offer.client.flush();
             }
             self.send_action(action);
-            // self.data.client.flush();
+            // self.data.owner().flush();
+        }
+    }
+
+    /// Returns the action requested by the user via keyboard modifiers.
+    ///
+    /// Holding Ctrl selects copy, holding Shift selects move.
+    fn forced_action(&self) -> u32 {
+        let Some(seat) = self.data.seat.get() else {
+            return DND_NONE;
+        };
+        let mods = seat.modifiers();
+        if mods.0 & CTRL.0 != 0 {
+            DND_COPY
+        } else if mods.0 & SHIFT.0 != 0 {
+            DND_MOVE
+        } else {
+            DND_NONE
         }
     }
 
@@ -119,9 +176,15 @@ impl WlDataSource {
     }
 
     pub fn send_cancelled(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>) {
+        if let Some(cache) = self.cache.take() {
+            cache.clear();
+        }
+        if self.data.is_compositor_owned() {
+            return;
+        }
         if self.data.is_xwm {
             self.data
-                .client
+                .owner()
                 .state
                 .xwayland
                 .queue
@@ -131,14 +194,20 @@ impl WlDataSource {
             if let Some(drag) = self.toplevel_drag.take() {
                 drag.finish_drag(seat);
             }
-            self.data.client.event(Cancelled { self_id: self.id })
+            self.data.owner().event(Cancelled { self_id: self.id })
         }
     }
 
     pub fn send_send(self: &Rc<Self>, mime_type: &str, fd: Rc<OwnedFd>) {
+        if self.data.is_compositor_owned() {
+            if let Some(cache) = self.cache.get() {
+                cache.send(mime_type, fd);
+            }
+            return;
+        }
         if self.data.is_xwm {
             self.data
-                .client
+                .owner()
                 .state
                 .xwayland
                 .queue
@@ -148,7 +217,7 @@ impl WlDataSource {
                     fd,
                 ));
         } else {
-            self.data.client.event(Send {
+            self.data.owner().event(Send {
                 self_id: self.id,
                 mime_type,
                 fd,
@@ -157,8 +226,8 @@ impl WlDataSource {
     }
 
     pub fn send_target(&self, mime_type: Option<&str>) {
-        if !self.data.is_xwm {
-            self.data.client.event(Target {
+        if !self.data.is_xwm && !self.data.is_compositor_owned() {
+            self.data.owner().event(Target {
                 self_id: self.id,
                 mime_type,
             })
@@ -166,14 +235,14 @@ impl WlDataSource {
     }
 
     pub fn send_dnd_finished(&self) {
-        if !self.data.is_xwm {
-            self.data.client.event(DndFinished { self_id: self.id })
+        if !self.data.is_xwm && !self.data.is_compositor_owned() {
+            self.data.owner().event(DndFinished { self_id: self.id })
         }
     }
 
     pub fn send_action(&self, dnd_action: u32) {
-        if !self.data.is_xwm {
-            self.data.client.event(Action {
+        if !self.data.is_xwm && !self.data.is_compositor_owned() {
+            self.data.owner().event(Action {
                 self_id: self.id,
                 dnd_action,
             })
@@ -181,28 +250,31 @@ impl WlDataSource {
     }
 
     pub fn send_dnd_drop_performed(&self) {
-        if !self.data.is_xwm {
+        if !self.data.is_xwm && !self.data.is_compositor_owned() {
             self.data
-                .client
+                .owner()
                 .event(DndDropPerformed { self_id: self.id })
         }
     }
 
     fn offer(&self, parser: MsgParser<'_, '_>) -> Result<(), WlDataSourceError> {
-        let req: Offer = self.data.client.parse(self, parser)?;
+        let req: Offer = self.data.owner().parse(self, parser)?;
         add_data_source_mime_type::<ClipboardIpc>(self, req.mime_type);
         Ok(())
     }
 
     fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WlDataSourceError> {
-        let _req: Destroy = self.data.client.parse(self, parser)?;
+        let _req: Destroy = self.data.owner().parse(self, parser)?;
+        if let Some(cache) = self.cache.take() {
+            cache.clear();
+        }
         destroy_data_source::<ClipboardIpc>(self);
-        self.data.client.remove_obj(self)?;
+        self.data.owner().remove_obj(self)?;
         Ok(())
     }
 
     fn set_actions(&self, parser: MsgParser<'_, '_>) -> Result<(), WlDataSourceError> {
-        let req: SetActions = self.data.client.parse(self, parser)?;
+        let req: SetActions = self.data.owner().parse(self, parser)?;
         if self.data.actions.is_some() {
             return Err(WlDataSourceError::AlreadySet);
         }
@@ -224,8 +296,17 @@ object_base! {
 
 impl Object for WlDataSource {
     fn break_loops(&self) {
+        let copy = self.copy_to_compositor();
+        if let Some(cache) = self.cache.take() {
+            if copy.is_none() {
+                cache.clear();
+            }
+        }
         break_source_loops::<ClipboardIpc>(self);
         self.toplevel_drag.take();
+        if let Some((seat, src)) = copy {
+            seat.restore_selection(src);
+        }
     }
 }
 
@@ -34,20 +34,20 @@ impl ZwpPrimarySelectionSourceV1 {
     pub fn send_cancelled(self: &Rc<Self>) {
         if self.data.is_xwm {
             self.data
-                .client
+                .owner()
                 .state
                 .xwayland
                 .queue
                 .push(XWaylandEvent::PrimarySelectionCancelSource(self.clone()));
         } else {
-            self.data.client.event(Cancelled { self_id: self.id });
+            self.data.owner().event(Cancelled { self_id: self.id });
         }
     }
 
     pub fn send_send(self: &Rc<Self>, mime_type: &str, fd: Rc<OwnedFd>) {
         if self.data.is_xwm {
             self.data
-                .client
+                .owner()
                 .state
                 .xwayland
                 .queue
@@ -57,7 +57,7 @@ impl ZwpPrimarySelectionSourceV1 {
                     fd,
                 ));
         } else {
-            self.data.client.event(Send {
+            self.data.owner().event(Send {
                 self_id: self.id,
                 mime_type,
                 fd,
@@ -66,15 +66,15 @@ impl ZwpPrimarySelectionSourceV1 {
     }
 
     fn offer(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpPrimarySelectionSourceV1Error> {
-        let req: Offer = self.data.client.parse(self, parser)?;
+        let req: Offer = self.data.owner().parse(self, parser)?;
         add_data_source_mime_type::<PrimarySelectionIpc>(self, req.mime_type);
         Ok(())
     }
 
     fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpPrimarySelectionSourceV1Error> {
-        let _req: Destroy = self.data.client.parse(self, parser)?;
+        let _req: Destroy = self.data.owner().parse(self, parser)?;
         destroy_data_source::<PrimarySelectionIpc>(self);
-        self.data.client.remove_obj(self)?;
+        self.data.owner().remove_obj(self)?;
         Ok(())
     }
 }
@@ -51,6 +51,10 @@ impl WlBuffer {
         self.destroyed.get()
     }
 
+    pub fn dmabuf(&self) -> Option<&DmaBuf> {
+        self.dmabuf.as_ref()
+    }
+
     #[allow(clippy::too_many_arguments)]
     pub fn new_dmabuf(
         id: WlBufferId,
@@ -139,6 +139,22 @@ impl WlOutputGlobal {
         self.pos.get()
     }
 
+    /// Returns a human-readable description of the output.
+    pub fn description(&self) -> String {
+        let id = &self.output_id;
+        let mut description = String::new();
+        for part in [&id.manufacturer, &id.model, &id.serial_number] {
+            if !part.is_empty() {
+                description.push_str(part);
+                description.push(' ');
+            }
+        }
+        description.push('(');
+        description.push_str(&self.connector.name);
+        description.push(')');
+        description
+    }
+
     pub fn for_each_binding<F: FnMut(&Rc<WlOutput>)>(&self, client: ClientId, mut f: F) {
         let bindings = self.bindings.borrow_mut();
         if let Some(bindings) = bindings.get(&client) {
@@ -166,8 +182,12 @@ impl WlOutputGlobal {
             for binding in binding.values() {
                 binding.send_geometry();
                 binding.send_mode();
-                binding.send_scale();
-                binding.send_done();
+                if binding.version >= SEND_SCALE_SINCE {
+                    binding.send_scale();
+                }
+                if binding.version >= SEND_DONE_SINCE {
+                    binding.send_done();
+                }
                 let xdg = binding.xdg_outputs.lock();
                 for xdg in xdg.values() {
                     xdg.send_updates();
@@ -205,6 +225,7 @@ impl WlOutputGlobal {
         }
         if obj.version >= SEND_NAME_SINCE {
             obj.send_name();
+            obj.send_description();
         }
         if obj.version >= SEND_DONE_SINCE {
             obj.send_done();
@@ -323,7 +344,7 @@ pub const SEND_NAME_SINCE: u32 = 4;
 
 impl WlOutput {
     fn send_geometry(&self) {
-        let pos = self.global.pos.get();
+        let pos = self.global.pos.get().scale_int(self.client.wire_scale);
         let event = Geometry {
             self_id: self.id,
             x: pos.x1(),
@@ -365,6 +386,13 @@ impl WlOutput {
         });
     }
 
+    fn send_description(&self) {
+        self.client.event(Description {
+            self_id: self.id,
+            description: &self.global.description(),
+        });
+    }
+
     pub fn send_done(&self) {
         let event = Done { self_id: self.id };
         self.client.event(event);
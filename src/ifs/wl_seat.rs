@@ -4,7 +4,13 @@ mod pointer_owner;
 pub mod wl_keyboard;
 pub mod wl_pointer;
 pub mod wl_touch;
+pub mod zwp_keyboard_shortcuts_inhibit_manager_v1;
+pub mod zwp_keyboard_shortcuts_inhibitor_v1;
 pub mod zwp_pointer_constraints_v1;
+pub mod zwp_pointer_gesture_hold_v1;
+pub mod zwp_pointer_gesture_pinch_v1;
+pub mod zwp_pointer_gesture_swipe_v1;
+pub mod zwp_pointer_gestures_v1;
 pub mod zwp_relative_pointer_manager_v1;
 pub mod zwp_relative_pointer_v1;
 
@@ -12,6 +18,7 @@ pub use event_handling::NodeSeatState;
 use {
     crate::{
         async_engine::SpawnedFuture,
+        backend::InputDeviceCapability,
         client::{Client, ClientError, ClientId},
         cursor::{Cursor, KnownCursor},
         fixed::Fixed,
@@ -20,6 +27,7 @@ use {
             ext_idle_notification_v1::ExtIdleNotificationV1,
             ipc::{
                 self,
+                clipboard_cache::ClipboardCache,
                 wl_data_device::{ClipboardIpc, WlDataDevice},
                 wl_data_source::WlDataSource,
                 zwp_primary_selection_device_v1::{
@@ -35,16 +43,20 @@ use {
                 wl_pointer::WlPointer,
                 wl_touch::WlTouch,
                 zwp_pointer_constraints_v1::{SeatConstraint, SeatConstraintStatus},
+                zwp_pointer_gesture_hold_v1::ZwpPointerGestureHoldV1,
+                zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1,
+                zwp_pointer_gesture_swipe_v1::ZwpPointerGestureSwipeV1,
                 zwp_relative_pointer_v1::ZwpRelativePointerV1,
             },
             wl_surface::WlSurface,
+            wp_content_type_v1::ContentType,
             xdg_toplevel_drag_v1::XdgToplevelDragV1,
         },
         leaks::Tracker,
         object::Object,
         rect::Rect,
         state::State,
-        time::now_usec,
+        theme::Color,
         tree::{
             generic_node_visitor, ContainerNode, ContainerSplit, Direction, FloatNode, FoundNode,
             Node, OutputNode, ToplevelNode, WorkspaceNode,
@@ -62,12 +74,19 @@ use {
         },
         wire::{
             wl_seat::*, ExtIdleNotificationV1Id, WlDataDeviceId, WlKeyboardId, WlPointerId,
-            WlSeatId, ZwpPrimarySelectionDeviceV1Id, ZwpRelativePointerV1Id,
+            WlSeatId, WlTouchId, ZwpPointerGestureHoldV1Id, ZwpPointerGesturePinchV1Id,
+            ZwpPointerGestureSwipeV1Id, ZwpPrimarySelectionDeviceV1Id, ZwpRelativePointerV1Id,
         },
         xkbcommon::{XkbKeymap, XkbState},
     },
     ahash::{AHashMap, AHashSet},
-    jay_config::keyboard::mods::Modifiers,
+    jay_config::{
+        keyboard::{
+            mods::{Modifiers, LOGO},
+            syms::SYM_Escape,
+        },
+        WindowState,
+    },
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -81,14 +100,14 @@ use {
 };
 
 pub const POINTER: u32 = 1;
-const KEYBOARD: u32 = 2;
-#[allow(dead_code)]
+pub const KEYBOARD: u32 = 2;
 const TOUCH: u32 = 4;
 
 #[allow(dead_code)]
 const MISSING_CAPABILITY: u32 = 0;
 
 pub const BTN_LEFT: u32 = 0x110;
+pub const BTN_RIGHT: u32 = 0x111;
 
 pub const SEAT_NAME_SINCE: u32 = 2;
 
@@ -140,6 +159,7 @@ pub struct WlSeatGlobal {
     >,
     repeat_rate: Cell<(i32, i32)>,
     kb_map: CloneCell<Rc<XkbKeymap>>,
+    kb_map_stack: RefCell<Vec<Rc<XkbKeymap>>>,
     kb_state: RefCell<XkbState>,
     cursor: CloneCell<Option<Rc<dyn Cursor>>>,
     tree_changed: Rc<AsyncEvent>,
@@ -151,8 +171,12 @@ pub struct WlSeatGlobal {
     kb_owner: KbOwnerHolder,
     dropped_dnd: RefCell<Option<DroppedDnd>>,
     shortcuts: CopyHashMap<(u32, u32), Modifiers>,
+    shortcut_inhibit_break: Cell<(u32, u32)>,
+    pub move_pointer_on_output_focus: Cell<bool>,
+    pub float_modifier: Cell<Modifiers>,
     queue_link: Cell<Option<LinkedNode<Rc<Self>>>>,
     tree_changed_handler: Cell<Option<SpawnedFuture<()>>>,
+    selection_restore: Cell<Option<SpawnedFuture<()>>>,
     output: CloneCell<Rc<OutputNode>>,
     desired_known_cursor: Cell<Option<KnownCursor>>,
     changes: NumCell<u32>,
@@ -161,6 +185,16 @@ pub struct WlSeatGlobal {
     constraint: CloneCell<Option<Rc<SeatConstraint>>>,
     idle_notifications: CopyHashMap<(ClientId, ExtIdleNotificationV1Id), Rc<ExtIdleNotificationV1>>,
     last_input_usec: Cell<u64>,
+    gesture_surface: CloneCell<Option<Rc<WlSurface>>>,
+    touch_points: RefCell<AHashMap<i32, TouchPoint>>,
+    touch_frame_clients: RefCell<AHashSet<ClientId>>,
+    capabilities: Cell<u32>,
+}
+
+struct TouchPoint {
+    surface: Rc<WlSurface>,
+    x_origin: i32,
+    y_origin: i32,
 }
 
 const CHANGE_CURSOR_MOVED: u32 = 1 << 0;
@@ -196,6 +230,7 @@ impl WlSeatGlobal {
             primary_selection_devices: RefCell::new(Default::default()),
             repeat_rate: Cell::new((25, 250)),
             kb_map: CloneCell::new(state.default_keymap.clone()),
+            kb_map_stack: Default::default(),
             kb_state: RefCell::new(state.default_keymap.state().unwrap()),
             cursor: Default::default(),
             tree_changed: Default::default(),
@@ -207,8 +242,12 @@ impl WlSeatGlobal {
             kb_owner: Default::default(),
             dropped_dnd: RefCell::new(None),
             shortcuts: Default::default(),
+            shortcut_inhibit_break: Cell::new((LOGO.0, SYM_Escape.0)),
+            move_pointer_on_output_focus: Cell::new(false),
+            float_modifier: Cell::new(Modifiers(0)),
             queue_link: Cell::new(None),
             tree_changed_handler: Cell::new(None),
+            selection_restore: Cell::new(None),
             output: CloneCell::new(state.dummy_output.get().unwrap()),
             desired_known_cursor: Cell::new(None),
             changes: NumCell::new(CHANGE_CURSOR_MOVED | CHANGE_TREE),
@@ -216,7 +255,11 @@ impl WlSeatGlobal {
             hardware_cursor: Cell::new(state.globals.seats.len() == 0),
             constraint: Default::default(),
             idle_notifications: Default::default(),
-            last_input_usec: Cell::new(now_usec()),
+            last_input_usec: Cell::new(state.clock.now().map(|t| t.usec()).unwrap_or(0)),
+            gesture_surface: Default::default(),
+            touch_points: Default::default(),
+            touch_frame_clients: Default::default(),
+            capabilities: Cell::new(0),
         });
         state.add_cursor_size(DEFAULT_CURSOR_SIZE);
         let seat = slf.clone();
@@ -375,38 +418,7 @@ impl WlSeatGlobal {
             Some(tl) => tl,
             _ => return,
         };
-        if tl.tl_data().is_fullscreen.get() {
-            return;
-        }
-        let old_ws = match tl.tl_data().workspace.get() {
-            Some(ws) => ws,
-            _ => return,
-        };
-        if old_ws.id == ws.id {
-            return;
-        }
-        let cn = match tl.tl_data().parent.get() {
-            Some(cn) => cn,
-            _ => return,
-        };
-        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
-        cn.cnode_remove_child2(tl.tl_as_node(), true);
-        if !ws.visible.get() {
-            for focus in kb_foci {
-                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
-            }
-        }
-        if tl.tl_data().is_floating.get() {
-            self.state.map_floating(
-                tl.clone(),
-                tl.tl_data().float_width.get(),
-                tl.tl_data().float_height.get(),
-                ws,
-                None,
-            );
-        } else {
-            self.state.map_tiled_on(tl, ws);
-        }
+        self.state.move_toplevel_to_workspace(tl, ws);
     }
 
     pub fn mark_last_active(self: &Rc<Self>) {
@@ -468,6 +480,22 @@ impl WlSeatGlobal {
         false
     }
 
+    pub fn get_focused_surface(&self) -> Option<Rc<WlSurface>> {
+        self.keyboard_node.get().node_into_surface()
+    }
+
+    pub fn get_focused_toplevel(&self) -> Option<Rc<dyn ToplevelNode>> {
+        self.keyboard_node.get().node_toplevel()
+    }
+
+    pub fn get_content_type(&self) -> Option<ContentType> {
+        self.keyboard_node
+            .get()
+            .node_toplevel()?
+            .tl_scanout_surface()?
+            .content_type()
+    }
+
     pub fn set_keymap(&self, keymap: &Rc<XkbKeymap>) {
         let state = match keymap.state() {
             Ok(s) => s,
@@ -496,6 +524,20 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn push_keymap(&self, keymap: &Rc<XkbKeymap>) {
+        self.release_pressed_keys();
+        self.kb_map_stack.borrow_mut().push(self.kb_map.get());
+        self.set_keymap(keymap);
+    }
+
+    pub fn pop_keymap(&self) {
+        let Some(keymap) = self.kb_map_stack.borrow_mut().pop() else {
+            return;
+        };
+        self.release_pressed_keys();
+        self.set_keymap(&keymap);
+    }
+
     pub fn prepare_for_lock(self: &Rc<Self>) {
         self.pointer_owner.revert_to_default(self);
         self.kb_owner.ungrab(self);
@@ -505,18 +547,29 @@ impl WlSeatGlobal {
         self.pos.set((Fixed::from_int(x), Fixed::from_int(y)));
         self.update_hardware_cursor_position();
         self.trigger_tree_changed();
-        let output = 'set_output: {
-            let outputs = self.state.outputs.lock();
-            for output in outputs.values() {
-                if output.node.global.pos.get().contains(x, y) {
-                    break 'set_output output.node.clone();
-                }
-            }
-            self.state.dummy_output.get().unwrap()
-        };
+        let output = self
+            .state
+            .output_at(x, y)
+            .unwrap_or_else(|| self.state.dummy_output.get().unwrap());
         self.set_output(&output);
     }
 
+    pub fn pointer_focus(self: &Rc<Self>) -> Option<Rc<dyn Node>> {
+        self.pointer_owner.gesture_node(self)
+    }
+
+    pub fn center_pointer_on_focus(&self) {
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        let rect = tl.node_absolute_position();
+        if rect.is_empty() {
+            return;
+        }
+        self.set_position(rect.x1() + rect.width() / 2, rect.y1() + rect.height() / 2);
+    }
+
     fn set_output(&self, output: &Rc<OutputNode>) {
         self.output.set(output.clone());
         if let Some(cursor) = self.cursor.get() {
@@ -648,10 +701,139 @@ impl WlSeatGlobal {
         }
     }
 
+    pub fn focus_output(self: &Rc<Self>, output: &Rc<OutputNode>) {
+        self.focus_output_workspace(output, Direction::Unspecified);
+    }
+
+    fn focus_output_workspace(self: &Rc<Self>, output: &Rc<OutputNode>, direction: Direction) {
+        let old = self.keyboard_node.get();
+        let ws = output.ensure_workspace();
+        ws.clone().node_do_focus(self, direction);
+        if self.keyboard_node.get().node_id() == old.node_id() {
+            self.focus_node(ws);
+        }
+        if !self.move_pointer_on_output_focus.get() {
+            return;
+        }
+        let focus_ws = self
+            .keyboard_node
+            .get()
+            .node_toplevel()
+            .and_then(|tl| tl.tl_data().workspace.get());
+        let focus_on_output = match focus_ws {
+            Some(ws) => ws.output.get().id == output.id,
+            _ => false,
+        };
+        if focus_on_output {
+            self.center_pointer_on_focus();
+        } else {
+            let rect = output.global.pos.get();
+            self.set_position(rect.x1() + rect.width() / 2, rect.y1() + rect.height() / 2);
+        }
+    }
+
+    pub fn set_window_border_color(&self, color: Option<Color>) {
+        if let Some(tl) = self.keyboard_node.get().node_toplevel() {
+            tl.tl_data().set_border_color(color);
+        }
+    }
+
+    pub fn cycle_window_state(self: &Rc<Self>) {
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        let data = tl.tl_data();
+        let current = if data.is_fullscreen.get() {
+            WindowState::Fullscreen
+        } else if data.is_floating.get() {
+            WindowState::Floating
+        } else {
+            WindowState::Tiled
+        };
+        let next = {
+            let cycle = self.state.window_state_cycle.borrow();
+            let next = match cycle.iter().position(|s| *s == current) {
+                Some(idx) => cycle.get((idx + 1) % cycle.len()),
+                _ => cycle.first(),
+            };
+            match next {
+                Some(next) => *next,
+                _ => return,
+            }
+        };
+        if next == current {
+            return;
+        }
+        if current == WindowState::Fullscreen {
+            tl.clone().tl_set_fullscreen(false);
+        }
+        match next {
+            WindowState::Tiled => self.set_tl_floating(tl, false),
+            WindowState::Floating => self.set_tl_floating(tl, true),
+            WindowState::Fullscreen => tl.tl_set_fullscreen(true),
+        }
+    }
+
+    pub fn set_sticky(self: &Rc<Self>, sticky: bool) {
+        let tl = match self.keyboard_node.get().node_toplevel() {
+            Some(tl) => tl,
+            _ => return,
+        };
+        if sticky {
+            self.set_tl_floating(tl.clone(), true);
+        }
+        let data = tl.tl_data();
+        if !data.is_floating.get() {
+            return;
+        }
+        let float = match data
+            .parent
+            .get()
+            .and_then(|p| p.cnode_into_node().node_into_float())
+        {
+            Some(f) => f,
+            _ => return,
+        };
+        float.set_sticky(sticky);
+    }
+
     pub fn get_rate(&self) -> (i32, i32) {
         self.repeat_rate.get()
     }
 
+    /// Recomputes the capabilities from the input devices attached to this seat.
+    ///
+    /// If they have changed, the new capabilities are sent to all bound clients.
+    pub fn update_capabilities(&self) {
+        let mut capabilities = 0;
+        for dev in self.state.input_device_handlers.borrow().values() {
+            let attached = dev.data.seat.get().map(|s| s.id) == Some(self.id);
+            if !attached {
+                continue;
+            }
+            let dev = &dev.data.device;
+            if dev.has_capability(InputDeviceCapability::Pointer) {
+                capabilities |= POINTER;
+            }
+            if dev.has_capability(InputDeviceCapability::Keyboard) {
+                capabilities |= KEYBOARD;
+            }
+            if dev.has_capability(InputDeviceCapability::Touch) {
+                capabilities |= TOUCH;
+            }
+        }
+        if self.capabilities.replace(capabilities) == capabilities {
+            return;
+        }
+        let bindings = self.bindings.borrow();
+        for client in bindings.values() {
+            for seat in client.values() {
+                seat.send_capabilities();
+            }
+        }
+    }
+
     pub fn set_rate(&self, rate: i32, delay: i32) {
         self.repeat_rate.set((rate, delay));
         let bindings = self.bindings.borrow_mut();
@@ -675,17 +857,54 @@ impl WlSeatGlobal {
     }
 
     pub fn move_focus(self: &Rc<Self>, direction: Direction) {
-        let tl = match self.keyboard_node.get().node_toplevel() {
-            Some(tl) => tl,
-            _ => return,
+        let kb_node = self.keyboard_node.get();
+        if let Some(tl) = kb_node.clone().node_toplevel() {
+            if direction == Direction::Down && tl.node_is_container() {
+                tl.node_do_focus(self, direction);
+            } else if let Some(p) = tl.tl_data().parent.get() {
+                if let Some(c) = p.node_into_container() {
+                    c.move_focus_from_child(self, tl.deref(), direction);
+                }
+            }
+        }
+        if self.keyboard_node.get().node_id() == kb_node.node_id() {
+            self.move_focus_to_output(kb_node, direction);
+        }
+    }
+
+    fn move_focus_to_output(self: &Rc<Self>, kb_node: Rc<dyn Node>, direction: Direction) {
+        let output = match kb_node.clone().node_toplevel() {
+            Some(tl) => tl.tl_data().workspace.get(),
+            _ => kb_node.clone().node_into_workspace(),
         };
-        if direction == Direction::Down && tl.node_is_container() {
-            tl.node_do_focus(self, direction);
-        } else if let Some(p) = tl.tl_data().parent.get() {
-            if let Some(c) = p.node_into_container() {
-                c.move_focus_from_child(self, tl.deref(), direction);
+        let output = match output {
+            Some(ws) => ws.output.get(),
+            _ => self.get_output(),
+        };
+        let rect = output.global.pos.get();
+        let cx = rect.x1() + rect.width() / 2;
+        let cy = rect.y1() + rect.height() / 2;
+        let mut best = None;
+        let mut best_dist = (i32::MAX, i32::MAX);
+        for other in self.state.root.outputs.lock().values() {
+            let orect = other.global.pos.get();
+            let ox = orect.x1() + orect.width() / 2;
+            let oy = orect.y1() + orect.height() / 2;
+            let dist = match direction {
+                Direction::Left if orect.x2() <= rect.x1() => (cx - ox, (cy - oy).abs()),
+                Direction::Right if orect.x1() >= rect.x2() => (ox - cx, (cy - oy).abs()),
+                Direction::Up if orect.y2() <= rect.y1() => (cy - oy, (cx - ox).abs()),
+                Direction::Down if orect.y1() >= rect.y2() => (oy - cy, (cx - ox).abs()),
+                _ => continue,
+            };
+            if dist < best_dist {
+                best_dist = dist;
+                best = Some(other.clone());
             }
         }
+        if let Some(target) = best {
+            self.focus_output_workspace(&target, direction);
+        }
     }
 
     pub fn move_focused(self: &Rc<Self>, direction: Direction) {
@@ -745,6 +964,21 @@ impl WlSeatGlobal {
         self.pointer_owner.cancel_dnd(self);
     }
 
+    /// Sets the selection to a compositor-owned source that serves the given contents.
+    pub fn set_selection_contents(
+        self: &Rc<Self>,
+        mime_type: &str,
+        data: Vec<u8>,
+    ) -> Result<(), WlSeatError> {
+        let src = WlDataSource::with_contents(&self.state, mime_type, data);
+        self.set_selection(Some(src), None)
+    }
+
+    /// Returns the contents of the selection if the compositor has a copy of them.
+    pub fn selection_contents(&self, mime_type: &str) -> Option<Vec<u8>> {
+        self.selection.get()?.cache.get()?.get(mime_type)
+    }
+
     pub fn unset_selection(self: &Rc<Self>) {
         let _ = self.set_selection(None, None);
     }
@@ -762,7 +996,45 @@ impl WlSeatGlobal {
                 return Err(WlSeatError::OfferHasDrag);
             }
         }
-        self.set_selection_::<ClipboardIpc>(&self.selection, selection)
+        self.set_selection_::<ClipboardIpc>(&self.selection, selection.clone())?;
+        if let Some(selection) = selection {
+            if self.state.persistent_clipboard.get()
+                && !selection.data.is_xwm
+                && selection.cache.is_none()
+            {
+                let cache = ClipboardCache::new(&self.state);
+                selection.cache.set(Some(cache.clone()));
+                cache.fill(&selection);
+            }
+        }
+        Ok(())
+    }
+
+    /// Installs a compositor-owned copy of a selection whose client has been destroyed.
+    ///
+    /// This happens in a later iteration of the event loop because the selection is
+    /// offered to the focused client, which might be the client that is being
+    /// destroyed. The copy is discarded if another selection has been set in the
+    /// meantime.
+    pub fn restore_selection(self: &Rc<Self>, src: Rc<WlDataSource>) {
+        let slf = Rc::downgrade(self);
+        let eng = self.state.eng.clone();
+        let future = self.state.eng.spawn(async move {
+            eng.yield_now().await;
+            let Some(slf) = slf.upgrade() else {
+                return;
+            };
+            if slf.selection.is_none() {
+                match slf.set_selection(Some(src.clone()), None) {
+                    Ok(()) => return,
+                    Err(e) => log::error!("Could not restore the selection: {}", ErrorFmt(e)),
+                }
+            }
+            if let Some(cache) = src.cache.take() {
+                cache.clear();
+            }
+        });
+        self.selection_restore.set(Some(future));
     }
 
     pub fn may_modify_selection(&self, client: &Rc<Client>, serial: u32) -> bool {
@@ -880,8 +1152,13 @@ impl WlSeatGlobal {
         self.pointer_owner.dnd_icon()
     }
 
+    pub fn modifiers(&self) -> Modifiers {
+        Modifiers(self.kb_state.borrow().mods().mods_effective)
+    }
+
     pub fn remove_dnd_icon(&self) {
         self.pointer_owner.remove_dnd_icon();
+        self.state.damage();
     }
 
     pub fn get_position(&self) -> (Fixed, Fixed) {
@@ -913,8 +1190,12 @@ impl WlSeatGlobal {
         *self.dropped_dnd.borrow_mut() = None;
         self.queue_link.set(None);
         self.tree_changed_handler.set(None);
+        self.selection_restore.take();
         self.output.set(self.state.dummy_output.get().unwrap());
         self.constraint.take();
+        self.gesture_surface.take();
+        self.touch_points.borrow_mut().clear();
+        self.touch_frame_clients.borrow_mut().clear();
     }
 
     pub fn id(&self) -> SeatId {
@@ -937,6 +1218,10 @@ impl WlSeatGlobal {
             client: client.clone(),
             pointers: Default::default(),
             relative_pointers: Default::default(),
+            swipe_gestures: Default::default(),
+            pinch_gestures: Default::default(),
+            hold_gestures: Default::default(),
+            touches: Default::default(),
             keyboards: Default::default(),
             version,
             tracker: Default::default(),
@@ -987,6 +1272,7 @@ impl Global for WlSeatGlobal {
         self.bindings.borrow_mut().clear();
         self.queue_link.take();
         self.tree_changed_handler.take();
+        self.selection_restore.take();
     }
 }
 
@@ -998,6 +1284,10 @@ pub struct WlSeat {
     pub client: Rc<Client>,
     pointers: CopyHashMap<WlPointerId, Rc<WlPointer>>,
     relative_pointers: CopyHashMap<ZwpRelativePointerV1Id, Rc<ZwpRelativePointerV1>>,
+    swipe_gestures: CopyHashMap<ZwpPointerGestureSwipeV1Id, Rc<ZwpPointerGestureSwipeV1>>,
+    pinch_gestures: CopyHashMap<ZwpPointerGesturePinchV1Id, Rc<ZwpPointerGesturePinchV1>>,
+    hold_gestures: CopyHashMap<ZwpPointerGestureHoldV1Id, Rc<ZwpPointerGestureHoldV1>>,
+    touches: CopyHashMap<WlTouchId, Rc<WlTouch>>,
     keyboards: CopyHashMap<WlKeyboardId, Rc<WlKeyboard>>,
     version: u32,
     tracker: Tracker<Self>,
@@ -1009,7 +1299,7 @@ impl WlSeat {
     fn send_capabilities(self: &Rc<Self>) {
         self.client.event(Capabilities {
             self_id: self.id,
-            capabilities: POINTER | KEYBOARD,
+            capabilities: self.global.capabilities.get(),
         })
     }
 
@@ -1078,6 +1368,7 @@ impl WlSeat {
         let p = Rc::new(WlTouch::new(req.id, self));
         track!(self.client, p);
         self.client.add_client_obj(&p)?;
+        self.touches.set(req.id, p);
         Ok(())
     }
 
@@ -1119,6 +1410,10 @@ impl Object for WlSeat {
         }
         self.pointers.clear();
         self.relative_pointers.clear();
+        self.swipe_gestures.clear();
+        self.pinch_gestures.clear();
+        self.hold_gestures.clear();
+        self.touches.clear();
         self.keyboards.clear();
     }
 }
@@ -19,25 +19,30 @@ use {
                     AXIS_STOP_SINCE_VERSION, AXIS_VALUE120_SINCE_VERSION, IDENTICAL, INVERTED,
                     POINTER_FRAME_SINCE_VERSION, WHEEL_TILT, WHEEL_TILT_SINCE_VERSION,
                 },
+                wl_touch::WlTouch,
                 zwp_pointer_constraints_v1::{ConstraintType, SeatConstraintStatus},
                 zwp_relative_pointer_v1::ZwpRelativePointerV1,
-                Dnd, SeatId, WlSeat, WlSeatGlobal, CHANGE_CURSOR_MOVED,
+                Dnd, SeatId, TouchPoint, WlSeat, WlSeatGlobal, CHANGE_CURSOR_MOVED,
             },
             wl_surface::{xdg_surface::xdg_popup::XdgPopup, WlSurface},
         },
         state::DeviceHandlerData,
-        tree::{Direction, FloatNode, Node, ToplevelNode},
-        utils::{bitflags::BitflagsExt, clonecell::CloneCell, smallmap::SmallMap},
+        tree::{Direction, FloatNode, FoundNode, Node, ToplevelNode},
+        utils::{
+            bitflags::BitflagsExt, clonecell::CloneCell, smallmap::SmallMap,
+            transform_ext::TransformExt,
+        },
         wire::WlDataOfferId,
         xkbcommon::{ModifierState, XKB_KEY_DOWN, XKB_KEY_UP},
     },
+    ahash::AHashSet,
     jay_config::keyboard::{
         mods::{Modifiers, CAPS, NUM},
         syms::KeySym,
         ModifiedKeySym,
     },
     smallvec::SmallVec,
-    std::rc::Rc,
+    std::{mem, rc::Rc},
 };
 
 #[derive(Default)]
@@ -175,8 +180,22 @@ impl WlSeatGlobal {
             | InputEvent::ConnectorPosition { time_usec, .. }
             | InputEvent::Motion { time_usec, .. }
             | InputEvent::Button { time_usec, .. }
-            | InputEvent::AxisFrame { time_usec, .. } => {
-                self.last_input_usec.set(time_usec);
+            | InputEvent::AxisFrame { time_usec, .. }
+            | InputEvent::TouchDown { time_usec, .. }
+            | InputEvent::TouchUp { time_usec, .. }
+            | InputEvent::TouchMotion { time_usec, .. }
+            | InputEvent::TouchCancel { time_usec, .. }
+            | InputEvent::TouchFrame { time_usec, .. }
+            | InputEvent::SwipeBegin { time_usec, .. }
+            | InputEvent::SwipeUpdate { time_usec, .. }
+            | InputEvent::SwipeEnd { time_usec, .. }
+            | InputEvent::PinchBegin { time_usec, .. }
+            | InputEvent::PinchUpdate { time_usec, .. }
+            | InputEvent::PinchEnd { time_usec, .. }
+            | InputEvent::HoldBegin { time_usec, .. }
+            | InputEvent::HoldEnd { time_usec, .. } => {
+                let now = self.state.clock.now().map(|t| t.usec());
+                self.last_input_usec.set(now.unwrap_or(time_usec));
                 if self.idle_notifications.is_not_empty() {
                     for (_, notification) in self.idle_notifications.lock().drain() {
                         notification.resume.trigger();
@@ -211,7 +230,7 @@ impl WlSeatGlobal {
                 time_usec,
                 button,
                 state,
-            } => self.button_event(time_usec, button, state),
+            } => self.button_event(time_usec, dev.map_button(button, state), state),
 
             InputEvent::AxisSource { source } => self.pointer_owner.axis_source(source),
             InputEvent::Axis120 {
@@ -226,9 +245,288 @@ impl WlSeatGlobal {
             } => self.pointer_owner.axis_px(dist, axis, inverted),
             InputEvent::AxisStop { axis } => self.pointer_owner.axis_stop(axis),
             InputEvent::AxisFrame { time_usec } => self.pointer_owner.frame(dev, self, time_usec),
+            InputEvent::TouchDown {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            } => self.touch_down(dev, time_usec, id, x_normed, y_normed),
+            InputEvent::TouchUp { time_usec, id } => self.touch_up(time_usec, id),
+            InputEvent::TouchMotion {
+                time_usec,
+                id,
+                x_normed,
+                y_normed,
+            } => self.touch_motion(dev, time_usec, id, x_normed, y_normed),
+            InputEvent::TouchCancel { .. } => self.touch_cancel(),
+            InputEvent::TouchFrame { .. } => self.touch_frame(),
+            InputEvent::SwipeBegin {
+                time_usec,
+                finger_count,
+            } => self.swipe_begin(time_usec, finger_count),
+            InputEvent::SwipeUpdate { time_usec, dx, dy } => self.swipe_update(time_usec, dx, dy),
+            InputEvent::SwipeEnd {
+                time_usec,
+                cancelled,
+            } => self.swipe_end(time_usec, cancelled),
+            InputEvent::PinchBegin {
+                time_usec,
+                finger_count,
+            } => self.pinch_begin(time_usec, finger_count),
+            InputEvent::PinchUpdate {
+                time_usec,
+                dx,
+                dy,
+                scale,
+                rotation,
+            } => self.pinch_update(time_usec, dx, dy, scale, rotation),
+            InputEvent::PinchEnd {
+                time_usec,
+                cancelled,
+            } => self.pinch_end(time_usec, cancelled),
+            InputEvent::HoldBegin {
+                time_usec,
+                finger_count,
+            } => self.hold_begin(time_usec, finger_count),
+            InputEvent::HoldEnd {
+                time_usec,
+                cancelled,
+            } => self.hold_end(time_usec, cancelled),
+        }
+    }
+
+    fn touch_position(
+        &self,
+        dev: &DeviceHandlerData,
+        x_normed: Fixed,
+        y_normed: Fixed,
+    ) -> (Fixed, Fixed) {
+        let output = dev
+            .output
+            .get()
+            .and_then(|id| self.state.root.outputs.get(&id))
+            .unwrap_or_else(|| self.output.get());
+        let (x, y) = output
+            .global
+            .transform
+            .get()
+            .invert_normed_point((x_normed.to_f64(), y_normed.to_f64()));
+        let pos = output.global.pos.get();
+        let x = pos.x1() as f64 + x * pos.width() as f64;
+        let y = pos.y1() as f64 + y * pos.height() as f64;
+        (Fixed::from_f64(x), Fixed::from_f64(y))
+    }
+
+    fn touch_down(
+        self: &Rc<Self>,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        id: i32,
+        x_normed: Fixed,
+        y_normed: Fixed,
+    ) {
+        let (x, y) = self.touch_position(dev, x_normed, y_normed);
+        let x_int = x.round_down();
+        let y_int = y.round_down();
+        let mut found_tree = vec![FoundNode {
+            node: self.state.root.clone(),
+            x: x_int,
+            y: y_int,
+        }];
+        self.state
+            .root
+            .node_find_tree_at(x_int, y_int, &mut found_tree);
+        let Some(found) = found_tree.pop() else {
+            return;
+        };
+        let Some(surface) = found.node.node_into_surface() else {
+            return;
+        };
+        if let Some(tl) = surface.get_toplevel() {
+            self.focus_toplevel(tl);
+        }
+        let x_origin = x_int - found.x;
+        let y_origin = y_int - found.y;
+        let serial = surface.client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        let x_rel = x - Fixed::from_int(x_origin);
+        let y_rel = y - Fixed::from_int(y_origin);
+        self.for_each_touch(surface.client.id, |t| {
+            t.send_down(serial, time, &surface, id, x_rel, y_rel);
+        });
+        self.touch_frame_clients
+            .borrow_mut()
+            .insert(surface.client.id);
+        self.touch_points.borrow_mut().insert(
+            id,
+            TouchPoint {
+                surface,
+                x_origin,
+                y_origin,
+            },
+        );
+    }
+
+    fn touch_up(&self, time_usec: u64, id: i32) {
+        let Some(tp) = self.touch_points.borrow_mut().remove(&id) else {
+            return;
+        };
+        let client = &tp.surface.client;
+        let serial = client.next_serial();
+        let time = (time_usec / 1000) as u32;
+        self.for_each_touch(client.id, |t| t.send_up(serial, time, id));
+        self.touch_frame_clients.borrow_mut().insert(client.id);
+    }
+
+    fn touch_motion(
+        &self,
+        dev: &DeviceHandlerData,
+        time_usec: u64,
+        id: i32,
+        x_normed: Fixed,
+        y_normed: Fixed,
+    ) {
+        let (x, y) = self.touch_position(dev, x_normed, y_normed);
+        let points = self.touch_points.borrow();
+        let Some(tp) = points.get(&id) else {
+            return;
+        };
+        let x_rel = x - Fixed::from_int(tp.x_origin);
+        let y_rel = y - Fixed::from_int(tp.y_origin);
+        let time = (time_usec / 1000) as u32;
+        let client = &tp.surface.client;
+        self.for_each_touch(client.id, |t| t.send_motion(time, id, x_rel, y_rel));
+        self.touch_frame_clients.borrow_mut().insert(client.id);
+    }
+
+    fn touch_cancel(&self) {
+        let mut clients = AHashSet::new();
+        for (_, tp) in self.touch_points.borrow_mut().drain() {
+            clients.insert(tp.surface.client.id);
+        }
+        for client in clients {
+            self.for_each_touch(client, |t| t.send_cancel());
+        }
+        self.touch_frame_clients.borrow_mut().clear();
+    }
+
+    fn touch_frame(&self) {
+        let clients = mem::take(&mut *self.touch_frame_clients.borrow_mut());
+        for client in clients {
+            self.for_each_touch(client, |t| t.send_frame());
         }
     }
 
+    fn gesture_begin_surface(self: &Rc<Self>) -> Option<Rc<WlSurface>> {
+        let surface = self
+            .pointer_owner
+            .gesture_node(self)
+            .and_then(|n| n.node_into_surface());
+        self.gesture_surface.set(surface.clone());
+        surface
+    }
+
+    fn swipe_begin(self: &Rc<Self>, time_usec: u64, finger_count: u32) {
+        let Some(surface) = self.gesture_begin_surface() else {
+            return;
+        };
+        let serial = surface.client.next_serial();
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.swipe_gestures.lock().values() {
+                gesture.send_begin(serial, time_usec, &surface, finger_count);
+            }
+        });
+    }
+
+    fn swipe_update(self: &Rc<Self>, time_usec: u64, dx: Fixed, dy: Fixed) {
+        let Some(surface) = self.gesture_surface.get() else {
+            return;
+        };
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.swipe_gestures.lock().values() {
+                gesture.send_update(time_usec, dx, dy);
+            }
+        });
+    }
+
+    fn swipe_end(self: &Rc<Self>, time_usec: u64, cancelled: bool) {
+        let Some(surface) = self.gesture_surface.take() else {
+            return;
+        };
+        let serial = surface.client.next_serial();
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.swipe_gestures.lock().values() {
+                gesture.send_end(serial, time_usec, cancelled);
+            }
+        });
+    }
+
+    fn pinch_begin(self: &Rc<Self>, time_usec: u64, finger_count: u32) {
+        let Some(surface) = self.gesture_begin_surface() else {
+            return;
+        };
+        let serial = surface.client.next_serial();
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.pinch_gestures.lock().values() {
+                gesture.send_begin(serial, time_usec, &surface, finger_count);
+            }
+        });
+    }
+
+    fn pinch_update(
+        self: &Rc<Self>,
+        time_usec: u64,
+        dx: Fixed,
+        dy: Fixed,
+        scale: Fixed,
+        rotation: Fixed,
+    ) {
+        let Some(surface) = self.gesture_surface.get() else {
+            return;
+        };
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.pinch_gestures.lock().values() {
+                gesture.send_update(time_usec, dx, dy, scale, rotation);
+            }
+        });
+    }
+
+    fn pinch_end(self: &Rc<Self>, time_usec: u64, cancelled: bool) {
+        let Some(surface) = self.gesture_surface.take() else {
+            return;
+        };
+        let serial = surface.client.next_serial();
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.pinch_gestures.lock().values() {
+                gesture.send_end(serial, time_usec, cancelled);
+            }
+        });
+    }
+
+    fn hold_begin(self: &Rc<Self>, time_usec: u64, finger_count: u32) {
+        let Some(surface) = self.gesture_begin_surface() else {
+            return;
+        };
+        let serial = surface.client.next_serial();
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.hold_gestures.lock().values() {
+                gesture.send_begin(serial, time_usec, &surface, finger_count);
+            }
+        });
+    }
+
+    fn hold_end(self: &Rc<Self>, time_usec: u64, cancelled: bool) {
+        let Some(surface) = self.gesture_surface.take() else {
+            return;
+        };
+        let serial = surface.client.next_serial();
+        self.for_each_seat(0, surface.client.id, |seat| {
+            for gesture in seat.hold_gestures.lock().values() {
+                gesture.send_end(serial, time_usec, cancelled);
+            }
+        });
+    }
+
     fn connector_position_event(
         self: &Rc<Self>,
         time_usec: u64,
@@ -308,14 +606,9 @@ impl WlSeatGlobal {
         let mut x_int = x.round_down();
         let mut y_int = y.round_down();
         if !pos.contains(x_int, y_int) {
-            'warp: {
-                let outputs = self.state.outputs.lock();
-                for output in outputs.values() {
-                    if output.node.global.pos.get().contains(x_int, y_int) {
-                        self.set_output(&output.node);
-                        break 'warp;
-                    }
-                }
+            if let Some(output) = self.state.output_at(x_int, y_int) {
+                self.set_output(&output);
+            } else {
                 if x_int < pos.x1() {
                     x_int = pos.x1();
                 } else if x_int >= pos.x2() {
@@ -359,14 +652,27 @@ impl WlSeatGlobal {
             }
         };
         let mut shortcuts = SmallVec::<[_; 1]>::new();
+        let mut break_inhibitor = None;
         let new_mods;
         {
             let mut kb_state = self.kb_state.borrow_mut();
             if !self.state.lock.locked.get() && state == wl_keyboard::PRESSED {
+                let inhibitor = self
+                    .keyboard_node
+                    .get()
+                    .node_into_surface()
+                    .and_then(|s| s.shortcut_inhibitors.get(&self.id))
+                    .filter(|i| i.active.get());
                 let old_mods = kb_state.mods();
                 let keysyms = kb_state.unmodified_keysyms(key);
                 for &sym in keysyms {
                     let mods = old_mods.mods_effective & !(CAPS.0 | NUM.0);
+                    if let Some(inhibitor) = &inhibitor {
+                        if self.shortcut_inhibit_break.get() == (mods, sym) {
+                            break_inhibitor = Some(inhibitor.clone());
+                        }
+                        continue;
+                    }
                     if let Some(mods) = self.shortcuts.get(&(mods, sym)) {
                         shortcuts.push(ModifiedKeySym {
                             mods,
@@ -381,7 +687,9 @@ impl WlSeatGlobal {
             t.send_key(self.id, time_usec, key, key_state);
         });
         let node = self.keyboard_node.get();
-        if shortcuts.is_empty() {
+        if let Some(inhibitor) = break_inhibitor {
+            inhibitor.deactivate();
+        } else if shortcuts.is_empty() {
             node.node_on_key(self, time_usec, key, state);
         } else if let Some(config) = self.state.config.get() {
             for shortcut in shortcuts {
@@ -393,11 +701,22 @@ impl WlSeatGlobal {
                 t.send_modifiers(self.id, &mods);
             });
             node.node_on_mods(self, mods);
+            if let Some(src) = self.pointer_owner.dnd_source() {
+                src.update_selected_action();
+            }
         }
     }
 }
 
 impl WlSeatGlobal {
+    pub fn release_pressed_keys(&self) {
+        let pressed_keys: Vec<_> = self.pressed_keys.borrow().iter().copied().collect();
+        let time_usec = self.state.clock.now().map(|t| t.usec()).unwrap_or(0);
+        for key in pressed_keys {
+            self.key_event(time_usec, key, KeyState::Released);
+        }
+    }
+
     pub fn pointer_node(&self) -> Option<Rc<dyn Node>> {
         self.pointer_stack.borrow().last().cloned()
     }
@@ -480,6 +799,18 @@ impl WlSeatGlobal {
         })
     }
 
+    fn for_each_touch<C>(&self, client: ClientId, mut f: C)
+    where
+        C: FnMut(&Rc<WlTouch>),
+    {
+        self.for_each_seat(0, client, |seat| {
+            let touches = seat.touches.lock();
+            for touch in touches.values() {
+                f(touch);
+            }
+        })
+    }
+
     fn for_each_kb<C>(&self, ver: u32, client: ClientId, mut f: C)
     where
         C: FnMut(&Rc<WlKeyboard>),
@@ -576,6 +907,10 @@ impl WlSeatGlobal {
         self.shortcuts.remove(&(mods.0, keysym.0));
     }
 
+    pub fn set_shortcut_inhibit_break(&self, mods: Modifiers, keysym: KeySym) {
+        self.shortcut_inhibit_break.set((mods.0, keysym.0));
+    }
+
     pub fn trigger_tree_changed(&self) {
         // log::info!("trigger_tree_changed");
         self.tree_changed.trigger();
@@ -1,21 +1,24 @@
 use {
     crate::{
         backend::{AxisSource, KeyState, ScrollAxis, AXIS_120},
+        cursor::KnownCursor,
         fixed::Fixed,
         ifs::{
             ipc,
             ipc::{wl_data_device::ClipboardIpc, wl_data_source::WlDataSource},
             wl_seat::{
-                wl_pointer::PendingScroll, Dnd, DroppedDnd, WlSeatError, WlSeatGlobal,
-                CHANGE_CURSOR_MOVED,
+                wl_pointer::PendingScroll, Dnd, DroppedDnd, WlSeatError, WlSeatGlobal, BTN_LEFT,
+                BTN_RIGHT, CHANGE_CURSOR_MOVED,
             },
             wl_surface::WlSurface,
             xdg_toplevel_drag_v1::XdgToplevelDragV1,
         },
+        rect::Rect,
         state::DeviceHandlerData,
-        tree::{FoundNode, Node},
+        tree::{FloatDrag, FloatNode, FoundNode, Node},
         utils::{clonecell::CloneCell, smallmap::SmallMap},
     },
+    jay_config::keyboard::mods::{CAPS, NUM},
     std::{cell::Cell, rc::Rc},
 };
 
@@ -96,6 +99,10 @@ impl PointerOwnerHolder {
         }
     }
 
+    pub fn gesture_node(&self, seat: &Rc<WlSeatGlobal>) -> Option<Rc<dyn Node>> {
+        self.owner.get().axis_node(seat)
+    }
+
     pub fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) {
         self.owner.get().apply_changes(seat)
     }
@@ -133,6 +140,10 @@ impl PointerOwnerHolder {
         self.owner.get().toplevel_drag()
     }
 
+    pub fn dnd_source(&self) -> Option<Rc<WlDataSource>> {
+        self.owner.get().dnd_source()
+    }
+
     pub fn remove_dnd_icon(&self) {
         self.owner.get().remove_dnd_icon()
     }
@@ -164,6 +175,7 @@ trait PointerOwner {
     fn dnd_target_removed(&self, seat: &Rc<WlSeatGlobal>);
     fn dnd_icon(&self) -> Option<Rc<WlSurface>>;
     fn toplevel_drag(&self) -> Option<Rc<XdgToplevelDragV1>>;
+    fn dnd_source(&self) -> Option<Rc<WlDataSource>>;
     fn remove_dnd_icon(&self);
 }
 
@@ -175,6 +187,15 @@ struct GrabPointerOwner {
     serial: u32,
 }
 
+struct FloatDragPointerOwner {
+    button: u32,
+    node: Rc<FloatNode>,
+    drag: FloatDrag,
+    start: Rect,
+    start_x: i32,
+    start_y: i32,
+}
+
 struct DndPointerOwner {
     button: u32,
     dnd: Dnd,
@@ -184,11 +205,63 @@ struct DndPointerOwner {
     pos_y: Cell<Fixed>,
 }
 
+impl DefaultPointerOwner {
+    fn start_float_drag(&self, seat: &Rc<WlSeatGlobal>, button: u32) -> bool {
+        let mods = seat.float_modifier.get().0;
+        if mods == 0 || (button != BTN_LEFT && button != BTN_RIGHT) {
+            return false;
+        }
+        if seat.modifiers().0 & !(CAPS.0 | NUM.0) & mods != mods {
+            return false;
+        }
+        let float = seat
+            .pointer_stack
+            .borrow()
+            .iter()
+            .rev()
+            .find_map(|n| n.clone().node_into_float());
+        let Some(float) = float else {
+            return false;
+        };
+        let (x, y) = seat.pos.get();
+        let (x, y) = (x.round_down(), y.round_down());
+        let start = float.position.get();
+        let (drag, cursor) = match button {
+            BTN_LEFT => (FloatDrag::Move, KnownCursor::Move),
+            _ => {
+                let left = x < start.x1() + start.width() / 2;
+                let top = y < start.y1() + start.height() / 2;
+                let cursor = match (left, top) {
+                    (true, true) => KnownCursor::NwResize,
+                    (false, true) => KnownCursor::NeResize,
+                    (true, false) => KnownCursor::SwResize,
+                    (false, false) => KnownCursor::SeResize,
+                };
+                (FloatDrag::Resize { left, top }, cursor)
+            }
+        };
+        float.restack();
+        seat.pointer_owner.owner.set(Rc::new(FloatDragPointerOwner {
+            button,
+            node: float,
+            drag,
+            start,
+            start_x: x,
+            start_y: y,
+        }));
+        seat.set_known_cursor(cursor);
+        true
+    }
+}
+
 impl PointerOwner for DefaultPointerOwner {
     fn button(&self, seat: &Rc<WlSeatGlobal>, time_usec: u64, button: u32, state: KeyState) {
         if state != KeyState::Pressed {
             return;
         }
+        if self.start_float_drag(seat, button) {
+            return;
+        }
         let pn = match seat.pointer_node() {
             Some(n) => n,
             _ => return,
@@ -305,6 +378,10 @@ impl PointerOwner for DefaultPointerOwner {
         None
     }
 
+    fn dnd_source(&self) -> Option<Rc<WlDataSource>> {
+        None
+    }
+
     fn remove_dnd_icon(&self) {
         // nothing
     }
@@ -428,6 +505,80 @@ impl PointerOwner for GrabPointerOwner {
         None
     }
 
+    fn dnd_source(&self) -> Option<Rc<WlDataSource>> {
+        None
+    }
+
+    fn remove_dnd_icon(&self) {
+        // nothing
+    }
+}
+
+impl FloatDragPointerOwner {
+    fn finish(&self, seat: &Rc<WlSeatGlobal>) {
+        self.node.finish_drag(seat);
+        seat.pointer_stack_modified.set(true);
+        seat.pointer_owner.set_default_pointer_owner(seat);
+        seat.tree_changed.trigger();
+    }
+}
+
+impl PointerOwner for FloatDragPointerOwner {
+    fn button(&self, seat: &Rc<WlSeatGlobal>, _time_usec: u64, button: u32, state: KeyState) {
+        if button == self.button && state == KeyState::Released {
+            self.finish(seat);
+        }
+    }
+
+    fn axis_node(&self, _seat: &Rc<WlSeatGlobal>) -> Option<Rc<dyn Node>> {
+        None
+    }
+
+    fn apply_changes(&self, seat: &Rc<WlSeatGlobal>) {
+        let (x, y) = seat.pos.get();
+        let dx = x.round_down() - self.start_x;
+        let dy = y.round_down() - self.start_y;
+        self.node.apply_drag(self.start, self.drag, dx, dy);
+    }
+
+    fn start_drag(
+        &self,
+        seat: &Rc<WlSeatGlobal>,
+        _origin: &Rc<WlSurface>,
+        source: Option<Rc<WlDataSource>>,
+        _icon: Option<Rc<WlSurface>>,
+        _serial: u32,
+    ) -> Result<(), WlSeatError> {
+        if let Some(src) = source {
+            src.send_cancelled(seat);
+        }
+        Ok(())
+    }
+
+    fn cancel_dnd(&self, seat: &Rc<WlSeatGlobal>) {
+        seat.dropped_dnd.borrow_mut().take();
+    }
+
+    fn revert_to_default(&self, seat: &Rc<WlSeatGlobal>) {
+        self.finish(seat);
+    }
+
+    fn dnd_target_removed(&self, seat: &Rc<WlSeatGlobal>) {
+        self.cancel_dnd(seat);
+    }
+
+    fn dnd_icon(&self) -> Option<Rc<WlSurface>> {
+        None
+    }
+
+    fn toplevel_drag(&self) -> Option<Rc<XdgToplevelDragV1>> {
+        None
+    }
+
+    fn dnd_source(&self) -> Option<Rc<WlDataSource>> {
+        None
+    }
+
     fn remove_dnd_icon(&self) {
         // nothing
     }
@@ -558,6 +709,10 @@ impl PointerOwner for DndPointerOwner {
         }
     }
 
+    fn dnd_source(&self) -> Option<Rc<WlDataSource>> {
+        self.dnd.src.clone()
+    }
+
     fn remove_dnd_icon(&self) {
         self.icon.set(None);
     }
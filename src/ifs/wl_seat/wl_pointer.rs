@@ -91,8 +91,8 @@ impl WlPointer {
             self_id: self.id,
             serial,
             surface,
-            surface_x: x,
-            surface_y: y,
+            surface_x: x * self.seat.client.wire_scale,
+            surface_y: y * self.seat.client.wire_scale,
         })
     }
 
@@ -108,8 +108,8 @@ impl WlPointer {
         self.seat.client.event(Motion {
             self_id: self.id,
             time,
-            surface_x: x,
-            surface_y: y,
+            surface_x: x * self.seat.client.wire_scale,
+            surface_y: y * self.seat.client.wire_scale,
         })
     }
 
@@ -185,7 +185,8 @@ impl WlPointer {
         if req.surface.is_some() {
             let surface = self.seat.client.lookup(req.surface)?;
             let cursor = surface.get_cursor(&self.seat.global)?;
-            cursor.set_hotspot(req.hotspot_x, req.hotspot_y);
+            let scale = self.seat.client.wire_scale;
+            cursor.set_hotspot(req.hotspot_x / scale, req.hotspot_y / scale);
             cursor_opt = Some(cursor as Rc<dyn Cursor>);
         }
         let pointer_node = match self.seat.global.pointer_node() {
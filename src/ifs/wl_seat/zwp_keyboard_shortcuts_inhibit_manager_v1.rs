@@ -0,0 +1,125 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{
+            zwp_keyboard_shortcuts_inhibit_manager_v1::*, ZwpKeyboardShortcutsInhibitManagerV1Id,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpKeyboardShortcutsInhibitManagerV1Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwpKeyboardShortcutsInhibitManagerV1 {
+    pub id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub _version: u32,
+}
+
+impl ZwpKeyboardShortcutsInhibitManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpKeyboardShortcutsInhibitManagerV1Id,
+        client: &Rc<Client>,
+        version: u32,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitManagerV1Error> {
+        let obj = Rc::new(ZwpKeyboardShortcutsInhibitManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            _version: version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpKeyboardShortcutsInhibitManagerV1Global,
+    ZwpKeyboardShortcutsInhibitManagerV1,
+    ZwpKeyboardShortcutsInhibitManagerV1Error
+);
+
+impl Global for ZwpKeyboardShortcutsInhibitManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(ZwpKeyboardShortcutsInhibitManagerV1Global);
+
+impl ZwpKeyboardShortcutsInhibitManagerV1 {
+    fn destroy(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitManagerV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn inhibit_shortcuts(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitManagerV1Error> {
+        let req: InhibitShortcuts = self.client.parse(self, parser)?;
+        let surface = self.client.lookup(req.surface)?;
+        let seat = self.client.lookup(req.seat)?;
+        if surface.shortcut_inhibitors.contains(&seat.global.id) {
+            return Err(ZwpKeyboardShortcutsInhibitManagerV1Error::AlreadyInhibited);
+        }
+        let inhibitor = Rc::new(ZwpKeyboardShortcutsInhibitorV1 {
+            id: req.id,
+            client: self.client.clone(),
+            surface,
+            seat: seat.global.clone(),
+            active: Default::default(),
+            tracker: Default::default(),
+        });
+        track!(self.client, inhibitor);
+        self.client.add_client_obj(&inhibitor)?;
+        inhibitor.install();
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpKeyboardShortcutsInhibitManagerV1;
+
+    DESTROY => destroy,
+    INHIBIT_SHORTCUTS => inhibit_shortcuts,
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitManagerV1 {}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitManagerV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitManagerV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error("The surface already has a shortcuts inhibitor for this seat")]
+    AlreadyInhibited,
+}
+efrom!(ZwpKeyboardShortcutsInhibitManagerV1Error, ClientError);
+efrom!(ZwpKeyboardShortcutsInhibitManagerV1Error, MsgParserError);
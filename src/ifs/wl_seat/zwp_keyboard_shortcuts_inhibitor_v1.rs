@@ -0,0 +1,84 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_seat::WlSeatGlobal, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::Object,
+        tree::Node,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_keyboard_shortcuts_inhibitor_v1::*, ZwpKeyboardShortcutsInhibitorV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+    thiserror::Error,
+};
+
+pub struct ZwpKeyboardShortcutsInhibitorV1 {
+    pub id: ZwpKeyboardShortcutsInhibitorV1Id,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub seat: Rc<WlSeatGlobal>,
+    pub active: Cell<bool>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpKeyboardShortcutsInhibitorV1 {
+    fn destroy(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpKeyboardShortcutsInhibitorV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.detach();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    pub fn install(self: &Rc<Self>) {
+        self.surface
+            .shortcut_inhibitors
+            .insert(self.seat.id, self.clone());
+        if self.seat.keyboard_node.get().node_id() == self.surface.node_id() {
+            self.activate();
+        }
+    }
+
+    pub fn activate(&self) {
+        if !self.active.replace(true) {
+            self.client.event(Active { self_id: self.id });
+        }
+    }
+
+    pub fn deactivate(&self) {
+        if self.active.replace(false) {
+            self.client.event(Inactive { self_id: self.id });
+        }
+    }
+
+    fn detach(&self) {
+        self.active.set(false);
+        self.surface.shortcut_inhibitors.remove(&self.seat.id);
+    }
+}
+
+object_base! {
+    self = ZwpKeyboardShortcutsInhibitorV1;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpKeyboardShortcutsInhibitorV1 {
+    fn break_loops(&self) {
+        self.detach();
+    }
+}
+
+simple_add_obj!(ZwpKeyboardShortcutsInhibitorV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpKeyboardShortcutsInhibitorV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, ClientError);
+efrom!(ZwpKeyboardShortcutsInhibitorV1Error, MsgParserError);
@@ -0,0 +1,77 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        fixed::Fixed,
+        ifs::{wl_seat::WlSeat, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_pointer_gesture_swipe_v1::*, ZwpPointerGestureSwipeV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpPointerGestureSwipeV1 {
+    pub id: ZwpPointerGestureSwipeV1Id,
+    pub client: Rc<Client>,
+    pub seat: Rc<WlSeat>,
+    pub tracker: Tracker<Self>,
+}
+
+impl ZwpPointerGestureSwipeV1 {
+    pub fn send_begin(&self, serial: u32, time_usec: u64, surface: &WlSurface, fingers: u32) {
+        self.client.event(Begin {
+            self_id: self.id,
+            serial,
+            time: (time_usec / 1000) as u32,
+            surface: surface.id,
+            fingers,
+        });
+    }
+
+    pub fn send_update(&self, time_usec: u64, dx: Fixed, dy: Fixed) {
+        self.client.event(Update {
+            self_id: self.id,
+            time: (time_usec / 1000) as u32,
+            dx,
+            dy,
+        });
+    }
+
+    pub fn send_end(&self, serial: u32, time_usec: u64, cancelled: bool) {
+        self.client.event(End {
+            self_id: self.id,
+            serial,
+            time: (time_usec / 1000) as u32,
+            cancelled: cancelled as _,
+        });
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpPointerGestureSwipeV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.seat.swipe_gestures.remove(&self.id);
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpPointerGestureSwipeV1;
+
+    DESTROY => destroy,
+}
+
+impl Object for ZwpPointerGestureSwipeV1 {}
+
+simple_add_obj!(ZwpPointerGestureSwipeV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpPointerGestureSwipeV1Error {
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(Box<MsgParserError>),
+}
+efrom!(ZwpPointerGestureSwipeV1Error, ClientError);
+efrom!(ZwpPointerGestureSwipeV1Error, MsgParserError);
@@ -0,0 +1,151 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::wl_seat::{
+            zwp_pointer_gesture_hold_v1::ZwpPointerGestureHoldV1,
+            zwp_pointer_gesture_pinch_v1::ZwpPointerGesturePinchV1,
+            zwp_pointer_gesture_swipe_v1::ZwpPointerGestureSwipeV1,
+        },
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{zwp_pointer_gestures_v1::*, ZwpPointerGesturesV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct ZwpPointerGesturesV1Global {
+    pub name: GlobalName,
+}
+
+pub struct ZwpPointerGesturesV1 {
+    pub id: ZwpPointerGesturesV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+    pub version: u32,
+}
+
+impl ZwpPointerGesturesV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: ZwpPointerGesturesV1Id,
+        client: &Rc<Client>,
+        version: u32,
+    ) -> Result<(), ZwpPointerGesturesV1Error> {
+        let obj = Rc::new(ZwpPointerGesturesV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+            version,
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    ZwpPointerGesturesV1Global,
+    ZwpPointerGesturesV1,
+    ZwpPointerGesturesV1Error
+);
+
+impl Global for ZwpPointerGesturesV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        3
+    }
+}
+
+simple_add_global!(ZwpPointerGesturesV1Global);
+
+impl ZwpPointerGesturesV1 {
+    fn get_swipe_gesture(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpPointerGesturesV1Error> {
+        let req: GetSwipeGesture = self.client.parse(self, parser)?;
+        let pointer = self.client.lookup(req.pointer)?;
+        let gesture = Rc::new(ZwpPointerGestureSwipeV1 {
+            id: req.id,
+            client: self.client.clone(),
+            seat: pointer.seat.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, gesture);
+        self.client.add_client_obj(&gesture)?;
+        pointer.seat.swipe_gestures.set(req.id, gesture);
+        Ok(())
+    }
+
+    fn get_pinch_gesture(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), ZwpPointerGesturesV1Error> {
+        let req: GetPinchGesture = self.client.parse(self, parser)?;
+        let pointer = self.client.lookup(req.pointer)?;
+        let gesture = Rc::new(ZwpPointerGesturePinchV1 {
+            id: req.id,
+            client: self.client.clone(),
+            seat: pointer.seat.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, gesture);
+        self.client.add_client_obj(&gesture)?;
+        pointer.seat.pinch_gestures.set(req.id, gesture);
+        Ok(())
+    }
+
+    fn release(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpPointerGesturesV1Error> {
+        let _req: Release = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_hold_gesture(&self, parser: MsgParser<'_, '_>) -> Result<(), ZwpPointerGesturesV1Error> {
+        let req: GetHoldGesture = self.client.parse(self, parser)?;
+        let pointer = self.client.lookup(req.pointer)?;
+        let gesture = Rc::new(ZwpPointerGestureHoldV1 {
+            id: req.id,
+            client: self.client.clone(),
+            seat: pointer.seat.clone(),
+            tracker: Default::default(),
+        });
+        track!(self.client, gesture);
+        self.client.add_client_obj(&gesture)?;
+        pointer.seat.hold_gestures.set(req.id, gesture);
+        Ok(())
+    }
+}
+
+object_base! {
+    self = ZwpPointerGesturesV1;
+
+    GET_SWIPE_GESTURE => get_swipe_gesture,
+    GET_PINCH_GESTURE => get_pinch_gesture,
+    RELEASE => release if self.version >= 2,
+    GET_HOLD_GESTURE => get_hold_gesture if self.version >= 3,
+}
+
+impl Object for ZwpPointerGesturesV1 {}
+
+simple_add_obj!(ZwpPointerGesturesV1);
+
+#[derive(Debug, Error)]
+pub enum ZwpPointerGesturesV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(ZwpPointerGesturesV1Error, MsgParserError);
+efrom!(ZwpPointerGesturesV1Error, ClientError);
@@ -2,6 +2,7 @@ pub mod cursor;
 pub mod ext_session_lock_surface_v1;
 pub mod wl_subsurface;
 pub mod wp_fractional_scale_v1;
+pub mod wp_linux_drm_syncobj_surface_v1;
 pub mod wp_tearing_control_v1;
 pub mod wp_viewport;
 pub mod x_surface;
@@ -12,6 +13,7 @@ pub mod zwp_idle_inhibitor_v1;
 
 use {
     crate::{
+        async_engine::SpawnedFuture,
         backend::KeyState,
         client::{Client, ClientError, RequestParser},
         drm_feedback::DrmFeedback,
@@ -21,14 +23,20 @@ use {
             wl_buffer::WlBuffer,
             wl_callback::WlCallback,
             wl_seat::{
-                wl_pointer::PendingScroll, zwp_pointer_constraints_v1::SeatConstraint, Dnd,
-                NodeSeatState, SeatId, WlSeatGlobal,
+                wl_pointer::PendingScroll,
+                zwp_keyboard_shortcuts_inhibitor_v1::ZwpKeyboardShortcutsInhibitorV1,
+                zwp_pointer_constraints_v1::SeatConstraint, Dnd, NodeSeatState, SeatId,
+                WlSeatGlobal,
             },
             wl_surface::{
-                cursor::CursorSurface, wl_subsurface::WlSubsurface,
+                cursor::CursorSurface,
+                wl_subsurface::WlSubsurface,
                 wp_fractional_scale_v1::WpFractionalScaleV1,
-                wp_tearing_control_v1::WpTearingControlV1, wp_viewport::WpViewport,
-                x_surface::XSurface, xdg_surface::XdgSurfaceError,
+                wp_linux_drm_syncobj_surface_v1::{SyncObjPoint, WpLinuxDrmSyncobjSurfaceV1},
+                wp_tearing_control_v1::WpTearingControlV1,
+                wp_viewport::WpViewport,
+                x_surface::XSurface,
+                xdg_surface::XdgSurfaceError,
                 zwlr_layer_surface_v1::ZwlrLayerSurfaceV1Error,
             },
             wp_content_type_v1::ContentType,
@@ -39,6 +47,7 @@ use {
         object::Object,
         rect::{Rect, Region},
         renderer::Renderer,
+        state::State,
         tree::{
             FindTreeResult, FoundNode, Node, NodeId, NodeVisitor, NodeVisitorBase, OutputNode,
             ToplevelNode,
@@ -48,6 +57,7 @@ use {
             cell_ext::CellExt,
             clonecell::CloneCell,
             copyhashmap::CopyHashMap,
+            errorfmt::ErrorFmt,
             linkedlist::LinkedList,
             numcell::NumCell,
             smallmap::SmallMap,
@@ -70,6 +80,7 @@ use {
         rc::Rc,
     },
     thiserror::Error,
+    uapi::OwnedFd,
     zwp_idle_inhibitor_v1::ZwpIdleInhibitorV1,
 };
 
@@ -133,6 +144,7 @@ pub struct WlSurface {
     pub client: Rc<Client>,
     visible: Cell<bool>,
     role: Cell<SurfaceRole>,
+    commits: NumCell<u64>,
     pending: PendingState,
     input_region: Cell<Option<Rc<Region>>>,
     opaque_region: Cell<Option<Rc<Region>>>,
@@ -162,6 +174,7 @@ pub struct WlSurface {
     output: CloneCell<Rc<OutputNode>>,
     fractional_scale: CloneCell<Option<Rc<WpFractionalScaleV1>>>,
     pub constraints: SmallMap<SeatId, Rc<SeatConstraint>, 1>,
+    pub shortcut_inhibitors: SmallMap<SeatId, Rc<ZwpKeyboardShortcutsInhibitorV1>, 1>,
     xwayland_serial: Cell<Option<u64>>,
     tearing_control: CloneCell<Option<Rc<WpTearingControlV1>>>,
     tearing: Cell<bool>,
@@ -169,6 +182,11 @@ pub struct WlSurface {
     pub has_content_type_manager: Cell<bool>,
     content_type: Cell<Option<ContentType>>,
     pub drm_feedback: CopyHashMap<ZwpLinuxDmabufFeedbackV1Id, Rc<ZwpLinuxDmabufFeedbackV1>>,
+    syncobj_surface: CloneCell<Option<Rc<WpLinuxDrmSyncobjSurfaceV1>>>,
+    pub acquire_sync: CloneCell<Option<Rc<OwnedFd>>>,
+    release_point: Cell<Option<SyncObjPoint>>,
+    last_commit_usec: Cell<u64>,
+    throttled_commit: Cell<Option<SpawnedFuture<()>>>,
 }
 
 impl Debug for WlSurface {
@@ -246,6 +264,10 @@ trait SurfaceExt {
     fn into_xsurface(self: Rc<Self>) -> Option<Rc<XSurface>> {
         None
     }
+
+    fn role_name(&self) -> Option<&'static str> {
+        None
+    }
 }
 
 pub struct NoneSurfaceExt;
@@ -272,6 +294,10 @@ struct PendingState {
     xwayland_serial: Cell<Option<u64>>,
     tearing: Cell<Option<bool>>,
     content_type: Cell<Option<Option<ContentType>>>,
+    acquire_point: Cell<Option<SyncObjPoint>>,
+    release_point: Cell<Option<SyncObjPoint>>,
+    buffer_committed: Cell<bool>,
+    committed_release_point: Cell<Option<SyncObjPoint>>,
 }
 
 #[derive(Default)]
@@ -294,13 +320,14 @@ impl WlSurface {
             client: client.clone(),
             visible: Default::default(),
             role: Cell::new(SurfaceRole::None),
+            commits: Default::default(),
             pending: Default::default(),
             input_region: Default::default(),
             opaque_region: Default::default(),
             buffer_points: Default::default(),
             buffer_points_norm: Default::default(),
             buffer_transform: Cell::new(Transform::None),
-            buffer_scale: Cell::new(1),
+            buffer_scale: Cell::new(client.wire_scale),
             src_rect: Cell::new(None),
             dst_size: Cell::new(None),
             extents: Default::default(),
@@ -323,6 +350,7 @@ impl WlSurface {
             output: CloneCell::new(client.state.dummy_output.get().unwrap()),
             fractional_scale: Default::default(),
             constraints: Default::default(),
+            shortcut_inhibitors: Default::default(),
             xwayland_serial: Default::default(),
             tearing_control: Default::default(),
             tearing: Cell::new(false),
@@ -330,6 +358,11 @@ impl WlSurface {
             has_content_type_manager: Default::default(),
             content_type: Default::default(),
             drm_feedback: Default::default(),
+            syncobj_surface: Default::default(),
+            acquire_sync: Default::default(),
+            release_point: Default::default(),
+            last_commit_usec: Default::default(),
+            throttled_commit: Default::default(),
         }
     }
 
@@ -378,6 +411,17 @@ impl WlSurface {
         self.send_preferred_buffer_scale();
     }
 
+    pub fn role_name(&self) -> &'static str {
+        self.ext
+            .get()
+            .role_name()
+            .unwrap_or_else(|| self.role.get().name())
+    }
+
+    pub fn commits(&self) -> u64 {
+        self.commits.get()
+    }
+
     pub fn get_toplevel(&self) -> Option<Rc<dyn ToplevelNode>> {
         self.toplevel.get()
     }
@@ -405,6 +449,31 @@ impl WlSurface {
             .push(fb.clone());
     }
 
+    /// Returns whether the surface is known to cover its whole extents with opaque
+    /// content.
+    pub fn is_opaque(&self) -> bool {
+        let buffer = match self.buffer.get() {
+            Some(b) => b,
+            _ => return false,
+        };
+        if !buffer.format.has_alpha {
+            return true;
+        }
+        if let Some(color) = &buffer.color {
+            if color.a >= 1.0 {
+                return true;
+            }
+        }
+        let opaque = match self.opaque_region.take() {
+            Some(r) => r,
+            _ => return false,
+        };
+        let extents = Region::new(self.buffer_abs_pos.get().at_point(0, 0));
+        let res = extents.subtract(&opaque).is_empty();
+        self.opaque_region.set(Some(opaque));
+        res
+    }
+
     pub fn is_cursor(&self) -> bool {
         self.role.get() == SurfaceRole::Cursor
     }
@@ -525,6 +594,26 @@ impl WlSurface {
         }
     }
 
+    fn absolute_extents(&self) -> Rect {
+        let pos = self.buffer_abs_pos.get();
+        self.extents.get().move_(pos.x1(), pos.y1())
+    }
+
+    /// Damages the outputs on which this surface is or was visible.
+    fn damage_outputs(&self, old_extents: Rect) {
+        let state = &self.client.state;
+        match self.role.get() {
+            SurfaceRole::Cursor | SurfaceRole::DndIcon => state.damage(),
+            _ => {
+                state.damage_rect(old_extents);
+                let new_extents = self.absolute_extents();
+                if new_extents != old_extents {
+                    state.damage_rect(new_extents);
+                }
+            }
+        }
+    }
+
     pub fn get_root(self: &Rc<Self>) -> Rc<WlSurface> {
         let mut root = self.clone();
         loop {
@@ -558,6 +647,7 @@ impl WlSurface {
 
     fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WlSurfaceError> {
         let _req: Destroy = self.parse(parser)?;
+        self.throttled_commit.take();
         self.unset_dnd_icons();
         self.unset_cursors();
         self.ext.get().on_surface_destroy()?;
@@ -572,10 +662,12 @@ impl WlSurface {
             *children = None;
         }
         if let Some(buffer) = self.buffer.set(None) {
-            if !buffer.destroyed() {
-                buffer.send_release();
-            }
+            release_buffer(&self.client.state, &buffer, self.release_point.take());
         }
+        self.acquire_sync.take();
+        self.syncobj_surface.take();
+        self.throttled_commit.take();
+        self.viewporter.take();
         if let Some(xwayland_serial) = self.xwayland_serial.get() {
             self.client
                 .surfaces_by_xwayland_serial
@@ -586,6 +678,7 @@ impl WlSurface {
         self.client.remove_obj(self)?;
         self.idle_inhibitors.clear();
         self.constraints.take();
+        self.shortcut_inhibitors.take();
         Ok(())
     }
 
@@ -603,7 +696,17 @@ impl WlSurface {
         } else {
             None
         };
-        self.pending.buffer.set(Some(buf));
+        let old = self.pending.buffer.replace(Some(buf.clone()));
+        if let Some(Some(old)) = old {
+            // The client considers the old buffer committed and waits for its release.
+            if self.pending.buffer_committed.replace(false)
+                && buf.as_ref().map(|b| b.id) != Some(old.id)
+                && self.buffer.get().map(|b| b.id) != Some(old.id)
+            {
+                let release_point = self.pending.committed_release_point.take();
+                release_buffer(&self.client.state, &old, release_point);
+            }
+        }
         Ok(())
     }
 
@@ -649,6 +752,7 @@ impl WlSurface {
         if ext.clone().pre_commit(ctx)? == CommitAction::AbortCommit {
             return Ok(());
         }
+        let old_extents = self.absolute_extents();
         {
             let children = self.children.borrow();
             if let Some(children) = children.deref() {
@@ -660,7 +764,7 @@ impl WlSurface {
         let mut scale_changed = false;
         if let Some(scale) = self.pending.scale.take() {
             scale_changed = true;
-            self.buffer_scale.set(scale);
+            self.buffer_scale.set(scale * self.client.wire_scale);
         }
         let mut buffer_transform_changed = false;
         if let Some(transform) = self.pending.transform.take() {
@@ -688,32 +792,48 @@ impl WlSurface {
         let mut buffer_changed = false;
         let mut old_raw_size = None;
         let (dx, dy) = self.pending.offset.take();
-        if let Some(buffer_change) = self.pending.buffer.take() {
+        let buffer_change = self.pending.buffer.take();
+        self.pending.buffer_committed.set(false);
+        let acquire_point = self.pending.acquire_point.take();
+        let committed_release_point = self.pending.committed_release_point.take();
+        let release_point = self
+            .pending
+            .release_point
+            .take()
+            .or(committed_release_point);
+        if self.syncobj_surface.is_some() {
+            check_sync_points(&buffer_change, &acquire_point, &release_point)?;
+        }
+        if let Some(buffer_change) = buffer_change {
             buffer_changed = true;
             if let Some(buffer) = self.buffer.take() {
                 old_raw_size = Some(buffer.rect);
-                if !buffer.destroyed() {
-                    'handle_release: {
-                        if let Some(tex) = buffer.texture.get() {
-                            let resv = tex.reservations();
-                            if resv.has_reservation() {
-                                let buffer = Rc::downgrade(&buffer);
-                                resv.on_released(move || {
-                                    if let Some(buffer) = buffer.upgrade() {
-                                        if !buffer.destroyed() {
-                                            buffer.send_release();
-                                        }
-                                    }
-                                });
-                                break 'handle_release;
-                            }
+                let release_point = self.release_point.take();
+                'handle_release: {
+                    if let Some(tex) = buffer.texture.get() {
+                        let resv = tex.reservations();
+                        if resv.has_reservation() {
+                            let state = self.client.state.clone();
+                            let buffer = Rc::downgrade(&buffer);
+                            resv.on_released(move || {
+                                if let Some(buffer) = buffer.upgrade() {
+                                    release_buffer(&state, &buffer, release_point);
+                                }
+                            });
+                            break 'handle_release;
                         }
-                        buffer.send_release();
                     }
+                    release_buffer(&self.client.state, &buffer, release_point);
                 }
             }
+            self.acquire_sync.set(None);
             if let Some(buffer) = buffer_change {
                 buffer.update_texture_or_log();
+                if let Some(point) = acquire_point {
+                    self.acquire_sync
+                        .set(point.export_acquire(&self.client.state));
+                }
+                self.release_point.set(release_point);
                 self.buffer.set(Some(buffer));
                 self.buf_x.fetch_add(dx);
                 self.buf_y.fetch_add(dy);
@@ -854,14 +974,134 @@ impl WlSurface {
             }
         }
         ext.post_commit();
-        self.client.state.damage();
+        self.damage_outputs(old_extents);
         Ok(())
     }
 
     fn commit(self: &Rc<Self>, parser: MsgParser<'_, '_>) -> Result<(), WlSurfaceError> {
         let _req: Commit = self.parse(parser)?;
-        self.do_commit(CommitContext::RootCommit)?;
-        Ok(())
+        self.commits.fetch_add(1);
+        if self.defer_commit() {
+            return Ok(());
+        }
+        self.apply_commit()
+    }
+
+    fn apply_commit(self: &Rc<Self>) -> Result<(), WlSurfaceError> {
+        if let Ok(now) = self.client.state.clock.now() {
+            self.last_commit_usec.set(now.usec());
+        }
+        self.do_commit(CommitContext::RootCommit)
+    }
+
+    fn is_commit_throttled(&self) -> bool {
+        let future = self.throttled_commit.take();
+        let throttled = future.is_some();
+        self.throttled_commit.set(future);
+        throttled
+    }
+
+    /// Defers the commit if it is throttled or if its acquire point has not yet been
+    /// materialized.
+    ///
+    /// The pending state keeps accumulating until the commit can be applied, at which
+    /// point all deferred commits are applied at once.
+    fn defer_commit(self: &Rc<Self>) -> bool {
+        if self.is_commit_throttled() {
+            self.mark_buffer_committed();
+            return true;
+        }
+        let throttle_ms = self.throttle_commit();
+        if throttle_ms.is_none() && self.unavailable_acquire_point().is_none() {
+            return false;
+        }
+        let state = self.client.state.clone();
+        let slf = Rc::downgrade(self);
+        let future = self.client.state.eng.spawn(async move {
+            if let Some(ms) = throttle_ms {
+                if let Err(e) = state.wheel.timeout(ms).await {
+                    log::error!("Could not wait for the next refresh cycle: {}", ErrorFmt(e));
+                }
+            }
+            loop {
+                let Some(slf) = slf.upgrade() else {
+                    return;
+                };
+                let Some(point) = slf.unavailable_acquire_point() else {
+                    if let Err(e) = slf.apply_commit() {
+                        slf.client.error(e);
+                    }
+                    slf.throttled_commit.take();
+                    return;
+                };
+                drop(slf);
+                point.wait_available(&state).await;
+            }
+        });
+        self.throttled_commit.set(Some(future));
+        self.mark_buffer_committed();
+        true
+    }
+
+    /// Returns the number of milliseconds by which the commit should be deferred if
+    /// commit throttling is enabled and the surface is a hidden root surface that was
+    /// last committed less than one refresh interval ago.
+    ///
+    /// Subsurfaces are never throttled since their commits are applied together with the
+    /// commits of their parents.
+    fn throttle_commit(&self) -> Option<u64> {
+        let state = &self.client.state;
+        if !state.commit_throttling.get()
+            || self.role.get() == SurfaceRole::Subsurface
+            || self.visible.get()
+            || self.buffer.is_none()
+            || self.syncobj_surface.is_some()
+        {
+            return None;
+        }
+        let refresh_mhz = self.output.get().global.mode.get().refresh_rate_millihz as u64;
+        if refresh_mhz == 0 {
+            return None;
+        }
+        let now = state.clock.now().ok()?.usec();
+        let next = self.last_commit_usec.get() + 1_000_000_000 / refresh_mhz;
+        if now >= next {
+            return None;
+        }
+        Some((next - now).div_ceil(1000))
+    }
+
+    /// Returns the pending acquire point if no fence has been attached to it yet.
+    ///
+    /// Commits with such a point must not be applied since the buffer cannot be
+    /// synchronized with.
+    fn unavailable_acquire_point(&self) -> Option<SyncObjPoint> {
+        if self.syncobj_surface.is_none() {
+            return None;
+        }
+        let point = self.pending.acquire_point.take();
+        self.pending.acquire_point.set(point.clone());
+        let point = point?;
+        match point.is_available(&self.client.state) {
+            true => None,
+            false => Some(point),
+        }
+    }
+
+    /// Remembers that the pending buffer belongs to a deferred commit.
+    ///
+    /// Only such buffers must be released when they are replaced before the commit is
+    /// applied. Buffers that were attached but never committed are still owned by the
+    /// client.
+    fn mark_buffer_committed(&self) {
+        let buffer = self.pending.buffer.take();
+        if let Some(Some(_)) = &buffer {
+            self.pending.buffer_committed.set(true);
+            if let Some(point) = self.pending.release_point.take() {
+                self.pending.committed_release_point.set(Some(point));
+            }
+        }
+        self.pending.buffer.set(buffer);
     }
 
     fn set_buffer_transform(&self, parser: MsgParser<'_, '_>) -> Result<(), WlSurfaceError> {
@@ -890,17 +1130,35 @@ impl WlSurface {
 
     fn offset(&self, parser: MsgParser<'_, '_>) -> Result<(), WlSurfaceError> {
         let req: Offset = self.parse(parser)?;
-        self.pending.offset.set((req.x, req.y));
+        let (mut x, mut y) = (req.x, req.y);
+        if self.is_commit_throttled() {
+            let (dx, dy) = self.pending.offset.get();
+            x += dx;
+            y += dy;
+        }
+        self.pending.offset.set((x, y));
         Ok(())
     }
 
-    fn find_surface_at(self: &Rc<Self>, x: i32, y: i32) -> Option<(Rc<Self>, i32, i32)> {
+    fn accepts_input_at(&self, x: i32, y: i32) -> bool {
         let rect = self.buffer_abs_pos.get().at_point(0, 0);
+        if !rect.contains(x, y) {
+            return false;
+        }
+        if let Some(region) = self.input_region.take() {
+            let contains = region.contains(x, y);
+            self.input_region.set(Some(region));
+            return contains;
+        }
+        true
+    }
+
+    fn find_surface_at(self: &Rc<Self>, x: i32, y: i32) -> Option<(Rc<Self>, i32, i32)> {
         let children = self.children.borrow();
         let children = match children.deref() {
             Some(c) => c,
             _ => {
-                return if rect.contains(x, y) {
+                return if self.accepts_input_at(x, y) {
                     Some((self.clone(), x, y))
                 } else {
                     None
@@ -913,7 +1171,13 @@ impl WlSurface {
                     continue;
                 }
                 let pos = child.sub_surface.position.get();
-                if pos.contains(x, y) {
+                let extents = child
+                    .sub_surface
+                    .surface
+                    .extents
+                    .get()
+                    .move_(pos.x1(), pos.y1());
+                if extents.contains(x, y) {
                     let (x, y) = pos.translate(x, y);
                     if let Some(res) = child.sub_surface.surface.find_surface_at(x, y) {
                         return Some(res);
@@ -925,7 +1189,7 @@ impl WlSurface {
         if let Some(res) = ss(&children.above) {
             return Some(res);
         }
-        if rect.contains(x, y) {
+        if self.accepts_input_at(x, y) {
             return Some((self.clone(), x, y));
         }
         if let Some(res) = ss(&children.below) {
@@ -1009,6 +1273,18 @@ impl WlSurface {
         self.pending.content_type.set(Some(content_type));
     }
 
+    pub fn content_type(&self) -> Option<ContentType> {
+        self.content_type.get()
+    }
+
+    /// Returns whether presentation of this surface may tear.
+    ///
+    /// Surfaces with the `game` content type opt in even without a tearing control
+    /// object.
+    pub fn tearing(&self) -> bool {
+        self.tearing.get() || self.content_type.get() == Some(ContentType::Game)
+    }
+
     pub fn request_activation(&self) {
         if let Some(tl) = self.toplevel.get() {
             tl.tl_data().request_attention(tl.tl_as_node());
@@ -1040,6 +1316,7 @@ object_base! {
 
 impl Object for WlSurface {
     fn break_loops(&self) {
+        self.throttled_commit.take();
         self.unset_dnd_icons();
         self.unset_cursors();
         self.destroy_node();
@@ -1055,10 +1332,49 @@ impl Object for WlSurface {
         self.fractional_scale.take();
         self.tearing_control.take();
         self.constraints.clear();
+        self.shortcut_inhibitors.clear();
         self.drm_feedback.clear();
+        self.syncobj_surface.take();
+        self.acquire_sync.take();
+        self.release_point.take();
     }
 }
 
+fn release_buffer(state: &State, buffer: &WlBuffer, release_point: Option<SyncObjPoint>) {
+    if let Some(point) = release_point {
+        point.signal_release(state, buffer);
+    }
+    if !buffer.destroyed() {
+        buffer.send_release();
+    }
+}
+
+fn check_sync_points(
+    buffer_change: &Option<Option<Rc<WlBuffer>>>,
+    acquire_point: &Option<SyncObjPoint>,
+    release_point: &Option<SyncObjPoint>,
+) -> Result<(), WlSurfaceError> {
+    let Some(Some(buffer)) = buffer_change else {
+        if acquire_point.is_some() || release_point.is_some() {
+            return Err(WlSurfaceError::SyncPointsWithoutBuffer);
+        }
+        return Ok(());
+    };
+    if buffer.dmabuf().is_none() {
+        return Err(WlSurfaceError::UnsupportedSyncBuffer);
+    }
+    let Some(acquire) = acquire_point else {
+        return Err(WlSurfaceError::NoAcquirePoint);
+    };
+    let Some(release) = release_point else {
+        return Err(WlSurfaceError::NoReleasePoint);
+    };
+    if Rc::ptr_eq(&acquire.syncobj, &release.syncobj) && acquire.point >= release.point {
+        return Err(WlSurfaceError::ConflictingSyncPoints);
+    }
+    Ok(())
+}
+
 dedicated_add_obj!(WlSurface, WlSurfaceId, surfaces);
 
 tree_id!(SurfaceNodeId);
@@ -1139,10 +1455,16 @@ impl Node for WlSurface {
             tl.tl_on_activate();
         }
         seat.focus_surface(&self);
+        if let Some(inhibitor) = self.shortcut_inhibitors.get(&seat.id()) {
+            inhibitor.activate();
+        }
     }
 
     fn node_on_unfocus(&self, seat: &WlSeatGlobal) {
         seat.unfocus_surface(self);
+        if let Some(inhibitor) = self.shortcut_inhibitors.get(&seat.id()) {
+            inhibitor.deactivate();
+        }
     }
 
     fn node_on_leave(&self, seat: &WlSeatGlobal) {
@@ -1222,6 +1544,16 @@ pub enum WlSurfaceError {
     ViewportOutsideBuffer,
     #[error("attach request must not contain offset")]
     OffsetInAttach,
+    #[error("Sync points were set but no buffer was attached")]
+    SyncPointsWithoutBuffer,
+    #[error("Explicit sync is only supported for dmabuf buffers")]
+    UnsupportedSyncBuffer,
+    #[error("A buffer was attached without an acquire point")]
+    NoAcquirePoint,
+    #[error("A buffer was attached without a release point")]
+    NoReleasePoint,
+    #[error("The acquire point is not before the release point")]
+    ConflictingSyncPoints,
 }
 efrom!(WlSurfaceError, ClientError);
 efrom!(WlSurfaceError, XdgSurfaceError);
@@ -0,0 +1,217 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        ifs::{wl_buffer::WlBuffer, wl_surface::WlSurface},
+        leaks::Tracker,
+        object::Object,
+        state::State,
+        utils::{
+            buffd::{MsgParser, MsgParserError},
+            errorfmt::ErrorFmt,
+            oserror::OsError,
+        },
+        video::dmabuf::{dma_buf_export_sync_file, DMA_BUF_SYNC_WRITE},
+        wire::{
+            wp_linux_drm_syncobj_surface_v1::*, WlSurfaceId, WpLinuxDrmSyncobjSurfaceV1Id,
+            WpLinuxDrmSyncobjTimelineV1Id,
+        },
+    },
+    std::rc::Rc,
+    thiserror::Error,
+    uapi::{c, OwnedFd},
+};
+
+#[derive(Clone)]
+pub struct SyncObjPoint {
+    pub syncobj: Rc<OwnedFd>,
+    pub point: u64,
+}
+
+impl SyncObjPoint {
+    /// Returns whether a fence has been attached to the point.
+    ///
+    /// Errors are logged and treated as an available point so that the commit is not
+    /// deferred forever.
+    pub fn is_available(&self, state: &State) -> bool {
+        let Some(ctx) = state.render_ctx.get() else {
+            return true;
+        };
+        match ctx
+            .gbm()
+            .drm
+            .syncobj_point_available(&self.syncobj, self.point)
+        {
+            Ok(available) => available,
+            Err(e) => {
+                log::warn!("Could not query acquire point: {}", ErrorFmt(e));
+                true
+            }
+        }
+    }
+
+    /// Waits until a fence has been attached to the point.
+    pub async fn wait_available(&self, state: &State) {
+        let Some(ctx) = state.render_ctx.get() else {
+            return;
+        };
+        let eventfd = match uapi::eventfd(0, c::EFD_CLOEXEC) {
+            Ok(fd) => Rc::new(fd),
+            Err(e) => {
+                log::error!(
+                    "Could not create an eventfd: {}",
+                    ErrorFmt(OsError::from(e))
+                );
+                return;
+            }
+        };
+        let res = ctx
+            .gbm()
+            .drm
+            .syncobj_eventfd_available(&self.syncobj, self.point, &eventfd);
+        if let Err(e) = res {
+            log::error!("Could not wait for acquire point: {}", ErrorFmt(e));
+            return;
+        }
+        if let Err(e) = state.ring.readable(&eventfd).await {
+            log::error!(
+                "Could not wait for the eventfd to become readable: {}",
+                ErrorFmt(e)
+            );
+        }
+    }
+
+    /// Exports the fence of the acquire point as a sync file.
+    ///
+    /// Commits are only applied once the point has been materialized, so the fence can
+    /// always be exported.
+    pub fn export_acquire(&self, state: &State) -> Option<Rc<OwnedFd>> {
+        let ctx = state.render_ctx.get()?;
+        match ctx
+            .gbm()
+            .drm
+            .syncobj_export_sync_file(&self.syncobj, self.point)
+        {
+            Ok(sync_file) => Some(Rc::new(sync_file)),
+            Err(e) => {
+                log::warn!("Could not export acquire point: {}", ErrorFmt(e));
+                None
+            }
+        }
+    }
+
+    /// Signals the release point once all pending accesses to the buffer have completed.
+    pub fn signal_release(&self, state: &State, buffer: &WlBuffer) {
+        let Some(ctx) = state.render_ctx.get() else {
+            return;
+        };
+        // The renderers attach their release fence to every plane of the dmabuf, so
+        // the fences of the first plane cover all accesses.
+        let Some(plane) = buffer.dmabuf().and_then(|d| d.planes.first()) else {
+            return;
+        };
+        let sync_file = match dma_buf_export_sync_file(&plane.fd, DMA_BUF_SYNC_WRITE) {
+            Ok(sync_file) => sync_file,
+            Err(e) => {
+                log::error!("Could not export sync file from dmabuf: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let res = ctx
+            .gbm()
+            .drm
+            .syncobj_import_sync_file(&self.syncobj, self.point, &sync_file);
+        if let Err(e) = res {
+            log::error!("Could not signal release point: {}", ErrorFmt(e));
+        }
+    }
+}
+
+pub struct WpLinuxDrmSyncobjSurfaceV1 {
+    pub id: WpLinuxDrmSyncobjSurfaceV1Id,
+    pub client: Rc<Client>,
+    pub surface: Rc<WlSurface>,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpLinuxDrmSyncobjSurfaceV1 {
+    pub fn install(self: &Rc<Self>) -> Result<(), WpLinuxDrmSyncobjSurfaceV1Error> {
+        if self.surface.syncobj_surface.is_some() {
+            return Err(WpLinuxDrmSyncobjSurfaceV1Error::AlreadyAttached(
+                self.surface.id,
+            ));
+        }
+        self.surface.syncobj_surface.set(Some(self.clone()));
+        Ok(())
+    }
+
+    fn point(
+        &self,
+        timeline: WpLinuxDrmSyncobjTimelineV1Id,
+        point_hi: u32,
+        point_lo: u32,
+    ) -> Result<SyncObjPoint, WpLinuxDrmSyncobjSurfaceV1Error> {
+        if self.surface.syncobj_surface.is_none() {
+            return Err(WpLinuxDrmSyncobjSurfaceV1Error::NoSurface);
+        }
+        let timeline = self.client.lookup(timeline)?;
+        Ok(SyncObjPoint {
+            syncobj: timeline.syncobj.clone(),
+            point: ((point_hi as u64) << 32) | point_lo as u64,
+        })
+    }
+
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WpLinuxDrmSyncobjSurfaceV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.surface.pending.acquire_point.take();
+        self.surface.pending.release_point.take();
+        self.surface.syncobj_surface.take();
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn set_acquire_point(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), WpLinuxDrmSyncobjSurfaceV1Error> {
+        let req: SetAcquirePoint = self.client.parse(self, parser)?;
+        let point = self.point(req.timeline, req.point_hi, req.point_lo)?;
+        self.surface.pending.acquire_point.set(Some(point));
+        Ok(())
+    }
+
+    fn set_release_point(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), WpLinuxDrmSyncobjSurfaceV1Error> {
+        let req: SetReleasePoint = self.client.parse(self, parser)?;
+        let point = self.point(req.timeline, req.point_hi, req.point_lo)?;
+        self.surface.pending.release_point.set(Some(point));
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpLinuxDrmSyncobjSurfaceV1;
+
+    DESTROY => destroy,
+    SET_ACQUIRE_POINT => set_acquire_point,
+    SET_RELEASE_POINT => set_release_point,
+}
+
+impl Object for WpLinuxDrmSyncobjSurfaceV1 {}
+
+simple_add_obj!(WpLinuxDrmSyncobjSurfaceV1);
+
+#[derive(Debug, Error)]
+pub enum WpLinuxDrmSyncobjSurfaceV1Error {
+    #[error("Surface {0} already has a wp_linux_drm_syncobj_surface_v1")]
+    AlreadyAttached(WlSurfaceId),
+    #[error("The surface has been destroyed")]
+    NoSurface,
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+}
+efrom!(WpLinuxDrmSyncobjSurfaceV1Error, ClientError);
+efrom!(WpLinuxDrmSyncobjSurfaceV1Error, MsgParserError);
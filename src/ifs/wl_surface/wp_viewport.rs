@@ -45,8 +45,16 @@ impl WpViewport {
         Ok(())
     }
 
+    fn check_surface(&self) -> Result<(), WpViewportError> {
+        match self.surface.viewporter.get() {
+            Some(vp) if vp.id == self.id => Ok(()),
+            _ => Err(WpViewportError::NoSurface),
+        }
+    }
+
     fn set_source(&self, msg: MsgParser<'_, '_>) -> Result<(), WpViewportError> {
         let req: SetSource = self.client.parse(self, msg)?;
+        self.check_surface()?;
         let rect = if req.x == -1 && req.y == -1 && req.width == -1 && req.height == -1 {
             None
         } else {
@@ -62,6 +70,7 @@ impl WpViewport {
 
     fn set_destination(&self, msg: MsgParser<'_, '_>) -> Result<(), WpViewportError> {
         let req: SetDestination = self.client.parse(self, msg)?;
+        self.check_surface()?;
         let size = if req.width == -1 && req.height == -1 {
             None
         } else if req.width <= 0 || req.height <= 0 {
@@ -98,6 +107,8 @@ pub enum WpViewportError {
     InvalidSourceRect,
     #[error("Rectangle is empty")]
     InvalidDestRect,
+    #[error("The surface of the viewport has been destroyed")]
+    NoSurface,
 }
 efrom!(WpViewportError, MsgParserError);
 efrom!(WpViewportError, ClientError);
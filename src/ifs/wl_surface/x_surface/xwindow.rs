@@ -11,12 +11,16 @@ use {
         renderer::Renderer,
         state::State,
         tree::{
-            Direction, FindTreeResult, FoundNode, Node, NodeId, NodeVisitor, StackedNode,
-            ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
+            Direction, FindTreeResult, FoundNode, Node, NodeId, NodeVisitor, OutputNodeId,
+            SizeIncrements, StackedNode, ToplevelData, ToplevelNode, ToplevelNodeBase,
+            WorkspaceNode,
         },
         utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, linkedlist::LinkedNode},
         wire::WlSurfaceId,
         wire_xcon::CreateNotify,
+        xcon::consts::{
+            ICCCM_SIZE_HINT_BASE_SIZE, ICCCM_SIZE_HINT_P_MIN_SIZE, ICCCM_SIZE_HINT_P_RESIZE_INC,
+        },
         xwayland::XWaylandEvent,
     },
     bstr::BString,
@@ -135,6 +139,7 @@ pub struct Xwindow {
     pub x: Rc<XSurface>,
     pub display_link: RefCell<Option<LinkedNode<Rc<dyn StackedNode>>>>,
     pub toplevel_data: ToplevelData,
+    scanout_output: Cell<Option<OutputNodeId>>,
 }
 
 impl XwindowData {
@@ -145,7 +150,8 @@ impl XwindowData {
             event.width as _,
             event.height as _,
         )
-        .unwrap();
+        .unwrap()
+        .unscale_int(client.wire_scale);
         // log::info!("xwin {} new {:?} or {}", event.window, extents, event.override_redirect);
         Self {
             state: state.clone(),
@@ -217,6 +223,7 @@ impl Xwindow {
             display_link: Default::default(),
             toplevel_data: tld,
             x: xsurface,
+            scanout_output: Default::default(),
         });
         slf.x.xwindow.set(Some(slf.clone()));
         slf.x.surface.set_toplevel(Some(slf.clone()));
@@ -250,6 +257,41 @@ impl Xwindow {
         }
     }
 
+    /// Sends scanout feedback for the output if this override-redirect window covers
+    /// an entire output.
+    /// Returns the workspace the program that created this window was spawned on.
+    ///
+    /// All X windows belong to the Xwayland client, so the program is identified by the
+    /// pid of the window.
+    fn take_spawn_workspace(&self) -> Option<Rc<String>> {
+        let pid = self.data.info.pid.get()?;
+        self.data.state.spawn_workspaces.remove(&(pid as _))
+    }
+
+    fn update_scanout_feedback(&self) {
+        let mut output = None;
+        if self.display_link.borrow().is_some() {
+            let extents = self.data.info.extents.get();
+            for o in self.data.state.root.outputs.lock().values() {
+                if extents.contains_rect(&o.global.pos.get()) {
+                    output = Some(o.clone());
+                    break;
+                }
+            }
+        }
+        let id = output.as_ref().map(|o| o.id);
+        if self.scanout_output.replace(id) == id {
+            return;
+        }
+        let fb = match output {
+            Some(o) => o.global.connector.connector.drm_feedback(),
+            _ => self.data.client.drm_feedback(),
+        };
+        if let Some(fb) = fb {
+            self.x.surface.send_feedback(&fb);
+        }
+    }
+
     pub fn map_status_changed(self: &Rc<Self>) {
         let map_change = self.map_change();
         match map_change {
@@ -266,10 +308,14 @@ impl Xwindow {
                     .tl_change_extents(&self.data.info.pending_extents.get());
                 *self.display_link.borrow_mut() =
                     Some(self.data.state.root.stacked.add_last(self.clone()));
+                self.update_scanout_feedback();
                 self.data.state.tree_changed();
             }
             Change::Map if self.data.info.wants_floating.get() => {
-                let ws = self.data.state.float_map_ws();
+                let ws = match self.take_spawn_workspace() {
+                    Some(ws) => self.data.state.named_map_ws(&ws),
+                    _ => self.data.state.float_map_ws(),
+                };
                 let ext = self.data.info.pending_extents.get();
                 self.data
                     .state
@@ -277,7 +323,10 @@ impl Xwindow {
                 self.data.title_changed();
             }
             Change::Map => {
-                self.data.state.map_tiled(self.clone());
+                match self.take_spawn_workspace() {
+                    Some(ws) => self.data.state.map_tiled_on_named(self.clone(), &ws),
+                    _ => self.data.state.map_tiled(self.clone()),
+                }
                 self.data.title_changed();
             }
         }
@@ -397,6 +446,9 @@ impl ToplevelNodeBase for Xwindow {
             if old.position() != rect.position() {
                 self.x.surface.set_absolute_position(rect.x1(), rect.y1());
             }
+            if self.data.info.override_redirect.get() {
+                self.update_scanout_feedback();
+            }
         }
     }
 
@@ -414,6 +466,7 @@ impl ToplevelNodeBase for Xwindow {
 
     fn tl_destroy_impl(&self) {
         self.display_link.borrow_mut().take();
+        self.update_scanout_feedback();
         self.x.surface.destroy_node();
     }
 
@@ -424,6 +477,37 @@ impl ToplevelNodeBase for Xwindow {
     fn tl_scanout_surface(&self) -> Option<Rc<WlSurface>> {
         Some(self.x.surface.clone())
     }
+
+    fn tl_x11_class(&self) -> Option<(String, String)> {
+        let instance = self.data.info.instance.borrow();
+        let class = self.data.info.class.borrow();
+        match (&*instance, &*class) {
+            (Some(instance), Some(class)) => Some((instance.to_string(), class.to_string())),
+            _ => None,
+        }
+    }
+
+    fn tl_size_increments(&self) -> Option<SizeIncrements> {
+        let hints = &self.data.info.normal_hints;
+        let flags = hints.flags.get();
+        if flags & ICCCM_SIZE_HINT_P_RESIZE_INC == 0 {
+            return None;
+        }
+        let (base_width, base_height) = if flags & ICCCM_SIZE_HINT_BASE_SIZE != 0 {
+            (hints.base_width.get(), hints.base_height.get())
+        } else if flags & ICCCM_SIZE_HINT_P_MIN_SIZE != 0 {
+            (hints.min_width.get(), hints.min_height.get())
+        } else {
+            (0, 0)
+        };
+        let scale = self.data.client.wire_scale;
+        Some(SizeIncrements {
+            base_width: base_width / scale,
+            base_height: base_height / scale,
+            width_inc: (hints.width_inc.get() / scale).max(1),
+            height_inc: (hints.height_inc.get() / scale).max(1),
+        })
+    }
 }
 
 impl StackedNode for Xwindow {
@@ -432,6 +516,10 @@ impl StackedNode for Xwindow {
     fn stacked_set_visible(&self, visible: bool) {
         self.tl_set_visible(visible);
     }
+
+    fn stacked_scanout_surface(&self) -> Option<Rc<WlSurface>> {
+        Some(self.x.surface.clone())
+    }
 }
 
 #[derive(Debug, Error)]
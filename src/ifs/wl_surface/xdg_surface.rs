@@ -3,6 +3,7 @@ pub mod xdg_toplevel;
 
 use {
     crate::{
+        async_engine::SpawnedFuture,
         client::ClientError,
         ifs::{
             wl_surface::{
@@ -26,12 +27,19 @@ use {
         },
         wire::{xdg_surface::*, WlSurfaceId, XdgPopupId, XdgSurfaceId},
     },
-    std::{cell::Cell, fmt::Debug, rc::Rc},
+    std::{
+        cell::{Cell, RefCell},
+        fmt::Debug,
+        rc::Rc,
+    },
     thiserror::Error,
 };
 
 #[allow(dead_code)]
 const NOT_CONSTRUCTED: u32 = 1;
+
+/// The time after which a toplevel that has not acked a configure is considered unresponsive.
+const CONFIGURE_TIMEOUT_MS: u64 = 5000;
 const ALREADY_CONSTRUCTED: u32 = 2;
 #[allow(dead_code)]
 const UNCONFIGURED_BUFFER: u32 = 3;
@@ -60,6 +68,7 @@ pub struct XdgSurface {
     pub surface: Rc<WlSurface>,
     requested_serial: NumCell<u32>,
     acked_serial: Cell<Option<u32>>,
+    configure_timeout: RefCell<Option<SpawnedFuture<()>>>,
     geometry: Cell<Option<Rect>>,
     extents: Cell<Rect>,
     pub absolute_desired_extents: Cell<Rect>,
@@ -87,6 +96,14 @@ pub trait XdgSurfaceExt: Debug {
     fn extents_changed(&self) {
         // nothing
     }
+
+    fn configure_acked(&self) {
+        // nothing
+    }
+
+    fn configure_timed_out(self: Rc<Self>) {
+        // nothing
+    }
 }
 
 impl XdgSurface {
@@ -98,6 +115,7 @@ impl XdgSurface {
             surface: surface.clone(),
             requested_serial: NumCell::new(1),
             acked_serial: Cell::new(None),
+            configure_timeout: Default::default(),
             geometry: Cell::new(None),
             extents: Cell::new(Default::default()),
             absolute_desired_extents: Cell::new(Default::default()),
@@ -181,6 +199,26 @@ impl XdgSurface {
     pub fn do_send_configure(&self) {
         let serial = self.requested_serial.fetch_add(1) + 1;
         self.send_configure(serial);
+        if self.configure_timeout.borrow().is_none() {
+            self.start_configure_timeout();
+        }
+    }
+
+    fn start_configure_timeout(&self) {
+        if self.role.get() != XdgSurfaceRole::XdgToplevel {
+            return;
+        }
+        let Some(ext) = self.ext.get() else {
+            return;
+        };
+        let state = &self.surface.client.state;
+        let wheel = state.wheel.clone();
+        let future = state.eng.spawn(async move {
+            if wheel.timeout(CONFIGURE_TIMEOUT_MS).await.is_ok() {
+                ext.configure_timed_out();
+            }
+        });
+        *self.configure_timeout.borrow_mut() = Some(future);
     }
 
     pub fn send_configure(&self, serial: u32) {
@@ -288,6 +326,13 @@ impl XdgSurface {
         let req: AckConfigure = self.surface.client.parse(self, parser)?;
         if self.requested_serial.get() == req.serial {
             self.acked_serial.set(Some(req.serial));
+            self.configure_timeout.borrow_mut().take();
+        } else if self.configure_timeout.borrow().is_some() {
+            // The client is making progress. Give it more time for the remaining configures.
+            self.start_configure_timeout();
+        }
+        if let Some(ext) = self.ext.get() {
+            ext.configure_acked();
         }
         Ok(())
     }
@@ -348,6 +393,7 @@ object_base! {
 
 impl Object for XdgSurface {
     fn break_loops(&self) {
+        self.configure_timeout.borrow_mut().take();
         self.ext.take();
         self.popups.clear();
         self.workspace.set(None);
@@ -357,6 +403,10 @@ impl Object for XdgSurface {
 dedicated_add_obj!(XdgSurface, XdgSurfaceId, xdg_surfaces);
 
 impl SurfaceExt for XdgSurface {
+    fn role_name(&self) -> Option<&'static str> {
+        Some(self.role.get().name())
+    }
+
     fn pre_commit(self: Rc<Self>, _ctx: CommitContext) -> Result<CommitAction, WlSurfaceError> {
         {
             let ase = self.acked_serial.get();
@@ -30,6 +30,7 @@ use {
         wire::{xdg_toplevel::*, XdgToplevelId},
     },
     ahash::{AHashMap, AHashSet},
+    jay_config::UnresponsiveWindow,
     num_derive::FromPrimitive,
     std::{
         cell::{Cell, RefCell},
@@ -102,6 +103,8 @@ pub struct XdgToplevel {
     toplevel_data: ToplevelData,
     pub drag: CloneCell<Option<Rc<XdgToplevelDragV1>>>,
     is_mapped: Cell<bool>,
+    unresponsive: Cell<bool>,
+    occluded: Cell<bool>,
 }
 
 impl Debug for XdgToplevel {
@@ -140,6 +143,8 @@ impl XdgToplevel {
             ),
             drag: Default::default(),
             is_mapped: Cell::new(false),
+            unresponsive: Cell::new(false),
+            occluded: Cell::new(false),
         }
     }
 
@@ -153,6 +158,15 @@ impl XdgToplevel {
         self.xdg.do_send_configure();
     }
 
+    fn set_suspended(&self, suspended: bool) {
+        if suspended {
+            self.states.borrow_mut().insert(STATE_SUSPENDED);
+        } else {
+            self.states.borrow_mut().remove(&STATE_SUSPENDED);
+        }
+        self.send_current_configure();
+    }
+
     fn send_configure_checked(&self, mut width: i32, mut height: i32) {
         width = width.max(1);
         height = height.max(1);
@@ -199,6 +213,7 @@ impl XdgToplevel {
         let _req: Destroy = self.xdg.surface.client.parse(self.deref(), parser)?;
         self.tl_destroy();
         self.xdg.ext.set(None);
+        self.xdg.configure_timeout.borrow_mut().take();
         {
             let mut children = self.children.borrow_mut();
             let parent = self.parent.get();
@@ -357,9 +372,15 @@ impl XdgToplevel {
     }
 
     fn map_floating(self: &Rc<Self>, workspace: &Rc<WorkspaceNode>, abs_pos: Option<(i32, i32)>) {
-        let (width, height) = self.toplevel_data.float_size(workspace);
+        let mut workspace = workspace.clone();
+        if abs_pos.is_none() {
+            if let Some(ws) = self.xdg.surface.client.spawn_workspace.take() {
+                workspace = self.state.named_map_ws(&ws);
+            }
+        }
+        let (width, height) = self.toplevel_data.float_size(&workspace);
         self.state
-            .map_floating(self.clone(), width, height, workspace, abs_pos);
+            .map_floating(self.clone(), width, height, &workspace, abs_pos);
     }
 
     fn map_child(self: &Rc<Self>, parent: &XdgToplevel, pos: Option<(&Rc<OutputNode>, i32, i32)>) {
@@ -375,6 +396,10 @@ impl XdgToplevel {
     }
 
     fn map_tiled(self: &Rc<Self>) {
+        if let Some(ws) = self.xdg.surface.client.spawn_workspace.take() {
+            self.state.map_tiled_on_named(self.clone(), &ws);
+            return;
+        }
         self.state.map_tiled(self.clone());
     }
 
@@ -587,13 +612,18 @@ impl ToplevelNodeBase for XdgToplevel {
         //     log::info!("\n{:?}", Backtrace::new());
         // }
         self.xdg.set_visible(visible);
+        self.occluded.set(false);
         if self.xdg.base.version >= SUSPENDED_SINCE {
-            if visible {
-                self.states.borrow_mut().remove(&STATE_SUSPENDED);
-            } else {
-                self.states.borrow_mut().insert(STATE_SUSPENDED);
-            }
-            self.send_current_configure();
+            self.set_suspended(!visible);
+        }
+    }
+
+    fn tl_set_occluded(&self, occluded: bool) {
+        if self.occluded.replace(occluded) == occluded {
+            return;
+        }
+        if self.xdg.base.version >= SUSPENDED_SINCE {
+            self.set_suspended(occluded);
         }
     }
 
@@ -654,6 +684,33 @@ impl XdgSurfaceExt for XdgToplevel {
         self.toplevel_data.pos.set(self.xdg.extents.get());
         self.tl_extents_changed();
     }
+
+    fn configure_acked(&self) {
+        if self.unresponsive.replace(false) {
+            log::info!(
+                "Toplevel {} is responsive again",
+                self.toplevel_data.title.borrow()
+            );
+        }
+    }
+
+    fn configure_timed_out(self: Rc<Self>) {
+        self.xdg.configure_timeout.borrow_mut().take();
+        if self.unresponsive.replace(true) {
+            return;
+        }
+        let title = self.toplevel_data.title.borrow().clone();
+        log::warn!("Toplevel {} did not respond to a configure event", title);
+        if let Some(config) = self.state.config.get() {
+            let client = &self.xdg.surface.client;
+            config.window_unresponsive(UnresponsiveWindow {
+                app_id: self.toplevel_data.app_id.borrow().clone(),
+                title,
+                pid: client.pid_info.pid as _,
+                comm: client.pid_info.comm.clone(),
+            });
+        }
+    }
 }
 
 #[derive(Debug, Error)]
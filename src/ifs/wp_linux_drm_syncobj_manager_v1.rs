@@ -0,0 +1,145 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        globals::{Global, GlobalName},
+        ifs::{
+            wl_surface::wp_linux_drm_syncobj_surface_v1::{
+                WpLinuxDrmSyncobjSurfaceV1, WpLinuxDrmSyncobjSurfaceV1Error,
+            },
+            wp_linux_drm_syncobj_timeline_v1::WpLinuxDrmSyncobjTimelineV1,
+        },
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        video::drm::DrmError,
+        wire::{wp_linux_drm_syncobj_manager_v1::*, WpLinuxDrmSyncobjManagerV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+};
+
+pub struct WpLinuxDrmSyncobjManagerV1Global {
+    name: GlobalName,
+}
+
+impl WpLinuxDrmSyncobjManagerV1Global {
+    pub fn new(name: GlobalName) -> Self {
+        Self { name }
+    }
+
+    fn bind_(
+        self: Rc<Self>,
+        id: WpLinuxDrmSyncobjManagerV1Id,
+        client: &Rc<Client>,
+        _version: u32,
+    ) -> Result<(), WpLinuxDrmSyncobjManagerV1Error> {
+        let obj = Rc::new(WpLinuxDrmSyncobjManagerV1 {
+            id,
+            client: client.clone(),
+            tracker: Default::default(),
+        });
+        track!(client, obj);
+        client.add_client_obj(&obj)?;
+        Ok(())
+    }
+}
+
+global_base!(
+    WpLinuxDrmSyncobjManagerV1Global,
+    WpLinuxDrmSyncobjManagerV1,
+    WpLinuxDrmSyncobjManagerV1Error
+);
+
+impl Global for WpLinuxDrmSyncobjManagerV1Global {
+    fn singleton(&self) -> bool {
+        true
+    }
+
+    fn version(&self) -> u32 {
+        1
+    }
+}
+
+simple_add_global!(WpLinuxDrmSyncobjManagerV1Global);
+
+pub struct WpLinuxDrmSyncobjManagerV1 {
+    pub id: WpLinuxDrmSyncobjManagerV1Id,
+    pub client: Rc<Client>,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpLinuxDrmSyncobjManagerV1 {
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WpLinuxDrmSyncobjManagerV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+
+    fn get_surface(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), WpLinuxDrmSyncobjManagerV1Error> {
+        let req: GetSurface = self.client.parse(self, parser)?;
+        let surface = self.client.lookup(req.surface)?;
+        let sync = Rc::new(WpLinuxDrmSyncobjSurfaceV1 {
+            id: req.id,
+            client: self.client.clone(),
+            surface,
+            tracker: Default::default(),
+        });
+        track!(self.client, sync);
+        self.client.add_client_obj(&sync)?;
+        sync.install()?;
+        Ok(())
+    }
+
+    fn import_timeline(
+        &self,
+        parser: MsgParser<'_, '_>,
+    ) -> Result<(), WpLinuxDrmSyncobjManagerV1Error> {
+        let req: ImportTimeline = self.client.parse(self, parser)?;
+        let Some(ctx) = self.client.state.render_ctx.get() else {
+            return Err(WpLinuxDrmSyncobjManagerV1Error::NoRenderContext);
+        };
+        if let Err(e) = ctx.gbm().drm.syncobj_validate(&req.fd) {
+            return Err(WpLinuxDrmSyncobjManagerV1Error::InvalidTimeline(e));
+        }
+        let timeline = Rc::new(WpLinuxDrmSyncobjTimelineV1 {
+            id: req.id,
+            client: self.client.clone(),
+            syncobj: req.fd,
+            tracker: Default::default(),
+        });
+        track!(self.client, timeline);
+        self.client.add_client_obj(&timeline)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpLinuxDrmSyncobjManagerV1;
+
+    DESTROY => destroy,
+    GET_SURFACE => get_surface,
+    IMPORT_TIMELINE => import_timeline,
+}
+
+impl Object for WpLinuxDrmSyncobjManagerV1 {}
+
+simple_add_obj!(WpLinuxDrmSyncobjManagerV1);
+
+#[derive(Debug, Error)]
+pub enum WpLinuxDrmSyncobjManagerV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+    #[error(transparent)]
+    WpLinuxDrmSyncobjSurfaceV1Error(#[from] WpLinuxDrmSyncobjSurfaceV1Error),
+    #[error("The compositor has no render context")]
+    NoRenderContext,
+    #[error("The file descriptor is not a valid syncobj")]
+    InvalidTimeline(#[source] DrmError),
+}
+efrom!(WpLinuxDrmSyncobjManagerV1Error, ClientError);
+efrom!(WpLinuxDrmSyncobjManagerV1Error, MsgParserError);
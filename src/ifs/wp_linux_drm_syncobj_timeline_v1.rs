@@ -0,0 +1,51 @@
+use {
+    crate::{
+        client::{Client, ClientError},
+        leaks::Tracker,
+        object::Object,
+        utils::buffd::{MsgParser, MsgParserError},
+        wire::{wp_linux_drm_syncobj_timeline_v1::*, WpLinuxDrmSyncobjTimelineV1Id},
+    },
+    std::rc::Rc,
+    thiserror::Error,
+    uapi::OwnedFd,
+};
+
+pub struct WpLinuxDrmSyncobjTimelineV1 {
+    pub id: WpLinuxDrmSyncobjTimelineV1Id,
+    pub client: Rc<Client>,
+    pub syncobj: Rc<OwnedFd>,
+    pub tracker: Tracker<Self>,
+}
+
+impl WpLinuxDrmSyncobjTimelineV1 {
+    fn destroy(&self, parser: MsgParser<'_, '_>) -> Result<(), WpLinuxDrmSyncobjTimelineV1Error> {
+        let _req: Destroy = self.client.parse(self, parser)?;
+        self.client.remove_obj(self)?;
+        Ok(())
+    }
+}
+
+object_base! {
+    self = WpLinuxDrmSyncobjTimelineV1;
+
+    DESTROY => destroy,
+}
+
+impl Object for WpLinuxDrmSyncobjTimelineV1 {}
+
+dedicated_add_obj!(
+    WpLinuxDrmSyncobjTimelineV1,
+    WpLinuxDrmSyncobjTimelineV1Id,
+    syncobj_timelines
+);
+
+#[derive(Debug, Error)]
+pub enum WpLinuxDrmSyncobjTimelineV1Error {
+    #[error("Parsing failed")]
+    MsgParserError(#[source] Box<MsgParserError>),
+    #[error(transparent)]
+    ClientError(Box<ClientError>),
+}
+efrom!(WpLinuxDrmSyncobjTimelineV1Error, ClientError);
+efrom!(WpLinuxDrmSyncobjTimelineV1Error, MsgParserError);
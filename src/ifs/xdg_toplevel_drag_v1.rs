@@ -31,7 +31,7 @@ impl XdgToplevelDragV1 {
     pub fn new(id: XdgToplevelDragV1Id, source: &Rc<WlDataSource>) -> Self {
         Self {
             id,
-            client: source.data.client.clone(),
+            client: source.data.owner().clone(),
             source: source.clone(),
             tracker: Default::default(),
             toplevel: Default::default(),
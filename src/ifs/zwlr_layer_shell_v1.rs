@@ -74,9 +74,8 @@ impl ZwlrLayerShellV1 {
                         break 'get_output output;
                     }
                 }
-                let outputs = self.client.state.outputs.lock();
-                if let Some(output) = outputs.values().next() {
-                    break 'get_output output.node.clone();
+                if let Some(output) = self.client.state.primary_output() {
+                    break 'get_output output;
                 }
                 return Err(ZwlrLayerShellV1Error::NoOutputs);
             }
@@ -45,6 +45,13 @@ impl ZwpLinuxDmabufV1Global {
                     obj.send_format(format.format.drm);
                     if version >= MODIFIERS_SINCE_VERSION {
                         for &modifier in &format.read_modifiers {
+                            if client
+                                .state
+                                .disabled_modifiers
+                                .contains(&(format.format.drm, modifier))
+                            {
+                                continue;
+                            }
                             obj.send_modifier(format.format.drm, modifier);
                         }
                     }
@@ -129,7 +136,7 @@ impl ZwpLinuxDmabufV1 {
             .state
             .drm_feedback_consumers
             .set((self.client.id, id), fb.clone());
-        if let Some(feedback) = self.client.state.drm_feedback.get() {
+        if let Some(feedback) = self.client.drm_feedback() {
             fb.send_feedback(&feedback);
         }
         Ok(fb)
@@ -12,7 +12,6 @@ use {
 };
 
 pub const NAME_SINCE: u32 = 2;
-#[allow(dead_code)]
 pub const DESCRIPTION_SINCE: u32 = 2;
 pub const NO_DONE_SINCE: u32 = 3;
 
@@ -52,7 +51,6 @@ impl ZxdgOutputV1 {
         });
     }
 
-    #[allow(dead_code)]
     pub fn send_description(&self, description: &str) {
         self.client.event(Description {
             self_id: self.id,
@@ -61,12 +59,19 @@ impl ZxdgOutputV1 {
     }
 
     pub fn send_updates(&self) {
-        let pos = self.output.global.position();
+        let pos = self
+            .output
+            .global
+            .position()
+            .scale_int(self.client.wire_scale);
         self.send_logical_position(pos.x1(), pos.y1());
         self.send_logical_size(pos.width(), pos.height());
         if self.version >= NAME_SINCE {
             self.send_name(&self.output.global.connector.name);
         }
+        if self.version >= DESCRIPTION_SINCE {
+            self.send_description(&self.output.global.description());
+        }
         if self.version >= NO_DONE_SINCE {
             if self.output.version >= SEND_DONE_SINCE {
                 self.output.send_done();
@@ -1,22 +1,31 @@
 use {
-    crate::io_uring::{
-        ops::TaskResult,
-        pending_result::PendingResult,
-        sys::{io_uring_sqe, IORING_OP_POLL_ADD},
-        IoUring, IoUringData, IoUringError, Task, TaskResultExt,
+    crate::{
+        io_uring::{
+            ops::TaskResult,
+            pending_result::PendingResult,
+            sys::{io_uring_sqe, IORING_OP_POLL_ADD},
+            IoUring, IoUringData, IoUringError, Task, TaskResultExt,
+        },
+        time::Time,
     },
     std::rc::Rc,
     uapi::{c, OwnedFd},
 };
 
 impl IoUring {
-    pub async fn poll(&self, fd: &Rc<OwnedFd>, events: c::c_short) -> TaskResult<c::c_short> {
+    pub async fn poll(
+        &self,
+        fd: &Rc<OwnedFd>,
+        events: c::c_short,
+        timeout: Option<Time>,
+    ) -> TaskResult<c::c_short> {
         self.ring.check_destroyed()?;
         let id = self.ring.id();
         let pr = self.ring.pending_results.acquire();
         {
             let mut pw = self.ring.cached_polls.pop().unwrap_or_default();
             pw.id = id.id;
+            pw.has_timeout = timeout.is_some();
             pw.fd = fd.raw() as _;
             pw.events = events as _;
             pw.data = Some(Data {
@@ -24,17 +33,28 @@ impl IoUring {
                 _fd: fd.clone(),
             });
             self.ring.schedule(pw);
+            if let Some(time) = timeout {
+                self.schedule_timeout(time);
+            }
         }
         Ok(pr.await.map(|v| v as c::c_short))
     }
 
     pub async fn readable(&self, fd: &Rc<OwnedFd>) -> Result<c::c_short, IoUringError> {
-        self.poll(fd, c::POLLIN).await.merge()
+        self.poll(fd, c::POLLIN, None).await.merge()
+    }
+
+    pub async fn readable_with_timeout(
+        &self,
+        fd: &Rc<OwnedFd>,
+        timeout: Time,
+    ) -> Result<c::c_short, IoUringError> {
+        self.poll(fd, c::POLLIN, Some(timeout)).await.merge()
     }
 
     #[allow(dead_code)]
     pub async fn writable(&self, fd: &Rc<OwnedFd>) -> Result<c::c_short, IoUringError> {
-        self.poll(fd, c::POLLOUT).await.merge()
+        self.poll(fd, c::POLLOUT, None).await.merge()
     }
 }
 
@@ -46,6 +66,7 @@ struct Data {
 #[derive(Default)]
 pub struct PollTask {
     id: u64,
+    has_timeout: bool,
     events: u16,
     fd: i32,
     data: Option<Data>,
@@ -68,4 +89,8 @@ unsafe impl Task for PollTask {
         sqe.fd = self.fd;
         sqe.u3.poll_events = self.events;
     }
+
+    fn has_timeout(&self) -> bool {
+        self.has_timeout
+    }
 }
@@ -9,6 +9,7 @@ use {
         },
         leaks,
         utils::{errorfmt::ErrorFmt, num_cpus::num_cpus},
+        wheel::Wheel,
     },
     ahash::AHashMap,
     futures_util::{future, future::Either},
@@ -126,6 +127,7 @@ fn run_test(it_run: &ItRun, test: &'static dyn TestCase, cfg: Rc<TestConfig>) {
             addr
         };
         let backend: Rc<TestBackend> = state.backend.get().into_any().downcast().unwrap();
+        cfg.backend.set(Some(backend.clone()));
         let testrun = Rc::new(TestRun {
             state: state.clone(),
             backend,
@@ -139,7 +141,9 @@ fn run_test(it_run: &ItRun, test: &'static dyn TestCase, cfg: Rc<TestConfig>) {
         Box::new(async move {
             let future: Pin<_> = test.run(testrun.clone()).into();
             let future = state.eng.spawn2(Phase::Present, future);
-            let timeout = state.wheel.timeout(5000);
+            // The compositor runs on a virtual clock, so the test timeout needs its own wheel.
+            let wheel = Wheel::new(&state.eng, &state.ring).unwrap();
+            let timeout = wheel.timeout(5000);
             match future::select(future, timeout).await {
                 Either::Left((Ok(..), _)) => {}
                 Either::Left((Err(e), _)) => {
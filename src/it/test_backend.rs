@@ -14,11 +14,13 @@ use {
         state::State,
         time::now_usec,
         utils::{
-            clonecell::CloneCell, copyhashmap::CopyHashMap, oserror::OsError, syncqueue::SyncQueue,
+            clonecell::CloneCell, copyhashmap::CopyHashMap, numcell::NumCell, oserror::OsError,
+            syncqueue::SyncQueue,
         },
         video::drm::{ConnectorType, Drm},
     },
     bstr::ByteSlice,
+    jay_config::video::HotplugPolicy,
     std::{any::Any, cell::Cell, io, os::unix::ffi::OsStrExt, pin::Pin, rc::Rc},
     thiserror::Error,
     uapi::c,
@@ -55,6 +57,8 @@ impl TestBackend {
             },
             events: Default::default(),
             on_change: Default::default(),
+            hotplug_policy: Default::default(),
+            accepted_hotplugs: Default::default(),
         });
         let default_mouse = Rc::new(TestBackendMouse {
             common: TestInputDeviceCommon {
@@ -98,6 +102,12 @@ impl TestBackend {
         }
     }
 
+    pub fn input_device(&self, id: InputDeviceId) -> Option<&TestInputDeviceCommon> {
+        [&self.default_kb.common, &self.default_mouse.common]
+            .into_iter()
+            .find(|c| c.id == id)
+    }
+
     pub fn install_render_context(&self) -> TestResult {
         if self.render_context_installed.get() {
             return Ok(());
@@ -112,22 +122,9 @@ impl TestBackend {
         self.state
             .backend_events
             .push(BackendEvent::NewConnector(self.default_connector.clone()));
-        let mode = Mode {
-            width: 800,
-            height: 600,
-            refresh_rate_millihz: 60_000,
-        };
         self.default_connector
             .events
-            .push(ConnectorEvent::Connected(MonitorInfo {
-                modes: vec![mode],
-                manufacturer: "jay".to_string(),
-                product: "TestConnector".to_string(),
-                serial_number: self.default_connector.id.to_string(),
-                initial_mode: mode,
-                width_mm: 80,
-                height_mm: 60,
-            }));
+            .push(ConnectorEvent::Connected(self.default_monitor_info()));
         self.state
             .backend_events
             .push(BackendEvent::NewInputDevice(self.default_kb.clone()));
@@ -137,6 +134,31 @@ impl TestBackend {
         Ok(())
     }
 
+    pub fn default_monitor_info(&self) -> MonitorInfo {
+        let mode = Mode {
+            width: 800,
+            height: 600,
+            refresh_rate_millihz: 60_000,
+        };
+        MonitorInfo {
+            modes: vec![
+                mode,
+                Mode {
+                    width: 1024,
+                    height: 768,
+                    refresh_rate_millihz: 60_000,
+                },
+            ],
+            manufacturer: "jay".to_string(),
+            product: "TestConnector".to_string(),
+            serial_number: self.default_connector.id.to_string(),
+            initial_mode: mode,
+            width_mm: 80,
+            height_mm: 60,
+            non_desktop: false,
+        }
+    }
+
     fn create_render_context(&self) -> Result<(), TestBackendError> {
         let dri = match std::fs::read_dir("/dev/dri") {
             Ok(d) => d,
@@ -216,6 +238,17 @@ pub struct TestConnector {
     pub kernel_id: ConnectorKernelId,
     pub events: SyncQueue<ConnectorEvent>,
     pub on_change: CloneCell<Option<Rc<dyn Fn()>>>,
+    pub hotplug_policy: Cell<HotplugPolicy>,
+    pub accepted_hotplugs: NumCell<u32>,
+}
+
+impl TestConnector {
+    pub fn send_event(&self, event: ConnectorEvent) {
+        self.events.push(event);
+        if let Some(cb) = self.on_change.get() {
+            cb();
+        }
+    }
 }
 
 impl Connector for TestConnector {
@@ -246,6 +279,18 @@ impl Connector for TestConnector {
     fn set_enabled(&self, _enabled: bool) {
         // todo
     }
+
+    fn set_mode(&self, mode: Mode) {
+        self.send_event(ConnectorEvent::ModeChanged(mode));
+    }
+
+    fn set_hotplug_policy(&self, policy: HotplugPolicy) {
+        self.hotplug_policy.set(policy);
+    }
+
+    fn accept_hotplug(&self) {
+        self.accepted_hotplugs.fetch_add(1);
+    }
 }
 
 pub struct TestMouseClick {
@@ -1,23 +1,31 @@
 use {
     crate::{
-        backend::InputDeviceId,
+        backend::{ConnectorId, InputDeviceId, InputEvent, KeyState},
+        fixed::Fixed,
         ifs::wl_seat::SeatId,
-        it::test_error::{TestError, TestResult},
-        utils::{copyhashmap::CopyHashMap, stack::Stack},
+        it::{
+            test_backend::{TestBackend, TestInputDeviceCommon},
+            test_error::{TestError, TestResult},
+        },
+        time::{now_usec, VirtualClock},
+        tree::NodeId,
+        utils::{clonecell::CloneCell, copyhashmap::CopyHashMap, stack::Stack},
     },
     bincode::Options,
     isnt::std_1::primitive::IsntConstPtrExt,
     jay_config::{
         _private::{
             bincode_ops,
-            ipc::{ClientMessage, Response, ServerMessage},
+            ipc::{ClientMessage, FocusedWindow, Response, ServerMessage},
             ConfigEntry, VERSION,
         },
-        input::{InputDevice, Seat},
+        input::{InputDevice, PointerInfo, Seat},
         keyboard::{Keymap, ModifiedKeySym},
-        Axis, Direction,
+        timer::{MissedTicks, Timer},
+        video::{Connector, HotplugPolicy, OutputLayout},
+        Axis, Direction, EmptyWorkspaceAction, Window, WindowInfo,
     },
-    std::{cell::Cell, ops::Deref, ptr, rc::Rc},
+    std::{cell::Cell, ops::Deref, ptr, rc::Rc, time::Duration},
 };
 
 pub static TEST_CONFIG_ENTRY: ConfigEntry = ConfigEntry {
@@ -40,6 +48,10 @@ where
         responses: Default::default(),
         invoked_shortcuts: Default::default(),
         graphics_initialized: Cell::new(false),
+        backend: Default::default(),
+        idle: Cell::new(false),
+        timer_ticks: Default::default(),
+        hotplugs: Default::default(),
     });
     let old = CONFIG.get();
     CONFIG.set(tc.deref());
@@ -99,13 +111,23 @@ unsafe extern "C" fn handle_msg(data: *const u8, msg: *const u8, size: usize) {
         ServerMessage::ConnectorDisconnect { .. } => {}
         ServerMessage::NewConnector { .. } => {}
         ServerMessage::DelConnector { .. } => {}
-        ServerMessage::TimerExpired { .. } => {}
+        ServerMessage::TimerExpired { timer } => {
+            let ticks = tc.timer_ticks.get(&timer).unwrap_or(0);
+            tc.timer_ticks.set(timer, ticks + 1);
+        }
         ServerMessage::GraphicsInitialized => tc.graphics_initialized.set(true),
         ServerMessage::Clear => tc.clear(),
         ServerMessage::NewDrmDev { .. } => {}
         ServerMessage::DelDrmDev { .. } => {}
-        ServerMessage::Idle => {}
+        ServerMessage::Idle => tc.idle.set(true),
         ServerMessage::DevicesEnumerated => {}
+        ServerMessage::ConnectorScaleChanged { .. } => {}
+        ServerMessage::ConnectorTransformChanged { .. } => {}
+        ServerMessage::ConnectorHotplug { connector } => {
+            tc.hotplugs.set(ConnectorId::from_raw(connector.0 as _), ());
+        }
+        ServerMessage::OutputsReady => {}
+        ServerMessage::WindowUnresponsive { .. } => {}
     }
 }
 
@@ -121,6 +143,10 @@ pub struct TestConfig {
     responses: Stack<Response>,
     pub invoked_shortcuts: CopyHashMap<(SeatId, ModifiedKeySym), ()>,
     pub graphics_initialized: Cell<bool>,
+    pub backend: CloneCell<Option<Rc<TestBackend>>>,
+    pub idle: Cell<bool>,
+    pub timer_ticks: CopyHashMap<Timer, u64>,
+    pub hotplugs: CopyHashMap<ConnectorId, ()>,
 }
 
 macro_rules! get_response {
@@ -164,6 +190,10 @@ impl TestConfig {
         self.send(ClientMessage::Quit)
     }
 
+    pub fn reload(&self) -> TestResult {
+        self.send(ClientMessage::Reload)
+    }
+
     pub fn get_seat(&self, name: &str) -> Result<SeatId, TestError> {
         let reply = self.send_with_reply(ClientMessage::GetSeat { name })?;
         get_response!(reply, GetSeat { seat });
@@ -179,6 +209,10 @@ impl TestConfig {
         })
     }
 
+    pub fn set_workspace_switch_animation(&self, axis: Axis, duration: Duration) -> TestResult {
+        self.send(ClientMessage::SetWorkspaceSwitchAnimation { axis, duration })
+    }
+
     pub fn parse_keymap(&self, keymap: &str) -> Result<Keymap, TestError> {
         let reply = self.send_with_reply(ClientMessage::ParseKeymap { keymap })?;
         get_response!(reply, ParseKeymap { keymap });
@@ -202,6 +236,57 @@ impl TestConfig {
         })
     }
 
+    pub fn flush_layout(&self) -> TestResult {
+        self.send(ClientMessage::FlushLayout)
+    }
+
+    pub fn set_empty_workspace_action(&self, action: EmptyWorkspaceAction) -> TestResult {
+        self.send(ClientMessage::SetEmptyWorkspaceAction { action })
+    }
+
+    pub fn set_commit_throttling(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetCommitThrottling { enabled })
+    }
+
+    pub fn set_hotplug_debounce_ms(&self, ms: u64) -> TestResult {
+        self.send(ClientMessage::SetHotplugDebounceMs { ms })
+    }
+
+    pub fn set_persistent_clipboard(&self, enabled: bool) -> TestResult {
+        self.send(ClientMessage::SetPersistentClipboard { enabled })
+    }
+
+    pub fn set_floating(&self, seat: SeatId, floating: bool) -> TestResult {
+        self.send(ClientMessage::SetFloating {
+            seat: Seat(seat.raw() as _),
+            floating,
+        })
+    }
+
+    pub fn set_hotplug_policy(&self, connector: ConnectorId, policy: HotplugPolicy) -> TestResult {
+        self.send(ClientMessage::ConnectorSetHotplugPolicy {
+            connector: Connector(connector.raw() as _),
+            policy,
+        })
+    }
+
+    pub fn accept_hotplug(&self, connector: ConnectorId) -> TestResult {
+        self.send(ClientMessage::ConnectorAcceptHotplug {
+            connector: Connector(connector.raw() as _),
+        })
+    }
+
+    pub fn set_throttle_frame_callbacks(
+        &self,
+        connector: ConnectorId,
+        throttle: bool,
+    ) -> TestResult {
+        self.send(ClientMessage::ConnectorSetThrottleFrameCallbacks {
+            connector: Connector(connector.raw() as _),
+            throttle,
+        })
+    }
+
     pub fn set_mono(&self, seat: SeatId, mono: bool) -> TestResult {
         self.send(ClientMessage::SetMono {
             seat: Seat(seat.raw() as _),
@@ -243,7 +328,184 @@ impl TestConfig {
         })
     }
 
+    pub fn get_focused(&self, seat: SeatId) -> Result<Option<FocusedWindow>, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetFocusedWindow {
+            seat: Seat(seat.raw() as _),
+        })?;
+        get_response!(reply, GetFocusedWindow { window });
+        Ok(window)
+    }
+
+    pub fn assert_focused(&self, seat: SeatId, node: Option<NodeId>) -> TestResult {
+        let focused = self.get_focused(seat)?;
+        let expected = node.map(|n| Window(n.raw() as _));
+        if focused.as_ref().map(|w| w.window) != expected {
+            bail!(
+                "Expected node {:?} to have focus but {:?} has focus",
+                expected,
+                focused,
+            );
+        }
+        Ok(())
+    }
+
+    pub fn get_window_info(&self, window: Window) -> Result<Option<WindowInfo>, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetWindowInfo { window })?;
+        get_response!(reply, GetWindowInfo { info });
+        Ok(info)
+    }
+
+    pub fn focus_window(&self, seat: SeatId, window: Window) -> TestResult {
+        self.send(ClientMessage::FocusWindow {
+            window,
+            seat: Seat(seat.raw() as _),
+        })
+    }
+
+    pub fn close_window(&self, window: Window) -> TestResult {
+        self.send(ClientMessage::CloseWindow { window })
+    }
+
+    pub fn move_window_to_workspace(&self, window: Window, name: &str) -> TestResult {
+        let reply = self.send_with_reply(ClientMessage::GetWorkspace { name })?;
+        get_response!(reply, GetWorkspace { workspace });
+        self.send(ClientMessage::MoveWindowToWorkspace { window, workspace })
+    }
+
+    pub fn set_clipboard(&self, seat: SeatId, mime_type: &str, data: &[u8]) -> TestResult {
+        self.send(ClientMessage::SetClipboard {
+            seat: Seat(seat.raw() as _),
+            mime_type,
+            data,
+        })
+    }
+
+    pub fn get_clipboard(
+        &self,
+        seat: SeatId,
+        mime_type: &str,
+    ) -> Result<Option<Vec<u8>>, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetClipboard {
+            seat: Seat(seat.raw() as _),
+            mime_type,
+        })?;
+        get_response!(reply, GetClipboard { data });
+        Ok(data)
+    }
+
+    pub fn get_pointer(&self, seat: SeatId) -> Result<PointerInfo, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetPointer {
+            seat: Seat(seat.raw() as _),
+        })?;
+        get_response!(reply, GetPointer { info });
+        Ok(info)
+    }
+
+    pub fn set_pointer_position(&self, seat: SeatId, x: i32, y: i32) -> TestResult {
+        self.send(ClientMessage::SetPointerPosition {
+            seat: Seat(seat.raw() as _),
+            x,
+            y,
+        })
+    }
+
+    pub fn get_output_layout(&self) -> Result<OutputLayout, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetOutputLayout)?;
+        get_response!(reply, GetOutputLayout { layout });
+        Ok(layout)
+    }
+
+    pub fn apply_output_layout(&self, layout: OutputLayout) -> TestResult {
+        self.send(ClientMessage::ApplyOutputLayout { layout })
+    }
+
+    pub fn get_seat_idle_time(&self, seat: SeatId) -> Result<u64, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetSeatIdleTime {
+            seat: Seat(seat.raw() as _),
+        })?;
+        get_response!(reply, GetSeatIdleTime { ms });
+        Ok(ms)
+    }
+
+    pub fn get_timer(&self, name: &str) -> Result<Timer, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetTimer { name })?;
+        get_response!(reply, GetTimer { timer });
+        Ok(timer)
+    }
+
+    pub fn program_timer(
+        &self,
+        timer: Timer,
+        initial: Option<Duration>,
+        periodic: Option<Duration>,
+        missed: MissedTicks,
+    ) -> TestResult {
+        self.send(ClientMessage::ProgramTimer2 {
+            timer,
+            initial,
+            periodic,
+            missed,
+        })
+    }
+
+    pub fn get_timer_remaining(&self, timer: Timer) -> Result<Option<Duration>, TestError> {
+        let reply = self.send_with_reply(ClientMessage::GetTimerRemaining { timer })?;
+        get_response!(reply, TimerRemaining { remaining });
+        Ok(remaining)
+    }
+
+    pub fn advance_time(&self, duration: Duration) -> TestResult {
+        let Some(backend) = self.backend.get() else {
+            bail!("backend not set");
+        };
+        let state = &backend.state;
+        let Ok(clock) = state.clock.clone().into_any().downcast::<VirtualClock>() else {
+            bail!("The compositor does not use a virtual clock");
+        };
+        clock.advance(duration);
+        state.wheel.time_advanced();
+        state.idle.handle_clock_advanced();
+        Ok(())
+    }
+
+    fn with_input_device<F>(&self, id: InputDeviceId, f: F) -> TestResult
+    where
+        F: FnOnce(&TestInputDeviceCommon),
+    {
+        let Some(backend) = self.backend.get() else {
+            bail!("backend not set");
+        };
+        match backend.input_device(id) {
+            Some(dev) => f(dev),
+            _ => bail!("Input device {} does not exist", id),
+        }
+        Ok(())
+    }
+
+    pub fn send_key(&self, device: InputDeviceId, key: u32, state: KeyState) -> TestResult {
+        self.with_input_device(device, |dev| {
+            dev.event(InputEvent::Key {
+                time_usec: now_usec(),
+                key,
+                state,
+            })
+        })
+    }
+
+    pub fn send_pointer_motion(&self, device: InputDeviceId, dx: f64, dy: f64) -> TestResult {
+        self.with_input_device(device, |dev| {
+            dev.event(InputEvent::Motion {
+                time_usec: now_usec(),
+                dx: Fixed::from_f64(dx),
+                dy: Fixed::from_f64(dy),
+                dx_unaccelerated: Fixed::from_f64(dx),
+                dy_unaccelerated: Fixed::from_f64(dy),
+            })
+        })
+    }
+
     fn clear(&self) {
+        self.backend.take();
         unsafe {
             if let Some(srv) = self.srv.take() {
                 (srv.srv_unref)(srv.srv_data);
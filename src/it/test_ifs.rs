@@ -1,5 +1,11 @@
 pub mod test_callback;
 pub mod test_compositor;
+pub mod test_content_type;
+pub mod test_content_type_manager;
+pub mod test_data_device;
+pub mod test_data_device_manager;
+pub mod test_data_offer;
+pub mod test_data_source;
 pub mod test_display;
 pub mod test_jay_compositor;
 pub mod test_keyboard;
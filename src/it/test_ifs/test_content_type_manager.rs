@@ -0,0 +1,55 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_content_type::TestContentType,
+            test_object::TestObject, test_transport::TestTransport,
+        },
+        wire::{wp_content_type_manager_v1::*, WlSurfaceId, WpContentTypeManagerV1Id},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestContentTypeManager {
+    pub id: WpContentTypeManagerV1Id,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+}
+
+impl TestContentTypeManager {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn get_surface_content_type(
+        &self,
+        surface: WlSurfaceId,
+    ) -> Result<Rc<TestContentType>, TestError> {
+        let ct = Rc::new(TestContentType {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.tran.send(GetSurfaceContentType {
+            self_id: self.id,
+            id: ct.id,
+            surface,
+        })?;
+        self.tran.add_obj(ct.clone())?;
+        Ok(ct)
+    }
+}
+
+impl Drop for TestContentTypeManager {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestContentTypeManager, WpContentTypeManagerV1;
+}
+
+impl TestObject for TestContentTypeManager {}
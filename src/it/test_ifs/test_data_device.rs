@@ -0,0 +1,126 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_ifs::test_data_offer::TestDataOffer,
+            test_object::TestObject, test_transport::TestTransport, testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, clonecell::CloneCell, copyhashmap::CopyHashMap},
+        wire::{wl_data_device::*, WlDataDeviceId, WlDataOfferId, WlDataSourceId, WlSurfaceId},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+};
+
+pub struct TestDataDevice {
+    pub id: WlDataDeviceId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub offers: CopyHashMap<WlDataOfferId, Rc<TestDataOffer>>,
+    pub selection: CloneCell<Option<Rc<TestDataOffer>>>,
+}
+
+impl TestDataDevice {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Release { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn start_drag(
+        &self,
+        source: Option<WlDataSourceId>,
+        origin: WlSurfaceId,
+        serial: u32,
+    ) -> Result<(), TestError> {
+        self.tran.send(StartDrag {
+            self_id: self.id,
+            source: source.unwrap_or(WlDataSourceId::NONE),
+            origin,
+            icon: WlSurfaceId::NONE,
+            serial,
+        })
+    }
+
+    pub fn set_selection(
+        &self,
+        source: Option<WlDataSourceId>,
+        serial: u32,
+    ) -> Result<(), TestError> {
+        self.tran.send(SetSelection {
+            self_id: self.id,
+            source: source.unwrap_or(WlDataSourceId::NONE),
+            serial,
+        })
+    }
+
+    fn handle_data_offer(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = DataOffer::parse_full(parser)?;
+        let offer = Rc::new(TestDataOffer {
+            id: ev.id,
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            mime_types: RefCell::new(vec![]),
+        });
+        self.tran.add_obj(offer.clone())?;
+        self.offers.set(ev.id, offer);
+        Ok(())
+    }
+
+    fn handle_enter(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Enter::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_leave(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Leave::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_motion(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Motion::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_drop(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Drop::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_selection(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Selection::parse_full(parser)?;
+        let offer = if ev.id.is_none() {
+            None
+        } else {
+            match self.offers.remove(&ev.id) {
+                Some(offer) => Some(offer),
+                _ => bail!("Compositor sent selection with unknown offer {}", ev.id),
+            }
+        };
+        if let Some(old) = self.selection.set(offer) {
+            old.destroy()?;
+        }
+        Ok(())
+    }
+}
+
+impl std::ops::Drop for TestDataDevice {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestDataDevice, WlDataDevice;
+
+    DATA_OFFER => handle_data_offer,
+    ENTER => handle_enter,
+    LEAVE => handle_leave,
+    MOTION => handle_motion,
+    DROP => handle_drop,
+    SELECTION => handle_selection,
+}
+
+impl TestObject for TestDataDevice {}
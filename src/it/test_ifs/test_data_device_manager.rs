@@ -0,0 +1,58 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError,
+            test_ifs::{test_data_device::TestDataDevice, test_data_source::TestDataSource},
+            test_object::TestObject,
+            test_transport::TestTransport,
+        },
+        wire::{wl_data_device_manager::*, WlDataDeviceManagerId, WlSeatId},
+    },
+    std::{cell::Cell, rc::Rc},
+};
+
+pub struct TestDataDeviceManager {
+    pub id: WlDataDeviceManagerId,
+    pub tran: Rc<TestTransport>,
+}
+
+impl TestDataDeviceManager {
+    pub fn create_data_source(&self) -> Result<Rc<TestDataSource>, TestError> {
+        let source = Rc::new(TestDataSource {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            send: Default::default(),
+            cancelled: Cell::new(false),
+        });
+        self.tran.send(CreateDataSource {
+            self_id: self.id,
+            id: source.id,
+        })?;
+        self.tran.add_obj(source.clone())?;
+        Ok(source)
+    }
+
+    pub fn get_data_device(&self, seat: WlSeatId) -> Result<Rc<TestDataDevice>, TestError> {
+        let dd = Rc::new(TestDataDevice {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+            offers: Default::default(),
+            selection: Default::default(),
+        });
+        self.tran.send(GetDataDevice {
+            self_id: self.id,
+            id: dd.id,
+            seat,
+        })?;
+        self.tran.add_obj(dd.clone())?;
+        Ok(dd)
+    }
+}
+
+test_object! {
+    TestDataDeviceManager, WlDataDeviceManager;
+}
+
+impl TestObject for TestDataDeviceManager {}
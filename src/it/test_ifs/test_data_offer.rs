@@ -0,0 +1,89 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestError, TestErrorExt, TestResult},
+            test_object::TestObject,
+            test_transport::TestTransport,
+            testrun::ParseFull,
+        },
+        utils::{buffd::MsgParser, oserror::OsErrorExt},
+        wire::{wl_data_offer::*, WlDataOfferId},
+    },
+    std::{
+        cell::{Cell, RefCell},
+        rc::Rc,
+    },
+    uapi::{c, OwnedFd},
+};
+
+pub struct TestDataOffer {
+    pub id: WlDataOfferId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub mime_types: RefCell<Vec<String>>,
+}
+
+impl TestDataOffer {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+            self.tran.objects.remove(&self.id.into());
+        }
+        Ok(())
+    }
+
+    pub fn accept(&self, serial: u32, mime_type: Option<&str>) -> Result<(), TestError> {
+        self.tran.send(Accept {
+            self_id: self.id,
+            serial,
+            mime_type,
+        })
+    }
+
+    pub fn set_actions(&self, dnd_actions: u32, preferred_action: u32) -> Result<(), TestError> {
+        self.tran.send(SetActions {
+            self_id: self.id,
+            dnd_actions,
+            preferred_action,
+        })
+    }
+
+    /// Requests the contents of the offer and returns the read end of the pipe.
+    pub fn receive(&self, mime_type: &str) -> TestResult<OwnedFd> {
+        let (rx, tx) = uapi::pipe2(c::O_CLOEXEC)
+            .to_os_error()
+            .with_context(|| "Could not create a pipe")?;
+        self.tran.send(Receive {
+            self_id: self.id,
+            mime_type,
+            fd: Rc::new(tx),
+        })?;
+        Ok(rx)
+    }
+
+    fn handle_offer(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Offer::parse_full(parser)?;
+        self.mime_types.borrow_mut().push(ev.mime_type.to_string());
+        Ok(())
+    }
+
+    fn handle_source_actions(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = SourceActions::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_action(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Action::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+test_object! {
+    TestDataOffer, WlDataOffer;
+
+    OFFER => handle_offer,
+    SOURCE_ACTIONS => handle_source_actions,
+    ACTION => handle_action,
+}
+
+impl TestObject for TestDataOffer {}
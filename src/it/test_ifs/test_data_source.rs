@@ -0,0 +1,95 @@
+use {
+    crate::{
+        it::{
+            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
+            test_utils::test_expected_event::TEEH, testrun::ParseFull,
+        },
+        utils::buffd::MsgParser,
+        wire::{wl_data_source::*, WlDataSourceId},
+    },
+    std::{cell::Cell, rc::Rc},
+    uapi::OwnedFd,
+};
+
+pub struct TestSendEvent {
+    pub mime_type: String,
+    pub fd: Rc<OwnedFd>,
+}
+
+pub struct TestDataSource {
+    pub id: WlDataSourceId,
+    pub tran: Rc<TestTransport>,
+    pub destroyed: Cell<bool>,
+    pub send: TEEH<TestSendEvent>,
+    pub cancelled: Cell<bool>,
+}
+
+impl TestDataSource {
+    pub fn destroy(&self) -> Result<(), TestError> {
+        if !self.destroyed.replace(true) {
+            self.tran.send(Destroy { self_id: self.id })?;
+        }
+        Ok(())
+    }
+
+    pub fn offer(&self, mime_type: &str) -> Result<(), TestError> {
+        self.tran.send(Offer {
+            self_id: self.id,
+            mime_type,
+        })
+    }
+
+    fn handle_target(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Target::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_send(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let ev = Send::parse_full(parser)?;
+        self.send.push(TestSendEvent {
+            mime_type: ev.mime_type.to_string(),
+            fd: ev.fd,
+        });
+        Ok(())
+    }
+
+    fn handle_cancelled(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Cancelled::parse_full(parser)?;
+        self.cancelled.set(true);
+        Ok(())
+    }
+
+    fn handle_dnd_drop_performed(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = DndDropPerformed::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_dnd_finished(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = DndFinished::parse_full(parser)?;
+        Ok(())
+    }
+
+    fn handle_action(&self, parser: MsgParser<'_, '_>) -> Result<(), TestError> {
+        let _ev = Action::parse_full(parser)?;
+        Ok(())
+    }
+}
+
+impl Drop for TestDataSource {
+    fn drop(&mut self) {
+        let _ = self.destroy();
+    }
+}
+
+test_object! {
+    TestDataSource, WlDataSource;
+
+    TARGET => handle_target,
+    SEND => handle_send,
+    CANCELLED => handle_cancelled,
+    DND_DROP_PERFORMED => handle_dnd_drop_performed,
+    DND_FINISHED => handle_dnd_finished,
+    ACTION => handle_action,
+}
+
+impl TestObject for TestDataSource {}
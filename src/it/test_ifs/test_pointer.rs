@@ -19,6 +19,7 @@ pub struct TestPointer {
     pub leave: TEEH<Leave>,
     pub enter: TEEH<Enter>,
     pub motion: TEEH<Motion>,
+    pub button: TEEH<Button>,
 }
 
 impl TestPointer {
@@ -48,7 +49,8 @@ impl TestPointer {
     }
 
     fn handle_button(&self, parser: MsgParser<'_, '_>) -> TestResult {
-        let _ev = Button::parse_full(parser)?;
+        let ev = Button::parse_full(parser)?;
+        self.button.push(ev);
         Ok(())
     }
 
@@ -5,9 +5,10 @@ use {
         it::{
             test_error::TestError,
             test_ifs::{
-                test_compositor::TestCompositor, test_jay_compositor::TestJayCompositor,
-                test_shm::TestShm, test_subcompositor::TestSubcompositor,
-                test_xdg_base::TestXdgWmBase,
+                test_compositor::TestCompositor, test_content_type_manager::TestContentTypeManager,
+                test_data_device_manager::TestDataDeviceManager,
+                test_jay_compositor::TestJayCompositor, test_shm::TestShm,
+                test_subcompositor::TestSubcompositor, test_xdg_base::TestXdgWmBase,
             },
             test_object::TestObject,
             test_transport::TestTransport,
@@ -31,6 +32,8 @@ pub struct TestRegistrySingletons {
     pub wl_subcompositor: u32,
     pub wl_shm: u32,
     pub xdg_wm_base: u32,
+    pub wl_data_device_manager: u32,
+    pub wp_content_type_manager_v1: u32,
 }
 
 pub struct TestRegistry {
@@ -43,6 +46,8 @@ pub struct TestRegistry {
     pub subcompositor: CloneCell<Option<Rc<TestSubcompositor>>>,
     pub shm: CloneCell<Option<Rc<TestShm>>>,
     pub xdg: CloneCell<Option<Rc<TestXdgWmBase>>>,
+    pub data_device_manager: CloneCell<Option<Rc<TestDataDeviceManager>>>,
+    pub content_type_manager: CloneCell<Option<Rc<TestContentTypeManager>>>,
     pub seats: CopyHashMap<GlobalName, Rc<WlSeatGlobal>>,
 }
 
@@ -90,6 +95,8 @@ impl TestRegistry {
             wl_subcompositor,
             wl_shm,
             xdg_wm_base,
+            wl_data_device_manager,
+            wp_content_type_manager_v1,
         };
         self.singletons.set(Some(singletons.clone()));
         Ok(singletons)
@@ -165,6 +172,33 @@ impl TestRegistry {
         Ok(jc)
     }
 
+    pub async fn get_data_device_manager(&self) -> Result<Rc<TestDataDeviceManager>, TestError> {
+        singleton!(self.data_device_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.data_device_manager);
+        let jc = Rc::new(TestDataDeviceManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+        });
+        self.bind(&jc, singletons.wl_data_device_manager, 3)?;
+        self.data_device_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
+    pub async fn get_content_type_manager(&self) -> Result<Rc<TestContentTypeManager>, TestError> {
+        singleton!(self.content_type_manager);
+        let singletons = self.get_singletons().await?;
+        singleton!(self.content_type_manager);
+        let jc = Rc::new(TestContentTypeManager {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            destroyed: Cell::new(false),
+        });
+        self.bind(&jc, singletons.wp_content_type_manager_v1, 1)?;
+        self.content_type_manager.set(Some(jc.clone()));
+        Ok(jc)
+    }
+
     pub fn bind<O: TestObject>(
         &self,
         obj: &Rc<O>,
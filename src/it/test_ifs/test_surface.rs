@@ -2,11 +2,11 @@ use {
     crate::{
         ifs::wl_surface::WlSurface,
         it::{
-            test_error::TestError, test_object::TestObject, test_transport::TestTransport,
-            testrun::ParseFull,
+            test_error::TestError, test_ifs::test_callback::TestCallback, test_object::TestObject,
+            test_transport::TestTransport, testrun::ParseFull,
         },
         utils::buffd::MsgParser,
-        wire::{wl_surface::*, WlBufferId, WlSurfaceId},
+        wire::{wl_surface::*, WlBufferId, WlRegionId, WlSurfaceId},
     },
     std::{cell::Cell, rc::Rc},
 };
@@ -36,6 +36,37 @@ impl TestSurface {
         Ok(())
     }
 
+    pub fn set_input_region(&self, region: WlRegionId) -> Result<(), TestError> {
+        self.tran.send(SetInputRegion {
+            self_id: self.id,
+            region,
+        })?;
+        Ok(())
+    }
+
+    pub fn set_opaque_region(&self, region: WlRegionId) -> Result<(), TestError> {
+        self.tran.send(SetOpaqueRegion {
+            self_id: self.id,
+            region,
+        })?;
+        Ok(())
+    }
+
+    pub fn frame(&self) -> Result<Rc<TestCallback>, TestError> {
+        let cb = Rc::new(TestCallback {
+            id: self.tran.id(),
+            tran: self.tran.clone(),
+            handler: Cell::new(None),
+            done: Cell::new(false),
+        });
+        self.tran.send(Frame {
+            self_id: self.id,
+            callback: cb.id,
+        })?;
+        self.tran.add_obj(cb.clone())?;
+        Ok(cb)
+    }
+
     pub fn commit(&self) -> Result<(), TestError> {
         self.tran.send(Commit { self_id: self.id })?;
         Ok(())
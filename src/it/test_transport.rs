@@ -57,6 +57,8 @@ impl TestTransport {
             subcompositor: Default::default(),
             shm: Default::default(),
             xdg: Default::default(),
+            data_device_manager: Default::default(),
+            content_type_manager: Default::default(),
             seats: Default::default(),
         });
         self.send(wl_display::GetRegistry {
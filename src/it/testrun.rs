@@ -1,16 +1,18 @@
 use {
     crate::{
         client::{ClientId, RequestParser},
+        format::ARGB8888,
         ifs::wl_seat::WlSeatGlobal,
         it::{
             test_backend::{TestBackend, TestBackendKb, TestBackendMouse, TestConnector},
             test_client::TestClient,
             test_config::TestConfig,
-            test_error::{TestError, TestErrorExt},
+            test_error::{TestError, TestErrorExt, TestResult},
             test_ifs::test_display::TestDisplay,
             test_transport::TestTransport,
         },
         object::WL_DISPLAY_ID,
+        renderer::RenderResult,
         state::State,
         tree::OutputNode,
         utils::{bitfield::Bitfield, buffd::MsgParser, oserror::OsErrorExt, stack::Stack},
@@ -123,6 +125,28 @@ impl TestRun {
     pub async fn sync(&self) {
         self.state.eng.yield_now().await;
     }
+
+    /// Renders a frame of the output like a backend would and dispatches its frame requests.
+    pub fn render_frame(&self, output: &Rc<OutputNode>) -> TestResult {
+        let Some(ctx) = self.state.render_ctx.get() else {
+            bail!("There is no render context");
+        };
+        let pos = output.global.pos.get();
+        let fb = ctx
+            .create_fb(pos.width(), pos.height(), pos.width() * 4, ARGB8888)
+            .with_context(|| "Could not create a framebuffer")?;
+        let mut rr = RenderResult::default();
+        fb.render_output(
+            output,
+            &self.state,
+            Some(pos),
+            Some(&mut rr),
+            output.global.preferred_scale.get(),
+            true,
+        );
+        output.dispatch_frame_requests(&mut rr);
+        Ok(())
+    }
 }
 
 pub trait ParseFull<'a>: Sized {
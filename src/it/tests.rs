@@ -44,6 +44,27 @@ mod t0015_scroll_partial;
 mod t0016_scroll_ws;
 mod t0017_remove_unused_ws;
 mod t0018_click_to_active_ws;
+mod t0019_injected_input;
+mod t0020_virtual_clock;
+mod t0021_reload;
+mod t0022_workspace_switch_animation;
+mod t0023_input_region;
+mod t0024_clipboard;
+mod t0025_flush_layout;
+mod t0026_seat_capabilities;
+mod t0027_empty_workspace_action;
+mod t0028_commit_throttling;
+mod t0029_hotplug_debounce;
+mod t0030_window_ids;
+mod t0031_persisted_selection_offer;
+mod t0032_opaque_float_frames;
+mod t0033_game_content_tearing;
+mod t0034_virtual_timer;
+mod t0035_seat_idle_time;
+mod t0036_apply_output_layout;
+mod t0037_assert_focused;
+mod t0038_pointer_info;
+mod t0039_hotplug_policy;
 
 pub trait TestCase: Sync {
     fn name(&self) -> &'static str;
@@ -80,5 +101,26 @@ pub fn tests() -> Vec<&'static dyn TestCase> {
         t0016_scroll_ws,
         t0017_remove_unused_ws,
         t0018_click_to_active_ws,
+        t0019_injected_input,
+        t0020_virtual_clock,
+        t0021_reload,
+        t0022_workspace_switch_animation,
+        t0023_input_region,
+        t0024_clipboard,
+        t0025_flush_layout,
+        t0026_seat_capabilities,
+        t0027_empty_workspace_action,
+        t0028_commit_throttling,
+        t0029_hotplug_debounce,
+        t0030_window_ids,
+        t0031_persisted_selection_offer,
+        t0032_opaque_float_frames,
+        t0033_game_content_tearing,
+        t0034_virtual_timer,
+        t0035_seat_idle_time,
+        t0036_apply_output_layout,
+        t0037_assert_focused,
+        t0038_pointer_info,
+        t0039_hotplug_policy,
     }
 }
@@ -0,0 +1,64 @@
+use {
+    crate::{
+        backend::KeyState,
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+    },
+    jay_config::keyboard::syms::SYM_F13,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9; # ESC
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+    };
+};
+    "#;
+
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F13)?;
+    run.sync().await;
+
+    let kb = ds.kb.common.id;
+    run.cfg.send_key(kb, 1, KeyState::Pressed)?;
+    run.cfg.send_key(kb, 1, KeyState::Released)?;
+    run.sync().await;
+    tassert!(run
+        .cfg
+        .invoked_shortcuts
+        .contains(&(ds.seat.id(), SYM_F13.into())));
+
+    let (x, y) = ds.seat.position();
+    run.cfg.send_pointer_motion(ds.mouse.common.id, 1.0, 1.0)?;
+    run.sync().await;
+    tassert_eq!(ds.seat.position(), (x + 1, y + 1));
+
+    let client = run.create_client().await?;
+    let default_seat = client.get_default_seat().await?;
+
+    let enter = default_seat.kb.enter.expect()?;
+
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    let enter = enter.next().with_context(|| "Did not enter")?;
+    tassert_eq!(enter.surface, window.surface.id);
+
+    Ok(())
+}
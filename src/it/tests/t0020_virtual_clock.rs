@@ -0,0 +1,40 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::{cell::Cell, rc::Rc, time::Duration},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    run.state.idle.set_timeout(Duration::from_secs(60));
+    run.sync().await;
+
+    let expired = Rc::new(Cell::new(false));
+    let _timeout = {
+        let expired = expired.clone();
+        let timeout = run.state.wheel.timeout(10_000);
+        run.state.eng.spawn(async move {
+            if timeout.await.is_ok() {
+                expired.set(true);
+            }
+        })
+    };
+
+    run.cfg.advance_time(Duration::from_secs(5))?;
+    run.sync().await;
+    tassert!(!expired.get());
+    tassert!(!run.cfg.idle.get());
+
+    run.cfg.advance_time(Duration::from_secs(6))?;
+    run.sync().await;
+    tassert!(expired.get());
+    tassert!(!run.cfg.idle.get());
+
+    run.cfg.advance_time(Duration::from_secs(50))?;
+    run.sync().await;
+    tassert!(run.cfg.idle.get());
+
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::keyboard::syms::SYM_F13,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.mouse.rel(1.0, 1.0);
+
+    let keymap = r#"
+xkb_keymap {
+    xkb_keycodes {
+          <1> = 9; # ESC
+    };
+    xkb_types {
+    };
+    xkb_compatibility {
+    };
+    xkb_symbols {
+        key <1> { [ F13 ] };
+    };
+};
+    "#;
+
+    let keymap = run.cfg.parse_keymap(keymap)?;
+    run.cfg.set_keymap(ds.seat.id(), keymap)?;
+    run.cfg.add_shortcut(ds.seat.id(), SYM_F13)?;
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert!(run.cfg.invoked_shortcuts.is_not_empty());
+    run.cfg.invoked_shortcuts.clear();
+
+    run.cfg.reload()?;
+    run.sync().await;
+
+    ds.kb.press(1);
+    run.sync().await;
+    tassert!(run.cfg.invoked_shortcuts.is_empty());
+
+    let name = ds.output.workspace.get().map(|ws| ws.name.clone());
+    tassert_eq!(name.as_deref(), Some("2"));
+    tassert_eq!(run.cfg.get_seat("default")?, ds.seat.id());
+    run.cfg
+        .assert_focused(ds.seat.id(), Some(window.tl.server.node_id()))?;
+
+    Ok(())
+}
@@ -0,0 +1,42 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::Axis,
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    run.cfg
+        .set_workspace_switch_animation(Axis::Horizontal, Duration::from_millis(100))?;
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    run.sync().await;
+
+    let (old, (old_x, old_y), (new_x, new_y)) = match ds.output.workspace_transition_offsets() {
+        Some(t) => t,
+        _ => bail!("Workspace switch is not animated"),
+    };
+    tassert_eq!(old.name.as_str(), "1");
+    tassert_eq!((old_x, old_y), (0, 0));
+    tassert!(new_x > 0);
+    tassert_eq!(new_y, 0);
+
+    run.cfg.advance_time(Duration::from_millis(50))?;
+    run.sync().await;
+    let (_, (old_x, _), (new_x, _)) = match ds.output.workspace_transition_offsets() {
+        Some(t) => t,
+        _ => bail!("Workspace switch animation ended early"),
+    };
+    tassert!(old_x < 0);
+    tassert!(new_x > 0);
+
+    run.cfg.advance_time(Duration::from_millis(60))?;
+    run.sync().await;
+    tassert!(ds.output.workspace_transition.borrow().is_none());
+    tassert!(ds.output.workspace_transition_offsets().is_none());
+
+    Ok(())
+}
@@ -0,0 +1,65 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+        rect::Rect,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that pointer input ignores the parts of a subsurface outside its input region
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let cds = client.get_default_seat().await?;
+
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    let ns = client.comp.create_surface().await?;
+    let nss = client.sub.get_subsurface(ns.id, window.surface.id).await?;
+    nss.set_position(100, 100)?;
+    let region = client.comp.create_region().await?;
+    region.add(Rect::new_sized(0, 0, 50, 100).unwrap())?;
+    ns.set_input_region(region.id)?;
+    let buffer = client.shm.create_buffer(100, 100)?;
+    ns.attach(buffer.id)?;
+    ns.commit()?;
+
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    client.sync().await;
+    window.map().await?;
+
+    ds.mouse.rel(-1000.0, -1000.0);
+    ds.mouse.rel(175.0, 150.0);
+
+    client.sync().await;
+
+    let enters = cds.pointer.enter.expect()?;
+
+    ds.mouse.rel(-50.0, 0.0);
+
+    client.sync().await;
+
+    let enter = enters.next().with_context(|| "enters")?;
+    tassert_eq!(enter.surface, ns.id);
+
+    let leaves = cds.pointer.leave.expect()?;
+    let enters = cds.pointer.enter.expect()?;
+
+    ds.mouse.rel(50.0, 0.0);
+
+    client.sync().await;
+
+    let leave = leaves.next().with_context(|| "leaves")?;
+    tassert_eq!(leave.surface, ns.id);
+    let enter = enters.next().with_context(|| "enters")?;
+    tassert_eq!(enter.surface, window.surface.id);
+
+    Ok(())
+}
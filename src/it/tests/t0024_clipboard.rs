@@ -0,0 +1,98 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+        utils::oserror::OsErrorExt,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the selection of one client can be pasted into another client
+async fn test(run: Rc<TestRun>) -> TestResult {
+    const MIME_TYPE: &str = "text/plain;charset=utf-8";
+    const CONTENTS: &[u8] = b"hello world";
+
+    let ds = run.create_default_setup().await?;
+
+    let client1 = run.create_client().await?;
+    let seat1 = client1.get_default_seat().await?;
+    let ddm1 = client1.registry.get_data_device_manager().await?;
+    let dd1 = ddm1.get_data_device(seat1.seat.id)?;
+
+    let enters = seat1.kb.enter.expect()?;
+    let window1 = client1.create_window().await?;
+    window1.map().await?;
+    let enter = enters.next().with_context(|| "client1 enter")?;
+
+    let source = ddm1.create_data_source()?;
+    source.offer(MIME_TYPE)?;
+    dd1.set_selection(Some(source.id), enter.serial)?;
+    client1.sync().await;
+
+    let client2 = run.create_client().await?;
+    let seat2 = client2.get_default_seat().await?;
+    let ddm2 = client2.registry.get_data_device_manager().await?;
+    let dd2 = ddm2.get_data_device(seat2.seat.id)?;
+
+    let enters = seat2.kb.enter.expect()?;
+    let window2 = client2.create_window().await?;
+    window2.map().await?;
+    enters.next().with_context(|| "client2 enter")?;
+
+    let offer = match dd2.selection.get() {
+        Some(offer) => offer,
+        _ => bail!("client2 did not receive a selection"),
+    };
+    tassert_eq!(offer.mime_types.borrow().clone(), [MIME_TYPE]);
+
+    let sends = source.send.expect()?;
+    let rx = offer.receive(MIME_TYPE)?;
+    client2.sync().await;
+    client1.sync().await;
+
+    let send = sends.next().with_context(|| "send")?;
+    tassert_eq!(&send.mime_type, MIME_TYPE);
+    uapi::write(send.fd.raw(), CONTENTS)
+        .to_os_error()
+        .with_context(|| "Could not write to the pipe")?;
+    drop(send);
+
+    let mut buf = [0u8; 64];
+    let received = uapi::read(rx.raw(), &mut buf[..])
+        .to_os_error()
+        .with_context(|| "Could not read from the pipe")?;
+    tassert_eq!(&*received, CONTENTS);
+
+    // The compositor has no copy of client selections unless the persistent clipboard
+    // is enabled.
+    tassert_eq!(run.cfg.get_clipboard(ds.seat.id(), MIME_TYPE)?, None);
+
+    const CONFIG_CONTENTS: &[u8] = b"hello config";
+    run.cfg
+        .set_clipboard(ds.seat.id(), MIME_TYPE, CONFIG_CONTENTS)?;
+    client2.sync().await;
+    tassert_eq!(
+        run.cfg.get_clipboard(ds.seat.id(), MIME_TYPE)?,
+        Some(CONFIG_CONTENTS.to_vec())
+    );
+
+    let offer = match dd2.selection.get() {
+        Some(offer) => offer,
+        _ => bail!("client2 did not receive the config selection"),
+    };
+    tassert_eq!(offer.mime_types.borrow().clone(), [MIME_TYPE]);
+    let rx = offer.receive(MIME_TYPE)?;
+    client2.sync().await;
+    run.sync().await;
+
+    let received = uapi::read(rx.raw(), &mut buf[..])
+        .to_os_error()
+        .with_context(|| "Could not read from the pipe")?;
+    tassert_eq!(&*received, CONFIG_CONTENTS);
+
+    Ok(())
+}
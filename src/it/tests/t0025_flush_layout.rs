@@ -0,0 +1,32 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::ToplevelNodeBase,
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that flushing the layout applies pending layout work immediately
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    let w1 = client.create_window().await?;
+    w1.map2().await?;
+    let w2 = client.create_window().await?;
+    w2.map2().await?;
+
+    let container = w2.tl.container_parent()?;
+    let container_width = container.tl_data().pos.get().width();
+    tassert!(w2.tl.server.tl_data().pos.get().width() < container_width);
+
+    run.cfg.set_mono(ds.seat.id(), true)?;
+    run.cfg.flush_layout()?;
+
+    tassert_eq!(w2.tl.server.tl_data().pos.get().width(), container_width);
+
+    Ok(())
+}
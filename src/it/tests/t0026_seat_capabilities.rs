@@ -0,0 +1,30 @@
+use {
+    crate::{
+        ifs::wl_seat::{KEYBOARD, POINTER},
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the capabilities of a seat follow the attached devices
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+    let default_seat = client.get_default_seat().await?;
+    tassert_eq!(default_seat.seat.caps.get(), POINTER | KEYBOARD);
+
+    let other = run.cfg.get_seat("other")?;
+    run.cfg.set_input_device_seat(ds.kb.common.id, other)?;
+    client.sync().await;
+    tassert_eq!(default_seat.seat.caps.get(), POINTER);
+
+    run.cfg
+        .set_input_device_seat(ds.kb.common.id, ds.seat.id())?;
+    client.sync().await;
+    tassert_eq!(default_seat.seat.caps.get(), POINTER | KEYBOARD);
+
+    Ok(())
+}
@@ -0,0 +1,36 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::EmptyWorkspaceAction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that closing the last window on a visible workspace switches to the previous one
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    run.cfg
+        .set_empty_workspace_action(EmptyWorkspaceAction::SwitchToPrevious)?;
+
+    let client = run.create_client().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let w1 = client.create_window().await?;
+    w1.map2().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    let w2 = client.create_window().await?;
+    w2.map2().await?;
+
+    tassert_eq!(run.state.workspaces.len(), 2);
+
+    w2.tl.destroy()?;
+    client.sync().await;
+    run.sync().await;
+
+    let ws = ds.output.workspace.get();
+    tassert_eq!(ws.as_ref().map(|ws| ws.name.as_str()), Some("1"));
+    tassert_eq!(run.state.workspaces.len(), 1);
+
+    Ok(())
+}
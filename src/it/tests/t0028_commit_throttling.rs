@@ -0,0 +1,59 @@
+use {
+    crate::{
+        format::ARGB8888,
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test that deferred commits of a hidden surface are coalesced into a single commit
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let client = run.create_client().await?;
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let win = client.create_window().await?;
+    win.map2().await?;
+    let initial = win.buffer.get();
+
+    run.cfg.show_workspace(ds.seat.id(), "2")?;
+    run.cfg.set_commit_throttling(true)?;
+    client.sync().await;
+
+    let width = win.tl.width.get();
+    let height = win.tl.height.get();
+    let b1 = win
+        .shm
+        .create_buffer(0, width, height, width * 4, ARGB8888)?;
+    let b2 = win
+        .shm
+        .create_buffer(0, width, height, width * 4, ARGB8888)?;
+    let commits = win.surface.server.commits();
+
+    win.surface.attach(b1.id)?;
+    win.surface.commit()?;
+    win.surface.attach(b2.id)?;
+    win.surface.commit()?;
+    client.sync().await;
+
+    tassert_eq!(win.surface.server.commits(), commits + 2);
+    tassert_eq!(
+        win.surface.server.buffer.get().map(|b| b.id),
+        Some(initial.id)
+    );
+    tassert!(b1.released.get());
+    tassert!(!initial.released.get());
+
+    run.cfg.advance_time(Duration::from_millis(20))?;
+    run.sync().await;
+    client.sync().await;
+
+    tassert_eq!(win.surface.server.buffer.get().map(|b| b.id), Some(b2.id));
+    tassert!(initial.released.get());
+    tassert!(!b2.released.get());
+
+    Ok(())
+}
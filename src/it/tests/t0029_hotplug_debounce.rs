@@ -0,0 +1,55 @@
+use {
+    crate::{
+        backend::ConnectorEvent,
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test that a flapping connection keeps its output and that a different monitor replaces it
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let id = ds.connector.id;
+
+    run.cfg.set_hotplug_debounce_ms(100)?;
+    run.sync().await;
+
+    ds.connector.send_event(ConnectorEvent::Disconnected);
+    run.sync().await;
+    tassert!(run.state.root.outputs.get(&id).is_some());
+
+    run.cfg.advance_time(Duration::from_millis(50))?;
+    ds.connector.send_event(ConnectorEvent::Connected(
+        run.backend.default_monitor_info(),
+    ));
+    run.sync().await;
+
+    run.cfg.advance_time(Duration::from_millis(200))?;
+    run.sync().await;
+    let output = run.state.root.outputs.get(&id);
+    tassert!(output.is_some());
+    tassert_eq!(output.unwrap().id, ds.output.id);
+
+    ds.connector.send_event(ConnectorEvent::Disconnected);
+    run.sync().await;
+
+    run.cfg.advance_time(Duration::from_millis(50))?;
+    let mut info = run.backend.default_monitor_info();
+    info.serial_number = "other".to_string();
+    ds.connector.send_event(ConnectorEvent::Connected(info));
+    run.sync().await;
+    tassert!(run.state.root.outputs.get(&id).is_none());
+
+    run.cfg.advance_time(Duration::from_millis(200))?;
+    run.sync().await;
+    let output = match run.state.root.outputs.get(&id) {
+        Some(o) => o,
+        _ => bail!("The new monitor has no output"),
+    };
+    tassert!(output.id != ds.output.id);
+    tassert_eq!(output.global.output_id.serial_number.as_str(), "other");
+
+    Ok(())
+}
@@ -0,0 +1,54 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::{Node, ToplevelNodeBase},
+    },
+    jay_config::Window,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that windows can be focused, moved, and closed by their id
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.mouse.rel(1.0, 1.0);
+
+    run.cfg.show_workspace(ds.seat.id(), "1")?;
+    let client = run.create_client().await?;
+    let window1 = client.create_window().await?;
+    window1.map().await?;
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+    let id1 = Window(window1.tl.server.node_id().raw() as _);
+    let id2 = Window(window2.tl.server.node_id().raw() as _);
+
+    let focused = run.cfg.get_focused(ds.seat.id())?;
+    tassert_eq!(focused.map(|f| f.window), Some(id2));
+    let info = run.cfg.get_window_info(id1)?;
+    tassert_eq!(info.map(|i| i.window), Some(id1));
+
+    run.cfg.focus_window(ds.seat.id(), id1)?;
+    client.sync().await;
+    run.cfg
+        .assert_focused(ds.seat.id(), Some(window1.tl.server.node_id()))?;
+
+    run.cfg.move_window_to_workspace(id2, "2")?;
+    client.sync().await;
+    let ws = window2.tl.server.tl_data().workspace.get();
+    tassert_eq!(ws.map(|ws| ws.name.clone()), Some("2".to_string()));
+
+    run.cfg.close_window(id1)?;
+    client.sync().await;
+    tassert!(window1.tl.close_requested.get());
+    tassert!(!window2.tl.close_requested.get());
+
+    window1.tl.destroy()?;
+    window1.xdg.destroy()?;
+    window1.surface.destroy()?;
+    client.sync().await;
+    tassert!(run.cfg.get_window_info(id1)?.is_none());
+    tassert!(run.cfg.get_window_info(id2)?.is_some());
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use {
+    crate::{
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+        utils::oserror::OsErrorExt,
+    },
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+const DND_COPY: u32 = 1;
+
+/// Test that clients can accept and set actions on the offer of a persisted selection
+async fn test(run: Rc<TestRun>) -> TestResult {
+    const MIME_TYPE: &str = "text/plain;charset=utf-8";
+    const CONTENTS: &[u8] = b"hello world";
+
+    let _ds = run.create_default_setup().await?;
+    run.cfg.set_persistent_clipboard(true)?;
+
+    let client1 = run.create_client().await?;
+    let seat1 = client1.get_default_seat().await?;
+    let ddm1 = client1.registry.get_data_device_manager().await?;
+    let dd1 = ddm1.get_data_device(seat1.seat.id)?;
+
+    let enters = seat1.kb.enter.expect()?;
+    let window1 = client1.create_window().await?;
+    window1.map().await?;
+    let enter = enters.next().with_context(|| "client1 enter")?;
+
+    let source = ddm1.create_data_source()?;
+    source.offer(MIME_TYPE)?;
+    let sends = source.send.expect()?;
+    dd1.set_selection(Some(source.id), enter.serial)?;
+    client1.sync().await;
+
+    run.cfg.advance_time(Duration::from_millis(600))?;
+    client1.sync().await;
+    let send = sends.next().with_context(|| "send")?;
+    uapi::write(send.fd.raw(), CONTENTS)
+        .to_os_error()
+        .with_context(|| "Could not write to the pipe")?;
+    drop(send);
+    client1.sync().await;
+    run.sync().await;
+
+    drop(window1);
+    drop(source);
+    drop(dd1);
+    drop(ddm1);
+    drop(seat1);
+    drop(client1);
+    run.sync().await;
+
+    let client2 = run.create_client().await?;
+    let seat2 = client2.get_default_seat().await?;
+    let ddm2 = client2.registry.get_data_device_manager().await?;
+    let dd2 = ddm2.get_data_device(seat2.seat.id)?;
+
+    let enters = seat2.kb.enter.expect()?;
+    let window2 = client2.create_window().await?;
+    window2.map().await?;
+    let enter = enters.next().with_context(|| "client2 enter")?;
+
+    let offer = match dd2.selection.get() {
+        Some(offer) => offer,
+        _ => bail!("client2 did not receive the persisted selection"),
+    };
+    tassert_eq!(offer.mime_types.borrow().clone(), [MIME_TYPE]);
+
+    offer.accept(enter.serial, Some(MIME_TYPE))?;
+    offer.set_actions(DND_COPY, DND_COPY)?;
+    client2.sync().await;
+    run.sync().await;
+    tassert!(dd2.selection.get().is_some());
+
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        rect::Rect,
+        tree::ToplevelNodeBase,
+    },
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+/// Test that an opaque floating window does not withhold its own frame callbacks
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.mouse.rel(1.0, 1.0);
+    run.cfg
+        .set_throttle_frame_callbacks(ds.connector.id, true)?;
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.set_color(255, 0, 0, 255);
+    window.map().await?;
+    run.cfg.set_floating(ds.seat.id(), true)?;
+    client.sync().await;
+
+    let region = client.comp.create_region().await?;
+    region.add(Rect::new_sized(0, 0, 10000, 10000).unwrap())?;
+    window.surface.set_opaque_region(region.id)?;
+    let frame = window.surface.frame()?;
+    window.map().await?;
+    tassert!(window.tl.server.tl_data().is_floating.get());
+    tassert!(window.surface.server.is_opaque());
+
+    run.render_frame(&ds.output)?;
+    run.cfg.advance_time(Duration::from_millis(20))?;
+    run.sync().await;
+    client.sync().await;
+    tassert!(frame.done.get());
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::rc::Rc,
+};
+
+testcase!();
+
+const NONE: u32 = 0;
+const GAME: u32 = 3;
+
+/// Test that fullscreen surfaces with the game content type allow tearing
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.mouse.rel(1.0, 1.0);
+
+    let client = run.create_client().await?;
+    let window = client.create_window().await?;
+    window.map().await?;
+    run.cfg.set_fullscreen(ds.seat.id(), true)?;
+    client.sync().await;
+    window.map().await?;
+    tassert!(!ds.output.fullscreen_wants_tearing());
+
+    let ctm = client.registry.get_content_type_manager().await?;
+    let ct = ctm.get_surface_content_type(window.surface.id)?;
+    ct.set_content_type(GAME)?;
+    window.surface.commit()?;
+    client.sync().await;
+    tassert!(ds.output.fullscreen_wants_tearing());
+
+    ct.set_content_type(NONE)?;
+    window.surface.commit()?;
+    client.sync().await;
+    tassert!(!ds.output.fullscreen_wants_tearing());
+
+    Ok(())
+}
@@ -0,0 +1,58 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::timer::MissedTicks,
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let _ds = run.create_default_setup().await?;
+
+    let timer = run.cfg.get_timer("timer")?;
+    let ticks = || run.cfg.timer_ticks.get(&timer).unwrap_or(0);
+
+    run.cfg.program_timer(
+        timer,
+        Some(Duration::from_secs(10)),
+        Some(Duration::from_secs(1)),
+        MissedTicks::CatchUp,
+    )?;
+    tassert_eq!(
+        run.cfg.get_timer_remaining(timer)?,
+        Some(Duration::from_secs(10))
+    );
+
+    run.cfg.advance_time(Duration::from_secs(5))?;
+    run.sync().await;
+    tassert_eq!(ticks(), 0);
+    tassert_eq!(
+        run.cfg.get_timer_remaining(timer)?,
+        Some(Duration::from_secs(5))
+    );
+
+    run.cfg.advance_time(Duration::from_secs(5))?;
+    run.sync().await;
+    tassert_eq!(ticks(), 1);
+    tassert_eq!(
+        run.cfg.get_timer_remaining(timer)?,
+        Some(Duration::from_secs(1))
+    );
+
+    run.cfg.advance_time(Duration::from_millis(3500))?;
+    run.sync().await;
+    tassert_eq!(ticks(), 4);
+    tassert_eq!(
+        run.cfg.get_timer_remaining(timer)?,
+        Some(Duration::from_millis(500))
+    );
+
+    run.cfg
+        .program_timer(timer, None, None, MissedTicks::CatchUp)?;
+    tassert_eq!(run.cfg.get_timer_remaining(timer)?, None);
+    run.cfg.advance_time(Duration::from_secs(10))?;
+    run.sync().await;
+    tassert_eq!(ticks(), 4);
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    std::{rc::Rc, time::Duration},
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    ds.mouse.rel(1.0, 1.0);
+    run.sync().await;
+    tassert_eq!(run.cfg.get_seat_idle_time(ds.seat.id())?, 0);
+
+    run.cfg.advance_time(Duration::from_secs(5))?;
+    tassert_eq!(run.cfg.get_seat_idle_time(ds.seat.id())?, 5000);
+
+    ds.mouse.rel(1.0, 1.0);
+    run.sync().await;
+    tassert_eq!(run.cfg.get_seat_idle_time(ds.seat.id())?, 0);
+
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use {
+    crate::it::{test_error::TestResult, testrun::TestRun},
+    jay_config::video::OutputLayout,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    let layout = run.cfg.get_output_layout()?;
+    tassert_eq!(layout.outputs.len(), 1);
+    let mut entry = layout.outputs[0].clone();
+    entry.x = 100;
+    entry.width = 1024;
+    entry.height = 768;
+
+    let mut other_connector = entry.clone();
+    other_connector.connector = "other".to_string();
+    run.cfg.apply_output_layout(OutputLayout {
+        outputs: vec![entry.clone(), other_connector],
+    })?;
+    run.sync().await;
+    tassert_eq!(ds.output.global.pos.get().x1(), 0);
+    tassert_eq!(ds.output.global.mode.get().width, 800);
+
+    let mut invalid_mode = entry.clone();
+    invalid_mode.width = 1234;
+    run.cfg.apply_output_layout(OutputLayout {
+        outputs: vec![invalid_mode],
+    })?;
+    run.sync().await;
+    tassert_eq!(ds.output.global.pos.get().x1(), 0);
+    tassert_eq!(ds.output.global.mode.get().width, 800);
+
+    run.cfg.apply_output_layout(OutputLayout {
+        outputs: vec![entry],
+    })?;
+    run.sync().await;
+    tassert_eq!(ds.output.global.pos.get().x1(), 100);
+    tassert_eq!(ds.output.global.mode.get().width, 1024);
+    tassert_eq!(ds.output.global.mode.get().height, 768);
+
+    Ok(())
+}
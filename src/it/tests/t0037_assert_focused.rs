@@ -0,0 +1,37 @@
+use {
+    crate::{
+        it::{test_error::TestResult, testrun::TestRun},
+        tree::Node,
+    },
+    jay_config::Direction,
+    std::rc::Rc,
+};
+
+testcase!();
+
+/// Test that the config can query the focused window
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    ds.mouse.rel(1.0, 1.0);
+
+    run.cfg.assert_focused(ds.seat.id(), None)?;
+
+    let client = run.create_client().await?;
+
+    let window = client.create_window().await?;
+    window.map().await?;
+    run.cfg
+        .assert_focused(ds.seat.id(), Some(window.tl.server.node_id()))?;
+
+    let window2 = client.create_window().await?;
+    window2.map().await?;
+    run.cfg
+        .assert_focused(ds.seat.id(), Some(window2.tl.server.node_id()))?;
+
+    run.cfg.focus(ds.seat.id(), Direction::Left)?;
+    client.sync().await;
+    run.cfg
+        .assert_focused(ds.seat.id(), Some(window.tl.server.node_id()))?;
+
+    Ok(())
+}
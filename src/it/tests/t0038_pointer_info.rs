@@ -0,0 +1,58 @@
+use {
+    crate::{
+        ifs::wl_seat::BTN_LEFT,
+        it::{
+            test_error::{TestErrorExt, TestResult},
+            testrun::TestRun,
+        },
+    },
+    jay_config::input::SurfaceId,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+
+    run.cfg.set_pointer_position(ds.seat.id(), 100, 200)?;
+    let info = run.cfg.get_pointer(ds.seat.id())?;
+    tassert_eq!((info.x, info.y), (100.0, 200.0));
+
+    // Positions outside of all outputs are clamped to the nearest output.
+    run.cfg.set_pointer_position(ds.seat.id(), -50, 5000)?;
+    let info = run.cfg.get_pointer(ds.seat.id())?;
+    tassert_eq!((info.x, info.y), (0.0, 599.0));
+
+    let client = run.create_client().await?;
+    let seat = client.get_default_seat().await?;
+    let ddm = client.registry.get_data_device_manager().await?;
+    let dd = ddm.get_data_device(seat.seat.id)?;
+    let window = client.create_window().await?;
+    window.map().await?;
+
+    run.cfg.set_pointer_position(ds.seat.id(), 400, 300)?;
+    client.sync().await;
+    let surface = Some(SurfaceId {
+        client: client.server.id.raw(),
+        surface: window.surface.id.raw(),
+    });
+    let info = run.cfg.get_pointer(ds.seat.id())?;
+    tassert_eq!(info.surface, surface);
+    tassert_eq!(info.focus, surface);
+
+    let buttons = seat.pointer.button.expect()?;
+    let _click = ds.mouse.click(BTN_LEFT);
+    client.sync().await;
+    let button = buttons.next().with_context(|| "button")?;
+    let info = run.cfg.get_pointer(ds.seat.id())?;
+    tassert_eq!(info.focus, surface);
+
+    // The compositor handles the pointer during drag-and-drop.
+    dd.start_drag(None, window.surface.id, button.serial)?;
+    client.sync().await;
+    let info = run.cfg.get_pointer(ds.seat.id())?;
+    tassert_eq!(info.focus, None);
+
+    Ok(())
+}
@@ -0,0 +1,28 @@
+use {
+    crate::{
+        backend::ConnectorEvent,
+        it::{test_error::TestResult, testrun::TestRun},
+    },
+    jay_config::video::HotplugPolicy,
+    std::rc::Rc,
+};
+
+testcase!();
+
+async fn test(run: Rc<TestRun>) -> TestResult {
+    let ds = run.create_default_setup().await?;
+    let id = ds.connector.id;
+
+    tassert_eq!(ds.connector.hotplug_policy.get(), HotplugPolicy::Auto);
+    run.cfg.set_hotplug_policy(id, HotplugPolicy::AskConfig)?;
+    tassert_eq!(ds.connector.hotplug_policy.get(), HotplugPolicy::AskConfig);
+
+    ds.connector.send_event(ConnectorEvent::HotplugPending);
+    run.sync().await;
+    tassert!(run.cfg.hotplugs.remove(&id).is_some());
+
+    run.cfg.accept_hotplug(id)?;
+    tassert_eq!(ds.connector.accepted_hotplugs.get(), 1);
+
+    Ok(())
+}
@@ -3,8 +3,13 @@ use {
         consts::{ButtonState, EventType, KeyState, PointerAxis},
         device::LibInputDevice,
         sys::{
-            libinput_event, libinput_event_destroy, libinput_event_get_device,
-            libinput_event_get_keyboard_event, libinput_event_get_pointer_event,
+            libinput_event, libinput_event_destroy, libinput_event_gesture,
+            libinput_event_gesture_get_angle_delta, libinput_event_gesture_get_cancelled,
+            libinput_event_gesture_get_dx, libinput_event_gesture_get_dy,
+            libinput_event_gesture_get_finger_count, libinput_event_gesture_get_scale,
+            libinput_event_gesture_get_time_usec, libinput_event_get_device,
+            libinput_event_get_gesture_event, libinput_event_get_keyboard_event,
+            libinput_event_get_pointer_event, libinput_event_get_touch_event,
             libinput_event_get_type, libinput_event_keyboard, libinput_event_keyboard_get_key,
             libinput_event_keyboard_get_key_state, libinput_event_keyboard_get_time_usec,
             libinput_event_pointer, libinput_event_pointer_get_button,
@@ -12,7 +17,9 @@ use {
             libinput_event_pointer_get_dx_unaccelerated, libinput_event_pointer_get_dy,
             libinput_event_pointer_get_dy_unaccelerated, libinput_event_pointer_get_scroll_value,
             libinput_event_pointer_get_scroll_value_v120, libinput_event_pointer_get_time_usec,
-            libinput_event_pointer_has_axis,
+            libinput_event_pointer_has_axis, libinput_event_touch,
+            libinput_event_touch_get_seat_slot, libinput_event_touch_get_time_usec,
+            libinput_event_touch_get_x_transformed, libinput_event_touch_get_y_transformed,
         },
     },
     std::marker::PhantomData,
@@ -33,6 +40,16 @@ pub struct LibInputEventPointer<'a> {
     pub(super) _phantom: PhantomData<&'a ()>,
 }
 
+pub struct LibInputEventGesture<'a> {
+    pub(super) event: *mut libinput_event_gesture,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
+pub struct LibInputEventTouch<'a> {
+    pub(super) event: *mut libinput_event_touch,
+    pub(super) _phantom: PhantomData<&'a ()>,
+}
+
 impl<'a> Drop for LibInputEvent<'a> {
     fn drop(&mut self) {
         unsafe {
@@ -76,6 +93,30 @@ impl<'a> LibInputEvent<'a> {
             })
         }
     }
+
+    pub fn touch_event(&self) -> Option<LibInputEventTouch> {
+        let res = unsafe { libinput_event_get_touch_event(self.event) };
+        if res.is_null() {
+            None
+        } else {
+            Some(LibInputEventTouch {
+                event: res,
+                _phantom: Default::default(),
+            })
+        }
+    }
+
+    pub fn gesture_event(&self) -> Option<LibInputEventGesture> {
+        let res = unsafe { libinput_event_get_gesture_event(self.event) };
+        if res.is_null() {
+            None
+        } else {
+            Some(LibInputEventGesture {
+                event: res,
+                _phantom: Default::default(),
+            })
+        }
+    }
 }
 
 impl<'a> LibInputEventKeyboard<'a> {
@@ -134,3 +175,53 @@ impl<'a> LibInputEventPointer<'a> {
         unsafe { libinput_event_pointer_get_time_usec(self.event) }
     }
 }
+
+impl<'a> LibInputEventGesture<'a> {
+    pub fn time_usec(&self) -> u64 {
+        unsafe { libinput_event_gesture_get_time_usec(self.event) }
+    }
+
+    pub fn finger_count(&self) -> u32 {
+        unsafe { libinput_event_gesture_get_finger_count(self.event) as u32 }
+    }
+
+    pub fn cancelled(&self) -> bool {
+        unsafe { libinput_event_gesture_get_cancelled(self.event) != 0 }
+    }
+
+    pub fn dx(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_dx(self.event) }
+    }
+
+    pub fn dy(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_dy(self.event) }
+    }
+
+    pub fn scale(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_scale(self.event) }
+    }
+
+    pub fn angle_delta(&self) -> f64 {
+        unsafe { libinput_event_gesture_get_angle_delta(self.event) }
+    }
+}
+
+impl<'a> LibInputEventTouch<'a> {
+    pub fn time_usec(&self) -> u64 {
+        unsafe { libinput_event_touch_get_time_usec(self.event) }
+    }
+
+    pub fn seat_slot(&self) -> i32 {
+        unsafe { libinput_event_touch_get_seat_slot(self.event) }
+    }
+
+    /// The x coordinate normalized to `[0, 1]`.
+    pub fn x_normed(&self) -> f64 {
+        unsafe { libinput_event_touch_get_x_transformed(self.event, 1) }
+    }
+
+    /// The y coordinate normalized to `[0, 1]`.
+    pub fn y_normed(&self) -> f64 {
+        unsafe { libinput_event_touch_get_y_transformed(self.event, 1) }
+    }
+}
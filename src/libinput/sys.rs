@@ -14,6 +14,10 @@ pub struct libinput_event(u8);
 pub struct libinput_event_keyboard(u8);
 #[repr(transparent)]
 pub struct libinput_event_pointer(u8);
+#[repr(transparent)]
+pub struct libinput_event_gesture(u8);
+#[repr(transparent)]
+pub struct libinput_event_touch(u8);
 
 #[link(name = "input")]
 extern "C" {
@@ -96,6 +100,29 @@ extern "C" {
     ) -> libinput_key_state;
     pub fn libinput_event_keyboard_get_time_usec(event: *mut libinput_event_keyboard) -> u64;
 
+    pub fn libinput_event_get_touch_event(event: *mut libinput_event) -> *mut libinput_event_touch;
+    pub fn libinput_event_touch_get_time_usec(event: *mut libinput_event_touch) -> u64;
+    pub fn libinput_event_touch_get_seat_slot(event: *mut libinput_event_touch) -> i32;
+    pub fn libinput_event_touch_get_x_transformed(
+        event: *mut libinput_event_touch,
+        width: u32,
+    ) -> f64;
+    pub fn libinput_event_touch_get_y_transformed(
+        event: *mut libinput_event_touch,
+        height: u32,
+    ) -> f64;
+
+    pub fn libinput_event_get_gesture_event(
+        event: *mut libinput_event,
+    ) -> *mut libinput_event_gesture;
+    pub fn libinput_event_gesture_get_time_usec(event: *mut libinput_event_gesture) -> u64;
+    pub fn libinput_event_gesture_get_finger_count(event: *mut libinput_event_gesture) -> c::c_int;
+    pub fn libinput_event_gesture_get_cancelled(event: *mut libinput_event_gesture) -> c::c_int;
+    pub fn libinput_event_gesture_get_dx(event: *mut libinput_event_gesture) -> f64;
+    pub fn libinput_event_gesture_get_dy(event: *mut libinput_event_gesture) -> f64;
+    pub fn libinput_event_gesture_get_scale(event: *mut libinput_event_gesture) -> f64;
+    pub fn libinput_event_gesture_get_angle_delta(event: *mut libinput_event_gesture) -> f64;
+
     pub fn libinput_event_get_pointer_event(
         event: *mut libinput_event,
     ) -> *mut libinput_event_pointer;
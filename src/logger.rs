@@ -3,15 +3,18 @@ use {
     backtrace::Backtrace,
     bstr::{BStr, BString, ByteSlice},
     log::{Level, Log, Metadata, Record},
+    once_cell::sync::Lazy,
     std::{
         cell::Cell,
+        collections::VecDeque,
+        fmt,
         fs::DirBuilder,
         io::Write,
         os::unix::{ffi::OsStringExt, fs::DirBuilderExt},
         ptr,
         sync::{
-            atomic::{AtomicU32, Ordering::Relaxed},
-            Arc,
+            atomic::{AtomicBool, AtomicU32, Ordering::Relaxed},
+            Arc, Mutex, RwLock,
         },
         time::SystemTime,
     },
@@ -22,12 +25,47 @@ thread_local! {
     static BUFFER: Cell<*mut Vec<u8>> = const { Cell::new(ptr::null_mut()) };
 }
 
+const RECENT_LINES: usize = 1000;
+const MAX_RECENT_LINE_LEN: usize = 1024;
+
+static JSON_LOGS: Lazy<bool> =
+    Lazy::new(|| std::env::var("JAY_LOG_JSON").ok().as_deref() == Some("1"));
+
 pub struct Logger {
     level: AtomicU32,
     path: BString,
     file: OwnedFd,
+    json: bool,
+    has_module_levels: AtomicBool,
+    module_levels: RwLock<Vec<(String, Level)>>,
+    recent: Mutex<VecDeque<String>>,
 }
 
+// Maps subsystem names to the module paths they cover.
+const SUBSYSTEMS: &[(&str, &[&str])] = &[
+    ("vulkan", &["jay::gfx_apis::vulkan"]),
+    ("opengl", &["jay::gfx_apis::gl"]),
+    ("metal", &["jay::backends::metal"]),
+    ("x11", &["jay::backends::x"]),
+    (
+        "input",
+        &[
+            "jay::libinput",
+            "jay::backends::metal::input",
+            "jay::ifs::wl_seat",
+        ],
+    ),
+    ("xwayland", &["jay::xwayland", "jay::xcon"]),
+    ("wayland", &["jay::ifs", "jay::client"]),
+    ("config", &["jay::config"]),
+    ("pipewire", &["jay::pipewire"]),
+    ("portal", &["jay::portal"]),
+    ("dbus", &["jay::dbus"]),
+];
+
+#[derive(Debug)]
+pub struct UnknownSubsystem;
+
 impl Logger {
     pub fn install_stderr(level: Level) -> Arc<Self> {
         let file = match uapi::fcntl_dupfd_cloexec(2, 0) {
@@ -92,6 +130,10 @@ impl Logger {
             level: AtomicU32::new(level as _),
             path: path.to_vec().into(),
             file,
+            json: *JSON_LOGS,
+            has_module_levels: AtomicBool::new(false),
+            module_levels: Default::default(),
+            recent: Default::default(),
         });
         log::set_boxed_logger(Box::new(LogWrapper {
             logger: slf.clone(),
@@ -103,7 +145,81 @@ impl Logger {
 
     pub fn set_level(&self, level: Level) {
         self.level.store(level as _, Relaxed);
-        log::set_max_level(level.to_level_filter());
+        self.update_max_level();
+    }
+
+    pub fn set_subsystem_level(
+        &self,
+        subsystem: &str,
+        level: Level,
+    ) -> Result<(), UnknownSubsystem> {
+        let custom = [subsystem];
+        let modules = match SUBSYSTEMS.iter().find(|s| s.0 == subsystem) {
+            Some((_, modules)) => *modules,
+            None if subsystem.contains("::") => &custom[..],
+            None => return Err(UnknownSubsystem),
+        };
+        {
+            let mut levels = self.module_levels.write().unwrap();
+            for &module in modules {
+                levels.retain(|(m, _)| m != module);
+                levels.push((module.to_string(), level));
+            }
+            // Longer module paths take precedence.
+            levels.sort_by_key(|(m, _)| std::cmp::Reverse(m.len()));
+            self.has_module_levels.store(true, Relaxed);
+        }
+        self.update_max_level();
+        Ok(())
+    }
+
+    fn update_max_level(&self) {
+        let mut max = self.level.load(Relaxed);
+        for (_, level) in self.module_levels.read().unwrap().iter() {
+            max = max.max(*level as u32);
+        }
+        let filter = log::LevelFilter::iter()
+            .find(|f| *f as u32 == max)
+            .unwrap_or(log::LevelFilter::Trace);
+        log::set_max_level(filter);
+    }
+
+    fn level_for(&self, target: &str) -> u32 {
+        if self.has_module_levels.load(Relaxed) {
+            for (module, level) in self.module_levels.read().unwrap().iter() {
+                if let Some(rest) = target.strip_prefix(module.as_str()) {
+                    if rest.is_empty() || rest.starts_with("::") {
+                        return *level as u32;
+                    }
+                }
+            }
+        }
+        self.level.load(Relaxed)
+    }
+
+    /// Returns up to `max_lines` of the most recent log lines, oldest first.
+    pub fn recent_lines(&self, max_lines: usize) -> Vec<String> {
+        let recent = self.recent.lock().unwrap();
+        let skip = recent.len().saturating_sub(max_lines);
+        recent.iter().skip(skip).cloned().collect()
+    }
+
+    fn add_recent_line(&self, line: &[u8]) {
+        let line = line.strip_suffix(b"\n").unwrap_or(line);
+        let mut line = String::from_utf8_lossy(line).into_owned();
+        if line.len() > MAX_RECENT_LINE_LEN {
+            let mut end = MAX_RECENT_LINE_LEN;
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+            line.truncate(end);
+            line.push_str("...");
+        }
+        let mut recent = self.recent.lock().unwrap();
+        if recent.len() >= RECENT_LINES {
+            recent.pop_front();
+        }
+        recent.push_back(line);
     }
 
     pub fn path(&self) -> &BStr {
@@ -137,11 +253,11 @@ struct LogWrapper {
 
 impl Log for LogWrapper {
     fn enabled(&self, metadata: &Metadata) -> bool {
-        metadata.level() as u32 <= self.logger.level.load(Relaxed)
+        metadata.level() as u32 <= self.logger.level_for(metadata.target())
     }
 
     fn log(&self, record: &Record) {
-        if record.level() as u32 > self.logger.level.load(Relaxed) {
+        if record.level() as u32 > self.logger.level_for(record.target()) {
             return;
         }
         let mut buffer = BUFFER.get();
@@ -152,7 +268,9 @@ impl Log for LogWrapper {
         let buffer = unsafe { &mut *buffer };
         buffer.clear();
         let now = SystemTime::now();
-        let _ = if let Some(mp) = record.module_path() {
+        let _ = if self.logger.json {
+            write_json(buffer, now, record)
+        } else if let Some(mp) = record.module_path() {
             writeln!(
                 buffer,
                 "[{} {:5} {}] {}",
@@ -172,9 +290,48 @@ impl Log for LogWrapper {
         };
         let mut fd = Fd::new(self.logger.file.raw());
         let _ = fd.write_all(buffer);
+        self.logger.add_recent_line(buffer);
     }
 
     fn flush(&self) {
         // nothing
     }
 }
+
+fn write_json(buffer: &mut Vec<u8>, now: SystemTime, record: &Record) -> std::io::Result<()> {
+    write!(
+        buffer,
+        "{{\"ts\":\"{}\",\"level\":\"{}\",\"target\":\"",
+        humantime::format_rfc3339_millis(now),
+        record.level(),
+    )?;
+    let _ = fmt::Write::write_str(&mut JsonEscape(buffer), record.target());
+    buffer.extend_from_slice(b"\",\"msg\":\"");
+    let _ = fmt::Write::write_fmt(&mut JsonEscape(buffer), *record.args());
+    buffer.extend_from_slice(b"\"}\n");
+    Ok(())
+}
+
+struct JsonEscape<'a>(&'a mut Vec<u8>);
+
+impl fmt::Write for JsonEscape<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.extend_from_slice(b"\\\""),
+                '\\' => self.0.extend_from_slice(b"\\\\"),
+                '\n' => self.0.extend_from_slice(b"\\n"),
+                '\r' => self.0.extend_from_slice(b"\\r"),
+                '\t' => self.0.extend_from_slice(b"\\t"),
+                c if (c as u32) < 0x20 => {
+                    let _ = write!(self.0, "\\u{:04x}", c as u32);
+                }
+                c => {
+                    let mut buf = [0; 4];
+                    self.0.extend_from_slice(c.encode_utf8(&mut buf).as_bytes());
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -114,6 +114,23 @@ impl Session {
         );
     }
 
+    pub fn set_brightness(&self, subsystem: &str, name: &str, brightness: u32) {
+        self.socket.call(
+            LOGIND_NAME,
+            &self.session_path,
+            org::freedesktop::login1::session::SetBrightness {
+                subsystem: subsystem.into(),
+                name: name.into(),
+                brightness,
+            },
+            |res| {
+                if let Err(e) = res {
+                    log::warn!("Could not set brightness: {}", ErrorFmt(e));
+                }
+            },
+        );
+    }
+
     pub fn on_pause<F>(&self, f: F) -> Result<SignalHandler, DbusError>
     where
         F: for<'b> Fn(PauseDevice<'b>) + 'static,
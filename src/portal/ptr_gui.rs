@@ -222,6 +222,7 @@ impl GuiElement for Button {
                 None,
                 r.scale(),
                 None,
+                None,
             );
         }
     }
@@ -323,6 +324,7 @@ impl GuiElement for Label {
                 None,
                 r.scale(),
                 None,
+                None,
             );
         }
     }
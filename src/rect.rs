@@ -104,6 +104,14 @@ impl Rect {
             && other.raw.y1 < self.raw.y2
     }
 
+    pub fn touches(&self, other: &Self) -> bool {
+        let x_adjacent = self.raw.x2 == other.raw.x1 || other.raw.x2 == self.raw.x1;
+        let y_adjacent = self.raw.y2 == other.raw.y1 || other.raw.y2 == self.raw.y1;
+        let x_overlap = self.raw.x1 < other.raw.x2 && other.raw.x1 < self.raw.x2;
+        let y_overlap = self.raw.y1 < other.raw.y2 && other.raw.y1 < self.raw.y2;
+        (x_adjacent && y_overlap) || (y_adjacent && x_overlap)
+    }
+
     pub fn intersect(&self, other: Self) -> Self {
         let x1 = self.raw.x1.max(other.raw.x1);
         let y1 = self.raw.y1.max(other.raw.y1);
@@ -134,10 +142,9 @@ impl Rect {
         dx * dx + dy * dy
     }
 
-    #[allow(dead_code)]
     pub fn contains_rect(&self, rect: &Self) -> bool {
         self.raw.x1 <= rect.raw.x1
-            && self.raw.y1 <= rect.raw.x1
+            && self.raw.y1 <= rect.raw.y1
             && rect.raw.x2 <= self.raw.x2
             && rect.raw.y2 <= self.raw.y2
     }
@@ -189,6 +196,30 @@ impl Rect {
         }
     }
 
+    pub fn scale_int(&self, scale: i32) -> Self {
+        Self {
+            raw: RectRaw {
+                x1: self.raw.x1.saturating_mul(scale),
+                y1: self.raw.y1.saturating_mul(scale),
+                x2: self.raw.x2.saturating_mul(scale),
+                y2: self.raw.y2.saturating_mul(scale),
+            },
+        }
+    }
+
+    pub fn unscale_int(&self, scale: i32) -> Self {
+        let down = |v: i32| v.div_euclid(scale);
+        let up = |v: i32| -(-v).div_euclid(scale);
+        Self {
+            raw: RectRaw {
+                x1: down(self.raw.x1),
+                y1: down(self.raw.y1),
+                x2: up(self.raw.x2),
+                y2: up(self.raw.y2),
+            },
+        }
+    }
+
     pub fn with_size(&self, width: i32, height: i32) -> Option<Self> {
         Self::new_sized(self.raw.x1, self.raw.y1, width, height)
     }
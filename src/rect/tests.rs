@@ -111,3 +111,13 @@ fn rects_to_bands2() {
     // println!("{:#?}", r.rects);
     assert_eq!(&r.rects[..], &[Rect::new(0, 0, 10, 20).unwrap().raw,]);
 }
+
+#[test]
+fn touches() {
+    let r1 = Rect::new(0, 0, 10, 10).unwrap();
+    assert!(r1.touches(&Rect::new(10, 5, 20, 15).unwrap()));
+    assert!(r1.touches(&Rect::new(-5, -10, 5, 0).unwrap()));
+    assert!(!r1.touches(&Rect::new(10, 10, 20, 20).unwrap()));
+    assert!(!r1.touches(&Rect::new(11, 0, 20, 10).unwrap()));
+    assert!(!r1.touches(&Rect::new(5, 5, 15, 15).unwrap()));
+}
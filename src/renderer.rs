@@ -1,6 +1,6 @@
 use {
     crate::{
-        gfx_api::{GfxApiOpt, SampleRect},
+        gfx_api::{GfxApiOpt, GradientDirection, SampleRect},
         ifs::{
             wl_buffer::WlBuffer,
             wl_callback::WlCallback,
@@ -15,8 +15,8 @@ use {
         state::State,
         theme::Color,
         tree::{
-            ContainerNode, DisplayNode, FloatNode, OutputNode, PlaceholderNode, ToplevelNodeBase,
-            WorkspaceNode,
+            ContainerNode, DisplayNode, FloatNode, NodeId, OutputNode, PlaceholderNode,
+            ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
     },
     std::{
@@ -25,6 +25,7 @@ use {
         rc::Rc,
         slice,
     },
+    uapi::OwnedFd,
 };
 
 pub mod renderer_base;
@@ -33,9 +34,18 @@ pub mod renderer_base;
 pub struct RenderResult {
     pub frame_requests: Vec<Rc<WlCallback>>,
     pub presentation_feedbacks: Vec<Rc<WpPresentationFeedback>>,
+    /// The toplevels rendered in this frame and whether they are occluded.
+    pub occlusion: Vec<(Rc<dyn ToplevelNode>, bool)>,
 }
 
 impl RenderResult {
+    /// Informs the toplevels rendered in this frame whether they are occluded.
+    pub fn update_occlusion(&mut self) {
+        for (tl, occluded) in self.occlusion.drain(..) {
+            tl.tl_set_occluded(occluded);
+        }
+    }
+
     pub fn dispatch_frame_requests(&mut self) {
         for fr in self.frame_requests.drain(..) {
             fr.send_done();
@@ -56,6 +66,14 @@ pub struct Renderer<'a> {
     pub result: Option<&'a mut RenderResult>,
     pub logical_extents: Rect,
     pub pixel_extents: Rect,
+    /// Absolute rectangles covered by opaque floating windows, from bottom to top.
+    /// Toplevels fully contained in one of them are suspended.
+    pub occluders: Vec<(NodeId, Rect)>,
+    /// The index of the first occluder that is stacked above the node being rendered.
+    pub first_occluder: usize,
+    /// Whether frame requests of surfaces fully contained in one of the occluders are
+    /// held back.
+    pub throttle_occluded: bool,
 }
 
 impl Renderer<'_> {
@@ -91,6 +109,13 @@ impl Renderer<'_> {
             return;
         }
         let opos = output.global.pos.get();
+        if output.is_dummy {
+            if let Some(color) = self.state.dummy_output_color.get() {
+                if let Some(rect) = Rect::new_sized(x, y, opos.width(), opos.height()) {
+                    self.base.fill_boxes(&[rect], &color);
+                }
+            }
+        }
         macro_rules! render_layer {
             ($layer:expr) => {
                 for ls in $layer.iter() {
@@ -104,6 +129,18 @@ impl Renderer<'_> {
                 }
             };
         }
+        if let Some((stacked, _)) = output.covering_stacked_node() {
+            let pos = stacked.node_absolute_position();
+            stacked.node_render(
+                self,
+                x + pos.x1() - opos.x1(),
+                y + pos.y1() - opos.y1(),
+                None,
+            );
+            render_layer!(output.layers[2]);
+            render_layer!(output.layers[3]);
+            return;
+        }
         if let Some(ws) = output.workspace.get() {
             if let Some(fs) = ws.fullscreen.get() {
                 fs.tl_as_node().node_render(self, x, y, None);
@@ -114,6 +151,43 @@ impl Renderer<'_> {
         }
         render_layer!(output.layers[0]);
         render_layer!(output.layers[1]);
+        let th = self.state.theme.sizes.title_height.get();
+        if let Some(ws) = output.workspace.get() {
+            self.throttle_occluded = output.throttle_frame_callbacks.get();
+            self.collect_occluders();
+            let y = y + th + 1;
+            match output.workspace_transition_offsets() {
+                Some((old, (ox, oy), (nx, ny))) => {
+                    self.render_workspace(&old, x + ox, y + oy);
+                    self.render_workspace(&ws, x + nx, y + ny);
+                }
+                _ => self.render_workspace(&ws, x, y),
+            }
+        }
+        self.render_bar(output, x, y);
+        for stacked in self.state.root.stacked.iter() {
+            if stacked.node_visible() {
+                if let Some((id, _)) = self.occluders.get(self.first_occluder) {
+                    if *id == stacked.node_id() {
+                        self.first_occluder += 1;
+                    }
+                }
+                self.base.ops.push(GfxApiOpt::Sync);
+                let pos = stacked.node_absolute_position();
+                if pos.intersects(&opos) {
+                    let (x, y) = opos.translate(pos.x1(), pos.y1());
+                    stacked.node_render(self, x, y, None);
+                }
+            }
+        }
+        self.occluders.clear();
+        self.first_occluder = 0;
+        render_layer!(output.layers[2]);
+        render_layer!(output.layers[3]);
+    }
+
+    fn render_bar(&mut self, output: &OutputNode, x: i32, y: i32) {
+        let opos = output.global.pos.get();
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         {
@@ -152,29 +226,58 @@ impl Renderer<'_> {
             for title in &rd.titles {
                 let (x, y) = self.base.scale_point(x + title.tex_x, y + title.tex_y);
                 self.base
-                    .render_texture(&title.tex, x, y, None, None, scale, None);
+                    .render_texture(&title.tex, x, y, None, None, scale, None, None);
             }
             if let Some(status) = &rd.status {
                 let (x, y) = self.base.scale_point(x + status.tex_x, y + status.tex_y);
                 self.base
-                    .render_texture(&status.tex.texture, x, y, None, None, scale, None);
+                    .render_texture(&status.tex.texture, x, y, None, None, scale, None, None);
             }
         }
-        if let Some(ws) = output.workspace.get() {
-            self.render_workspace(&ws, x, y + th + 1);
-        }
+    }
+
+    fn collect_occluders(&mut self) {
         for stacked in self.state.root.stacked.iter() {
-            if stacked.node_visible() {
-                self.base.ops.push(GfxApiOpt::Sync);
-                let pos = stacked.node_absolute_position();
-                if pos.intersects(&opos) {
-                    let (x, y) = opos.translate(pos.x1(), pos.y1());
-                    stacked.node_render(self, x, y, None);
-                }
+            if !stacked.node_visible() {
+                continue;
+            }
+            let Some(float) = stacked
+                .deref()
+                .clone()
+                .stacked_into_node()
+                .node_into_float()
+            else {
+                continue;
+            };
+            let Some(surface) = float.child.get().and_then(|c| c.tl_scanout_surface()) else {
+                continue;
+            };
+            if surface.is_opaque() {
+                self.occluders
+                    .push((stacked.node_id(), surface.buffer_abs_pos.get()));
             }
         }
-        render_layer!(output.layers[2]);
-        render_layer!(output.layers[3]);
+    }
+
+    fn fill_title_boxes(&mut self, boxes: &[Rect], color: &Color, x: i32, y: i32) {
+        match self.state.theme.title_gradient.get() {
+            Some(end) => self.base.fill_gradient_boxes2(
+                boxes,
+                color,
+                &end,
+                GradientDirection::Horizontal,
+                x,
+                y,
+            ),
+            _ => self.base.fill_boxes2(boxes, color, x, y),
+        }
+    }
+
+    fn fill_border_boxes(&mut self, boxes: &[Rect], color: &Color, x: i32, y: i32) {
+        match self.state.theme.border_antialiasing.get() {
+            true => self.base.fill_boxes2_aa(boxes, color, x, y),
+            false => self.base.fill_boxes2(boxes, color, x, y),
+        }
     }
 
     pub fn render_workspace(&mut self, workspace: &WorkspaceNode, x: i32, y: i32) {
@@ -194,7 +297,7 @@ impl Renderer<'_> {
             let x = x + (pos.width() - tex_width) / 2;
             let y = y + (pos.height() - tex_height) / 2;
             self.base
-                .render_texture(&tex.texture, x, y, None, None, self.base.scale, None);
+                .render_texture(&tex.texture, x, y, None, None, self.base.scale, None, None);
         }
     }
 
@@ -202,15 +305,18 @@ impl Renderer<'_> {
         {
             let rd = container.render_data.borrow_mut();
             let c = self.state.theme.colors.unfocused_title_background.get();
-            self.base.fill_boxes2(&rd.title_rects, &c, x, y);
+            self.fill_title_boxes(&rd.title_rects, &c, x, y);
             let c = self.state.theme.colors.focused_title_background.get();
-            self.base.fill_boxes2(&rd.active_title_rects, &c, x, y);
+            self.fill_title_boxes(&rd.active_title_rects, &c, x, y);
             let c = self.state.theme.colors.attention_requested_background.get();
-            self.base.fill_boxes2(&rd.attention_title_rects, &c, x, y);
+            self.fill_title_boxes(&rd.attention_title_rects, &c, x, y);
             let c = self.state.theme.colors.separator.get();
             self.base.fill_boxes2(&rd.underline_rects, &c, x, y);
             let c = self.state.theme.colors.border.get();
-            self.base.fill_boxes2(&rd.border_rects, &c, x, y);
+            self.fill_border_boxes(&rd.border_rects, &c, x, y);
+            for (rect, c) in &rd.custom_border_rects {
+                self.fill_border_boxes(std::slice::from_ref(rect), c, x, y);
+            }
             if let Some(lar) = &rd.last_active_rect {
                 let c = self
                     .state
@@ -218,7 +324,7 @@ impl Renderer<'_> {
                     .colors
                     .focused_inactive_title_background
                     .get();
-                self.base.fill_boxes2(std::slice::from_ref(lar), &c, x, y);
+                self.fill_title_boxes(std::slice::from_ref(lar), &c, x, y);
             }
             if let Some(titles) = rd.titles.get(&self.base.scale) {
                 for title in titles {
@@ -231,6 +337,7 @@ impl Renderer<'_> {
                         None,
                         self.base.scale,
                         None,
+                        None,
                     );
                 }
             }
@@ -297,6 +404,8 @@ impl Renderer<'_> {
             }
         };
         let tpoints = surface.buffer_points_norm.borrow_mut();
+        let acquire_sync = surface.acquire_sync.get();
+        let opaque = surface.is_opaque();
         let mut size = surface.buffer_abs_pos.get().size();
         if let Some((x_rel, y_rel)) = pos_rel {
             let (x, y) = self.base.scale_point(x_rel, y_rel);
@@ -326,13 +435,22 @@ impl Renderer<'_> {
                 };
             }
             render!(&children.below);
-            self.render_buffer(&buffer, x, y, *tpoints, size, bounds);
+            self.render_buffer(&buffer, x, y, *tpoints, size, bounds, acquire_sync, opaque);
             render!(&children.above);
         } else {
-            self.render_buffer(&buffer, x, y, *tpoints, size, bounds);
+            self.render_buffer(&buffer, x, y, *tpoints, size, bounds, acquire_sync, opaque);
         }
         if let Some(result) = self.result.as_deref_mut() {
-            {
+            let abs_pos = surface.buffer_abs_pos.get();
+            let occluded = self.occluders[self.first_occluder..]
+                .iter()
+                .any(|(_, o)| o.contains_rect(&abs_pos));
+            if !is_subsurface {
+                if let Some(tl) = surface.get_toplevel() {
+                    result.occlusion.push((tl, occluded));
+                }
+            }
+            if !occluded || !self.throttle_occluded {
                 let mut fr = surface.frame_requests.borrow_mut();
                 result.frame_requests.extend(fr.drain(..));
             }
@@ -351,8 +469,11 @@ impl Renderer<'_> {
         tpoints: SampleRect,
         tsize: (i32, i32),
         bounds: Option<&Rect>,
+        acquire_sync: Option<Rc<OwnedFd>>,
+        opaque: bool,
     ) {
         if let Some(tex) = buffer.texture.get() {
+            let idx = self.base.ops.len();
             self.base.render_texture(
                 &tex,
                 x,
@@ -361,7 +482,11 @@ impl Renderer<'_> {
                 Some(tsize),
                 self.base.scale,
                 bounds,
+                acquire_sync,
             );
+            if let Some(GfxApiOpt::CopyTexture(ct)) = self.base.ops.get_mut(idx) {
+                ct.opaque = opaque;
+            }
         } else if let Some(color) = &buffer.color {
             if let Some(rect) = Rect::new_sized(x, y, tsize.0, tsize.1) {
                 let rect = match bounds {
@@ -386,7 +511,10 @@ impl Renderer<'_> {
         let theme = &self.state.theme;
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
-        let bc = theme.colors.border.get();
+        let bc = match child.tl_data().border_color.get() {
+            Some(c) => c,
+            _ => theme.colors.border.get(),
+        };
         let tc = if floating.active.get() {
             theme.colors.focused_title_background.get()
         } else if floating.attention_requested.get() {
@@ -401,16 +529,24 @@ impl Renderer<'_> {
             Rect::new_sized(x + pos.width() - bw, y + bw, bw, pos.height() - bw).unwrap(),
             Rect::new_sized(x + bw, y + pos.height() - bw, pos.width() - 2 * bw, bw).unwrap(),
         ];
-        self.base.fill_boxes(&borders, &bc);
+        self.fill_border_boxes(&borders, &bc, 0, 0);
         let title = [Rect::new_sized(x + bw, y + bw, pos.width() - 2 * bw, th).unwrap()];
-        self.base.fill_boxes(&title, &tc);
+        self.fill_title_boxes(&title, &tc, 0, 0);
         let title_underline =
             [Rect::new_sized(x + bw, y + bw + th, pos.width() - 2 * bw, 1).unwrap()];
         self.base.fill_boxes(&title_underline, &uc);
         if let Some(title) = floating.title_textures.get(&self.base.scale) {
             let (x, y) = self.base.scale_point(x + bw, y + bw);
-            self.base
-                .render_texture(&title.texture, x, y, None, None, self.base.scale, None);
+            self.base.render_texture(
+                &title.texture,
+                x,
+                y,
+                None,
+                None,
+                self.base.scale,
+                None,
+                None,
+            );
         }
         let body = Rect::new_sized(
             x + bw,
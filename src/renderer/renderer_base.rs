@@ -1,6 +1,9 @@
 use {
     crate::{
-        gfx_api::{CopyTexture, FillRect, FramebufferRect, GfxApiOpt, GfxTexture, SampleRect},
+        gfx_api::{
+            CopyTexture, FillGradient, FillRect, FillRectAa, FramebufferRect, GfxApiOpt,
+            GfxTexture, GradientDirection, SampleRect,
+        },
         rect::Rect,
         scale::Scale,
         theme::Color,
@@ -8,6 +11,7 @@ use {
     },
     jay_config::video::Transform,
     std::rc::Rc,
+    uapi::OwnedFd,
 };
 
 pub struct RendererBase<'a> {
@@ -85,6 +89,94 @@ impl RendererBase<'_> {
                     self.fb_height,
                 ),
                 color: *color,
+                aa: None,
+            }));
+        }
+    }
+
+    /// Like `fill_boxes2` but positions the edges of the boxes exactly at fractional
+    /// scales and renders partially covered pixels with reduced opacity.
+    ///
+    /// Pixels covered by `fill_boxes2` are always filled completely. Otherwise there would
+    /// be a seam where a box meets content that is positioned with the rounded scale.
+    pub fn fill_boxes2_aa(&mut self, boxes: &[Rect], color: &Color, dx: i32, dy: i32) {
+        if self.scalef.fract() == 0.0 {
+            self.fill_boxes2(boxes, color, dx, dy);
+            return;
+        }
+        if boxes.is_empty() || *color == Color::TRANSPARENT {
+            return;
+        }
+        let (sdx, sdy) = self.scale_point(dx, dy);
+        for bx in boxes {
+            let scale = |v: i32| (v as f64 * self.scalef) as f32;
+            let x1 = scale(bx.x1() + dx);
+            let y1 = scale(bx.y1() + dy);
+            let x2 = scale(bx.x2() + dx);
+            let y2 = scale(bx.y2() + dy);
+            let (ex1, ey1, ex2, ey2) = (x1.floor(), y1.floor(), x2.ceil(), y2.ceil());
+            let rounded = self.scale_rect(*bx);
+            let rx1 = (rounded.x1() + sdx) as f32;
+            let ry1 = (rounded.y1() + sdy) as f32;
+            let rx2 = (rounded.x2() + sdx) as f32;
+            let ry2 = (rounded.y2() + sdy) as f32;
+            self.ops.push(GfxApiOpt::FillRect(FillRect {
+                rect: FramebufferRect::new(
+                    rx1,
+                    ry1,
+                    rx2,
+                    ry2,
+                    self.transform,
+                    self.fb_width,
+                    self.fb_height,
+                ),
+                color: *color,
+                aa: Some(FillRectAa {
+                    rect: FramebufferRect::new(
+                        ex1,
+                        ey1,
+                        ex2,
+                        ey2,
+                        self.transform,
+                        self.fb_width,
+                        self.fb_height,
+                    ),
+                    pos: [[ex2, ey1], [ex1, ey1], [ex2, ey2], [ex1, ey2]],
+                    bounds: [x1.min(rx1), y1.min(ry1), x2.max(rx2), y2.max(ry2)],
+                }),
+            }));
+        }
+    }
+
+    pub fn fill_gradient_boxes2(
+        &mut self,
+        boxes: &[Rect],
+        start: &Color,
+        end: &Color,
+        direction: GradientDirection,
+        dx: i32,
+        dy: i32,
+    ) {
+        if start == end {
+            self.fill_boxes2(boxes, start, dx, dy);
+            return;
+        }
+        let (dx, dy) = self.scale_point(dx, dy);
+        for bx in boxes {
+            let bx = self.scale_rect(*bx);
+            self.ops.push(GfxApiOpt::FillGradient(FillGradient {
+                rect: FramebufferRect::new(
+                    (bx.x1() + dx) as f32,
+                    (bx.y1() + dy) as f32,
+                    (bx.x2() + dx) as f32,
+                    (bx.y2() + dy) as f32,
+                    self.transform,
+                    self.fb_width,
+                    self.fb_height,
+                ),
+                start: *start,
+                end: *end,
+                direction,
             }));
         }
     }
@@ -117,6 +209,7 @@ impl RendererBase<'_> {
                     self.fb_height,
                 ),
                 color: *color,
+                aa: None,
             }));
         }
     }
@@ -130,6 +223,7 @@ impl RendererBase<'_> {
         tsize: Option<(i32, i32)>,
         tscale: Scale,
         bounds: Option<&Rect>,
+        acquire_sync: Option<Rc<OwnedFd>>,
     ) {
         let mut texcoord = tpoints.unwrap_or_else(SampleRect::identity);
 
@@ -166,6 +260,8 @@ impl RendererBase<'_> {
                 self.fb_width,
                 self.fb_height,
             ),
+            acquire_sync,
+            opaque: false,
         }));
     }
 }
@@ -4,7 +4,8 @@ use {
         async_engine::{AsyncEngine, SpawnedFuture},
         backend::{
             Backend, BackendDrmDevice, BackendEvent, Connector, ConnectorId, ConnectorIds,
-            DrmDeviceId, DrmDeviceIds, InputDevice, InputDeviceId, InputDeviceIds, MonitorInfo,
+            DrmDeviceId, DrmDeviceIds, InputDevice, InputDeviceId, InputDeviceIds, KeyState,
+            MonitorInfo,
         },
         backends::dummy::DummyBackend,
         cli::RunArgs,
@@ -18,7 +19,7 @@ use {
         forker::ForkerProxy,
         format::Format,
         gfx_api::{GfxContext, GfxError, GfxFramebuffer, GfxTexture, SampleRect},
-        gfx_apis::create_gfx_context,
+        gfx_apis::{create_exact_gfx_context, create_gfx_context, probe_gfx_apis},
         globals::{Globals, GlobalsError, WaylandGlobal},
         ifs::{
             ext_foreign_toplevel_list_v1::ExtForeignToplevelListV1,
@@ -28,11 +29,12 @@ use {
             jay_workspace_watcher::JayWorkspaceWatcher,
             wl_drm::WlDrmGlobal,
             wl_output::OutputId,
-            wl_seat::{SeatIds, WlSeatGlobal},
+            wl_seat::{collect_kb_foci, SeatIds, WlSeatGlobal},
             wl_surface::{
                 zwp_idle_inhibitor_v1::{IdleInhibitorId, IdleInhibitorIds, ZwpIdleInhibitorV1},
                 NoneSurfaceExt, WlSurface,
             },
+            wp_linux_drm_syncobj_manager_v1::WpLinuxDrmSyncobjManagerV1Global,
             zwlr_screencopy_frame_v1::ZwlrScreencopyFrameV1,
             zwp_linux_dmabuf_feedback_v1::ZwpLinuxDmabufFeedbackV1,
             zwp_linux_dmabuf_v1::ZwpLinuxDmabufV1Global,
@@ -43,11 +45,13 @@ use {
         rect::Rect,
         renderer::{RenderResult, Renderer},
         scale::Scale,
+        tasks::PendingConnectors,
         theme::{Color, Theme},
+        time::Clock,
         tree::{
-            ContainerNode, ContainerSplit, Direction, DisplayNode, FloatNode, Node, NodeIds,
-            NodeVisitorBase, OutputNode, PlaceholderNode, ToplevelNode, ToplevelNodeBase,
-            WorkspaceNode,
+            flush_container_layout, flush_float_layout, ContainerNode, ContainerSplit, Direction,
+            DisplayNode, FloatNode, Node, NodeIds, NodeVisitorBase, OutputNode, PlaceholderNode,
+            ToplevelNode, ToplevelNodeBase, WorkspaceNode,
         },
         utils::{
             activation_token::ActivationToken, asyncevent::AsyncEvent, clonecell::CloneCell,
@@ -55,7 +59,7 @@ use {
             linkedlist::LinkedList, numcell::NumCell, queue::AsyncQueue, refcounted::RefCounted,
             run_toplevel::RunToplevel,
         },
-        video::{dmabuf::DmaBufIds, drm::Drm},
+        video::{dmabuf::DmaBufIds, drm::Drm, Modifier},
         wheel::Wheel,
         wire::{
             ExtForeignToplevelListV1Id, JayRenderCtxId, JaySeatEventsId, JayWorkspaceWatcherId,
@@ -64,11 +68,11 @@ use {
         xkbcommon::{XkbContext, XkbKeymap},
         xwayland::{self, XWaylandEvent},
     },
-    ahash::AHashMap,
+    ahash::{AHashMap, AHashSet},
     bstr::ByteSlice,
     jay_config::{
         video::{GfxApi, Transform},
-        PciId,
+        Axis, EmptyWorkspaceAction, FloatPlacement, PciId, WindowState,
     },
     std::{
         cell::{Cell, RefCell},
@@ -78,8 +82,9 @@ use {
         ops::DerefMut,
         rc::Rc,
         sync::Arc,
-        time::Duration,
+        time::{Duration, Instant},
     },
+    uapi::c,
 };
 
 pub struct State {
@@ -94,8 +99,11 @@ pub struct State {
         CopyHashMap<(ClientId, ZwpLinuxDmabufFeedbackV1Id), Rc<ZwpLinuxDmabufFeedbackV1>>,
     pub render_ctx_version: NumCell<u32>,
     pub render_ctx_ever_initialized: Cell<bool>,
+    pub syncobj_manager_global: CloneCell<Option<Rc<WpLinuxDrmSyncobjManagerV1Global>>>,
+    pub outputs_ready: Cell<Option<SpawnedFuture<()>>>,
     pub cursors: CloneCell<Option<Rc<ServerCursors>>>,
     pub wheel: Rc<Wheel>,
+    pub clock: Rc<dyn Clock>,
     pub clients: Clients,
     pub globals: Globals,
     pub connector_ids: ConnectorIds,
@@ -106,6 +114,9 @@ pub struct State {
     pub node_ids: NodeIds,
     pub root: Rc<DisplayNode>,
     pub workspaces: CopyHashMap<String, Rc<WorkspaceNode>>,
+    pub spawn_workspaces: CopyHashMap<c::pid_t, Rc<String>>,
+    pub spawn_render_devices: CopyHashMap<c::pid_t, c::dev_t>,
+    pub disabled_modifiers: CopyHashMap<(u32, Modifier), ()>,
     pub dummy_output: CloneCell<Option<Rc<OutputNode>>>,
     pub backend_events: AsyncQueue<BackendEvent>,
     pub input_device_handlers: RefCell<AHashMap<InputDeviceId, InputDeviceData>>,
@@ -120,6 +131,7 @@ pub struct State {
     pub pending_output_render_data: AsyncQueue<Rc<OutputNode>>,
     pub pending_float_layout: AsyncQueue<Rc<FloatNode>>,
     pub pending_float_titles: AsyncQueue<Rc<FloatNode>>,
+    pub pending_empty_workspaces: AsyncQueue<Rc<WorkspaceNode>>,
     pub dbus: Dbus,
     pub fdcloser: Arc<FdCloser>,
     pub logger: Option<Arc<Logger>>,
@@ -146,6 +158,14 @@ pub struct State {
     pub render_ctx_watchers: CopyHashMap<(ClientId, JayRenderCtxId), Rc<JayRenderCtx>>,
     pub workspace_watchers: CopyHashMap<(ClientId, JayWorkspaceWatcherId), Rc<JayWorkspaceWatcher>>,
     pub default_workspace_capture: Cell<bool>,
+    pub workspace_switch_animation: Cell<(Axis, Duration)>,
+    pub float_default_size: Cell<(i32, i32)>,
+    pub float_placement: Cell<FloatPlacement>,
+    pub float_cascade_idx: Cell<i32>,
+    pub auto_destroy_empty_workspaces: Cell<bool>,
+    pub persistent_workspaces: RefCell<AHashSet<String>>,
+    pub empty_workspace_action: RefCell<EmptyWorkspaceAction>,
+    pub window_state_cycle: RefCell<Vec<WindowState>>,
     pub default_gfx_api: Cell<GfxApi>,
     pub activation_tokens: CopyHashMap<ActivationToken, ()>,
     pub toplevel_lists:
@@ -153,9 +173,18 @@ pub struct State {
     pub dma_buf_ids: DmaBufIds,
     pub drm_feedback_ids: DrmFeedbackIds,
     pub direct_scanout_enabled: Cell<bool>,
+    pub persistent_clipboard: Cell<bool>,
+    pub start_time: Instant,
     pub output_transforms: RefCell<AHashMap<Rc<OutputId>, Transform>>,
     pub double_click_interval_usec: Cell<u64>,
     pub double_click_distance: Cell<i32>,
+    pub primary_output: Cell<Option<ConnectorId>>,
+    pub max_in_flight_frames: Cell<usize>,
+    pub linear_blending: Cell<bool>,
+    pub commit_throttling: Cell<bool>,
+    pub dummy_output_color: Cell<Option<Color>>,
+    pub hotplug_debounce_ms: Cell<u64>,
+    pub pending_connectors: Rc<PendingConnectors>,
 }
 
 // impl Drop for State {
@@ -179,6 +208,8 @@ pub struct XWaylandState {
     pub enabled: Cell<bool>,
     pub handler: RefCell<Option<SpawnedFuture<()>>>,
     pub queue: Rc<AsyncQueue<XWaylandEvent>>,
+    pub scale: Cell<Scale>,
+    pub lazy: Cell<bool>,
 }
 
 pub struct IdleState {
@@ -188,6 +219,8 @@ pub struct IdleState {
     pub timeout_changed: Cell<bool>,
     pub inhibitors: CopyHashMap<IdleInhibitorId, Rc<ZwpIdleInhibitorV1>>,
     pub inhibitors_changed: Cell<bool>,
+    pub inhibited_override: Cell<Option<bool>>,
+    pub clock_advanced: Cell<bool>,
 }
 
 impl IdleState {
@@ -197,6 +230,12 @@ impl IdleState {
         self.change.trigger();
     }
 
+    #[cfg_attr(not(feature = "it"), allow(dead_code))]
+    pub fn handle_clock_advanced(&self) {
+        self.clock_advanced.set(true);
+        self.change.trigger();
+    }
+
     pub fn add_inhibitor(&self, inhibitor: &Rc<ZwpIdleInhibitorV1>) {
         self.inhibitors.set(inhibitor.inhibit_id, inhibitor.clone());
         self.inhibitors_changed.set(true);
@@ -208,6 +247,18 @@ impl IdleState {
         self.inhibitors_changed.set(true);
         self.change.trigger();
     }
+
+    pub fn set_inhibited_override(&self, inhibited: Option<bool>) {
+        self.inhibited_override.set(inhibited);
+        self.inhibitors_changed.set(true);
+        self.change.trigger();
+    }
+
+    pub fn is_inhibited(&self) -> bool {
+        self.inhibited_override
+            .get()
+            .unwrap_or_else(|| self.inhibitors.len() > 0)
+    }
 }
 
 pub struct InputDeviceData {
@@ -220,18 +271,46 @@ pub struct InputDeviceData {
 pub struct DeviceHandlerData {
     pub seat: CloneCell<Option<Rc<WlSeatGlobal>>>,
     pub px_per_scroll_wheel: Cell<f64>,
+    pub button_map: CopyHashMap<u32, u32>,
+    pub pressed_buttons: CopyHashMap<u32, u32>,
     pub device: Rc<dyn InputDevice>,
+    pub output: Cell<Option<ConnectorId>>,
+}
+
+impl DeviceHandlerData {
+    pub fn map_button(&self, button: u32, state: KeyState) -> u32 {
+        match state {
+            KeyState::Pressed => {
+                let logical = self.button_map.get(&button).unwrap_or(button);
+                self.pressed_buttons.set(button, logical);
+                logical
+            }
+            // The map might have changed since the button was pressed.
+            KeyState::Released => match self.pressed_buttons.remove(&button) {
+                Some(logical) => logical,
+                _ => self.button_map.get(&button).unwrap_or(button),
+            },
+        }
+    }
 }
 
 pub struct ConnectorData {
     pub connector: Rc<dyn Connector>,
     pub handler: Cell<Option<SpawnedFuture<()>>>,
     pub connected: Cell<bool>,
+    pub enabled: Cell<bool>,
+    pub non_desktop_override: Cell<Option<bool>>,
     pub name: String,
     pub drm_dev: Option<Rc<DrmDevData>>,
     pub async_event: Rc<AsyncEvent>,
 }
 
+impl ConnectorData {
+    pub fn is_non_desktop(&self, info: &MonitorInfo) -> bool {
+        self.non_desktop_override.get().unwrap_or(info.non_desktop)
+    }
+}
+
 pub struct OutputData {
     pub connector: Rc<ConnectorData>,
     pub monitor_info: MonitorInfo,
@@ -244,6 +323,7 @@ pub struct DrmDevData {
     pub connectors: CopyHashMap<ConnectorId, Rc<ConnectorData>>,
     pub syspath: Option<String>,
     pub devnode: Option<String>,
+    pub render_node: Option<String>,
     pub vendor: Option<String>,
     pub model: Option<String>,
     pub pci_id: Option<PciId>,
@@ -296,6 +376,31 @@ impl State {
         )
     }
 
+    pub fn create_exact_gfx_context(
+        &self,
+        drm: &Drm,
+        api: GfxApi,
+    ) -> Result<Rc<dyn GfxContext>, GfxError> {
+        create_exact_gfx_context(&self.eng, &self.ring, drm, api)
+    }
+
+    /// Performs all pending container and float layouts.
+    ///
+    /// Once this function returns, the geometry of the tree is settled.
+    pub fn flush_layout(&self) {
+        loop {
+            let containers = flush_container_layout(self);
+            let floats = flush_float_layout(self);
+            if !containers && !floats {
+                break;
+            }
+        }
+    }
+
+    pub fn probe_gfx_apis(&self, drm: &Drm) -> Vec<GfxApi> {
+        probe_gfx_apis(&self.eng, &self.ring, drm)
+    }
+
     pub fn add_output_scale(&self, scale: Scale) {
         if self.scales.add(scale) {
             self.output_scales_changed();
@@ -353,27 +458,56 @@ impl State {
         }
     }
 
-    pub fn set_render_ctx(&self, ctx: Option<Rc<dyn GfxContext>>) {
-        self.render_ctx.set(ctx.clone());
-        self.render_ctx_version.fetch_add(1);
-        self.cursors.set(None);
+    pub fn update_drm_feedback(&self) {
         self.drm_feedback.set(None);
-
-        'handle_new_feedback: {
-            if let Some(ctx) = &ctx {
-                let feedback = match DrmFeedback::new(&self.drm_feedback_ids, &**ctx) {
-                    Ok(fb) => fb,
-                    Err(e) => {
-                        log::error!("Could not create new DRM feedback: {}", ErrorFmt(e));
-                        break 'handle_new_feedback;
-                    }
-                };
-                for watcher in self.drm_feedback_consumers.lock().values() {
-                    watcher.send_feedback(&feedback);
+        let Some(ctx) = self.render_ctx.get() else {
+            return;
+        };
+        let feedback =
+            match DrmFeedback::new(&self.drm_feedback_ids, &*ctx, &self.disabled_modifiers) {
+                Ok(fb) => fb,
+                Err(e) => {
+                    log::error!("Could not create new DRM feedback: {}", ErrorFmt(e));
+                    return;
                 }
-                self.drm_feedback.set(Some(Rc::new(feedback)));
+            };
+        self.drm_feedback.set(Some(Rc::new(feedback)));
+        for watcher in self.drm_feedback_consumers.lock().values() {
+            if let Some(feedback) = watcher.client.drm_feedback() {
+                watcher.send_feedback(&feedback);
             }
         }
+    }
+
+    /// Advertises the explicit-sync global only while the render device supports
+    /// timeline syncobjs.
+    fn update_syncobj_manager_global(&self) {
+        let supported = match self.render_ctx.get() {
+            Some(ctx) => ctx.gbm().drm.supports_syncobj_timeline(),
+            None => false,
+        };
+        let global = self.syncobj_manager_global.get();
+        if supported && global.is_none() {
+            let global = Rc::new(WpLinuxDrmSyncobjManagerV1Global::new(self.globals.name()));
+            self.add_global(&global);
+            self.syncobj_manager_global.set(Some(global));
+        } else if !supported {
+            if let Some(global) = global {
+                let _ = self.remove_global(&*global);
+                self.syncobj_manager_global.take();
+            }
+        }
+    }
+
+    pub fn set_render_ctx(&self, ctx: Option<Rc<dyn GfxContext>>) {
+        if let Some(ctx) = &ctx {
+            ctx.set_max_in_flight_frames(self.max_in_flight_frames.get());
+            ctx.set_linear_blending(self.linear_blending.get());
+        }
+        self.render_ctx.set(ctx.clone());
+        self.render_ctx_version.fetch_add(1);
+        self.cursors.set(None);
+        self.update_drm_feedback();
 
         {
             struct Walker;
@@ -425,12 +559,26 @@ impl State {
         for seat in seats.values() {
             seat.render_ctx_changed();
         }
+        drop(seats);
+
+        self.update_syncobj_manager_global();
 
         if ctx.is_some() && !self.render_ctx_ever_initialized.replace(true) {
             self.add_global(&Rc::new(WlDrmGlobal::new(self.globals.name())));
             self.add_global(&Rc::new(ZwpLinuxDmabufV1Global::new(self.globals.name())));
             if let Some(config) = self.config.get() {
                 config.graphics_initialized();
+                // Connectors are started after the render context has been set. Once they
+                // have registered themselves, wait until each of them has either created its
+                // output or turned out to be disconnected.
+                let eng = self.eng.clone();
+                let pending = self.pending_connectors.clone();
+                let future = self.eng.spawn(async move {
+                    eng.yield_now().await;
+                    pending.wait().await;
+                    config.outputs_ready();
+                });
+                self.outputs_ready.set(Some(future));
             }
         }
 
@@ -494,17 +642,31 @@ impl State {
         }
     }
 
-    fn do_map_tiled(self: &Rc<Self>, seat: Option<&Rc<WlSeatGlobal>>, node: Rc<dyn ToplevelNode>) {
-        let output = seat
-            .map(|s| s.get_output())
-            .or_else(|| self.root.outputs.lock().values().next().cloned())
+    pub fn map_tiled_on_named(self: &Rc<Self>, node: Rc<dyn ToplevelNode>, name: &str) {
+        let seat = self.seat_queue.last();
+        let ws = self.named_map_ws(name);
+        self.map_tiled_on(node.clone(), &ws);
+        if node.node_visible() {
+            if let Some(seat) = seat {
+                node.node_do_focus(&seat, Direction::Unspecified);
+            }
+        }
+    }
+
+    fn map_output(&self, seat: Option<&Rc<WlSeatGlobal>>) -> Rc<OutputNode> {
+        seat.map(|s| s.get_output())
+            .or_else(|| self.primary_output())
             .or_else(|| self.dummy_output.get())
-            .unwrap();
-        let ws = output.ensure_workspace();
+            .unwrap()
+    }
+
+    fn do_map_tiled(self: &Rc<Self>, seat: Option<&Rc<WlSeatGlobal>>, node: Rc<dyn ToplevelNode>) {
+        let ws = self.map_output(seat).ensure_workspace();
         self.map_tiled_on(node, &ws);
     }
 
     pub fn map_tiled_on(self: &Rc<Self>, node: Rc<dyn ToplevelNode>, ws: &Rc<WorkspaceNode>) {
+        self.warn_if_mapped_on_dummy(ws);
         if let Some(c) = ws.container.get() {
             let la = c.clone().tl_last_active_child();
             let lap = la
@@ -546,28 +708,98 @@ impl State {
             x1 -= self.theme.sizes.border_width.get();
             Rect::new_sized(x1, y1, width, height).unwrap()
         } else {
-            let mut x1 = output_rect.x1();
-            let mut y1 = output_rect.y1();
-            if width < output_rect.width() {
-                x1 += (output_rect.width() - width) / 2;
-            } else {
-                width = output_rect.width();
-            }
-            if height < output_rect.height() {
-                y1 += (output_rect.height() - height) / 2;
-            } else {
-                height = output_rect.height();
-            }
+            width = width.min(output_rect.width());
+            height = height.min(output_rect.height());
+            let max_x1 = output_rect.x2() - width;
+            let max_y1 = output_rect.y2() - height;
+            let center = (
+                output_rect.x1() + (output_rect.width() - width) / 2,
+                output_rect.y1() + (output_rect.height() - height) / 2,
+            );
+            let (x1, y1) = match self.float_placement.get() {
+                FloatPlacement::Center => center,
+                FloatPlacement::Cursor => match self.seat_queue.last() {
+                    Some(seat) => {
+                        let (x, y) = seat.position();
+                        (
+                            (x.round_down() - width / 2).clamp(output_rect.x1(), max_x1),
+                            (y.round_down() - height / 2).clamp(output_rect.y1(), max_y1),
+                        )
+                    }
+                    _ => center,
+                },
+                FloatPlacement::Cascade => {
+                    let step = self.theme.sizes.title_height.get()
+                        + 1
+                        + self.theme.sizes.border_width.get();
+                    let mut idx = self.float_cascade_idx.get();
+                    let mut offset = idx * step;
+                    if output_rect.x1() + offset > max_x1 || output_rect.y1() + offset > max_y1 {
+                        idx = 0;
+                        offset = 0;
+                    }
+                    self.float_cascade_idx.set(idx + 1);
+                    (output_rect.x1() + offset, output_rect.y1() + offset)
+                }
+            };
             Rect::new_sized(x1, y1, width, height).unwrap()
         };
+        self.warn_if_mapped_on_dummy(workspace);
         FloatNode::new(self, workspace, position, node);
     }
 
+    pub fn move_toplevel_to_workspace(
+        self: &Rc<Self>,
+        tl: Rc<dyn ToplevelNode>,
+        ws: &Rc<WorkspaceNode>,
+    ) {
+        if tl.tl_data().is_fullscreen.get() {
+            return;
+        }
+        let old_ws = match tl.tl_data().workspace.get() {
+            Some(ws) => ws,
+            _ => return,
+        };
+        if old_ws.id == ws.id {
+            return;
+        }
+        let cn = match tl.tl_data().parent.get() {
+            Some(cn) => cn,
+            _ => return,
+        };
+        let kb_foci = collect_kb_foci(tl.clone().tl_into_node());
+        cn.cnode_remove_child2(tl.tl_as_node(), true);
+        if !ws.visible.get() {
+            for focus in kb_foci {
+                old_ws.clone().node_do_focus(&focus, Direction::Unspecified);
+            }
+        }
+        if tl.tl_data().is_floating.get() {
+            self.map_floating(
+                tl.clone(),
+                tl.tl_data().float_width.get(),
+                tl.tl_data().float_height.get(),
+                ws,
+                None,
+            );
+        } else {
+            self.map_tiled_on(tl, ws);
+        }
+    }
+
+    fn warn_if_mapped_on_dummy(&self, ws: &WorkspaceNode) {
+        if ws.output.get().is_dummy {
+            log::warn!(
+                "Mapping a window on the dummy output. It will not be visible until an output is connected or enabled."
+            );
+        }
+    }
+
     pub fn show_workspace(&self, seat: &Rc<WlSeatGlobal>, name: &str) {
         let (output, ws) = match self.workspaces.get(name) {
             Some(ws) => {
                 let output = ws.output.get();
-                let did_change = output.show_workspace(&ws);
+                let did_change = output.show_workspace_animated(&ws);
                 ws.clone().node_do_focus(seat, Direction::Unspecified);
                 if !did_change {
                     return;
@@ -581,7 +813,7 @@ impl State {
                     return;
                 }
                 let ws = output.create_workspace(name);
-                output.show_workspace(&ws);
+                output.show_workspace_animated(&ws);
                 (output, ws)
             }
         };
@@ -594,6 +826,18 @@ impl State {
         // }
     }
 
+    /// Returns the workspace with the given name.
+    ///
+    /// If no such workspace exists, it is created on the output of the last used seat.
+    pub fn named_map_ws(&self, name: &str) -> Rc<WorkspaceNode> {
+        match self.workspaces.get(name) {
+            Some(ws) => ws,
+            _ => self
+                .map_output(self.seat_queue.last().as_deref())
+                .create_workspace(name),
+        }
+    }
+
     pub fn float_map_ws(&self) -> Rc<WorkspaceNode> {
         if let Some(seat) = self.seat_queue.last() {
             let output = seat.get_output();
@@ -601,12 +845,36 @@ impl State {
                 return output.ensure_workspace();
             }
         }
-        if let Some(output) = self.root.outputs.lock().values().next().cloned() {
+        if let Some(output) = self.primary_output() {
             return output.ensure_workspace();
         }
         self.dummy_output.get().unwrap().ensure_workspace()
     }
 
+    pub fn output_at(&self, x: i32, y: i32) -> Option<Rc<OutputNode>> {
+        self.root
+            .outputs
+            .lock()
+            .iter()
+            .filter(|(_, output)| output.global.pos.get().contains(x, y))
+            .min_by_key(|(id, _)| id.raw())
+            .map(|(_, output)| output.clone())
+    }
+
+    pub fn primary_output(&self) -> Option<Rc<OutputNode>> {
+        if let Some(id) = self.primary_output.get() {
+            if let Some(output) = self.root.outputs.get(&id) {
+                return Some(output);
+            }
+        }
+        self.root
+            .outputs
+            .lock()
+            .iter()
+            .min_by_key(|(id, _)| id.raw())
+            .map(|(_, output)| output.clone())
+    }
+
     pub fn set_status(&self, status: &str) {
         let status = Rc::new(status.to_owned());
         self.status.set(status.clone());
@@ -664,8 +932,24 @@ impl State {
         }
     }
 
+    /// Damages only the outputs that intersect `rect`.
+    ///
+    /// `rect` is given in absolute (global) coordinates.
+    pub fn damage_rect(&self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        for output in self.root.outputs.lock().values() {
+            let connector = &output.global.connector;
+            if connector.connected.get() && output.global.pos.get().intersects(&rect) {
+                connector.connector.damage();
+            }
+        }
+    }
+
     pub fn clear(&self) {
         self.lock.lock.take();
+        self.outputs_ready.take();
         self.xwayland.handler.borrow_mut().take();
         self.clients.clear();
         if let Some(config) = self.config.set(None) {
@@ -697,6 +981,7 @@ impl State {
         self.pending_output_render_data.clear();
         self.pending_float_layout.clear();
         self.pending_float_titles.clear();
+        self.pending_empty_workspaces.clear();
         self.render_ctx_watchers.clear();
         self.workspace_watchers.clear();
         self.toplevel_lists.clear();
@@ -706,6 +991,8 @@ impl State {
         }
         self.backend_events.clear();
         self.workspaces.clear();
+        self.spawn_workspaces.clear();
+        self.spawn_render_devices.clear();
         {
             let seats = mem::take(self.globals.seats.lock().deref_mut());
             for seat in seats.values() {
@@ -758,7 +1045,7 @@ impl State {
 
     pub fn present_output(
         &self,
-        output: &OutputNode,
+        output: &Rc<OutputNode>,
         fb: &Rc<dyn GfxFramebuffer>,
         tex: &Rc<dyn GfxTexture>,
         rr: &mut RenderResult,
@@ -773,7 +1060,7 @@ impl State {
             render_hw_cursor,
         );
         output.perform_screencopies(tex, !render_hw_cursor, 0, 0, None);
-        rr.dispatch_frame_requests();
+        output.dispatch_frame_requests(rr);
     }
 
     pub fn perform_screencopy(
@@ -797,6 +1084,9 @@ impl State {
                 let (width, height) = target.logical_size(Transform::None);
                 Rect::new_sized(0, 0, width, height).unwrap()
             },
+            occluders: vec![],
+            first_occluder: 0,
+            throttle_occluded: false,
         };
         let mut sample_rect = SampleRect::identity();
         sample_rect.buffer_transform = transform;
@@ -808,6 +1098,7 @@ impl State {
             size,
             Scale::from_int(1),
             None,
+            None,
         );
         if render_hardware_cursors {
             for seat in self.globals.lock_seats().values() {
@@ -13,7 +13,7 @@ use {
     },
     std::rc::Rc,
 };
-pub use {hardware_cursor::handle_hardware_cursor_tick, idle::idle};
+pub use {connector::PendingConnectors, hardware_cursor::handle_hardware_cursor_tick, idle::idle};
 
 pub async fn handle_backend_events(state: Rc<State>) {
     let mut beh = BackendEventHandler { state };
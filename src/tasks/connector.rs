@@ -4,10 +4,15 @@ use {
         ifs::wl_output::WlOutputGlobal,
         state::{ConnectorData, OutputData, State},
         tree::{OutputNode, OutputRenderData},
-        utils::{asyncevent::AsyncEvent, clonecell::CloneCell},
+        utils::{
+            asyncevent::AsyncEvent, clonecell::CloneCell, errorfmt::ErrorFmt, numcell::NumCell,
+        },
+        wheel::WheelTimeoutFuture,
     },
+    futures_util::{future::Fuse, select, FutureExt},
     std::{
         cell::{Cell, RefCell},
+        collections::VecDeque,
         rc::Rc,
     },
 };
@@ -25,6 +30,8 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         connector: connector.clone(),
         handler: Default::default(),
         connected: Cell::new(false),
+        enabled: Cell::new(true),
+        non_desktop_override: Default::default(),
         name: connector.kernel_id().to_string(),
         drm_dev: drm_dev.clone(),
         async_event: Rc::new(AsyncEvent::default()),
@@ -36,6 +43,8 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
         id,
         state: state.clone(),
         data: data.clone(),
+        deferred: Default::default(),
+        pending: Cell::new(Some(state.pending_connectors.add())),
     };
     let future = state.eng.spawn(oh.handle());
     data.handler.set(Some(future));
@@ -44,10 +53,43 @@ pub fn handle(state: &Rc<State>, connector: &Rc<dyn Connector>) {
     }
 }
 
+/// Tracks the connectors whose initial state has not been determined yet.
+#[derive(Default)]
+pub struct PendingConnectors {
+    count: NumCell<usize>,
+    changed: AsyncEvent,
+}
+
+impl PendingConnectors {
+    fn add(self: &Rc<Self>) -> PendingConnector {
+        self.count.fetch_add(1);
+        PendingConnector(self.clone())
+    }
+
+    /// Waits until every connector has either created its output or turned out to be
+    /// disconnected, including the hotplug debounce interval.
+    pub async fn wait(&self) {
+        while self.count.get() > 0 {
+            self.changed.triggered().await;
+        }
+    }
+}
+
+struct PendingConnector(Rc<PendingConnectors>);
+
+impl Drop for PendingConnector {
+    fn drop(&mut self) {
+        self.0.count.fetch_sub(1);
+        self.0.changed.trigger();
+    }
+}
+
 struct ConnectorHandler {
     id: ConnectorId,
     state: Rc<State>,
     data: Rc<ConnectorData>,
+    deferred: RefCell<VecDeque<ConnectorEvent>>,
+    pending: Cell<Option<PendingConnector>>,
 }
 
 impl ConnectorHandler {
@@ -60,15 +102,17 @@ impl ConnectorHandler {
             config.new_connector(self.id);
         }
         'outer: loop {
-            while let Some(event) = self.data.connector.event() {
+            while let Some(event) = self.next_event() {
                 match event {
                     ConnectorEvent::Removed => break 'outer,
                     ConnectorEvent::Connected(mi) => self.handle_connected(mi).await,
                     _ => unreachable!(),
                 }
             }
+            self.pending.take();
             self.data.async_event.triggered().await;
         }
+        self.pending.take();
         if let Some(config) = self.state.config.get() {
             config.del_connector(self.id);
         }
@@ -76,9 +120,147 @@ impl ConnectorHandler {
         self.state.connectors.remove(&self.id);
     }
 
-    async fn handle_connected(&self, info: MonitorInfo) {
+    fn next_event(&self) -> Option<ConnectorEvent> {
+        let event = self.deferred.borrow_mut().pop_front();
+        event.or_else(|| self.data.connector.event())
+    }
+
+    fn debounce_timeout(&self) -> Option<Fuse<WheelTimeoutFuture>> {
+        match self.state.hotplug_debounce_ms.get() {
+            0 => None,
+            ms => Some(self.state.wheel.timeout(ms).fuse()),
+        }
+    }
+
+    /// Returns the next event or `None` once the timeout has expired.
+    async fn event_before(&self, timeout: &mut Fuse<WheelTimeoutFuture>) -> Option<ConnectorEvent> {
+        loop {
+            if let Some(event) = self.next_event() {
+                return Some(event);
+            }
+            select! {
+                res = &mut *timeout => {
+                    if let Err(e) = res {
+                        log::error!(
+                            "Could not wait for the hotplug debounce interval: {}",
+                            ErrorFmt(e)
+                        );
+                    }
+                    return None;
+                },
+                _ = self.data.async_event.triggered().fuse() => {},
+            }
+        }
+    }
+
+    /// Holds back a connection until it has been stable for the debounce interval.
+    ///
+    /// Returns `false` if the connector was disconnected or removed in the meantime.
+    /// Other events are replayed once the connection has been accepted.
+    async fn debounce_connect(&self, info: &mut MonitorInfo) -> bool {
+        let Some(mut timeout) = self.debounce_timeout() else {
+            return true;
+        };
+        let mut held = VecDeque::new();
+        while let Some(event) = self.event_before(&mut timeout).await {
+            match event {
+                ConnectorEvent::Disconnected => return false,
+                ConnectorEvent::Removed => {
+                    self.deferred.borrow_mut().push_back(event);
+                    return false;
+                }
+                ConnectorEvent::ModeChanged(mode) => info.initial_mode = mode,
+                event => held.push_back(event),
+            }
+        }
+        self.deferred.borrow_mut().extend(held);
+        true
+    }
+
+    /// Waits for the monitor to come back after a disconnect.
+    ///
+    /// Returns the new monitor info if the same monitor was reconnected within the
+    /// debounce interval.
+    async fn debounce_disconnect(&self, info: &MonitorInfo) -> Option<MonitorInfo> {
+        if !self.data.enabled.get() {
+            // The connector was disabled by the config.
+            return None;
+        }
+        let mut timeout = self.debounce_timeout()?;
+        while let Some(event) = self.event_before(&mut timeout).await {
+            match event {
+                ConnectorEvent::Connected(mi) => {
+                    if is_same_monitor(info, &mi) {
+                        return Some(mi);
+                    }
+                    self.deferred
+                        .borrow_mut()
+                        .push_back(ConnectorEvent::Connected(mi));
+                    return None;
+                }
+                ConnectorEvent::Removed => {
+                    self.deferred.borrow_mut().push_back(event);
+                    return None;
+                }
+                ConnectorEvent::HotplugPending => {
+                    if let Some(config) = self.state.config.get() {
+                        config.connector_hotplug(self.id);
+                    }
+                }
+                _ => {}
+            }
+        }
+        None
+    }
+
+    async fn handle_connected(&self, mut info: MonitorInfo) {
+        if !self.debounce_connect(&mut info).await {
+            log::info!(
+                "Ignoring unstable connection of connector {}",
+                self.data.connector.kernel_id()
+            );
+            self.pending.take();
+            return;
+        }
         log::info!("Connector {} connected", self.data.connector.kernel_id());
         self.data.connected.set(true);
+        loop {
+            let disconnected = match self.data.is_non_desktop(&info) {
+                true => self.handle_non_desktop(&info).await,
+                false => self.handle_desktop(&info).await,
+            };
+            if disconnected {
+                break;
+            }
+        }
+        log::info!("Connector {} disconnected", self.data.connector.kernel_id());
+        self.data.connected.set(false);
+    }
+
+    async fn handle_non_desktop(&self, info: &MonitorInfo) -> bool {
+        log::info!(
+            "Connector {} is a non-desktop output",
+            self.data.connector.kernel_id()
+        );
+        self.pending.take();
+        loop {
+            while let Some(event) = self.next_event() {
+                match event {
+                    ConnectorEvent::Disconnected => return true,
+                    ConnectorEvent::HardwareCursor(_)
+                    | ConnectorEvent::ModeChanged(_)
+                    | ConnectorEvent::HotplugPending => {}
+                    ev => unreachable!("received unexpected event {:?}", ev),
+                }
+            }
+            if !self.data.is_non_desktop(info) {
+                return false;
+            }
+            self.data.async_event.triggered().await;
+        }
+    }
+
+    async fn handle_desktop(&self, info: &MonitorInfo) -> bool {
         let name = self.state.globals.name();
         let x1 = self
             .state
@@ -128,12 +310,19 @@ impl ConnectorHandler {
             screencasts: Default::default(),
             update_render_data_scheduled: Cell::new(false),
             hardware_cursor_needs_render: Cell::new(false),
+            throttle_frame_callbacks: Cell::new(false),
+            frame_callbacks_dispatched: Cell::new(0),
+            throttled_frame_requests: Default::default(),
+            throttled_frame_flush: Default::default(),
+            workspace_transition: Default::default(),
+            sticky: Default::default(),
+            previous_workspace: Default::default(),
         });
         self.state.add_output_scale(on.global.preferred_scale.get());
         let mode = info.initial_mode;
         let output_data = Rc::new(OutputData {
             connector: self.data.clone(),
-            monitor_info: info,
+            monitor_info: info.clone(),
             node: on.clone(),
         });
         self.state.outputs.set(self.id, output_data);
@@ -195,10 +384,20 @@ impl ConnectorHandler {
         self.state.root.outputs.set(self.id, on.clone());
         self.state.root.update_extents();
         self.state.add_global(&global);
-        'outer: loop {
-            while let Some(event) = self.data.connector.event() {
+        self.pending.take();
+        let disconnected = 'outer: loop {
+            while let Some(event) = self.next_event() {
                 match event {
-                    ConnectorEvent::Disconnected => break 'outer,
+                    ConnectorEvent::Disconnected => match self.debounce_disconnect(info).await {
+                        Some(mi) => {
+                            log::info!(
+                                "Connector {} was reconnected within the debounce interval",
+                                self.data.connector.kernel_id()
+                            );
+                            on.update_mode(mi.initial_mode);
+                        }
+                        None => break 'outer true,
+                    },
                     ConnectorEvent::HardwareCursor(hc) => {
                         on.hardware_cursor.set(hc);
                         self.state.refresh_hardware_cursors();
@@ -206,12 +405,19 @@ impl ConnectorHandler {
                     ConnectorEvent::ModeChanged(mode) => {
                         on.update_mode(mode);
                     }
+                    ConnectorEvent::HotplugPending => {
+                        if let Some(config) = self.state.config.get() {
+                            config.connector_hotplug(self.id);
+                        }
+                    }
                     ev => unreachable!("received unexpected event {:?}", ev),
                 }
             }
+            if self.data.is_non_desktop(info) {
+                break false;
+            }
             self.data.async_event.triggered().await;
-        }
-        log::info!("Connector {} disconnected", self.data.connector.kernel_id());
+        };
         if let Some(config) = self.state.config.get() {
             config.connector_disconnected(self.id);
         }
@@ -226,7 +432,6 @@ impl ConnectorHandler {
         }
         global.destroyed.set(true);
         self.state.root.outputs.remove(&self.id);
-        self.data.connected.set(false);
         self.state.outputs.remove(&self.id);
         on.lock_surface.take();
         {
@@ -240,8 +445,8 @@ impl ConnectorHandler {
             }
         }
         let mut target_is_dummy = false;
-        let target = match self.state.outputs.lock().values().next() {
-            Some(o) => o.node.clone(),
+        let target = match self.state.primary_output() {
+            Some(o) => o,
             _ => {
                 target_is_dummy = true;
                 self.state.dummy_output.get().unwrap()
@@ -272,11 +477,24 @@ impl ConnectorHandler {
                 seat.set_position((tpos.x1() + tpos.x2()) / 2, (tpos.y1() + tpos.y2()) / 2);
             }
         }
-        if let Some(dev) = &self.data.drm_dev {
-            dev.connectors.remove(&self.id);
+        if disconnected {
+            if let Some(dev) = &self.data.drm_dev {
+                dev.connectors.remove(&self.id);
+            }
         }
         self.state
             .remove_output_scale(on.global.preferred_scale.get());
         let _ = self.state.remove_global(&*global);
+        disconnected
     }
 }
+
+fn is_same_monitor(a: &MonitorInfo, b: &MonitorInfo) -> bool {
+    a.manufacturer == b.manufacturer
+        && a.product == b.product
+        && a.serial_number == b.serial_number
+        && a.width_mm == b.width_mm
+        && a.height_mm == b.height_mm
+        && a.non_desktop == b.non_desktop
+        && a.modes == b.modes
+}
@@ -16,6 +16,13 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn BackendDrmDevice>) {
     let mut vendor = None;
     let mut model = None;
     let mut pci_id = None;
+    let render_node = match dev.render_node() {
+        Ok(node) => node.map(|s| s.to_string_lossy().into_owned()),
+        Err(e) => {
+            log::error!("Could not determine the render node: {}", ErrorFmt(e));
+            None
+        }
+    };
     'properties: {
         let udev = match Udev::new() {
             Ok(udev) => Rc::new(udev),
@@ -71,6 +78,7 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn BackendDrmDevice>) {
         connectors: Default::default(),
         syspath,
         devnode,
+        render_node,
         vendor,
         model,
         pci_id,
@@ -2,6 +2,7 @@ use {
     crate::{
         backend::Backend,
         state::State,
+        time::Time,
         utils::{
             errorfmt::ErrorFmt,
             timer::{TimerError, TimerFd},
@@ -22,6 +23,7 @@ pub async fn idle(state: Rc<State>, backend: Rc<dyn Backend>) {
     };
     state.idle.change.trigger();
     state.idle.timeout_changed.set(true);
+    let last_input = now(&state);
     let mut idle = Idle {
         state,
         backend,
@@ -29,7 +31,7 @@ pub async fn idle(state: Rc<State>, backend: Rc<dyn Backend>) {
         idle: false,
         dead: false,
         is_inhibited: false,
-        last_input: now(),
+        last_input,
     };
     idle.run().await;
 }
@@ -41,7 +43,7 @@ struct Idle {
     idle: bool,
     dead: bool,
     is_inhibited: bool,
-    last_input: c::timespec,
+    last_input: Time,
 }
 
 impl Idle {
@@ -62,7 +64,7 @@ impl Idle {
             return;
         }
         let timeout = self.state.idle.timeout.get();
-        let since = duration_since(self.last_input);
+        let since = duration_since(now(&self.state), self.last_input);
         if since >= timeout {
             if !timeout.is_zero() && !self.is_inhibited {
                 if let Some(config) = self.state.config.get() {
@@ -78,7 +80,7 @@ impl Idle {
 
     fn handle_idle_changes(&mut self) {
         if self.state.idle.inhibitors_changed.replace(false) {
-            let is_inhibited = self.state.idle.inhibitors.len() > 0;
+            let is_inhibited = self.state.idle.is_inhibited();
             if self.is_inhibited != is_inhibited {
                 self.is_inhibited = is_inhibited;
                 if !self.is_inhibited {
@@ -89,8 +91,11 @@ impl Idle {
         if self.state.idle.timeout_changed.replace(false) {
             self.program_timer();
         }
+        if self.state.idle.clock_advanced.replace(false) && !self.idle {
+            self.handle_expired(Ok(0));
+        }
         if self.state.idle.input.replace(false) {
-            self.last_input = now();
+            self.last_input = now(&self.state);
             if self.idle {
                 self.backend.set_idle(false);
                 self.idle = false;
@@ -111,14 +116,15 @@ impl Idle {
     }
 }
 
-fn now() -> c::timespec {
-    let mut now = uapi::pod_zeroed();
-    let _ = uapi::clock_gettime(c::CLOCK_MONOTONIC, &mut now);
-    now
+fn now(state: &State) -> Time {
+    state
+        .clock
+        .now()
+        .unwrap_or_else(|_| Time(uapi::pod_zeroed()))
 }
 
-fn duration_since(start: c::timespec) -> Duration {
-    let now = now();
+fn duration_since(now: Time, start: Time) -> Duration {
+    let (now, start) = (now.0, start.0);
     let mut nanos = (now.tv_sec as i64 - start.tv_sec as i64) * 1_000_000_000
         + (now.tv_nsec as i64 - start.tv_nsec as i64);
     if nanos < 0 {
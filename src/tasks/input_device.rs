@@ -12,7 +12,10 @@ pub fn handle(state: &Rc<State>, dev: Rc<dyn InputDevice>) {
     let data = Rc::new(DeviceHandlerData {
         seat: Default::default(),
         px_per_scroll_wheel: Cell::new(PX_PER_SCROLL),
+        button_map: Default::default(),
+        pressed_buttons: Default::default(),
         device: dev.clone(),
+        output: Default::default(),
     });
     let ae = Rc::new(AsyncEvent::default());
     let oh = DeviceHandler {
@@ -77,5 +80,8 @@ impl DeviceHandler {
             .input_device_handlers
             .borrow_mut()
             .remove(&self.dev.id());
+        if let Some(seat) = self.data.seat.get() {
+            seat.update_capabilities();
+        }
     }
 }
@@ -96,20 +96,23 @@ impl Color {
         [self.r, self.g, self.b, self.a]
     }
 
-    #[allow(dead_code)]
     pub fn to_array_linear(self) -> [f32; 4] {
         fn to_linear(srgb: f32) -> f32 {
             if srgb <= 0.04045 {
                 srgb / 12.92
             } else {
-                (srgb + 0.055 / 1.055).powf(2.4)
+                ((srgb + 0.055) / 1.055).powf(2.4)
             }
         }
+        if self.a == 0.0 {
+            return [0.0; 4];
+        }
+        let a = self.a;
         [
-            to_linear(self.r),
-            to_linear(self.g),
-            to_linear(self.b),
-            self.a,
+            to_linear(self.r / a) * a,
+            to_linear(self.g / a) * a,
+            to_linear(self.b / a) * a,
+            a,
         ]
     }
 }
@@ -250,6 +253,11 @@ pub struct Theme {
     pub colors: ThemeColors,
     pub sizes: ThemeSizes,
     pub font: RefCell<String>,
+    pub active_font: RefCell<Option<String>>,
+    /// If set, title backgrounds fade horizontally into this color.
+    pub title_gradient: Cell<Option<Color>>,
+    /// Whether borders are anti-aliased at fractional scales.
+    pub border_antialiasing: Cell<bool>,
 }
 
 impl Default for Theme {
@@ -258,6 +266,9 @@ impl Default for Theme {
             colors: Default::default(),
             sizes: Default::default(),
             font: RefCell::new(DEFAULT_FONT.to_string()),
+            active_font: Default::default(),
+            title_gradient: Default::default(),
+            border_antialiasing: Cell::new(false),
         }
     }
 }
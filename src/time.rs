@@ -1,8 +1,10 @@
 use {
     std::{
+        any::Any,
         cmp::Ordering,
         fmt::{Debug, Formatter},
         ops::{Add, Sub},
+        rc::Rc,
         time::Duration,
     },
     thiserror::Error,
@@ -119,6 +121,63 @@ impl Add<Duration> for Time {
     }
 }
 
+pub trait Clock {
+    fn now(&self) -> Result<Time, TimeError>;
+
+    /// Returns whether the clock only advances when explicitly told to.
+    fn is_virtual(&self) -> bool {
+        false
+    }
+
+    #[cfg_attr(not(feature = "it"), allow(dead_code))]
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any>;
+}
+
+pub struct MonotonicClock;
+
+impl Clock for MonotonicClock {
+    fn now(&self) -> Result<Time, TimeError> {
+        Time::now()
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+}
+
+#[cfg(feature = "it")]
+pub struct VirtualClock {
+    now: std::cell::Cell<Time>,
+}
+
+#[cfg(feature = "it")]
+impl VirtualClock {
+    pub fn new() -> Self {
+        Self {
+            now: std::cell::Cell::new(Time::now_unchecked()),
+        }
+    }
+
+    pub fn advance(&self, duration: Duration) {
+        self.now.set(self.now.get() + duration);
+    }
+}
+
+#[cfg(feature = "it")]
+impl Clock for VirtualClock {
+    fn now(&self) -> Result<Time, TimeError> {
+        Ok(self.now.get())
+    }
+
+    fn is_virtual(&self) -> bool {
+        true
+    }
+
+    fn into_any(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+}
+
 pub fn now_usec() -> u64 {
     Time::now_unchecked().usec()
 }
@@ -12,6 +12,7 @@ use {
         scale::Scale,
         state::State,
         text::{self, TextTexture},
+        theme::Color,
         tree::{
             walker::NodeVisitor, ContainingNode, Direction, FindTreeResult, FoundNode, Node,
             NodeId, ToplevelData, ToplevelNode, ToplevelNodeBase, WorkspaceNode,
@@ -88,6 +89,7 @@ pub struct ContainerRenderData {
     pub attention_title_rects: Vec<Rect>,
     pub last_active_rect: Option<Rect>,
     pub border_rects: Vec<Rect>,
+    pub custom_border_rects: Vec<(Rect, Color)>,
     pub underline_rects: Vec<Rect>,
     pub titles: SmallMapMut<Scale, Vec<ContainerTitle>, 2>,
 }
@@ -153,7 +155,7 @@ struct SeatState {
 impl ContainerChild {
     fn position_content(&self) {
         let mut content = self.content.get();
-        let body = self.body.get();
+        let body = self.extents(&self.body.get());
         let width = content.width();
         let height = content.height();
         // let x1 = body.x1() + (body.width() - width) / 2;
@@ -165,6 +167,13 @@ impl ContainerChild {
         // log::debug!("content: {:?}", content);
         self.content.set(content);
     }
+
+    fn extents(&self, body: &Rect) -> Rect {
+        match self.node.tl_size_increments() {
+            Some(inc) => inc.apply(body),
+            _ => *body,
+        }
+    }
 }
 
 impl ContainerNode {
@@ -367,7 +376,7 @@ impl ContainerNode {
     }
 
     fn perform_mono_layout(self: &Rc<Self>, child: &ContainerChild) {
-        let mb = self.mono_body.get();
+        let mb = child.extents(&self.mono_body.get());
         child
             .node
             .clone()
@@ -476,7 +485,9 @@ impl ContainerNode {
                 )
                 .unwrap(),
             );
-            let body = body.move_(self.abs_x1.get(), self.abs_y1.get());
+            let body = child
+                .extents(&body)
+                .move_(self.abs_x1.get(), self.abs_y1.get());
             child.node.clone().tl_change_extents(&body);
             child.position_content();
         }
@@ -641,6 +652,7 @@ impl ContainerNode {
         let th = theme.sizes.title_height.get();
         let bw = theme.sizes.border_width.get();
         let font = theme.font.borrow_mut();
+        let active_font = theme.active_font.borrow_mut();
         let cwidth = self.width.get();
         let cheight = self.height.get();
         let ctx = self.state.render_ctx.get();
@@ -651,6 +663,7 @@ impl ContainerNode {
         rd.active_title_rects.clear();
         rd.attention_title_rects.clear();
         rd.border_rects.clear();
+        rd.custom_border_rects.clear();
         rd.underline_rects.clear();
         rd.last_active_rect.take();
         let last_active = self.focus_history.last().map(|v| v.node.node_id());
@@ -658,8 +671,10 @@ impl ContainerNode {
         let split = self.split.get();
         let have_active = self.children.iter().any(|c| c.active.get());
         let scales = self.state.scales.lock();
+        let mut prev_border_color = None;
         for (i, child) in self.children.iter().enumerate() {
             let rect = child.title_rect.get();
+            let border_color = child.node.tl_data().border_color.get();
             if i > 0 {
                 let rect = if mono {
                     Rect::new_sized(rect.x1() - bw, 0, bw, th)
@@ -668,8 +683,12 @@ impl ContainerNode {
                 } else {
                     Rect::new_sized(0, rect.y1() - bw, cwidth, bw)
                 };
-                rd.border_rects.push(rect.unwrap());
+                match border_color.or(prev_border_color) {
+                    Some(color) => rd.custom_border_rects.push((rect.unwrap(), color)),
+                    _ => rd.border_rects.push(rect.unwrap()),
+                }
             }
+            prev_border_color = border_color;
             let color = if child.active.get() {
                 rd.active_title_rects.push(rect);
                 theme.colors.focused_title_text.get()
@@ -687,6 +706,10 @@ impl ContainerNode {
                 let rect = Rect::new_sized(rect.x1(), rect.y2(), rect.width(), 1).unwrap();
                 rd.underline_rects.push(rect);
             }
+            let font = match (child.active.get(), &*active_font) {
+                (true, Some(active_font)) => active_font,
+                _ => &*font,
+            };
             let title = child.title.borrow_mut();
             for (scale, _) in scales.iter() {
                 let old_tex = child.title_tex.remove(scale);
@@ -710,7 +733,7 @@ impl ContainerNode {
                             old_tex,
                             width,
                             th,
-                            &font,
+                            font,
                             title.deref(),
                             color,
                             scalef,
@@ -994,7 +1017,7 @@ impl ContainerNode {
         node.position_content();
         if let Some(mono) = self.mono_child.get() {
             if mono.node.node_id() == node.node.node_id() {
-                let body = self.mono_body.get();
+                let body = mono.extents(&self.mono_body.get());
                 self.mono_content.set(rect.at_point(body.x1(), body.y1()));
             }
         }
@@ -1056,6 +1079,20 @@ pub async fn container_layout(state: Rc<State>) {
     }
 }
 
+/// Performs all pending container layouts immediately.
+///
+/// Returns whether any container was processed.
+pub fn flush_container_layout(state: &State) -> bool {
+    let mut any = false;
+    while let Some(container) = state.pending_container_layout.try_pop() {
+        any = true;
+        if container.layout_scheduled.get() {
+            container.perform_layout();
+        }
+    }
+    any
+}
+
 pub async fn container_render_data(state: Rc<State>) {
     loop {
         let container = state.pending_container_render_data.pop().await;
@@ -1379,6 +1416,10 @@ impl ContainingNode for ContainerNode {
         self.pull_child_properties(&link_ref);
         new.tl_set_visible(visible);
         if let Some(body) = body {
+            let body = match new.tl_size_increments() {
+                Some(inc) => inc.apply(&body),
+                _ => body,
+            };
             let body = body.move_(self.abs_x1.get(), self.abs_y1.get());
             new.clone().tl_change_extents(&body);
         }
@@ -1496,14 +1537,15 @@ impl ToplevelNodeBase for ContainerNode {
             }
         } else {
             if let Some(c) = self.mono_child.get() {
-                let body = self
-                    .mono_body
-                    .get()
+                let body = c
+                    .extents(&self.mono_body.get())
                     .move_(self.abs_x1.get(), self.abs_y1.get());
                 c.node.clone().tl_change_extents(&body);
             } else {
                 for child in self.children.iter() {
-                    let body = child.body.get().move_(self.abs_x1.get(), self.abs_y1.get());
+                    let body = child
+                        .extents(&child.body.get())
+                        .move_(self.abs_x1.get(), self.abs_y1.get());
                     child.node.clone().tl_change_extents(&body);
                 }
             }
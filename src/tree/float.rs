@@ -36,6 +36,7 @@ pub struct FloatNode {
     pub position: Cell<Rect>,
     pub display_link: RefCell<Option<LinkedNode<Rc<dyn StackedNode>>>>,
     pub workspace_link: Cell<Option<LinkedNode<Rc<dyn StackedNode>>>>,
+    pub sticky_link: RefCell<Option<LinkedNode<Rc<FloatNode>>>>,
     pub workspace: CloneCell<Rc<WorkspaceNode>>,
     pub child: CloneCell<Option<Rc<dyn ToplevelNode>>>,
     pub active: Cell<bool>,
@@ -60,6 +61,13 @@ struct SeatState {
     double_click_state: DoubleClickState,
 }
 
+/// A move or resize operation started by dragging a window with the float modifier.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum FloatDrag {
+    Move,
+    Resize { left: bool, top: bool },
+}
+
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum OpType {
     Move,
@@ -82,6 +90,20 @@ pub async fn float_layout(state: Rc<State>) {
     }
 }
 
+/// Performs all pending float layouts immediately.
+///
+/// Returns whether any float was processed.
+pub fn flush_float_layout(state: &State) -> bool {
+    let mut any = false;
+    while let Some(node) = state.pending_float_layout.try_pop() {
+        any = true;
+        if node.layout_scheduled.get() {
+            node.perform_layout();
+        }
+    }
+    any
+}
+
 pub async fn float_titles(state: Rc<State>) {
     loop {
         let node = state.pending_float_titles.pop().await;
@@ -105,6 +127,7 @@ impl FloatNode {
             position: Cell::new(position),
             display_link: RefCell::new(None),
             workspace_link: Cell::new(None),
+            sticky_link: Default::default(),
             workspace: CloneCell::new(ws.clone()),
             child: CloneCell::new(Some(child.clone())),
             active: Cell::new(false),
@@ -179,6 +202,11 @@ impl FloatNode {
         };
         let bw = theme.sizes.border_width.get();
         let font = theme.font.borrow_mut();
+        let active_font = theme.active_font.borrow_mut();
+        let font = match (self.active.get(), &*active_font) {
+            (true, Some(active_font)) => active_font,
+            _ => &*font,
+        };
         let title = self.title.borrow_mut();
         let pos = self.position.get();
         if pos.width() <= 2 * bw || title.is_empty() {
@@ -203,7 +231,7 @@ impl FloatNode {
             if th == 0 || width == 0 {
                 continue;
             }
-            let texture = match text::render(&ctx, old_tex, width, th, &font, &title, tc, scalef) {
+            let texture = match text::render(&ctx, old_tex, width, th, font, &title, tc, scalef) {
                 Ok(t) => t,
                 Err(e) => {
                     log::error!("Could not render title {}: {}", title, ErrorFmt(e));
@@ -339,16 +367,83 @@ impl FloatNode {
         }
     }
 
-    fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
+    pub fn apply_drag(self: &Rc<Self>, start: Rect, drag: FloatDrag, dx: i32, dy: i32) {
+        let theme = &self.state.theme;
+        let bw = theme.sizes.border_width.get();
+        let th = theme.sizes.title_height.get();
+        let mut x1 = start.x1();
+        let mut y1 = start.y1();
+        let mut x2 = start.x2();
+        let mut y2 = start.y2();
+        match drag {
+            FloatDrag::Move => {
+                x1 += dx;
+                y1 += dy;
+                x2 += dx;
+                y2 += dy;
+            }
+            FloatDrag::Resize { left, top } => {
+                match left {
+                    true => x1 = (x1 + dx).min(x2 - 2 * bw),
+                    false => x2 = (x2 + dx).max(x1 + 2 * bw),
+                }
+                match top {
+                    true => y1 = (y1 + dy).min(y2 - 2 * bw - th - 1),
+                    false => y2 = (y2 + dy).max(y1 + 2 * bw + th + 1),
+                }
+            }
+        }
+        self.position.set(Rect::new(x1, y1, x2, y2).unwrap());
+        self.schedule_layout();
+    }
+
+    pub fn finish_drag(self: &Rc<Self>, seat: &Rc<WlSeatGlobal>) {
+        if self.child.is_none() {
+            return;
+        }
+        let ws = seat.get_output().ensure_workspace();
+        self.set_workspace(&ws);
+    }
+
+    pub fn set_workspace(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) {
         if let Some(c) = self.child.get() {
             c.tl_set_workspace(ws);
         }
         self.workspace_link
             .set(Some(ws.stacked.add_last(self.clone())));
-        self.workspace.set(ws.clone());
+        let old = self.workspace.set(ws.clone());
+        if old.id != ws.id {
+            old.schedule_empty_check();
+        }
+        self.relink_sticky();
         self.stacked_set_visible(ws.stacked_visible());
     }
 
+    pub fn is_sticky(&self) -> bool {
+        self.sticky_link.borrow().is_some()
+    }
+
+    pub fn set_sticky(self: &Rc<Self>, sticky: bool) {
+        let link = match sticky {
+            true => Some(
+                self.workspace
+                    .get()
+                    .output
+                    .get()
+                    .sticky
+                    .add_last(self.clone()),
+            ),
+            false => None,
+        };
+        *self.sticky_link.borrow_mut() = link;
+    }
+
+    pub fn relink_sticky(self: &Rc<Self>) {
+        if self.is_sticky() {
+            self.set_sticky(true);
+        }
+    }
+
     fn update_child_title(self: &Rc<Self>, title: &str) {
         let mut t = self.title.borrow_mut();
         if t.deref() != title {
@@ -389,7 +484,7 @@ impl FloatNode {
         }
     }
 
-    fn restack(&self) {
+    pub fn restack(&self) {
         if let Some(dl) = &*self.display_link.borrow() {
             self.state.root.stacked.add_last_existing(&dl);
             if let Some(tl) = self.child.get() {
@@ -591,6 +686,8 @@ impl ContainingNode for FloatNode {
         self.child.set(None);
         self.display_link.borrow_mut().take();
         self.workspace_link.set(None);
+        self.sticky_link.borrow_mut().take();
+        self.workspace.get().schedule_empty_check();
     }
 
     fn cnode_accepts_child(&self, _node: &dyn Node) -> bool {
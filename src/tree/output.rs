@@ -1,5 +1,6 @@
 use {
     crate::{
+        async_engine::SpawnedFuture,
         backend::{HardwareCursor, KeyState, Mode},
         client::ClientId,
         cursor::KnownCursor,
@@ -8,6 +9,7 @@ use {
         ifs::{
             jay_output::JayOutput,
             jay_screencast::JayScreencast,
+            wl_callback::WlCallback,
             wl_output::WlOutputGlobal,
             wl_seat::{
                 collect_kb_foci2, wl_pointer::PendingScroll, NodeSeatState, SeatId, WlSeatGlobal,
@@ -16,17 +18,18 @@ use {
             wl_surface::{
                 ext_session_lock_surface_v1::ExtSessionLockSurfaceV1,
                 zwlr_layer_surface_v1::ZwlrLayerSurfaceV1, SurfaceSendPreferredScaleVisitor,
-                SurfaceSendPreferredTransformVisitor,
+                SurfaceSendPreferredTransformVisitor, WlSurface,
             },
             zwlr_layer_shell_v1::{BACKGROUND, BOTTOM, OVERLAY, TOP},
         },
         rect::Rect,
-        renderer::Renderer,
+        renderer::{RenderResult, Renderer},
         scale::Scale,
         state::State,
         text::{self, TextTexture},
         tree::{
-            walker::NodeVisitor, Direction, FindTreeResult, FoundNode, Node, NodeId, WorkspaceNode,
+            walker::NodeVisitor, Direction, FindTreeResult, FloatNode, FoundNode, Node, NodeId,
+            StackedNode, WorkspaceNode, WorkspaceNodeId,
         },
         utils::{
             clonecell::CloneCell, copyhashmap::CopyHashMap, errorfmt::ErrorFmt,
@@ -35,7 +38,7 @@ use {
         wire::{JayOutputId, JayScreencastId},
     },
     ahash::AHashMap,
-    jay_config::video::Transform,
+    jay_config::{video::Transform, Axis},
     smallvec::SmallVec,
     std::{
         cell::{Cell, RefCell},
@@ -65,6 +68,22 @@ pub struct OutputNode {
     pub hardware_cursor_needs_render: Cell<bool>,
     pub update_render_data_scheduled: Cell<bool>,
     pub screencasts: CopyHashMap<(ClientId, JayScreencastId), Rc<JayScreencast>>,
+    pub throttle_frame_callbacks: Cell<bool>,
+    pub frame_callbacks_dispatched: Cell<u64>,
+    pub throttled_frame_requests: RefCell<Vec<Rc<WlCallback>>>,
+    pub throttled_frame_flush: Cell<Option<SpawnedFuture<()>>>,
+    pub workspace_transition: RefCell<Option<WorkspaceTransition>>,
+    pub sticky: LinkedList<Rc<FloatNode>>,
+    pub previous_workspace: Cell<Option<WorkspaceNodeId>>,
+}
+
+pub struct WorkspaceTransition {
+    pub old: Rc<WorkspaceNode>,
+    axis: Axis,
+    forward: bool,
+    start_usec: u64,
+    duration_usec: u64,
+    _task: SpawnedFuture<()>,
 }
 
 pub async fn output_render_data(state: Rc<State>) {
@@ -110,6 +129,80 @@ impl OutputNode {
         self.render_data.borrow_mut().titles.clear();
         self.lock_surface.take();
         self.jay_outputs.clear();
+        self.throttled_frame_requests.borrow_mut().clear();
+        self.throttled_frame_flush.take();
+        self.workspace_transition.take();
+        let sticky: Vec<_> = self.sticky.iter().collect();
+        for float in sticky {
+            float.sticky_link.borrow_mut().take();
+        }
+    }
+
+    pub fn set_throttle_frame_callbacks(&self, throttle: bool) {
+        self.throttle_frame_callbacks.set(throttle);
+        if !throttle {
+            self.throttled_frame_flush.take();
+            let now = self.state.clock.now().map(|t| t.usec()).unwrap_or(0);
+            self.flush_throttled_frame_requests(now);
+        }
+    }
+
+    /// Dispatches the frame requests collected while rendering this output.
+    ///
+    /// If frame callback throttling is enabled, callbacks are dispatched at most once
+    /// per refresh cycle. Callbacks that arrive earlier are held back.
+    ///
+    /// Toplevels whose occlusion changed in the frame are reconfigured first.
+    pub fn dispatch_frame_requests(self: &Rc<Self>, rr: &mut RenderResult) {
+        rr.update_occlusion();
+        if !self.throttle_frame_callbacks.get() {
+            rr.dispatch_frame_requests();
+            return;
+        }
+        self.throttled_frame_requests
+            .borrow_mut()
+            .extend(rr.frame_requests.drain(..));
+        let now = match self.state.clock.now() {
+            Ok(now) => now.usec(),
+            Err(e) => {
+                log::error!("Could not get the current time: {}", ErrorFmt(e));
+                self.flush_throttled_frame_requests(0);
+                return;
+            }
+        };
+        let refresh_mhz = self.global.mode.get().refresh_rate_millihz as u64;
+        let period = match refresh_mhz {
+            0 => 0,
+            _ => 1_000_000_000 / refresh_mhz,
+        };
+        let next = self.frame_callbacks_dispatched.get() + period;
+        if now >= next {
+            self.flush_throttled_frame_requests(now);
+            return;
+        }
+        if let Some(flush) = self.throttled_frame_flush.take() {
+            self.throttled_frame_flush.set(Some(flush));
+            return;
+        }
+        let slf = self.clone();
+        let ms = (next - now).div_ceil(1000);
+        let future = self.state.eng.spawn(async move {
+            if let Err(e) = slf.state.wheel.timeout(ms).await {
+                log::error!("Could not wait for the next refresh cycle: {}", ErrorFmt(e));
+            }
+            let now = slf.state.clock.now().map(|t| t.usec()).unwrap_or(0);
+            slf.flush_throttled_frame_requests(now);
+            slf.throttled_frame_flush.take();
+        });
+        self.throttled_frame_flush.set(Some(future));
+    }
+
+    fn flush_throttled_frame_requests(&self, now: u64) {
+        self.frame_callbacks_dispatched.set(now);
+        for fr in self.throttled_frame_requests.borrow_mut().drain(..) {
+            fr.send_done();
+            let _ = fr.client.remove_obj(&*fr);
+        }
     }
 
     pub fn on_spaces_changed(self: &Rc<Self>) {
@@ -124,12 +217,14 @@ impl OutputNode {
         if scale == old_scale {
             return;
         }
-        let legacy_scale = scale.round_up();
-        if self.global.legacy_scale.replace(legacy_scale) != legacy_scale {
-            self.global.send_mode();
-        }
+        self.global.legacy_scale.set(scale.round_up());
         self.state.remove_output_scale(old_scale);
         self.state.add_output_scale(scale);
+        if !self.is_dummy {
+            if let Some(config) = self.state.config.get() {
+                config.connector_scale_changed(self.global.connector.connector.id(), scale);
+            }
+        }
         let rect = self.calculate_extents();
         self.change_extents_(&rect);
         let mut visitor = SurfaceSendPreferredScaleVisitor;
@@ -303,14 +398,14 @@ impl OutputNode {
             if old.id == ws.id {
                 return false;
             }
+            self.previous_workspace.set(Some(old.id));
             collect_kb_foci2(old.clone(), &mut seats);
-            if old.is_empty() {
-                for jw in old.jay_workspaces.lock().values() {
-                    jw.send_destroyed();
-                    jw.workspace.set(None);
-                }
-                old.clear();
-                self.state.workspaces.remove(&old.name);
+            let sticky: Vec<_> = self.sticky.iter().collect();
+            for float in sticky {
+                float.set_workspace(ws);
+            }
+            if old.is_empty() && !old.is_persistent() {
+                self.remove_workspace(&old);
             } else {
                 old.set_visible(false);
                 old.flush_jay_workspaces();
@@ -327,6 +422,139 @@ impl OutputNode {
         true
     }
 
+    /// Returns the workspace that was shown before `ws`.
+    ///
+    /// If that workspace no longer exists on this output, a neighbor of `ws` is returned
+    /// instead.
+    pub fn previous_workspace(&self, ws: &WorkspaceNode) -> Option<Rc<WorkspaceNode>> {
+        let prev = self.previous_workspace.get();
+        let mut neighbor = None;
+        let mut found = false;
+        for other in self.workspaces.iter() {
+            if Some(other.id) == prev && other.id != ws.id {
+                return Some(Rc::clone(&other));
+            }
+            if other.id == ws.id {
+                found = true;
+            } else if neighbor.is_none() || !found {
+                neighbor = Some(Rc::clone(&other));
+            }
+        }
+        neighbor
+    }
+
+    pub fn remove_workspace(&self, ws: &WorkspaceNode) {
+        for jw in ws.jay_workspaces.lock().values() {
+            jw.send_destroyed();
+            jw.workspace.set(None);
+        }
+        ws.clear();
+        self.state.workspaces.remove(&ws.name);
+    }
+
+    /// Like `show_workspace` but plays the configured workspace switch animation.
+    pub fn show_workspace_animated(self: &Rc<Self>, ws: &Rc<WorkspaceNode>) -> bool {
+        let old = self.workspace.get();
+        let mut forward = true;
+        if let Some(old) = &old {
+            for other in self.workspaces.iter() {
+                if other.id == old.id {
+                    break;
+                }
+                if other.id == ws.id {
+                    forward = false;
+                    break;
+                }
+            }
+        }
+        if !self.show_workspace(ws) {
+            return false;
+        }
+        if let Some(old) = old {
+            self.start_workspace_transition(old, forward);
+        }
+        true
+    }
+
+    fn start_workspace_transition(self: &Rc<Self>, old: Rc<WorkspaceNode>, forward: bool) {
+        let (axis, duration) = self.state.workspace_switch_animation.get();
+        if duration.is_zero() {
+            return;
+        }
+        let now = match self.state.clock.now() {
+            Ok(now) => now.usec(),
+            Err(e) => {
+                log::error!("Could not get the current time: {}", ErrorFmt(e));
+                return;
+            }
+        };
+        let duration_usec = duration.as_micros() as u64;
+        let end = now + duration_usec;
+        let refresh_mhz = self.global.mode.get().refresh_rate_millihz as u64;
+        let frame_ms = match refresh_mhz {
+            0 => 16,
+            _ => (1_000_000 / refresh_mhz).max(1),
+        };
+        let slf = self.clone();
+        let task = self.state.eng.spawn(async move {
+            loop {
+                slf.global.connector.connector.damage();
+                if let Err(e) = slf.state.wheel.timeout(frame_ms).await {
+                    log::error!(
+                        "Could not wait for the next animation frame: {}",
+                        ErrorFmt(e)
+                    );
+                    break;
+                }
+                match slf.state.clock.now() {
+                    Ok(now) if now.usec() < end => {}
+                    _ => break,
+                }
+            }
+            slf.workspace_transition.take();
+            slf.global.connector.connector.damage();
+        });
+        *self.workspace_transition.borrow_mut() = Some(WorkspaceTransition {
+            old,
+            axis,
+            forward,
+            start_usec: now,
+            duration_usec,
+            _task: task,
+        });
+    }
+
+    /// Returns the outgoing workspace of a running workspace switch animation and the
+    /// offsets at which the outgoing and the incoming workspace should be rendered.
+    pub fn workspace_transition_offsets(
+        &self,
+    ) -> Option<(Rc<WorkspaceNode>, (i32, i32), (i32, i32))> {
+        let transition = self.workspace_transition.borrow();
+        let transition = transition.as_ref()?;
+        let now = self.state.clock.now().ok()?.usec();
+        let elapsed = now.saturating_sub(transition.start_usec);
+        if elapsed >= transition.duration_usec {
+            return None;
+        }
+        let progress = elapsed as f64 / transition.duration_usec as f64;
+        let progress = 1.0 - (1.0 - progress).powi(3);
+        let rect = self.workspace_rect();
+        let size = match transition.axis {
+            Axis::Horizontal => rect.width(),
+            Axis::Vertical => rect.height(),
+        };
+        let shift = (progress * size as f64).round() as i32;
+        let (old, new) = match transition.forward {
+            true => (-shift, size - shift),
+            false => (shift, shift - size),
+        };
+        let offset = |d| match transition.axis {
+            Axis::Horizontal => (d, 0),
+            Axis::Vertical => (0, d),
+        };
+        Some((transition.old.clone(), offset(old), offset(new)))
+    }
+
     pub fn create_workspace(self: &Rc<Self>, name: &str) -> Rc<WorkspaceNode> {
         let ws = Rc::new(WorkspaceNode {
             id: self.state.node_ids.next(),
@@ -408,6 +636,11 @@ impl OutputNode {
             .borrow_mut()
             .insert(self.global.output_id.clone(), transform);
         self.global.transform.set(transform);
+        if old_transform != transform && !self.is_dummy {
+            if let Some(config) = self.state.config.get() {
+                config.connector_transform_changed(self.global.connector.connector.id(), transform);
+            }
+        }
         let (new_width, new_height) = self.global.pixel_size();
         self.change_extents_(&self.calculate_extents());
 
@@ -506,6 +739,44 @@ impl OutputNode {
             .map(|w| w.fullscreen.is_some())
             .unwrap_or(false)
     }
+
+    /// Returns the topmost stacked node if it is opaque and covers the whole output.
+    ///
+    /// Such a node hides everything below it, which allows the output to be rendered
+    /// as if the node were fullscreen.
+    pub fn covering_stacked_node(&self) -> Option<(Rc<dyn StackedNode>, Rc<WlSurface>)> {
+        let pos = self.global.pos.get();
+        for stacked in self.state.root.stacked.rev_iter() {
+            if !stacked.node_visible() || !stacked.node_absolute_position().intersects(&pos) {
+                continue;
+            }
+            let surface = stacked.stacked_scanout_surface()?;
+            if surface.is_opaque() && surface.buffer_abs_pos.get().contains_rect(&pos) {
+                return Some((stacked.deref().clone(), surface));
+            }
+            return None;
+        }
+        None
+    }
+
+    pub fn fullscreen_wants_tearing(&self) -> bool {
+        if let Some((_, surface)) = self.covering_stacked_node() {
+            return surface.tearing();
+        }
+        let Some(ws) = self.workspace.get() else {
+            return false;
+        };
+        let Some(tl) = ws.fullscreen.get() else {
+            return false;
+        };
+        if !tl.tl_data().active.get() {
+            return false;
+        }
+        match tl.tl_scanout_surface() {
+            Some(surface) => surface.tearing(),
+            None => false,
+        }
+    }
 }
 
 pub struct OutputTitle {
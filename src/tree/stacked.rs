@@ -1,4 +1,7 @@
-use {crate::tree::Node, std::rc::Rc};
+use {
+    crate::{ifs::wl_surface::WlSurface, tree::Node},
+    std::rc::Rc,
+};
 
 pub trait StackedNode: Node {
     fn stacked_as_node(&self) -> &dyn Node;
@@ -9,4 +12,8 @@ pub trait StackedNode: Node {
     fn stacked_absolute_position_constrains_input(&self) -> bool {
         true
     }
+
+    fn stacked_scanout_surface(&self) -> Option<Rc<WlSurface>> {
+        None
+    }
 }
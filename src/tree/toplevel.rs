@@ -9,6 +9,7 @@ use {
         },
         rect::Rect,
         state::State,
+        theme::Color,
         tree::{ContainingNode, Direction, Node, OutputNode, PlaceholderNode, WorkspaceNode},
         utils::{
             clonecell::CloneCell,
@@ -185,6 +186,51 @@ pub trait ToplevelNodeBase: Node {
     fn tl_restack_popups(&self) {
         // nothing
     }
+    fn tl_set_occluded(&self, occluded: bool) {
+        let _ = occluded;
+    }
+    fn tl_size_increments(&self) -> Option<SizeIncrements> {
+        None
+    }
+
+    /// Returns the instance and class of an X11 window.
+    fn tl_x11_class(&self) -> Option<(String, String)> {
+        None
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub struct SizeIncrements {
+    pub base_width: i32,
+    pub base_height: i32,
+    pub width_inc: i32,
+    pub height_inc: i32,
+}
+
+impl SizeIncrements {
+    /// Shrinks `rect` so that its size is a multiple of the increments and centers the
+    /// result in `rect`.
+    ///
+    /// The values come from clients and are not trusted. The returned size is never
+    /// larger than the size of `rect`.
+    pub fn apply(&self, rect: &Rect) -> Rect {
+        let round = |size: i32, base: i32, inc: i32| {
+            if inc <= 1 || size <= 0 {
+                return size;
+            }
+            let base = base.clamp(0, size);
+            base + (size - base) / inc * inc
+        };
+        let width = round(rect.width(), self.base_width, self.width_inc);
+        let height = round(rect.height(), self.base_height, self.height_inc);
+        Rect::new_sized(
+            rect.x1().saturating_add((rect.width() - width) / 2),
+            rect.y1().saturating_add((rect.height() - height) / 2),
+            width,
+            height,
+        )
+        .unwrap_or(*rect)
+    }
 }
 
 pub struct FullscreenedData {
@@ -215,6 +261,7 @@ pub struct ToplevelData {
     pub identifier: Cell<ToplevelIdentifier>,
     pub handles:
         CopyHashMap<(ClientId, ExtForeignToplevelHandleV1Id), Rc<ExtForeignToplevelHandleV1>>,
+    pub border_color: Cell<Option<Color>>,
 }
 
 impl ToplevelData {
@@ -241,17 +288,37 @@ impl ToplevelData {
             app_id: Default::default(),
             identifier: Cell::new(toplevel_identifier()),
             handles: Default::default(),
+            border_color: Cell::new(None),
         }
     }
 
+    pub fn set_border_color(&self, color: Option<Color>) {
+        if self.border_color.replace(color) == color {
+            return;
+        }
+        if let Some(parent) = self.parent.get() {
+            if let Some(container) = parent.node_into_container() {
+                container.schedule_compute_render_data();
+            }
+        }
+        self.state.damage();
+    }
+
     pub fn active(&self) -> bool {
         self.active_surfaces.active() || self.active.get()
     }
 
     pub fn float_size(&self, ws: &WorkspaceNode) -> (i32, i32) {
         let output = ws.output.get().global.pos.get();
+        let (default_width, default_height) = self.state.float_default_size.get();
         let mut width = self.float_width.get();
         let mut height = self.float_height.get();
+        if width == 0 {
+            width = default_width;
+        }
+        if height == 0 {
+            height = default_height;
+        }
         if width == 0 {
             width = output.width() / 2;
         }
@@ -10,6 +10,7 @@ use {
         },
         rect::Rect,
         renderer::Renderer,
+        state::State,
         text::TextTexture,
         tree::{
             container::ContainerNode, walker::NodeVisitor, ContainingNode, Direction,
@@ -24,11 +25,65 @@ use {
         },
         wire::JayWorkspaceId,
     },
+    jay_config::EmptyWorkspaceAction,
     std::{cell::Cell, fmt::Debug, ops::Deref, rc::Rc},
 };
 
 tree_id!(WorkspaceNodeId);
 
+pub async fn handle_empty_workspaces(state: Rc<State>) {
+    loop {
+        let ws = state.pending_empty_workspaces.pop().await;
+        if !ws.is_empty() {
+            continue;
+        }
+        match state.workspaces.get(&ws.name) {
+            Some(w) if w.id == ws.id => {}
+            _ => continue,
+        }
+        let output = ws.output.get();
+        if ws.visible.get() {
+            if !output.is_dummy {
+                run_empty_workspace_action(&state, &output, &ws);
+            }
+        } else if state.auto_destroy_empty_workspaces.get() && !ws.is_persistent() {
+            output.remove_workspace(&ws);
+            output.schedule_update_render_data();
+        }
+    }
+}
+
+fn run_empty_workspace_action(state: &Rc<State>, output: &Rc<OutputNode>, ws: &Rc<WorkspaceNode>) {
+    let action = state.empty_workspace_action.borrow().clone();
+    match action {
+        EmptyWorkspaceAction::None => {}
+        EmptyWorkspaceAction::SwitchToPrevious => {
+            let Some(prev) = output.previous_workspace(ws) else {
+                return;
+            };
+            if output.show_workspace_animated(&prev) {
+                prev.flush_jay_workspaces();
+                output.schedule_update_render_data();
+                state.tree_changed();
+            }
+        }
+        EmptyWorkspaceAction::Run {
+            prog,
+            args,
+            env,
+            inherit_env,
+        } => match state.forker.get() {
+            Some(forker) => {
+                let name = Rc::new(ws.name.clone());
+                forker.spawn_on_workspace(state, prog, args, env, inherit_env, name);
+            }
+            _ => log::error!(
+                "Cannot run the empty workspace action because the forker is not available"
+            ),
+        },
+    }
+}
+
 pub struct WorkspaceNode {
     pub id: WorkspaceNodeId,
     pub is_dummy: bool,
@@ -50,6 +105,23 @@ pub struct WorkspaceNode {
 }
 
 impl WorkspaceNode {
+    pub fn schedule_empty_check(self: &Rc<Self>) {
+        if self.is_dummy {
+            return;
+        }
+        let state = &self.output.get().state;
+        let action = *state.empty_workspace_action.borrow() != EmptyWorkspaceAction::None;
+        if action || state.auto_destroy_empty_workspaces.get() {
+            state.pending_empty_workspaces.push(self.clone());
+        }
+    }
+
+    pub fn is_persistent(&self) -> bool {
+        let output = self.output.get();
+        let persistent = output.state.persistent_workspaces.borrow();
+        persistent.contains(&self.name)
+    }
+
     pub fn clear(&self) {
         self.container.set(None);
         self.output_link.set(None);
@@ -70,8 +142,17 @@ impl WorkspaceNode {
         }
         let mut visitor = OutputSetter(output);
         self.node_visit_children(&mut visitor);
+        self.send_scanout_feedback();
         for stacked in self.stacked.iter() {
             stacked.deref().clone().node_visit(&mut visitor);
+            if let Some(float) = stacked
+                .deref()
+                .clone()
+                .stacked_into_node()
+                .node_into_float()
+            {
+                float.relink_sticky();
+            }
         }
     }
 
@@ -142,8 +223,19 @@ impl WorkspaceNode {
         if plane_was_visible {
             self.plane_set_visible(false);
         }
+        self.send_scanout_feedback();
+    }
+
+    pub fn send_scanout_feedback(&self) {
+        let Some(node) = self.fullscreen.get() else {
+            return;
+        };
         if let Some(surface) = node.tl_scanout_surface() {
-            if let Some(fb) = self.output.get().global.connector.connector.drm_feedback() {
+            let fb = match self.output.get().global.connector.connector.drm_feedback() {
+                Some(fb) => Some(fb),
+                _ => surface.client.drm_feedback(),
+            };
+            if let Some(fb) = fb {
                 surface.send_feedback(&fb);
             }
         }
@@ -156,7 +248,7 @@ impl WorkspaceNode {
                 self.plane_set_visible(true);
             }
             if let Some(surface) = node.tl_scanout_surface() {
-                if let Some(fb) = surface.client.state.drm_feedback.get() {
+                if let Some(fb) = surface.client.drm_feedback() {
                     surface.send_feedback(&fb);
                 }
             }
@@ -280,12 +372,14 @@ impl ContainingNode for WorkspaceNode {
             if container.node_id() == child.node_id() {
                 self.discard_child_properties(&*container);
                 self.container.set(None);
+                self.schedule_empty_check();
                 return;
             }
         }
         if let Some(fs) = self.fullscreen.get() {
             if fs.tl_as_node().node_id() == child.node_id() {
                 self.remove_fullscreen_node();
+                self.schedule_empty_check();
                 return;
             }
         }
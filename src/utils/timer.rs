@@ -16,6 +16,8 @@ pub enum TimerError {
     TimerReadError(#[source] IoUringError),
     #[error("Could not set a timer")]
     SetTimer(#[source] OsError),
+    #[error("Could not get the state of a timer")]
+    GetTimer(#[source] OsError),
     #[error("The io-uring returned an error")]
     IoUringError(#[from] IoUringError),
 }
@@ -66,4 +68,16 @@ impl TimerFd {
         }
         Ok(())
     }
+
+    pub fn remaining(&self) -> Result<Option<Duration>, TimerError> {
+        let timerspec = match uapi::timerfd_gettime(self.fd.raw()) {
+            Ok(t) => t,
+            Err(e) => return Err(TimerError::GetTimer(e.into())),
+        };
+        let value = timerspec.it_value;
+        if value.tv_sec == 0 && value.tv_nsec == 0 {
+            return Ok(None);
+        }
+        Ok(Some(Duration::new(value.tv_sec as _, value.tv_nsec as _)))
+    }
 }
@@ -9,10 +9,15 @@ use {
             mode_addfb2, mode_atomic, mode_create_blob, mode_destroy_blob, mode_get_resources,
             mode_getconnector, mode_getencoder, mode_getplane, mode_getplaneresources,
             mode_getprobblob, mode_getproperty, mode_obj_getproperties, mode_rmfb,
-            prime_fd_to_handle, set_client_cap, DRM_DISPLAY_MODE_LEN, DRM_MODE_ATOMIC_TEST_ONLY,
+            prime_fd_to_handle, set_client_cap, syncobj_create, syncobj_destroy, syncobj_eventfd,
+            syncobj_fd_to_handle, syncobj_handle_to_fd, syncobj_timeline_wait, syncobj_transfer,
+            DRM_CAP_SYNCOBJ_TIMELINE, DRM_DISPLAY_MODE_LEN, DRM_MODE_ATOMIC_TEST_ONLY,
             DRM_MODE_FB_MODIFIERS, DRM_MODE_OBJECT_BLOB, DRM_MODE_OBJECT_CONNECTOR,
             DRM_MODE_OBJECT_CRTC, DRM_MODE_OBJECT_ENCODER, DRM_MODE_OBJECT_FB,
             DRM_MODE_OBJECT_MODE, DRM_MODE_OBJECT_PLANE, DRM_MODE_OBJECT_PROPERTY,
+            DRM_SYNCOBJ_FD_TO_HANDLE_FLAGS_IMPORT_SYNC_FILE,
+            DRM_SYNCOBJ_HANDLE_TO_FD_FLAGS_EXPORT_SYNC_FILE, DRM_SYNCOBJ_WAIT_FLAGS_WAIT_AVAILABLE,
+            DRM_SYNCOBJ_WAIT_FLAGS_WAIT_FOR_SUBMIT,
         },
     },
     ahash::AHashMap,
@@ -39,14 +44,16 @@ use crate::{
         dmabuf::DmaBuf,
         drm::sys::{
             drm_format_modifier, drm_format_modifier_blob, get_version, DRM_CAP_CURSOR_HEIGHT,
-            DRM_CAP_CURSOR_WIDTH, FORMAT_BLOB_CURRENT,
+            DRM_CAP_CURSOR_WIDTH, DRM_MODE_FLAG_DBLSCAN, DRM_MODE_FLAG_INTERLACE,
+            FORMAT_BLOB_CURRENT,
         },
         Modifier, INVALID_MODIFIER,
     },
 };
 pub use sys::{
-    drm_mode_modeinfo, DRM_CLIENT_CAP_ATOMIC, DRM_MODE_ATOMIC_ALLOW_MODESET,
-    DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_EVENT,
+    drm_mode_modeinfo, DRM_CAP_ATOMIC_ASYNC_PAGE_FLIP, DRM_CLIENT_CAP_ATOMIC,
+    DRM_MODE_ATOMIC_ALLOW_MODESET, DRM_MODE_ATOMIC_NONBLOCK, DRM_MODE_PAGE_FLIP_ASYNC,
+    DRM_MODE_PAGE_FLIP_EVENT,
 };
 
 #[derive(Debug, Error)]
@@ -113,6 +120,18 @@ pub enum DrmError {
     Version(#[source] OsError),
     #[error("Format of IN_FORMATS property is invalid")]
     InFormats,
+    #[error("Could not create a syncobj handle")]
+    SyncObjHandle(#[source] OsError),
+    #[error("Could not transfer a syncobj point")]
+    SyncObjTransfer(#[source] OsError),
+    #[error("Could not export a sync file from a syncobj")]
+    SyncObjExportSyncFile(#[source] OsError),
+    #[error("Could not import a sync file into a syncobj")]
+    SyncObjImportSyncFile(#[source] OsError),
+    #[error("Could not register an eventfd with a syncobj")]
+    SyncObjEventfd(#[source] OsError),
+    #[error("Could not wait for a syncobj point")]
+    SyncObjWait(#[source] OsError),
 }
 
 fn render_node_name(fd: c::c_int) -> Result<Ustring, DrmError> {
@@ -187,6 +206,116 @@ impl Drm {
     pub fn version(&self) -> Result<DrmVersion, DrmError> {
         get_version(self.fd.raw()).map_err(DrmError::Version)
     }
+
+    fn syncobj(&self, fd: Option<&OwnedFd>) -> Result<SyncObjHandle<'_>, DrmError> {
+        let handle = match fd {
+            Some(fd) => syncobj_fd_to_handle(self.raw(), fd.raw(), 0, 0),
+            _ => syncobj_create(self.raw()),
+        };
+        match handle {
+            Ok(handle) => Ok(SyncObjHandle { drm: self, handle }),
+            Err(e) => Err(DrmError::SyncObjHandle(e)),
+        }
+    }
+
+    pub fn syncobj_validate(&self, syncobj: &OwnedFd) -> Result<(), DrmError> {
+        self.syncobj(Some(syncobj)).map(drop)
+    }
+
+    pub fn syncobj_export_sync_file(
+        &self,
+        syncobj: &OwnedFd,
+        point: u64,
+    ) -> Result<OwnedFd, DrmError> {
+        let timeline = self.syncobj(Some(syncobj))?;
+        let binary = self.syncobj(None)?;
+        syncobj_transfer(
+            self.raw(),
+            timeline.handle,
+            point,
+            binary.handle,
+            0,
+            DRM_SYNCOBJ_WAIT_FLAGS_WAIT_FOR_SUBMIT,
+        )
+        .map_err(DrmError::SyncObjTransfer)?;
+        syncobj_handle_to_fd(
+            self.raw(),
+            binary.handle,
+            DRM_SYNCOBJ_HANDLE_TO_FD_FLAGS_EXPORT_SYNC_FILE,
+        )
+        .map_err(DrmError::SyncObjExportSyncFile)
+    }
+
+    pub fn syncobj_import_sync_file(
+        &self,
+        syncobj: &OwnedFd,
+        point: u64,
+        sync_file: &OwnedFd,
+    ) -> Result<(), DrmError> {
+        let timeline = self.syncobj(Some(syncobj))?;
+        let binary = self.syncobj(None)?;
+        syncobj_fd_to_handle(
+            self.raw(),
+            sync_file.raw(),
+            binary.handle,
+            DRM_SYNCOBJ_FD_TO_HANDLE_FLAGS_IMPORT_SYNC_FILE,
+        )
+        .map_err(DrmError::SyncObjImportSyncFile)?;
+        syncobj_transfer(self.raw(), binary.handle, 0, timeline.handle, point, 0)
+            .map_err(DrmError::SyncObjTransfer)
+    }
+
+    /// Returns whether a fence has been attached to the point. Does not block.
+    pub fn syncobj_point_available(&self, syncobj: &OwnedFd, point: u64) -> Result<bool, DrmError> {
+        let timeline = self.syncobj(Some(syncobj))?;
+        let res = syncobj_timeline_wait(
+            self.raw(),
+            timeline.handle,
+            point,
+            0,
+            DRM_SYNCOBJ_WAIT_FLAGS_WAIT_AVAILABLE,
+        );
+        match res {
+            Ok(()) => Ok(true),
+            Err(OsError(c::ETIME)) => Ok(false),
+            Err(e) => Err(DrmError::SyncObjWait(e)),
+        }
+    }
+
+    /// Arranges for the eventfd to be signaled once a fence has been attached to the point.
+    pub fn syncobj_eventfd_available(
+        &self,
+        syncobj: &OwnedFd,
+        point: u64,
+        eventfd: &OwnedFd,
+    ) -> Result<(), DrmError> {
+        let timeline = self.syncobj(Some(syncobj))?;
+        syncobj_eventfd(
+            self.raw(),
+            timeline.handle,
+            point,
+            eventfd.raw(),
+            DRM_SYNCOBJ_WAIT_FLAGS_WAIT_AVAILABLE,
+        )
+        .map_err(DrmError::SyncObjEventfd)
+    }
+
+    pub fn supports_syncobj_timeline(&self) -> bool {
+        matches!(get_cap(self.raw(), DRM_CAP_SYNCOBJ_TIMELINE), Ok(v) if v != 0)
+    }
+}
+
+struct SyncObjHandle<'a> {
+    drm: &'a Drm,
+    handle: u32,
+}
+
+impl Drop for SyncObjHandle<'_> {
+    fn drop(&mut self) {
+        if let Err(e) = syncobj_destroy(self.drm.raw(), self.handle) {
+            log::error!("Could not destroy syncobj handle: {}", ErrorFmt(e));
+        }
+    }
 }
 
 pub struct InFormat {
@@ -310,8 +439,9 @@ impl DrmMaster {
     pub fn add_fb(
         self: &Rc<Self>,
         dma: &DmaBuf,
-        format: Option<&Format>,
+        format: Option<&'static Format>,
     ) -> Result<DrmFramebuffer, DrmError> {
+        let format = format.unwrap_or(dma.format);
         let mut modifier = 0;
         let mut flags = 0;
         if dma.modifier != INVALID_MODIFIER {
@@ -335,7 +465,7 @@ impl DrmMaster {
             self.raw(),
             dma.width as _,
             dma.height as _,
-            format.unwrap_or(dma.format).drm,
+            format.drm,
             flags,
             handles,
             strides,
@@ -345,6 +475,10 @@ impl DrmMaster {
             Ok(fb) => Ok(DrmFramebuffer {
                 master: self.clone(),
                 fb,
+                width: dma.width,
+                height: dma.height,
+                format,
+                modifier: dma.modifier,
             }),
             Err(e) => Err(DrmError::AddFb(e)),
         }
@@ -506,6 +640,10 @@ pub enum DrmEvent {
 pub struct DrmFramebuffer {
     master: Rc<DrmMaster>,
     fb: DrmFb,
+    pub width: i32,
+    pub height: i32,
+    pub format: &'static Format,
+    pub modifier: Modifier,
 }
 
 impl Debug for DrmFramebuffer {
@@ -729,13 +867,23 @@ impl DrmModeInfo {
     }
 
     pub fn refresh_rate_millihz(&self) -> u32 {
-        let clock_millihz = self.clock as u64 * 1_000_000;
-        let htotal = self.htotal as u64;
-        let vtotal = self.vtotal as u64;
-        (((clock_millihz / htotal) + (vtotal / 2)) / vtotal) as u32
-        // simplifies to
-        //     clock_millihz / (htotal * vtotal) + 1/2
-        // why round up (+1/2) instead of down?
+        // clock is in kHz
+        let mut num = self.clock as u64 * 1_000_000;
+        let mut den = self.htotal as u64 * self.vtotal as u64;
+        if self.flags & DRM_MODE_FLAG_INTERLACE != 0 {
+            num *= 2;
+        }
+        if self.flags & DRM_MODE_FLAG_DBLSCAN != 0 {
+            den *= 2;
+        }
+        if self.vscan > 1 {
+            den *= self.vscan as u64;
+        }
+        if den == 0 {
+            return 0;
+        }
+        // Round only once so that rates such as 59.94 Hz come out as 59940.
+        ((num + den / 2) / den) as u32
     }
 }
 
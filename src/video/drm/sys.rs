@@ -219,6 +219,8 @@ const DRM_MODE_PROP_ATOMIC: u32 = 0x80000000;
 
 pub const DRM_CAP_CURSOR_WIDTH: u64 = 0x8;
 pub const DRM_CAP_CURSOR_HEIGHT: u64 = 0x9;
+pub const DRM_CAP_SYNCOBJ_TIMELINE: u64 = 0x14;
+pub const DRM_CAP_ATOMIC_ASYNC_PAGE_FLIP: u64 = 0x15;
 
 #[repr(C)]
 struct drm_mode_property_enum {
@@ -389,6 +391,9 @@ pub const DRM_MODE_OBJECT_PLANE: u32 = 0xeeeeeeee;
 #[allow(dead_code)]
 pub const DRM_MODE_OBJECT_ANY: u32 = 0;
 
+pub const DRM_MODE_FLAG_INTERLACE: u32 = 1 << 4;
+pub const DRM_MODE_FLAG_DBLSCAN: u32 = 1 << 5;
+
 #[allow(dead_code)]
 pub const DRM_MODE_CONNECTOR_Unknown: u32 = 0;
 pub const DRM_MODE_CONNECTOR_VGA: u32 = 1;
@@ -846,6 +851,7 @@ struct drm_mode_atomic {
 const DRM_IOCTL_MODE_ATOMIC: u64 = drm_iowr::<drm_mode_atomic>(0xbc);
 
 pub const DRM_MODE_PAGE_FLIP_EVENT: u32 = 0x01;
+pub const DRM_MODE_PAGE_FLIP_ASYNC: u32 = 0x02;
 pub const DRM_MODE_ATOMIC_TEST_ONLY: u32 = 0x0100;
 pub const DRM_MODE_ATOMIC_NONBLOCK: u32 = 0x0200;
 pub const DRM_MODE_ATOMIC_ALLOW_MODESET: u32 = 0x0400;
@@ -1025,6 +1031,189 @@ pub fn gem_close(fd: c::c_int, handle: u32) -> Result<(), OsError> {
     Ok(())
 }
 
+#[repr(C)]
+struct drm_syncobj_create {
+    handle: u32,
+    flags: u32,
+}
+
+const DRM_IOCTL_SYNCOBJ_CREATE: u64 = drm_iowr::<drm_syncobj_create>(0xbf);
+
+pub fn syncobj_create(fd: c::c_int) -> Result<u32, OsError> {
+    let mut res = drm_syncobj_create {
+        handle: 0,
+        flags: 0,
+    };
+    unsafe {
+        ioctl(fd, DRM_IOCTL_SYNCOBJ_CREATE, &mut res)?;
+    }
+    Ok(res.handle)
+}
+
+#[repr(C)]
+struct drm_syncobj_destroy {
+    handle: u32,
+    pad: u32,
+}
+
+const DRM_IOCTL_SYNCOBJ_DESTROY: u64 = drm_iowr::<drm_syncobj_destroy>(0xc0);
+
+pub fn syncobj_destroy(fd: c::c_int, handle: u32) -> Result<(), OsError> {
+    let mut res = drm_syncobj_destroy { handle, pad: 0 };
+    unsafe {
+        ioctl(fd, DRM_IOCTL_SYNCOBJ_DESTROY, &mut res)?;
+    }
+    Ok(())
+}
+
+pub const DRM_SYNCOBJ_FD_TO_HANDLE_FLAGS_IMPORT_SYNC_FILE: u32 = 1 << 0;
+pub const DRM_SYNCOBJ_HANDLE_TO_FD_FLAGS_EXPORT_SYNC_FILE: u32 = 1 << 0;
+
+#[repr(C)]
+struct drm_syncobj_handle {
+    handle: u32,
+    flags: u32,
+    fd: i32,
+    pad: u32,
+}
+
+const DRM_IOCTL_SYNCOBJ_HANDLE_TO_FD: u64 = drm_iowr::<drm_syncobj_handle>(0xc1);
+const DRM_IOCTL_SYNCOBJ_FD_TO_HANDLE: u64 = drm_iowr::<drm_syncobj_handle>(0xc2);
+
+pub fn syncobj_handle_to_fd(fd: c::c_int, handle: u32, flags: u32) -> Result<OwnedFd, OsError> {
+    let mut res = drm_syncobj_handle {
+        handle,
+        flags,
+        fd: -1,
+        pad: 0,
+    };
+    unsafe {
+        ioctl(fd, DRM_IOCTL_SYNCOBJ_HANDLE_TO_FD, &mut res)?;
+    }
+    Ok(OwnedFd::new(res.fd))
+}
+
+pub fn syncobj_fd_to_handle(
+    fd: c::c_int,
+    syncobj: c::c_int,
+    handle: u32,
+    flags: u32,
+) -> Result<u32, OsError> {
+    let mut res = drm_syncobj_handle {
+        handle,
+        flags,
+        fd: syncobj,
+        pad: 0,
+    };
+    unsafe {
+        ioctl(fd, DRM_IOCTL_SYNCOBJ_FD_TO_HANDLE, &mut res)?;
+    }
+    Ok(res.handle)
+}
+
+#[repr(C)]
+struct drm_syncobj_transfer {
+    src_handle: u32,
+    dst_handle: u32,
+    src_point: u64,
+    dst_point: u64,
+    flags: u32,
+    pad: u32,
+}
+
+const DRM_IOCTL_SYNCOBJ_TRANSFER: u64 = drm_iowr::<drm_syncobj_transfer>(0xcc);
+
+pub fn syncobj_transfer(
+    fd: c::c_int,
+    src_handle: u32,
+    src_point: u64,
+    dst_handle: u32,
+    dst_point: u64,
+    flags: u32,
+) -> Result<(), OsError> {
+    let mut res = drm_syncobj_transfer {
+        src_handle,
+        dst_handle,
+        src_point,
+        dst_point,
+        flags,
+        pad: 0,
+    };
+    unsafe {
+        ioctl(fd, DRM_IOCTL_SYNCOBJ_TRANSFER, &mut res)?;
+    }
+    Ok(())
+}
+
+pub const DRM_SYNCOBJ_WAIT_FLAGS_WAIT_FOR_SUBMIT: u32 = 1 << 1;
+pub const DRM_SYNCOBJ_WAIT_FLAGS_WAIT_AVAILABLE: u32 = 1 << 2;
+
+#[repr(C)]
+struct drm_syncobj_timeline_wait {
+    handles: u64,
+    points: u64,
+    timeout_nsec: i64,
+    count_handles: u32,
+    flags: u32,
+    first_signaled: u32,
+    pad: u32,
+}
+
+const DRM_IOCTL_SYNCOBJ_TIMELINE_WAIT: u64 = drm_iowr::<drm_syncobj_timeline_wait>(0xca);
+
+pub fn syncobj_timeline_wait(
+    fd: c::c_int,
+    handle: u32,
+    point: u64,
+    timeout_nsec: i64,
+    flags: u32,
+) -> Result<(), OsError> {
+    let mut res = drm_syncobj_timeline_wait {
+        handles: &handle as *const u32 as u64,
+        points: &point as *const u64 as u64,
+        timeout_nsec,
+        count_handles: 1,
+        flags,
+        first_signaled: 0,
+        pad: 0,
+    };
+    unsafe {
+        ioctl(fd, DRM_IOCTL_SYNCOBJ_TIMELINE_WAIT, &mut res)?;
+    }
+    Ok(())
+}
+
+#[repr(C)]
+struct drm_syncobj_eventfd {
+    handle: u32,
+    flags: u32,
+    point: u64,
+    fd: i32,
+    pad: u32,
+}
+
+const DRM_IOCTL_SYNCOBJ_EVENTFD: u64 = drm_iowr::<drm_syncobj_eventfd>(0xcf);
+
+pub fn syncobj_eventfd(
+    fd: c::c_int,
+    handle: u32,
+    point: u64,
+    eventfd: c::c_int,
+    flags: u32,
+) -> Result<(), OsError> {
+    let mut res = drm_syncobj_eventfd {
+        handle,
+        flags,
+        point,
+        fd: eventfd,
+        pad: 0,
+    };
+    unsafe {
+        ioctl(fd, DRM_IOCTL_SYNCOBJ_EVENTFD, &mut res)?;
+    }
+    Ok(())
+}
+
 #[allow(dead_code)]
 pub const DRM_EVENT_VBLANK: u32 = 0x01;
 pub const DRM_EVENT_FLIP_COMPLETE: u32 = 0x02;
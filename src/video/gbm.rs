@@ -269,6 +269,14 @@ impl GbmDevice {
             })
         }
     }
+
+    /// Asks the driver for the modifier of a buffer that uses an implicit modifier.
+    ///
+    /// The result can be [INVALID_MODIFIER] if the driver does not know it either.
+    pub fn query_implicit_modifier(&self, dmabuf: &DmaBuf) -> Result<Modifier, GbmError> {
+        let bo = self.import_dmabuf(dmabuf, 0)?;
+        Ok(unsafe { gbm_bo_get_modifier(bo.bo.bo) })
+    }
 }
 
 impl Drop for GbmDevice {
@@ -2,7 +2,7 @@ use {
     crate::{
         async_engine::{AsyncEngine, SpawnedFuture},
         io_uring::{IoUring, IoUringError},
-        time::{Time, TimeError},
+        time::{Clock, MonotonicClock, Time, TimeError},
         utils::{
             buf::TypedBuf, copyhashmap::CopyHashMap, errorfmt::ErrorFmt, numcell::NumCell,
             oserror::OsError, stack::Stack,
@@ -99,6 +99,7 @@ impl Future for WheelTimeoutFuture {
 pub struct WheelData {
     destroyed: Cell<bool>,
     ring: Rc<IoUring>,
+    clock: Rc<dyn Clock>,
     fd: Rc<OwnedFd>,
     next_id: NumCell<u64>,
     start: Time,
@@ -111,6 +112,14 @@ pub struct WheelData {
 
 impl Wheel {
     pub fn new(eng: &Rc<AsyncEngine>, ring: &Rc<IoUring>) -> Result<Rc<Self>, WheelError> {
+        Self::with_clock(eng, ring, &(Rc::new(MonotonicClock) as Rc<dyn Clock>))
+    }
+
+    pub fn with_clock(
+        eng: &Rc<AsyncEngine>,
+        ring: &Rc<IoUring>,
+        clock: &Rc<dyn Clock>,
+    ) -> Result<Rc<Self>, WheelError> {
         let fd = match uapi::timerfd_create(c::CLOCK_MONOTONIC, c::TFD_CLOEXEC) {
             Ok(fd) => Rc::new(fd),
             Err(e) => return Err(WheelError::CreateFailed(e.into())),
@@ -118,9 +127,10 @@ impl Wheel {
         let data = Rc::new(WheelData {
             destroyed: Cell::new(false),
             ring: ring.clone(),
+            clock: clock.clone(),
             fd,
             next_id: NumCell::new(1),
-            start: Time::now()?,
+            start: clock.now()?,
             current_expiration: Default::default(),
             dispatchers: Default::default(),
             expirations: Default::default(),
@@ -136,6 +146,18 @@ impl Wheel {
         self.data.kill();
     }
 
+    /// Dispatches the timeouts that expired after a virtual clock was advanced.
+    #[cfg_attr(not(feature = "it"), allow(dead_code))]
+    pub fn time_advanced(&self) {
+        if self.data.destroyed.get() {
+            return;
+        }
+        if let Err(e) = self.data.dispatch_expired() {
+            log::error!("Could not dispatch wheel expirations: {}", ErrorFmt(e));
+            self.data.kill();
+        }
+    }
+
     fn future(&self) -> WheelTimeoutFuture {
         let data = self.data.cached_futures.pop().unwrap_or_else(|| {
             Rc::new(WheelTimeoutData {
@@ -161,7 +183,7 @@ impl Wheel {
             };
         }
         let future = self.future();
-        let now = match Time::now() {
+        let now = match self.data.clock.now() {
             Ok(n) => n,
             Err(e) => {
                 future.data.expired.set(Some(Err(WheelError::TimeError(e))));
@@ -170,7 +192,11 @@ impl Wheel {
         };
         let expiration = (now + Duration::from_millis(ms)).round_to_ms();
         let current = self.data.current_expiration.get();
-        if current.is_none() || expiration - self.data.start < current.unwrap() - self.data.start {
+        // With a virtual clock, expirations are dispatched in `time_advanced`.
+        if !self.data.clock.is_virtual()
+            && (current.is_none()
+                || expiration - self.data.start < current.unwrap() - self.data.start)
+        {
             let res = uapi::timerfd_settime(
                 self.data.fd.raw(),
                 c::TFD_TIMER_ABSTIME,
@@ -224,7 +250,11 @@ impl WheelData {
         if let Err(e) = self.ring.read(&self.fd, n.buf()).await {
             return Err(WheelError::Read(e));
         }
-        let now = Time::now()?;
+        self.dispatch_expired()
+    }
+
+    fn dispatch_expired(&self) -> Result<(), WheelError> {
+        let now = self.clock.now()?;
         let dist = now - self.start;
         {
             let mut expirations = self.expirations.borrow_mut();
@@ -239,6 +269,9 @@ impl WheelData {
             }
             self.current_expiration.set(None);
             while let Some(Reverse(entry)) = expirations.peek() {
+                if self.clock.is_virtual() {
+                    break;
+                }
                 if self.dispatchers.get(&entry.id).is_some() {
                     let res = uapi::timerfd_settime(
                         self.fd.raw(),
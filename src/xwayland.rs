@@ -113,13 +113,15 @@ pub async fn manage(state: Rc<State>) {
         let display = format!(":{}", xsocket.id);
         forker.setenv(DISPLAY.as_bytes(), display.as_bytes());
         log::info!("Allocated display :{} for Xwayland", xsocket.id);
-        log::info!("Waiting for connection attempt");
         if state.backend.get().import_environment() {
             import_environment(&state, DISPLAY, &display).await;
         }
-        if let Err(e) = state.ring.readable(&socket).await {
-            log::error!("{}", ErrorFmt(e));
-            return;
+        if state.xwayland.lazy.get() {
+            log::info!("Waiting for connection attempt");
+            if let Err(e) = state.ring.readable(&socket).await {
+                log::error!("{}", ErrorFmt(e));
+                return;
+            }
         }
         log::info!("Starting Xwayland");
         if let Err(e) = run(&state, &forker, socket).await {
@@ -42,8 +42,9 @@ use {
         },
         xcon::{
             consts::{
-                ATOM_ATOM, ATOM_NONE, ATOM_STRING, ATOM_WINDOW, ATOM_WM_CLASS, ATOM_WM_NAME,
-                ATOM_WM_SIZE_HINTS, ATOM_WM_TRANSIENT_FOR, COMPOSITE_REDIRECT_MANUAL,
+                _NET_WM_STATE_ADD, _NET_WM_STATE_REMOVE, _NET_WM_STATE_TOGGLE, ATOM_ATOM,
+                ATOM_NONE, ATOM_RESOURCE_MANAGER, ATOM_STRING, ATOM_WINDOW, ATOM_WM_CLASS,
+                ATOM_WM_NAME, ATOM_WM_SIZE_HINTS, ATOM_WM_TRANSIENT_FOR, COMPOSITE_REDIRECT_MANUAL,
                 CONFIG_WINDOW_HEIGHT, CONFIG_WINDOW_WIDTH, CONFIG_WINDOW_X, CONFIG_WINDOW_Y,
                 EVENT_MASK_FOCUS_CHANGE, EVENT_MASK_PROPERTY_CHANGE,
                 EVENT_MASK_SUBSTRUCTURE_NOTIFY, EVENT_MASK_SUBSTRUCTURE_REDIRECT,
@@ -53,8 +54,7 @@ use {
                 PROP_MODE_APPEND, PROP_MODE_REPLACE, RES_CLIENT_ID_MASK_LOCAL_CLIENT_PID,
                 SELECTION_CLIENT_CLOSE_MASK, SELECTION_WINDOW_DESTROY_MASK,
                 SET_SELECTION_OWNER_MASK, STACK_MODE_ABOVE, STACK_MODE_BELOW,
-                WINDOW_CLASS_INPUT_OUTPUT, _NET_WM_STATE_ADD, _NET_WM_STATE_REMOVE,
-                _NET_WM_STATE_TOGGLE,
+                WINDOW_CLASS_INPUT_OUTPUT,
             },
             Event, XEvent, Xcon, XconError,
         },
@@ -420,6 +420,17 @@ impl Wm {
                 data: uapi::as_bytes(&0u32),
             });
         }
+        if client.wire_scale > 1 {
+            let resources = format!("Xft.dpi:\t{}\n", 96 * client.wire_scale);
+            c.call(&ChangeProperty {
+                mode: PROP_MODE_REPLACE,
+                window: root,
+                property: ATOM_RESOURCE_MANAGER,
+                ty: ATOM_STRING,
+                format: 8,
+                data: resources.as_bytes(),
+            });
+        }
         'set_root_cursor: {
             let cursors = match state.cursors.get() {
                 Some(g) => g,
@@ -857,7 +868,12 @@ impl Wm {
     }
 
     async fn send_configure(&mut self, window: Rc<Xwindow>) {
-        let extents = window.data.info.extents.get();
+        let extents = window
+            .data
+            .info
+            .extents
+            .get()
+            .scale_int(self.client.wire_scale);
         // log::info!("xwin {} send_configure {:?}", window.data.window_id, extents);
         let cw = ConfigureWindow {
             window: window.data.window_id,
@@ -2068,7 +2084,11 @@ impl Wm {
             self.num_mapped += 1;
         }
         self.set_net_client_list().await;
-        let pending = data.info.pending_extents.get();
+        let pending = data
+            .info
+            .pending_extents
+            .get()
+            .scale_int(self.client.wire_scale);
         if pending.width() > 0 && pending.height() > 0 {
             let dummy = Rect::new_sized(0, 0, 1, 1).unwrap();
             for rect in [dummy, pending] {
@@ -2157,7 +2177,8 @@ impl Wm {
                 event.width as _,
                 event.height as _,
             )
-            .unwrap();
+            .unwrap()
+            .unscale_int(self.client.wire_scale);
             if let Some(window) = data.window.get() {
                 window.tl_change_extents(&extents);
                 self.state.tree_changed();
@@ -2179,7 +2200,11 @@ impl Wm {
                 return Ok(());
             }
         }
-        let de = data.info.pending_extents.get();
+        let de = data
+            .info
+            .pending_extents
+            .get()
+            .scale_int(self.client.wire_scale);
         let mut x1 = de.x1();
         let mut y1 = de.y1();
         let mut width = de.width();
@@ -2196,9 +2221,11 @@ impl Wm {
         if event.value_mask.contains(CONFIG_WINDOW_HEIGHT) {
             height = event.height as _;
         }
-        data.info
-            .pending_extents
-            .set(Rect::new_sized(x1, y1, width, height).unwrap());
+        data.info.pending_extents.set(
+            Rect::new_sized(x1, y1, width, height)
+                .unwrap()
+                .unscale_int(self.client.wire_scale),
+        );
         Ok(())
     }
 